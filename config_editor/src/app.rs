@@ -1,13 +1,16 @@
-use crate::input::TextInputState;
-use crate::persistence::{save_entry, ConfigOrigins};
+use crate::input::{fuzzy_rank, TextInputState};
+use crate::persistence::{save_entry, ConfigOrigins, FileSnapshot};
+use crate::theme;
 use crate::views;
 use loot_core::config::{
     AffixConfig, AffixPoolConfig, AffixTierConfig, BaseTypeConfig, Config, CurrencyConfig,
     MappingMode, RecipeAffixRequirement, RecipeMapping, UniqueConfig, UniqueRecipeConfig,
 };
+use loot_core::import::{self, ImportedEntries};
+use loot_core::types::{AffixType, ItemClass};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs},
     Frame,
@@ -15,6 +18,67 @@ use ratatui::{
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Keybindings by context. Drives both the help bar and the '?' overlay, so update this
+/// alongside the key handlers in main.rs.
+pub const KEYBINDINGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Browse",
+        &[
+            ("1-5", "Tab"),
+            ("j/k", "Nav"),
+            ("e", "Edit"),
+            ("n", "New"),
+            ("d", "Delete"),
+            ("s", "Save"),
+            ("S", "Save all"),
+            ("I", "Import"),
+            ("M", "Move to file"),
+            ("/", "Jump to id"),
+            ("D", "Stats"),
+            ("T", "Theme"),
+            ("O", "Mod order"),
+            ("?", "Help"),
+            ("q", "Quit"),
+        ],
+    ),
+    (
+        "Edit",
+        &[("Tab", "Next"), ("Ctrl+S", "Save"), ("Esc", "Cancel")],
+    ),
+    (
+        "Edit fields",
+        &[
+            ("Shift+Tab", "Previous field"),
+            ("Ctrl+Up/Down", "Previous/next field"),
+            ("Up/Down", "Choose enum value or list entry"),
+            (
+                "Enter",
+                "Select enum value / add list entry / open nested editor",
+            ),
+            ("x", "Remove list entry (when input is empty)"),
+        ],
+    ),
+    (
+        "Nested editor",
+        &[
+            ("Up/Down", "Select item"),
+            ("Enter", "Edit item"),
+            ("+", "Add item (when input is empty)"),
+            ("x / Ctrl+Del", "Remove item"),
+            ("h/Left/Esc", "Back to fields"),
+        ],
+    ),
+    (
+        "File picker",
+        &[
+            ("j/k", "Select file"),
+            ("Enter", "Save to file"),
+            ("n", "New file"),
+            ("Esc", "Cancel"),
+        ],
+    ),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigTab {
     BaseTypes,
@@ -25,6 +89,14 @@ pub enum ConfigTab {
 }
 
 impl ConfigTab {
+    pub const ALL: [ConfigTab; 5] = [
+        ConfigTab::BaseTypes,
+        ConfigTab::Affixes,
+        ConfigTab::AffixPools,
+        ConfigTab::Currencies,
+        ConfigTab::Uniques,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ConfigTab::BaseTypes => "Base Types",
@@ -54,6 +126,7 @@ pub enum Mode {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
 pub enum Focus {
     List,
     Detail,
@@ -111,6 +184,29 @@ impl DirtyTracker {
         }
     }
 
+    /// All dirty entries across tabs, in tab order then ID order
+    pub fn dirty_entries(&self) -> Vec<(ConfigTab, String)> {
+        ConfigTab::ALL
+            .iter()
+            .flat_map(|&tab| {
+                let map = match tab {
+                    ConfigTab::BaseTypes => &self.base_types,
+                    ConfigTab::Affixes => &self.affixes,
+                    ConfigTab::AffixPools => &self.affix_pools,
+                    ConfigTab::Currencies => &self.currencies,
+                    ConfigTab::Uniques => &self.uniques,
+                };
+                let mut ids: Vec<&String> = map
+                    .iter()
+                    .filter(|(_, &dirty)| dirty)
+                    .map(|(id, _)| id)
+                    .collect();
+                ids.sort();
+                ids.into_iter().map(move |id| (tab, id.clone()))
+            })
+            .collect()
+    }
+
     pub fn is_entry_dirty(&self, tab: ConfigTab, id: &str) -> bool {
         match tab {
             ConfigTab::BaseTypes => self.base_types.get(id).copied().unwrap_or(false),
@@ -207,8 +303,13 @@ pub struct App {
     pub show_delete_confirm: bool,
     pub show_file_picker: bool,
     pub show_new_file_input: bool,
+    pub show_jump_input: bool,
+    pub show_help: bool,
+    pub show_stats: bool,
     pub file_picker_state: ListState,
     pub file_picker_files: Vec<PathBuf>,
+    /// Id of the entry the file picker moves, when it was opened to move rather than save
+    pub moving_entry: Option<String>,
     pub new_file_name: String,
 
     // Config load error
@@ -254,8 +355,12 @@ impl App {
             show_delete_confirm: false,
             show_file_picker: false,
             show_new_file_input: false,
+            show_jump_input: false,
+            show_help: false,
+            show_stats: false,
             file_picker_state: ListState::default(),
             file_picker_files: Vec::new(),
+            moving_entry: None,
             new_file_name: String::new(),
             config_error: error,
         };
@@ -265,25 +370,23 @@ impl App {
     }
 
     fn refresh_ids(&mut self) {
-        let mut base_type_ids: Vec<String> = self.config.base_types.keys().cloned().collect();
-        base_type_ids.sort();
-        self.base_types_state.ids = base_type_ids;
-
-        let mut affix_ids: Vec<String> = self.config.affixes.keys().cloned().collect();
-        affix_ids.sort();
-        self.affixes_state.ids = affix_ids;
-
-        let mut pool_ids: Vec<String> = self.config.affix_pools.keys().cloned().collect();
-        pool_ids.sort();
-        self.affix_pools_state.ids = pool_ids;
-
-        let mut currency_ids: Vec<String> = self.config.currencies.keys().cloned().collect();
-        currency_ids.sort();
-        self.currencies_state.ids = currency_ids;
-
-        let mut unique_ids: Vec<String> = self.config.uniques.keys().cloned().collect();
-        unique_ids.sort();
-        self.uniques_state.ids = unique_ids;
+        self.base_types_state.ids = self
+            .config
+            .base_types_sorted()
+            .map(|bt| bt.id.clone())
+            .collect();
+        self.affixes_state.ids = self.config.affixes_sorted().map(|a| a.id.clone()).collect();
+        self.affix_pools_state.ids = self
+            .config
+            .affix_pools_sorted()
+            .map(|p| p.id.clone())
+            .collect();
+        self.currencies_state.ids = self
+            .config
+            .currencies_sorted()
+            .map(|c| c.id.clone())
+            .collect();
+        self.uniques_state.ids = self.config.uniques_sorted().map(|u| u.id.clone()).collect();
 
         // Ensure selections are valid
         Self::clamp_selection_static(&mut self.base_types_state);
@@ -305,8 +408,8 @@ impl App {
         }
     }
 
-    pub fn current_view_state(&self) -> &ViewState {
-        match self.current_tab {
+    pub fn view_state(&self, tab: ConfigTab) -> &ViewState {
+        match tab {
             ConfigTab::BaseTypes => &self.base_types_state,
             ConfigTab::Affixes => &self.affixes_state,
             ConfigTab::AffixPools => &self.affix_pools_state,
@@ -315,8 +418,8 @@ impl App {
         }
     }
 
-    pub fn current_view_state_mut(&mut self) -> &mut ViewState {
-        match self.current_tab {
+    pub fn view_state_mut(&mut self, tab: ConfigTab) -> &mut ViewState {
+        match tab {
             ConfigTab::BaseTypes => &mut self.base_types_state,
             ConfigTab::Affixes => &mut self.affixes_state,
             ConfigTab::AffixPools => &mut self.affix_pools_state,
@@ -325,10 +428,30 @@ impl App {
         }
     }
 
+    pub fn current_view_state(&self) -> &ViewState {
+        self.view_state(self.current_tab)
+    }
+
+    pub fn current_view_state_mut(&mut self) -> &mut ViewState {
+        self.view_state_mut(self.current_tab)
+    }
+
     pub fn clear_message(&mut self) {
         self.message = None;
     }
 
+    /// Called on terminal resize. Clamps every list selection and drops its scroll offset,
+    /// so the next draw scrolls just far enough to keep the selection visible.
+    pub fn handle_resize(&mut self) {
+        for tab in ConfigTab::ALL {
+            let state = self.view_state_mut(tab);
+            let len = state.ids.len();
+            reclamp_list_state(&mut state.list_state, len);
+        }
+        let len = self.file_picker_files.len();
+        reclamp_list_state(&mut self.file_picker_state, len);
+    }
+
     pub fn switch_tab(&mut self, tab: ConfigTab) {
         if self.mode == Mode::Browse {
             self.current_tab = tab;
@@ -356,6 +479,57 @@ impl App {
         }
     }
 
+    /// Open the jump-to-id prompt, reusing the text input
+    pub fn open_jump_input(&mut self) {
+        if self.mode == Mode::Browse {
+            self.text_input = TextInputState::default();
+            self.show_jump_input = true;
+        }
+    }
+
+    /// Find the entry a typed id refers to: an exact match in the current tab, then in
+    /// any tab, then the first id containing the query (current tab first). Matching
+    /// ignores case.
+    pub fn find_entry(&self, query: &str) -> Option<(ConfigTab, usize)> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return None;
+        }
+
+        let tabs: Vec<ConfigTab> = std::iter::once(self.current_tab)
+            .chain(
+                ConfigTab::ALL
+                    .into_iter()
+                    .filter(|t| *t != self.current_tab),
+            )
+            .collect();
+        let find = |matches: &dyn Fn(&str) -> bool| {
+            tabs.iter().find_map(|&tab| {
+                self.view_state(tab)
+                    .ids
+                    .iter()
+                    .position(|id| matches(&id.to_lowercase()))
+                    .map(|idx| (tab, idx))
+            })
+        };
+
+        find(&|id| id == query).or_else(|| find(&|id| id.contains(&query)))
+    }
+
+    /// Jump to the entry matching the prompt, switching tabs if needed
+    pub fn confirm_jump(&mut self) {
+        self.show_jump_input = false;
+        let query = self.text_input.value().to_string();
+        match self.find_entry(&query) {
+            Some((tab, idx)) => {
+                self.current_tab = tab;
+                self.view_state_mut(tab).list_state.select(Some(idx));
+            }
+            None => self.message = Some(format!("No entry matching '{}'", query.trim())),
+        }
+        self.text_input = TextInputState::default();
+    }
+
     pub fn enter_edit_mode(&mut self) {
         let state = self.current_view_state();
         let Some(id) = state.selected_id().map(|s| s.to_string()) else {
@@ -421,16 +595,21 @@ impl App {
                 id: String::new(),
                 name: String::new(),
                 class: loot_core::types::ItemClass::OneHandSword,
+                group: None,
+                drop_weight: None,
                 tags: Vec::new(),
-                implicit: None,
+                blocked_tags: Vec::new(),
+                implicits: Vec::new(),
+                quality_affects_implicit: false,
                 defenses: None,
                 damage: None,
                 requirements: Default::default(),
+                extra: Default::default(),
             }),
             ConfigTab::Affixes => EditingEntry::Affix(AffixConfig {
                 id: String::new(),
                 name: String::new(),
-                affix_type: loot_core::types::AffixType::Prefix,
+                affix_type: AffixType::Prefix,
                 stat: loot_core::types::StatType::AddedPhysicalDamage,
                 scope: loot_core::types::AffixScope::Local,
                 tags: Vec::new(),
@@ -442,13 +621,19 @@ impl App {
                     max: 10,
                     max_value: None,
                     min_ilvl: 1,
+                    step: None,
+                    extra_stats: Vec::new(),
                 }],
+                annul_weight: None,
+                flavor: None,
+                extra: Default::default(),
             }),
             ConfigTab::AffixPools => EditingEntry::AffixPool(AffixPoolConfig {
                 id: String::new(),
                 name: String::new(),
                 description: String::new(),
                 affixes: Vec::new(),
+                extra: Default::default(),
             }),
             ConfigTab::Currencies => EditingEntry::Currency(CurrencyConfig {
                 id: String::new(),
@@ -457,6 +642,8 @@ impl App {
                 category: String::new(),
                 requires: Default::default(),
                 effects: Default::default(),
+                stack_size: None,
+                extra: Default::default(),
             }),
             ConfigTab::Uniques => {
                 // Initialize empty recipe for new uniques
@@ -466,7 +653,10 @@ impl App {
                     name: String::new(),
                     base_type: String::new(),
                     flavor: None,
+                    drop_level: 0,
+                    required_level: 0,
                     mods: Vec::new(),
+                    extra: Default::default(),
                 })
             }
         });
@@ -538,6 +728,18 @@ impl App {
         self.refresh_ids();
     }
 
+    /// Id of the entry being edited or created
+    fn editing_id(&self) -> Option<&str> {
+        let id = match self.editing.as_ref()? {
+            EditingEntry::BaseType(e) => &e.id,
+            EditingEntry::Affix(e) => &e.id,
+            EditingEntry::AffixPool(e) => &e.id,
+            EditingEntry::Currency(e) => &e.id,
+            EditingEntry::Unique(e) => &e.id,
+        };
+        Some(id)
+    }
+
     fn apply_edit_to_config(&mut self) {
         let Some(editing) = self.editing.take() else {
             return;
@@ -632,6 +834,252 @@ impl App {
         }
     }
 
+    /// Switch between the default and colorblind themes and remember the choice
+    pub fn toggle_theme(&mut self) {
+        let theme = theme::toggle();
+        self.message = Some(
+            match theme::save_settings(Path::new(theme::SETTINGS_FILE)) {
+                Ok(()) => format!("Theme: {}", theme.name),
+                Err(e) => format!("Theme: {} (not saved: {})", theme.name, e),
+            },
+        );
+    }
+
+    pub fn cycle_mod_order(&mut self) {
+        let order = theme::cycle_mod_order().name().replace('_', " ");
+        self.message = Some(
+            match theme::save_settings(Path::new(theme::SETTINGS_FILE)) {
+                Ok(()) => format!("Modifier order: {}", order),
+                Err(e) => format!("Modifier order: {} (not saved: {})", order, e),
+            },
+        );
+    }
+
+    /// Save every dirty entry to its origin file.
+    ///
+    /// Entries sharing a file are written once. Entries with no origin file are skipped
+    /// and stay dirty (save them individually to pick a file).
+    pub fn save_all(&mut self) {
+        let mut groups: Vec<(ConfigTab, PathBuf, Vec<String>)> = Vec::new();
+        let mut skipped: Vec<String> = Vec::new();
+
+        for (tab, id) in self.dirty.dirty_entries() {
+            let Some(path) = self.origins.get_origin(tab, &id).cloned() else {
+                skipped.push(id);
+                continue;
+            };
+            // Uniques are one file per entry; other tabs share files
+            let group = groups
+                .iter_mut()
+                .find(|(t, p, _)| *t == tab && *p == path && tab != ConfigTab::Uniques);
+            match group {
+                Some((_, _, ids)) => ids.push(id),
+                None => groups.push((tab, path, vec![id])),
+            }
+        }
+
+        if groups.is_empty() && skipped.is_empty() {
+            self.message = Some("Nothing to save".to_string());
+            return;
+        }
+
+        let mut saved = 0;
+        let mut failed: Vec<String> = Vec::new();
+        for (tab, path, ids) in &groups {
+            match save_entry(&self.config, &self.origins, *tab, &ids[0], path) {
+                Ok(()) => {
+                    for id in ids {
+                        self.dirty.mark_clean(*tab, id);
+                    }
+                    saved += ids.len();
+                }
+                Err(e) => failed.push(format!("{} ({})", path.display(), e)),
+            }
+        }
+
+        let mut message = format!("Saved {} entries", saved);
+        if !failed.is_empty() {
+            message.push_str(&format!(
+                ", {} files failed: {}",
+                failed.len(),
+                failed.join("; ")
+            ));
+        }
+        if !skipped.is_empty() {
+            message.push_str(&format!(
+                ", skipped {} with no file: {}",
+                skipped.len(),
+                skipped.join(", ")
+            ));
+        }
+        self.message = Some(message);
+    }
+
+    /// Import `import/affixes.csv` and `import/base_types.csv` from the config directory.
+    /// New entries are assigned to `imported.toml` and left dirty until saved.
+    pub fn import_csv(&mut self) {
+        let import_dir = self.config_dir.join("import");
+        let mut imported = ImportedEntries::default();
+        let mut errors: Vec<String> = Vec::new();
+        let mut found_any = false;
+
+        let affixes_path = import_dir.join("affixes.csv");
+        if let Ok(text) = std::fs::read_to_string(&affixes_path) {
+            found_any = true;
+            match import::parse_affixes_csv(&text) {
+                Ok(affixes) => imported.affixes = affixes,
+                Err(errs) => errors.extend(errs.iter().map(|e| format!("affixes.csv {}", e))),
+            }
+        }
+
+        let base_types_path = import_dir.join("base_types.csv");
+        if let Ok(text) = std::fs::read_to_string(&base_types_path) {
+            found_any = true;
+            match import::parse_base_types_csv(&text) {
+                Ok(base_types) => imported.base_types = base_types,
+                Err(errs) => errors.extend(errs.iter().map(|e| format!("base_types.csv {}", e))),
+            }
+        }
+
+        if !found_any {
+            self.message = Some(format!("No CSV files found in {}", import_dir.display()));
+            return;
+        }
+        if let Some(first) = errors.first() {
+            self.message = Some(format!(
+                "Import failed ({} errors): {}",
+                errors.len(),
+                first
+            ));
+            return;
+        }
+
+        let affix_ids: Vec<String> = imported.affixes.iter().map(|a| a.id.clone()).collect();
+        let base_type_ids: Vec<String> = imported.base_types.iter().map(|b| b.id.clone()).collect();
+
+        let mut merged = self.config.clone();
+        imported.merge_into(&mut merged);
+        if let Err(errs) = merged.validate() {
+            self.message = Some(format!(
+                "Imported config is invalid ({} errors): {}",
+                errs.len(),
+                errs[0]
+            ));
+            return;
+        }
+        self.config = merged;
+
+        for id in &affix_ids {
+            if self.origins.get_origin(ConfigTab::Affixes, id).is_none() {
+                let path = self.config_dir.join("affixes").join("imported.toml");
+                self.origins.set_origin(ConfigTab::Affixes, id, path);
+            }
+            self.dirty.mark_dirty(ConfigTab::Affixes, id);
+        }
+        for id in &base_type_ids {
+            if self.origins.get_origin(ConfigTab::BaseTypes, id).is_none() {
+                let path = self.config_dir.join("base_types").join("imported.toml");
+                self.origins.set_origin(ConfigTab::BaseTypes, id, path);
+            }
+            self.dirty.mark_dirty(ConfigTab::BaseTypes, id);
+        }
+
+        self.refresh_ids();
+        self.message = Some(format!(
+            "Imported {} affixes and {} base types (unsaved)",
+            affix_ids.len(),
+            base_type_ids.len()
+        ));
+    }
+
+    /// Open the file picker to move the selected entry to another file of its tab
+    pub fn open_move_picker(&mut self) {
+        let Some(id) = self
+            .current_view_state()
+            .selected_id()
+            .map(|s| s.to_string())
+        else {
+            self.message = Some("No entry selected".to_string());
+            return;
+        };
+        if self.current_tab == ConfigTab::Uniques {
+            self.message = Some("Uniques have one file each and can't be moved".to_string());
+            return;
+        }
+        if self.origins.get_origin(self.current_tab, &id).is_none() {
+            self.message = Some("No origin file for this entry".to_string());
+            return;
+        }
+
+        self.show_file_picker_for_current_tab();
+        self.moving_entry = Some(id);
+    }
+
+    /// Move an entry of the current tab to `path`, writing the new file with it and then
+    /// the old file without it. If either write fails the new file is put back as it was,
+    /// so the entry never ends up in both files.
+    pub fn move_entry_to_file(&mut self, id: &str, path: PathBuf) {
+        self.show_file_picker = false;
+        self.moving_entry = None;
+
+        let Some(old_path) = self.origins.get_origin(self.current_tab, id).cloned() else {
+            self.message = Some("No origin file for this entry".to_string());
+            return;
+        };
+        if old_path == path {
+            self.message = Some(format!("{} is already in {}", id, path.display()));
+            return;
+        }
+
+        let snapshot = match FileSnapshot::take(&path) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.message = Some(format!("Move failed: {}", e));
+                return;
+            }
+        };
+
+        // Origins decide which file each entry is written to, so switching it first
+        // drops the entry from the old file and adds it to the new one
+        self.origins.set_origin(self.current_tab, id, path.clone());
+        let result =
+            save_entry(&self.config, &self.origins, self.current_tab, id, &path).and_then(|()| {
+                save_entry(&self.config, &self.origins, self.current_tab, id, &old_path)
+            });
+
+        match result {
+            Ok(()) => {
+                self.dirty.mark_clean(self.current_tab, id);
+                self.message = Some(format!(
+                    "Moved {} from {} to {}",
+                    id,
+                    old_path.display(),
+                    path.display()
+                ));
+            }
+            Err(e) => {
+                self.origins.set_origin(self.current_tab, id, old_path);
+                self.message = Some(match snapshot.restore() {
+                    Ok(()) => format!("Move failed: {}", e),
+                    Err(restore_err) => format!(
+                        "Move failed: {} (and restoring {} failed: {})",
+                        e,
+                        path.display(),
+                        restore_err
+                    ),
+                });
+            }
+        }
+    }
+
+    /// Use the picked file for whatever the picker was opened for
+    fn file_picker_confirm(&mut self, path: PathBuf) {
+        match self.moving_entry.take() {
+            Some(id) => self.move_entry_to_file(&id, path),
+            None => self.complete_save_with_file(path),
+        }
+    }
+
     fn show_file_picker_for_current_tab(&mut self) {
         let subdir = match self.current_tab {
             ConfigTab::BaseTypes => "base_types",
@@ -649,7 +1097,7 @@ impl App {
                     entries
                         .filter_map(|e| e.ok())
                         .map(|e| e.path())
-                        .filter(|p| p.extension().map_or(false, |ext| ext == "toml"))
+                        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
                         .collect()
                 })
                 .unwrap_or_default()
@@ -660,6 +1108,7 @@ impl App {
         self.file_picker_files.sort();
         self.file_picker_state.select(Some(0));
         self.show_file_picker = true;
+        self.moving_entry = None;
     }
 
     pub fn file_picker_up(&mut self) {
@@ -681,7 +1130,7 @@ impl App {
     pub fn file_picker_select(&mut self) {
         if let Some(idx) = self.file_picker_state.selected() {
             if let Some(path) = self.file_picker_files.get(idx).cloned() {
-                self.complete_save_with_file(path);
+                self.file_picker_confirm(path);
             }
         }
     }
@@ -712,7 +1161,7 @@ impl App {
         let path = self.config_dir.join(subdir).join(filename);
         self.show_new_file_input = false;
         self.show_file_picker = false;
-        self.complete_save_with_file(path);
+        self.file_picker_confirm(path);
     }
 
     fn complete_save_with_file(&mut self, path: PathBuf) {
@@ -775,11 +1224,11 @@ impl App {
 
     fn get_field_count(&self) -> usize {
         match &self.editing {
-            Some(EditingEntry::BaseType(_)) => 8, // id, name, class, tags, implicit, defenses, damage, requirements
-            Some(EditingEntry::Affix(_)) => 8, // id, name, type, stat, scope, tags, allowed_classes, tiers
+            Some(EditingEntry::BaseType(_)) => 10, // id, name, class, tags, implicit, defenses, damage, requirements, group, drop_weight
+            Some(EditingEntry::Affix(_)) => 9, // id, name, type, stat, scope, tags, allowed_classes, tiers, flavor
             Some(EditingEntry::AffixPool(_)) => 4, // id, name, description, affixes
             Some(EditingEntry::Currency(_)) => 6, // id, name, description, category, requires, effects
-            Some(EditingEntry::Unique(_)) => 6,   // id, name, base_type, flavor, mods, recipe
+            Some(EditingEntry::Unique(_)) => 8, // id, name, base_type, flavor, mods, recipe, drop_level, required_level
             None => 0,
         }
     }
@@ -788,6 +1237,7 @@ impl App {
         let field_idx = self.current_view_state().field_index;
         let value = self.get_field_value(field_idx);
         self.text_input = TextInputState::new(value);
+        self.refresh_enum_picker();
     }
 
     fn get_field_value(&self, field_idx: usize) -> String {
@@ -804,6 +1254,8 @@ impl App {
                 1 => bt.name.clone(),
                 2 => format!("{:?}", bt.class),
                 3 => String::new(), // List field - start empty for adding
+                8 => bt.group.clone().unwrap_or_default(),
+                9 => bt.drop_weight.map(|w| w.to_string()).unwrap_or_default(),
                 _ => String::new(),
             },
             Some(EditingEntry::Affix(affix)) => match field_idx {
@@ -814,6 +1266,7 @@ impl App {
                 4 => format!("{:?}", affix.scope),
                 5 => String::new(), // List field - start empty for adding (tags)
                 6 => String::new(), // List field - start empty for adding (allowed_classes)
+                8 => affix.flavor.clone().unwrap_or_default(),
                 _ => String::new(),
             },
             Some(EditingEntry::Currency(curr)) => match field_idx {
@@ -828,6 +1281,8 @@ impl App {
                 1 => uniq.name.clone(),
                 2 => uniq.base_type.clone(),
                 3 => uniq.flavor.clone().unwrap_or_default(),
+                6 => uniq.drop_level.to_string(),
+                7 => uniq.required_level.to_string(),
                 _ => String::new(),
             },
             None => String::new(),
@@ -859,6 +1314,11 @@ impl App {
                         .filter(|s| !s.is_empty())
                         .collect()
                 }
+                8 => {
+                    let group = value.trim();
+                    bt.group = (!group.is_empty()).then(|| group.to_string());
+                }
+                9 => bt.drop_weight = value.trim().parse().ok(),
                 _ => {}
             },
             Some(EditingEntry::Affix(affix)) => match field_idx {
@@ -871,6 +1331,7 @@ impl App {
                         .filter(|s| !s.is_empty())
                         .collect()
                 }
+                8 => affix.flavor = if value.is_empty() { None } else { Some(value) },
                 _ => {}
             },
             Some(EditingEntry::Currency(curr)) => match field_idx {
@@ -885,6 +1346,8 @@ impl App {
                 1 => uniq.name = value,
                 2 => uniq.base_type = value,
                 3 => uniq.flavor = if value.is_empty() { None } else { Some(value) },
+                6 => uniq.drop_level = value.trim().parse().unwrap_or(0),
+                7 => uniq.required_level = value.trim().parse().unwrap_or(0),
                 _ => {}
             },
             None => {}
@@ -904,6 +1367,7 @@ impl App {
             let field_idx = self.current_view_state().field_index;
             self.set_field_value(field_idx, value);
         }
+        self.refresh_enum_picker();
     }
 
     pub fn text_input_backspace(&mut self) {
@@ -916,6 +1380,7 @@ impl App {
             let field_idx = self.current_view_state().field_index;
             self.set_field_value(field_idx, value);
         }
+        self.refresh_enum_picker();
     }
 
     pub fn text_input_delete(&mut self) {
@@ -928,6 +1393,7 @@ impl App {
             let field_idx = self.current_view_state().field_index;
             self.set_field_value(field_idx, value);
         }
+        self.refresh_enum_picker();
     }
 
     pub fn text_input_left(&mut self) {
@@ -969,10 +1435,10 @@ impl App {
     pub fn is_nested_field(&self) -> bool {
         let field_idx = self.current_view_state().field_index;
         match &self.editing {
-            Some(EditingEntry::BaseType(_)) => field_idx >= 4, // implicit, defenses, damage, requirements
-            Some(EditingEntry::Affix(_)) => field_idx == 7,    // tiers
-            Some(EditingEntry::Currency(_)) => field_idx >= 4, // requires, effects
-            Some(EditingEntry::Unique(_)) => field_idx >= 4,   // mods, recipe
+            Some(EditingEntry::BaseType(_)) => (4..=7).contains(&field_idx), // implicit, defenses, damage, requirements
+            Some(EditingEntry::Affix(_)) => field_idx == 7,                  // tiers
+            Some(EditingEntry::Currency(_)) => field_idx >= 4,               // requires, effects
+            Some(EditingEntry::Unique(_)) => field_idx == 4 || field_idx == 5, // mods, recipe
             _ => false,
         }
     }
@@ -993,9 +1459,36 @@ impl App {
         }
     }
 
+    /// Whether the focused field is the affix stat, which gets a fuzzy-ranked picker
+    fn is_stat_field(&self) -> bool {
+        matches!(self.editing, Some(EditingEntry::Affix(_)))
+            && self.current_view_state().field_index == 3
+    }
+
+    /// Re-rank the stat picker options against the current text input
+    fn refresh_enum_picker(&mut self) {
+        self.enum_picker_index = 0;
+        let query = self.text_input.value().trim();
+        self.enum_picker_options = if self.is_stat_field() && !query.is_empty() {
+            let stat_names: Vec<&str> = loot_core::types::StatType::all()
+                .iter()
+                .map(|s| s.name())
+                .collect();
+            fuzzy_rank(query, &stat_names)
+                .into_iter()
+                .map(String::from)
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
     pub fn enum_picker_select(&mut self) {
-        // Use text input value for enum selection
-        let value = self.text_input.value().trim().to_string();
+        // Prefer the highlighted fuzzy match, falling back to the typed value
+        let value = match self.enum_picker_options.get(self.enum_picker_index) {
+            Some(option) => option.clone(),
+            None => self.text_input.value().trim().to_string(),
+        };
         if value.is_empty() {
             return;
         }
@@ -1007,7 +1500,7 @@ impl App {
         let result = match &mut self.editing {
             Some(EditingEntry::BaseType(bt)) if field_idx == 2 => {
                 // Parse ItemClass
-                match Self::parse_item_class(value) {
+                match value.parse::<loot_core::types::ItemClass>() {
                     Ok(class) => {
                         bt.class = class;
                         Ok(format!("Class set to {:?}", class))
@@ -1027,7 +1520,7 @@ impl App {
             }
             Some(EditingEntry::Affix(affix)) if field_idx == 3 => {
                 // Parse StatType
-                match Self::parse_stat_type(value) {
+                match value.parse::<loot_core::types::StatType>() {
                     Ok(stat) => {
                         affix.stat = stat;
                         Ok(format!("Stat set to {:?}", stat))
@@ -1054,6 +1547,7 @@ impl App {
                     self.message = Some(msg);
                 }
                 self.text_input = TextInputState::new(String::new());
+                self.refresh_enum_picker();
             }
             Err(msg) => {
                 self.message = Some(msg);
@@ -1061,29 +1555,6 @@ impl App {
         }
     }
 
-    fn parse_item_class(s: &str) -> Result<loot_core::types::ItemClass, ()> {
-        use loot_core::types::ItemClass;
-        match s {
-            "OneHandSword" => Ok(ItemClass::OneHandSword),
-            "OneHandAxe" => Ok(ItemClass::OneHandAxe),
-            "OneHandMace" => Ok(ItemClass::OneHandMace),
-            "Dagger" => Ok(ItemClass::Dagger),
-            "Claw" => Ok(ItemClass::Claw),
-            "Wand" => Ok(ItemClass::Wand),
-            "TwoHandSword" => Ok(ItemClass::TwoHandSword),
-            "TwoHandAxe" => Ok(ItemClass::TwoHandAxe),
-            "TwoHandMace" => Ok(ItemClass::TwoHandMace),
-            "Bow" => Ok(ItemClass::Bow),
-            "Staff" => Ok(ItemClass::Staff),
-            "Shield" => Ok(ItemClass::Shield),
-            "Helmet" => Ok(ItemClass::Helmet),
-            "BodyArmour" => Ok(ItemClass::BodyArmour),
-            "Gloves" => Ok(ItemClass::Gloves),
-            "Boots" => Ok(ItemClass::Boots),
-            _ => Err(()),
-        }
-    }
-
     fn parse_affix_type(s: &str) -> Result<loot_core::types::AffixType, ()> {
         use loot_core::types::AffixType;
         match s {
@@ -1102,145 +1573,11 @@ impl App {
         }
     }
 
-    fn parse_rarity(s: &str) -> Result<loot_core::types::Rarity, ()> {
-        use loot_core::types::Rarity;
-        match s {
-            "Normal" => Ok(Rarity::Normal),
-            "Magic" => Ok(Rarity::Magic),
-            "Rare" => Ok(Rarity::Rare),
-            "Unique" => Ok(Rarity::Unique),
-            _ => Err(()),
-        }
-    }
-
-    pub fn get_all_rarities() -> Vec<&'static str> {
-        vec!["Normal", "Magic", "Rare", "Unique"]
-    }
-
     pub fn get_all_affix_pool_ids(&self) -> Vec<String> {
-        let mut ids: Vec<String> = self.config.affix_pools.keys().cloned().collect();
-        ids.sort();
-        ids
-    }
-
-    fn parse_stat_type(s: &str) -> Result<loot_core::types::StatType, ()> {
-        use loot_core::types::StatType;
-        match s {
-            "AddedPhysicalDamage" => Ok(StatType::AddedPhysicalDamage),
-            "AddedFireDamage" => Ok(StatType::AddedFireDamage),
-            "AddedColdDamage" => Ok(StatType::AddedColdDamage),
-            "AddedLightningDamage" => Ok(StatType::AddedLightningDamage),
-            "AddedChaosDamage" => Ok(StatType::AddedChaosDamage),
-            "IncreasedPhysicalDamage" => Ok(StatType::IncreasedPhysicalDamage),
-            "IncreasedFireDamage" => Ok(StatType::IncreasedFireDamage),
-            "IncreasedColdDamage" => Ok(StatType::IncreasedColdDamage),
-            "IncreasedLightningDamage" => Ok(StatType::IncreasedLightningDamage),
-            "IncreasedElementalDamage" => Ok(StatType::IncreasedElementalDamage),
-            "IncreasedChaosDamage" => Ok(StatType::IncreasedChaosDamage),
-            "IncreasedAttackSpeed" => Ok(StatType::IncreasedAttackSpeed),
-            "IncreasedCriticalChance" => Ok(StatType::IncreasedCriticalChance),
-            "IncreasedCriticalDamage" => Ok(StatType::IncreasedCriticalDamage),
-            "PoisonDamageOverTime" => Ok(StatType::PoisonDamageOverTime),
-            "IncreasedPoisonDuration" => Ok(StatType::IncreasedPoisonDuration),
-            "PoisonMagnitude" => Ok(StatType::PoisonMagnitude),
-            "PoisonMaxStacks" => Ok(StatType::PoisonMaxStacks),
-            "ConvertPhysicalToPoison" => Ok(StatType::ConvertPhysicalToPoison),
-            "ConvertFireToPoison" => Ok(StatType::ConvertFireToPoison),
-            "ConvertColdToPoison" => Ok(StatType::ConvertColdToPoison),
-            "ConvertLightningToPoison" => Ok(StatType::ConvertLightningToPoison),
-            "ConvertChaosToPoison" => Ok(StatType::ConvertChaosToPoison),
-            "BleedDamageOverTime" => Ok(StatType::BleedDamageOverTime),
-            "IncreasedBleedDuration" => Ok(StatType::IncreasedBleedDuration),
-            "BleedMagnitude" => Ok(StatType::BleedMagnitude),
-            "BleedMaxStacks" => Ok(StatType::BleedMaxStacks),
-            "ConvertPhysicalToBleed" => Ok(StatType::ConvertPhysicalToBleed),
-            "ConvertFireToBleed" => Ok(StatType::ConvertFireToBleed),
-            "ConvertColdToBleed" => Ok(StatType::ConvertColdToBleed),
-            "ConvertLightningToBleed" => Ok(StatType::ConvertLightningToBleed),
-            "ConvertChaosToBleed" => Ok(StatType::ConvertChaosToBleed),
-            "BurnDamageOverTime" => Ok(StatType::BurnDamageOverTime),
-            "IncreasedBurnDuration" => Ok(StatType::IncreasedBurnDuration),
-            "BurnMagnitude" => Ok(StatType::BurnMagnitude),
-            "BurnMaxStacks" => Ok(StatType::BurnMaxStacks),
-            "ConvertPhysicalToBurn" => Ok(StatType::ConvertPhysicalToBurn),
-            "ConvertFireToBurn" => Ok(StatType::ConvertFireToBurn),
-            "ConvertColdToBurn" => Ok(StatType::ConvertColdToBurn),
-            "ConvertLightningToBurn" => Ok(StatType::ConvertLightningToBurn),
-            "ConvertChaosToBurn" => Ok(StatType::ConvertChaosToBurn),
-            "IncreasedFreezeDuration" => Ok(StatType::IncreasedFreezeDuration),
-            "FreezeMagnitude" => Ok(StatType::FreezeMagnitude),
-            "FreezeMaxStacks" => Ok(StatType::FreezeMaxStacks),
-            "ConvertPhysicalToFreeze" => Ok(StatType::ConvertPhysicalToFreeze),
-            "ConvertFireToFreeze" => Ok(StatType::ConvertFireToFreeze),
-            "ConvertColdToFreeze" => Ok(StatType::ConvertColdToFreeze),
-            "ConvertLightningToFreeze" => Ok(StatType::ConvertLightningToFreeze),
-            "ConvertChaosToFreeze" => Ok(StatType::ConvertChaosToFreeze),
-            "IncreasedChillDuration" => Ok(StatType::IncreasedChillDuration),
-            "ChillMagnitude" => Ok(StatType::ChillMagnitude),
-            "ChillMaxStacks" => Ok(StatType::ChillMaxStacks),
-            "ConvertPhysicalToChill" => Ok(StatType::ConvertPhysicalToChill),
-            "ConvertFireToChill" => Ok(StatType::ConvertFireToChill),
-            "ConvertColdToChill" => Ok(StatType::ConvertColdToChill),
-            "ConvertLightningToChill" => Ok(StatType::ConvertLightningToChill),
-            "ConvertChaosToChill" => Ok(StatType::ConvertChaosToChill),
-            "IncreasedStaticDuration" => Ok(StatType::IncreasedStaticDuration),
-            "StaticMagnitude" => Ok(StatType::StaticMagnitude),
-            "StaticMaxStacks" => Ok(StatType::StaticMaxStacks),
-            "ConvertPhysicalToStatic" => Ok(StatType::ConvertPhysicalToStatic),
-            "ConvertFireToStatic" => Ok(StatType::ConvertFireToStatic),
-            "ConvertColdToStatic" => Ok(StatType::ConvertColdToStatic),
-            "ConvertLightningToStatic" => Ok(StatType::ConvertLightningToStatic),
-            "ConvertChaosToStatic" => Ok(StatType::ConvertChaosToStatic),
-            "IncreasedFearDuration" => Ok(StatType::IncreasedFearDuration),
-            "FearMagnitude" => Ok(StatType::FearMagnitude),
-            "FearMaxStacks" => Ok(StatType::FearMaxStacks),
-            "ConvertPhysicalToFear" => Ok(StatType::ConvertPhysicalToFear),
-            "ConvertFireToFear" => Ok(StatType::ConvertFireToFear),
-            "ConvertColdToFear" => Ok(StatType::ConvertColdToFear),
-            "ConvertLightningToFear" => Ok(StatType::ConvertLightningToFear),
-            "ConvertChaosToFear" => Ok(StatType::ConvertChaosToFear),
-            "IncreasedSlowDuration" => Ok(StatType::IncreasedSlowDuration),
-            "SlowMagnitude" => Ok(StatType::SlowMagnitude),
-            "SlowMaxStacks" => Ok(StatType::SlowMaxStacks),
-            "ConvertPhysicalToSlow" => Ok(StatType::ConvertPhysicalToSlow),
-            "ConvertFireToSlow" => Ok(StatType::ConvertFireToSlow),
-            "ConvertColdToSlow" => Ok(StatType::ConvertColdToSlow),
-            "ConvertLightningToSlow" => Ok(StatType::ConvertLightningToSlow),
-            "ConvertChaosToSlow" => Ok(StatType::ConvertChaosToSlow),
-            "AddedArmour" => Ok(StatType::AddedArmour),
-            "AddedEvasion" => Ok(StatType::AddedEvasion),
-            "AddedEnergyShield" => Ok(StatType::AddedEnergyShield),
-            "IncreasedArmour" => Ok(StatType::IncreasedArmour),
-            "IncreasedEvasion" => Ok(StatType::IncreasedEvasion),
-            "IncreasedEnergyShield" => Ok(StatType::IncreasedEnergyShield),
-            "AddedStrength" => Ok(StatType::AddedStrength),
-            "AddedDexterity" => Ok(StatType::AddedDexterity),
-            "AddedConstitution" => Ok(StatType::AddedConstitution),
-            "AddedIntelligence" => Ok(StatType::AddedIntelligence),
-            "AddedWisdom" => Ok(StatType::AddedWisdom),
-            "AddedCharisma" => Ok(StatType::AddedCharisma),
-            "AddedAllAttributes" => Ok(StatType::AddedAllAttributes),
-            "AddedLife" => Ok(StatType::AddedLife),
-            "AddedMana" => Ok(StatType::AddedMana),
-            "IncreasedLife" => Ok(StatType::IncreasedLife),
-            "IncreasedMana" => Ok(StatType::IncreasedMana),
-            "LifeRegeneration" => Ok(StatType::LifeRegeneration),
-            "ManaRegeneration" => Ok(StatType::ManaRegeneration),
-            "LifeOnHit" => Ok(StatType::LifeOnHit),
-            "LifeLeech" => Ok(StatType::LifeLeech),
-            "ManaLeech" => Ok(StatType::ManaLeech),
-            "FireResistance" => Ok(StatType::FireResistance),
-            "ColdResistance" => Ok(StatType::ColdResistance),
-            "LightningResistance" => Ok(StatType::LightningResistance),
-            "ChaosResistance" => Ok(StatType::ChaosResistance),
-            "AllResistances" => Ok(StatType::AllResistances),
-            "AddedAccuracy" => Ok(StatType::AddedAccuracy),
-            "IncreasedAccuracy" => Ok(StatType::IncreasedAccuracy),
-            "IncreasedMovementSpeed" => Ok(StatType::IncreasedMovementSpeed),
-            "IncreasedItemRarity" => Ok(StatType::IncreasedItemRarity),
-            "IncreasedItemQuantity" => Ok(StatType::IncreasedItemQuantity),
-            _ => Err(()),
-        }
+        self.config
+            .affix_pools_sorted()
+            .map(|p| p.id.clone())
+            .collect()
     }
 
     fn parse_damage_type(s: &str) -> Result<loot_core::types::DamageType, ()> {
@@ -1278,9 +1615,7 @@ impl App {
 
     /// Get all affix IDs from config
     pub fn get_all_affix_ids(&self) -> Vec<String> {
-        let mut ids: Vec<String> = self.config.affixes.keys().cloned().collect();
-        ids.sort();
-        ids
+        self.config.affixes_sorted().map(|a| a.id.clone()).collect()
     }
 
     /// Check if an affix ID exists in the config
@@ -1288,147 +1623,19 @@ impl App {
         self.config.affixes.contains_key(affix_id)
     }
 
-    /// Get all ItemClass variants (from types.rs enum)
-    pub fn get_all_classes() -> Vec<&'static str> {
-        vec![
-            // One-handed weapons
-            "OneHandSword",
-            "OneHandAxe",
-            "OneHandMace",
-            "Dagger",
-            "Claw",
-            "Wand",
-            // Two-handed weapons
-            "TwoHandSword",
-            "TwoHandAxe",
-            "TwoHandMace",
-            "Bow",
-            "Staff",
-            // Off-hand
-            "Shield",
-            // Armour
-            "Helmet",
-            "BodyArmour",
-            "Gloves",
-            "Boots",
-        ]
-    }
-
-    pub fn get_all_stat_types() -> Vec<&'static str> {
-        vec![
-            // Flat additions
-            "AddedPhysicalDamage",
-            "AddedFireDamage",
-            "AddedColdDamage",
-            "AddedLightningDamage",
-            "AddedChaosDamage",
-            // Percentage increases
-            "IncreasedPhysicalDamage",
-            "IncreasedFireDamage",
-            "IncreasedColdDamage",
-            "IncreasedLightningDamage",
-            "IncreasedElementalDamage",
-            "IncreasedChaosDamage",
-            "IncreasedAttackSpeed",
-            "IncreasedCriticalChance",
-            "IncreasedCriticalDamage",
-            // Status Effects
-            "PoisonDamageOverTime",
-            "IncreasedPoisonDuration",
-            "PoisonMagnitude",
-            "PoisonMaxStacks",
-            "BleedDamageOverTime",
-            "IncreasedBleedDuration",
-            "BleedMagnitude",
-            "BleedMaxStacks",
-            "BurnDamageOverTime",
-            "IncreasedBurnDuration",
-            "BurnMagnitude",
-            "BurnMaxStacks",
-            "IncreasedFreezeDuration",
-            "FreezeMagnitude",
-            "FreezeMaxStacks",
-            "IncreasedChillDuration",
-            "ChillMagnitude",
-            "ChillMaxStacks",
-            "IncreasedStaticDuration",
-            "StaticMagnitude",
-            "StaticMaxStacks",
-            "IncreasedFearDuration",
-            "FearMagnitude",
-            "FearMaxStacks",
-            "IncreasedSlowDuration",
-            "SlowMagnitude",
-            "SlowMaxStacks",
-            // Defenses
-            "AddedArmour",
-            "AddedEvasion",
-            "AddedEnergyShield",
-            "IncreasedArmour",
-            "IncreasedEvasion",
-            "IncreasedEnergyShield",
-            // Attributes
-            "AddedStrength",
-            "AddedDexterity",
-            "AddedConstitution",
-            "AddedIntelligence",
-            "AddedWisdom",
-            "AddedCharisma",
-            "AddedAllAttributes",
-            // Life and resources
-            "AddedLife",
-            "AddedMana",
-            "IncreasedLife",
-            "IncreasedMana",
-            "LifeRegeneration",
-            "ManaRegeneration",
-            "LifeOnHit",
-            "LifeLeech",
-            "ManaLeech",
-            // Resistances
-            "FireResistance",
-            "ColdResistance",
-            "LightningResistance",
-            "ChaosResistance",
-            "AllResistances",
-            // Accuracy and utility
-            "AddedAccuracy",
-            "IncreasedAccuracy",
-            "IncreasedMovementSpeed",
-            "IncreasedItemRarity",
-            "IncreasedItemQuantity",
-        ]
-    }
-
     fn is_valid_tag(&self, tag: &str) -> bool {
         let all_tags = self.get_all_tags();
         all_tags.iter().any(|t| t == tag)
     }
 
+    #[allow(dead_code)]
     pub fn get_enum_options(&self) -> Vec<String> {
         let field_idx = self.current_view_state().field_index;
         match &self.editing {
-            Some(EditingEntry::BaseType(_)) if field_idx == 2 => vec![
-                "OneHandSword",
-                "OneHandAxe",
-                "OneHandMace",
-                "Dagger",
-                "Claw",
-                "Wand",
-                "TwoHandSword",
-                "TwoHandAxe",
-                "TwoHandMace",
-                "Bow",
-                "Staff",
-                "Shield",
-                "Helmet",
-                "BodyArmour",
-                "Gloves",
-                "Boots",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
+            Some(EditingEntry::BaseType(_)) if field_idx == 2 => loot_core::types::ItemClass::all()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
             Some(EditingEntry::Affix(_)) if field_idx == 2 => vec!["Prefix", "Suffix"]
                 .into_iter()
                 .map(String::from)
@@ -1437,81 +1644,10 @@ impl App {
                 .into_iter()
                 .map(String::from)
                 .collect(),
-            Some(EditingEntry::Affix(_)) if field_idx == 3 => vec![
-                "AddedPhysicalDamage",
-                "AddedFireDamage",
-                "AddedColdDamage",
-                "AddedLightningDamage",
-                "AddedChaosDamage",
-                "IncreasedPhysicalDamage",
-                "IncreasedElementalDamage",
-                "IncreasedChaosDamage",
-                "IncreasedAttackSpeed",
-                "IncreasedCriticalChance",
-                "IncreasedCriticalDamage",
-                "PoisonDamageOverTime",
-                "IncreasedPoisonDuration",
-                "PoisonMagnitude",
-                "PoisonMaxStacks",
-                "BleedDamageOverTime",
-                "IncreasedBleedDuration",
-                "BleedMagnitude",
-                "BleedMaxStacks",
-                "BurnDamageOverTime",
-                "IncreasedBurnDuration",
-                "BurnMagnitude",
-                "BurnMaxStacks",
-                "IncreasedFreezeDuration",
-                "FreezeMagnitude",
-                "FreezeMaxStacks",
-                "IncreasedChillDuration",
-                "ChillMagnitude",
-                "ChillMaxStacks",
-                "IncreasedStaticDuration",
-                "StaticMagnitude",
-                "StaticMaxStacks",
-                "IncreasedFearDuration",
-                "FearMagnitude",
-                "FearMaxStacks",
-                "IncreasedSlowDuration",
-                "SlowMagnitude",
-                "SlowMaxStacks",
-                "AddedArmour",
-                "AddedEvasion",
-                "AddedEnergyShield",
-                "IncreasedArmour",
-                "IncreasedEvasion",
-                "IncreasedEnergyShield",
-                "AddedStrength",
-                "AddedDexterity",
-                "AddedConstitution",
-                "AddedIntelligence",
-                "AddedWisdom",
-                "AddedCharisma",
-                "AddedAllAttributes",
-                "AddedLife",
-                "AddedMana",
-                "IncreasedLife",
-                "IncreasedMana",
-                "LifeRegeneration",
-                "ManaRegeneration",
-                "LifeOnHit",
-                "LifeLeech",
-                "ManaLeech",
-                "FireResistance",
-                "ColdResistance",
-                "LightningResistance",
-                "ChaosResistance",
-                "AllResistances",
-                "AddedAccuracy",
-                "IncreasedAccuracy",
-                "IncreasedMovementSpeed",
-                "IncreasedItemRarity",
-                "IncreasedItemQuantity",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
+            Some(EditingEntry::Affix(_)) if field_idx == 3 => loot_core::types::StatType::all()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
             _ => Vec::new(),
         }
     }
@@ -1565,7 +1701,7 @@ impl App {
         // Pre-validate class for BaseType
         let class_result = match &self.editing {
             Some(EditingEntry::BaseType(_)) if field_idx == 2 => {
-                Some(Self::parse_item_class(&value))
+                Some(value.parse::<loot_core::types::ItemClass>())
             }
             _ => None,
         };
@@ -1615,7 +1751,7 @@ impl App {
             }
             Some(EditingEntry::Affix(affix)) if field_idx == 6 => {
                 // Parse ItemClass from string
-                if let Ok(class) = Self::parse_item_class(&value) {
+                if let Ok(class) = value.parse::<loot_core::types::ItemClass>() {
                     if !affix.allowed_classes.contains(&class) {
                         affix.allowed_classes.push(class);
                         self.text_input = TextInputState::new(String::new());
@@ -1901,7 +2037,7 @@ impl App {
             Some(EditingEntry::Unique(uniq)) if field_idx == 4 => uniq.mods.len(),
             Some(EditingEntry::Unique(_)) if field_idx == 5 => 3, // recipe: weight, required_affixes, mappings
             Some(EditingEntry::Affix(affix)) if field_idx == 7 => affix.tiers.len(),
-            Some(EditingEntry::BaseType(_)) if field_idx == 4 => 1, // implicit is single item
+            Some(EditingEntry::BaseType(bt)) if field_idx == 4 => bt.implicits.len(),
             Some(EditingEntry::BaseType(_)) if field_idx == 5 => 3, // defenses: armour, evasion, es
             Some(EditingEntry::BaseType(bt)) if field_idx == 6 => {
                 // damage: 3 stats + 1 header + damage entries
@@ -1909,7 +2045,7 @@ impl App {
                 4 + damage_count // attack_speed, crit_chance, spell_eff, "Damage Types:" header, + entries
             }
             Some(EditingEntry::BaseType(_)) if field_idx == 7 => 1, // requirements is single item (edited as one)
-            Some(EditingEntry::Currency(_)) if field_idx == 4 => 3, // requires: rarities, has_affix, has_affix_slot
+            Some(EditingEntry::Currency(_)) if field_idx == 4 => 4, // requires: rarities, has_affix, has_affix_slot, required_tags
             Some(EditingEntry::Currency(_)) if field_idx == 5 => 8, // effects: multiple fields
             _ => 0,
         }
@@ -1943,6 +2079,8 @@ impl App {
                     max: 10,
                     max_value: None,
                     min_ilvl: 1,
+                    step: None,
+                    extra_stats: Vec::new(),
                 });
                 let new_idx = affix.tiers.len() - 1;
                 self.current_view_state_mut().nested_index = new_idx;
@@ -1951,6 +2089,20 @@ impl App {
                 self.text_input = TextInputState::new(text);
                 self.current_view_state_mut().nested_depth = 2;
             }
+            Some(EditingEntry::BaseType(bt)) if field_idx == 4 => {
+                // Add a new implicit with default values
+                bt.implicits.push(loot_core::config::ImplicitConfig {
+                    stat: loot_core::types::StatType::AddedLife,
+                    min: 1,
+                    max: 10,
+                    weight: None,
+                });
+                let new_idx = bt.implicits.len() - 1;
+                self.current_view_state_mut().nested_index = new_idx;
+                // Enter edit mode for the new item
+                self.text_input = TextInputState::new("AddedLife 1 10".to_string());
+                self.current_view_state_mut().nested_depth = 2;
+            }
             Some(EditingEntry::BaseType(bt)) if field_idx == 6 => {
                 // Add a new damage entry
                 let damage = bt
@@ -1987,7 +2139,7 @@ impl App {
                     self.message = Some("Enter a rarity: Normal, Magic, Rare, Unique".to_string());
                     return;
                 }
-                match Self::parse_rarity(&value) {
+                match value.parse::<loot_core::types::Rarity>() {
                     Ok(rarity) => {
                         if curr.requires.rarities.contains(&rarity) {
                             self.message = Some(format!("{:?} already in list", rarity));
@@ -2019,7 +2171,7 @@ impl App {
                     self.message = Some("Enter at least a StatType".to_string());
                     return;
                 }
-                match Self::parse_stat_type(parts[0]) {
+                match parts[0].parse::<loot_core::types::StatType>() {
                     Ok(stat) => {
                         let affix_type = parts.get(1).and_then(|s| Self::parse_affix_type(s).ok());
                         let min_tier = parts.get(2).and_then(|s| s.parse().ok()).unwrap_or(1);
@@ -2064,7 +2216,7 @@ impl App {
                     self.message = Some("Enter at least: StatType mod_index".to_string());
                     return;
                 }
-                match Self::parse_stat_type(parts[0]) {
+                match parts[0].parse::<loot_core::types::StatType>() {
                     Ok(from_stat) => {
                         let to_mod_index = match parts[1].parse::<usize>() {
                             Ok(idx) => idx,
@@ -2203,7 +2355,7 @@ impl App {
 
         let new_len = match &mut self.editing {
             Some(EditingEntry::Unique(uniq)) if field_idx == 4 => {
-                if nested_idx < uniq.mods.len() && uniq.mods.len() > 0 {
+                if nested_idx < uniq.mods.len() && !uniq.mods.is_empty() {
                     uniq.mods.remove(nested_idx);
                     Some(uniq.mods.len())
                 } else {
@@ -2218,6 +2370,14 @@ impl App {
                     None
                 }
             }
+            Some(EditingEntry::BaseType(bt)) if field_idx == 4 => {
+                if nested_idx < bt.implicits.len() {
+                    bt.implicits.remove(nested_idx);
+                    Some(bt.implicits.len())
+                } else {
+                    None
+                }
+            }
             Some(EditingEntry::BaseType(bt)) if field_idx == 6 => {
                 // Only remove damage entries (index 4+), not stats
                 if nested_idx >= 4 {
@@ -2271,7 +2431,7 @@ impl App {
                 if nested_depth == 1 {
                     // Enter editing mode - populate with current values
                     if let Some(tier) = affix.tiers.get(nested_idx) {
-                        let text = if let Some(ref max_val) = tier.max_value {
+                        let mut text = if let Some(ref max_val) = tier.max_value {
                             format!(
                                 "{} {} {} {} {} {} {}",
                                 tier.tier,
@@ -2288,6 +2448,9 @@ impl App {
                                 tier.tier, tier.weight, tier.min, tier.max, tier.min_ilvl
                             )
                         };
+                        if let Some(step) = tier.step {
+                            text.push_str(&format!(" step:{}", step));
+                        }
                         self.text_input = TextInputState::new(text);
                         self.current_view_state_mut().nested_depth = 2;
                     }
@@ -2298,15 +2461,17 @@ impl App {
                 }
             }
             Some(EditingEntry::BaseType(bt)) if field_idx == 4 => {
-                // Implicit: "StatType min max" or "none" to clear
+                // Implicit: "StatType min max [weight]" or "none" to remove
                 if nested_depth == 1 {
-                    let text = bt
-                        .implicit
-                        .as_ref()
-                        .map(|i| format!("{:?} {} {}", i.stat, i.min, i.max))
-                        .unwrap_or_else(|| "none".to_string());
-                    self.text_input = TextInputState::new(text);
-                    self.current_view_state_mut().nested_depth = 2;
+                    if let Some(implicit) = bt.implicits.get(nested_idx) {
+                        let mut text =
+                            format!("{:?} {} {}", implicit.stat, implicit.min, implicit.max);
+                        if let Some(weight) = implicit.weight {
+                            text.push_str(&format!(" {}", weight));
+                        }
+                        self.text_input = TextInputState::new(text);
+                        self.current_view_state_mut().nested_depth = 2;
+                    }
                 } else if nested_depth >= 2 {
                     self.apply_nested_text_input();
                     self.current_view_state_mut().nested_depth = 1;
@@ -2400,7 +2565,7 @@ impl App {
                 }
             }
             Some(EditingEntry::Currency(curr)) if field_idx == 4 => {
-                // Requirements: nested_idx 0=rarities (list), 1=has_affix, 2=has_affix_slot
+                // Requirements: nested_idx 0=rarities (list), 1=has_affix, 2=has_affix_slot, 3=required_tags
                 if nested_idx == 0 {
                     // Rarities is now a list editor
                     if nested_depth == 1 {
@@ -2418,6 +2583,7 @@ impl App {
                         let text = match nested_idx {
                             1 => curr.requires.has_affix.to_string(),
                             2 => curr.requires.has_affix_slot.to_string(),
+                            3 => curr.requires.required_tags.join(", "),
                             _ => String::new(),
                         };
                         self.text_input = TextInputState::new(text);
@@ -2445,9 +2611,16 @@ impl App {
                             .add_specific_affix
                             .get(self.nested_sub_field_index)
                         {
-                            let tier_str = specific.tier.map(|t| t.to_string()).unwrap_or_default();
-                            let text = format!("{} {} {}", specific.id, tier_str, specific.weight);
-                            self.text_input = TextInputState::new(text.trim().to_string());
+                            let tier_str = specific.tier.map_or("-".to_string(), |t| t.to_string());
+                            let mut text =
+                                format!("{} {} {}", specific.id, tier_str, specific.weight);
+                            if let Some(value) = specific.value {
+                                text.push_str(&format!(" {}", value));
+                                if let Some(value_max) = specific.value_max {
+                                    text.push_str(&format!(" {}", value_max));
+                                }
+                            }
+                            self.text_input = TextInputState::new(text);
                             self.current_view_state_mut().nested_depth = 3;
                         }
                     } else if nested_depth >= 3 {
@@ -2570,7 +2743,7 @@ impl App {
                 let parts: Vec<&str> = value.split_whitespace().collect();
                 if parts.len() >= 3 {
                     if let Some(mod_cfg) = uniq.mods.get_mut(nested_idx) {
-                        if let Ok(stat) = Self::parse_stat_type(parts[0]) {
+                        if let Ok(stat) = parts[0].parse::<loot_core::types::StatType>() {
                             mod_cfg.stat = stat;
                         }
                         if let Ok(min) = parts[1].parse::<i32>() {
@@ -2583,12 +2756,19 @@ impl App {
                 }
             }
             Some(EditingEntry::Affix(affix)) if field_idx == 7 => {
-                // Parse format: "tier weight min max [max_min max_max] min_ilvl"
+                // Parse format: "tier weight min max [max_min max_max] min_ilvl [step:N]"
                 // 5 parts: tier weight min max min_ilvl (no damage range)
                 // 7 parts: tier weight min max max_min max_max min_ilvl (with damage range)
-                let parts: Vec<&str> = value.split_whitespace().collect();
+                // An optional "step:N" anywhere sets the rounding step; leaving it out clears it
+                let (step, parts): (Vec<&str>, Vec<&str>) = value
+                    .split_whitespace()
+                    .partition(|part| part.starts_with("step:"));
                 if parts.len() >= 4 {
                     if let Some(tier) = affix.tiers.get_mut(nested_idx) {
+                        tier.step = step
+                            .first()
+                            .and_then(|s| s["step:".len()..].parse::<i32>().ok())
+                            .filter(|&s| s > 1);
                         if let Ok(t) = parts[0].parse::<u32>() {
                             tier.tier = t;
                         }
@@ -2626,18 +2806,30 @@ impl App {
                 }
             }
             Some(EditingEntry::BaseType(bt)) if field_idx == 4 => {
-                // Implicit: "StatType min max" or "none" to clear
+                // Implicit: "StatType min max [weight]" or "none" to remove. A weight
+                // makes it one of the base's alternative implicits.
                 if value.trim().eq_ignore_ascii_case("none") {
-                    bt.implicit = None;
+                    if nested_idx < bt.implicits.len() {
+                        bt.implicits.remove(nested_idx);
+                    }
                 } else {
                     let parts: Vec<&str> = value.split_whitespace().collect();
                     if parts.len() >= 3 {
-                        if let Ok(stat) = Self::parse_stat_type(parts[0]) {
-                            if let (Ok(min), Ok(max)) =
-                                (parts[1].parse::<i32>(), parts[2].parse::<i32>())
-                            {
-                                bt.implicit =
-                                    Some(loot_core::config::ImplicitConfig { stat, min, max });
+                        if let Ok(stat) = parts[0].parse::<loot_core::types::StatType>() {
+                            let weight = parts.get(3).map(|w| w.parse::<u32>());
+                            if let (Ok(min), Ok(max), Some(implicit)) = (
+                                parts[1].parse::<i32>(),
+                                parts[2].parse::<i32>(),
+                                bt.implicits.get_mut(nested_idx),
+                            ) {
+                                if let Ok(weight) = weight.transpose() {
+                                    *implicit = loot_core::config::ImplicitConfig {
+                                        stat,
+                                        min,
+                                        max,
+                                        weight,
+                                    };
+                                }
                             }
                         }
                     }
@@ -2755,7 +2947,7 @@ impl App {
                 }
             }
             Some(EditingEntry::Currency(curr)) if field_idx == 4 => {
-                // Requirements: nested_idx 0=rarities (list editor), 1=has_affix, 2=has_affix_slot
+                // Requirements: nested_idx 0=rarities (list editor), 1=has_affix, 2=has_affix_slot, 3=required_tags
                 match nested_idx {
                     0 => {
                         // Rarities now uses list editor - this case is handled by nested_item_add
@@ -2766,6 +2958,13 @@ impl App {
                     2 => {
                         curr.requires.has_affix_slot = value.trim().eq_ignore_ascii_case("true");
                     }
+                    3 => {
+                        curr.requires.required_tags = value
+                            .split(',')
+                            .map(|t| t.trim().to_string())
+                            .filter(|t| !t.is_empty())
+                            .collect();
+                    }
                     _ => {}
                 }
             }
@@ -2776,7 +2975,8 @@ impl App {
                         // set_rarity
                         if value.trim().eq_ignore_ascii_case("none") {
                             curr.effects.set_rarity = None;
-                        } else if let Ok(rarity) = Self::parse_rarity(value.trim()) {
+                        } else if let Ok(rarity) = value.trim().parse::<loot_core::types::Rarity>()
+                        {
                             curr.effects.set_rarity = Some(rarity);
                         }
                     }
@@ -2806,27 +3006,36 @@ impl App {
                     }
                     3 => {
                         // add_specific_affix - edit existing or add new
-                        // format: "affix_id [tier] [weight]"
+                        // format: "affix_id [tier|-] [weight] [value] [value_max]"
                         let parts: Vec<&str> = value.split_whitespace().collect();
                         if !parts.is_empty() {
                             let id = parts[0].to_string();
                             // Validate the affix ID exists
-                            if !self.config.affixes.contains_key(&id) {
+                            let Some(affix) = self.config.affixes.get(&id) else {
                                 self.message = Some(format!("Unknown affix: {}", id));
                                 return;
-                            }
+                            };
                             let tier = parts.get(1).and_then(|s| s.parse::<u32>().ok());
                             let weight = parts
                                 .get(2)
                                 .and_then(|s| s.parse::<u32>().ok())
                                 .unwrap_or(100);
+                            let entry = loot_core::config::SpecificAffix {
+                                id,
+                                tier,
+                                weight,
+                                value: parts.get(3).and_then(|s| s.parse::<i32>().ok()),
+                                value_max: parts.get(4).and_then(|s| s.parse::<i32>().ok()),
+                            };
+                            if let Some(problem) = entry.fixed_value_problem(affix) {
+                                self.message = Some(format!("Invalid fixed value: {}", problem));
+                                return;
+                            }
                             let sub_idx = self.nested_sub_field_index;
 
                             if sub_idx == usize::MAX {
                                 // Adding new entry
-                                curr.effects
-                                    .add_specific_affix
-                                    .push(loot_core::config::SpecificAffix { id, tier, weight });
+                                curr.effects.add_specific_affix.push(entry);
                                 self.nested_sub_field_index =
                                     curr.effects.add_specific_affix.len() - 1;
                                 self.message = Some("Affix added".to_string());
@@ -2834,9 +3043,7 @@ impl App {
                                 curr.effects.add_specific_affix.get_mut(sub_idx)
                             {
                                 // Editing existing entry
-                                specific.id = id;
-                                specific.tier = tier;
-                                specific.weight = weight;
+                                *specific = entry;
                                 self.message = Some("Affix updated".to_string());
                             }
                         }
@@ -2888,6 +3095,11 @@ impl App {
             return;
         }
 
+        if too_small(f.area()) {
+            self.render_too_small(f);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -2914,6 +3126,38 @@ impl App {
         if self.show_new_file_input {
             self.render_new_file_input(f);
         }
+        if self.show_jump_input {
+            self.render_jump_input(f);
+        }
+        if self.show_help {
+            self.render_help_overlay(f);
+        }
+        if self.show_stats {
+            self.render_stats_overlay(f);
+        }
+    }
+
+    fn render_too_small(&self, f: &mut Frame) {
+        let area = f.area();
+        let lines = vec![
+            Line::from(Span::styled(
+                "Terminal too small",
+                Style::default()
+                    .fg(theme::current().error)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "{}x{}, need {}x{}",
+                    area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+                ),
+                Style::default().fg(theme::current().muted),
+            )),
+        ];
+        f.render_widget(
+            Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: true }),
+            area,
+        );
     }
 
     fn render_config_error(&self, f: &mut Frame, error: String) {
@@ -2923,21 +3167,23 @@ impl App {
         let text = vec![
             Line::from(Span::styled(
                 "Configuration Error",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme::current().error)
+                    .add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
             Line::from(error),
             Line::from(""),
             Line::from(Span::styled(
                 "Press q to quit",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )),
         ];
 
         let paragraph = Paragraph::new(text).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(theme::current().error)),
         );
         f.render_widget(paragraph, area);
     }
@@ -2956,7 +3202,7 @@ impl App {
             .style(Style::default())
             .highlight_style(
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme::current().accent)
                     .add_modifier(Modifier::BOLD),
             )
             .divider("|")
@@ -2989,14 +3235,17 @@ impl App {
                 let dirty = self.dirty.is_entry_dirty(self.current_tab, id);
                 let marker = if dirty { "* " } else { "  " };
                 ListItem::new(Line::from(vec![
-                    Span::styled(marker.to_string(), Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        marker.to_string(),
+                        Style::default().fg(theme::current().highlight),
+                    ),
                     Span::raw(id.clone()),
                 ]))
             })
             .collect();
 
         let border_style = if self.focus == Focus::List {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(theme::current().accent)
         } else {
             Style::default()
         };
@@ -3011,7 +3260,7 @@ impl App {
             )
             .highlight_style(
                 Style::default()
-                    .bg(Color::DarkGray)
+                    .bg(theme::current().muted)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("> ");
@@ -3027,16 +3276,42 @@ impl App {
         }
     }
 
+    /// File an entry is saved to, relative to the config directory
+    pub fn origin_label(&self, tab: ConfigTab, id: &str) -> String {
+        match self.origins.get_origin(tab, id) {
+            Some(path) => path
+                .strip_prefix(&self.config_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string(),
+            None => "(unsaved/no origin)".to_string(),
+        }
+    }
+
+    fn origin_line(&self, id: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled("File: ", Style::default().fg(theme::current().label)),
+            Span::styled(
+                self.origin_label(self.current_tab, id),
+                Style::default().fg(theme::current().muted),
+            ),
+        ])
+    }
+
     fn render_detail_preview(&self, f: &mut Frame, area: Rect) {
         let state = self.current_view_state();
         let content = match state.selected_id() {
-            Some(id) => match self.current_tab {
-                ConfigTab::BaseTypes => views::base_types::render_preview(&self.config, id),
-                ConfigTab::Affixes => views::affixes::render_preview(&self.config, id),
-                ConfigTab::AffixPools => views::affix_pools::render_preview(&self.config, id),
-                ConfigTab::Currencies => views::currencies::render_preview(&self.config, id),
-                ConfigTab::Uniques => views::uniques::render_preview(&self.config, id),
-            },
+            Some(id) => {
+                let mut lines = vec![self.origin_line(id), Line::from("")];
+                lines.extend(match self.current_tab {
+                    ConfigTab::BaseTypes => views::base_types::render_preview(&self.config, id),
+                    ConfigTab::Affixes => views::affixes::render_preview(&self.config, id),
+                    ConfigTab::AffixPools => views::affix_pools::render_preview(&self.config, id),
+                    ConfigTab::Currencies => views::currencies::render_preview(&self.config, id),
+                    ConfigTab::Uniques => views::uniques::render_preview(&self.config, id),
+                });
+                lines
+            }
             None => vec![Line::from("No entry selected")],
         };
 
@@ -3052,19 +3327,23 @@ impl App {
             " Edit "
         };
 
-        let content = match &self.editing {
+        let mut content = match self.editing_id() {
+            Some(id) => vec![self.origin_line(id), Line::from("")],
+            None => Vec::new(),
+        };
+        content.extend(match &self.editing {
             Some(EditingEntry::BaseType(bt)) => views::base_types::render_edit_form(bt, self),
             Some(EditingEntry::Affix(affix)) => views::affixes::render_edit_form(affix, self),
             Some(EditingEntry::AffixPool(pool)) => views::affix_pools::render_edit_form(pool, self),
             Some(EditingEntry::Currency(curr)) => views::currencies::render_edit_form(curr, self),
             Some(EditingEntry::Unique(uniq)) => views::uniques::render_edit_form(uniq, self),
             None => vec![Line::from("No entry being edited")],
-        };
+        });
 
         let paragraph = Paragraph::new(content).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme::current().accent))
                 .title(title),
         );
         f.render_widget(paragraph, area);
@@ -3072,18 +3351,26 @@ impl App {
 
     fn render_help(&self, f: &mut Frame, area: Rect) {
         let help_text = if let Some(ref msg) = self.message {
-            Span::styled(msg.clone(), Style::default().fg(Color::Yellow))
+            Span::styled(msg.clone(), Style::default().fg(theme::current().highlight))
         } else {
-            match self.mode {
-                Mode::Browse => {
-                    Span::raw("1-5:Tab | j/k:Nav | e:Edit | n:New | d:Delete | s:Save | q:Quit")
-                }
-                Mode::Edit | Mode::Create => Span::raw("Tab:Next | Ctrl+S:Save | Esc:Cancel"),
-            }
+            let context = match self.mode {
+                Mode::Browse => "Browse",
+                Mode::Edit | Mode::Create => "Edit",
+            };
+            let bindings: Vec<String> = KEYBINDINGS
+                .iter()
+                .filter(|(name, _)| *name == context)
+                .flat_map(|(_, keys)| keys.iter())
+                .map(|(key, action)| format!("{}:{}", key, action))
+                .collect();
+            Span::raw(bindings.join(" | "))
         };
 
         let dirty_indicator = if self.dirty.is_dirty() {
-            Span::styled(" [*Modified] ", Style::default().fg(Color::Yellow))
+            Span::styled(
+                " [*Modified] ",
+                Style::default().fg(theme::current().highlight),
+            )
         } else {
             Span::raw("")
         };
@@ -3093,6 +3380,150 @@ impl App {
         f.render_widget(help, area);
     }
 
+    fn render_help_overlay(&self, f: &mut Frame) {
+        let area = centered_rect(70, 80, f.area());
+        f.render_widget(Clear, area);
+
+        let heading = Style::default()
+            .fg(theme::current().accent)
+            .add_modifier(Modifier::BOLD);
+        let key_style = Style::default().fg(theme::current().highlight);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (context, keys) in KEYBINDINGS {
+            lines.push(Line::from(Span::styled(context.to_string(), heading)));
+            for (key, action) in keys.iter() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<14}", key), key_style),
+                    Span::raw(action.to_string()),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+
+        // Legend goes in a second column so everything fits without scrolling
+        let mut legend = vec![Line::from(Span::styled("Legend".to_string(), heading))];
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  * ".to_string(),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::raw("Entry has unsaved changes ([*Modified] in the help bar)"),
+        ]));
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  > ".to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::raw("Focused field"),
+        ]));
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  >> ".to_string(),
+                Style::default().fg(theme::current().success),
+            ),
+            Span::raw("Selected item in a list or nested editor"),
+        ]));
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  Prefix".to_string(),
+                Style::default().fg(theme::current().prefix),
+            ),
+            Span::raw(" / "),
+            Span::styled(
+                "Suffix".to_string(),
+                Style::default().fg(theme::current().suffix),
+            ),
+            Span::raw(" affix type"),
+        ]));
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  Local".to_string(),
+                Style::default().fg(theme::current().local),
+            ),
+            Span::raw(" / "),
+            Span::styled(
+                "Global".to_string(),
+                Style::default().fg(theme::current().global),
+            ),
+            Span::raw(" scope: affects the item / affects the character"),
+        ]));
+        legend.push(Line::from(vec![
+            Span::styled(
+                "  T1".to_string(),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::raw(" Affix tier, 1 is the best"),
+        ]));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().accent))
+            .title(" Help (Esc or ? to close) ");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(inner);
+
+        f.render_widget(Paragraph::new(lines), columns[0]);
+        f.render_widget(
+            Paragraph::new(legend).wrap(ratatui::widgets::Wrap { trim: false }),
+            columns[1],
+        );
+    }
+
+    fn render_stats_overlay(&self, f: &mut Frame) {
+        let area = centered_rect(60, 70, f.area());
+        f.render_widget(Clear, area);
+
+        let heading = Style::default()
+            .fg(theme::current().accent)
+            .add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "  {:<18} {:>8} {:>8} {:>12}",
+                    "Class", "Prefixes", "Suffixes", "Total weight"
+                ),
+                heading,
+            )),
+            Line::from(""),
+        ];
+        for coverage in class_coverage(&self.config) {
+            // A class missing either affix type can't roll full rares
+            let style = if coverage.prefixes == 0 || coverage.suffixes == 0 {
+                Style::default().fg(theme::current().error)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {:<18} {:>8} {:>8} {:>12}",
+                    coverage.class.name(),
+                    coverage.prefixes,
+                    coverage.suffixes,
+                    coverage.total_weight
+                ),
+                style,
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Counts affixes whose allowed classes include the class (empty allows all)",
+            Style::default().fg(theme::current().muted),
+        )));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().accent))
+            .title(" Affix Coverage (Esc or D to close) ");
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
     fn render_quit_confirm(&self, f: &mut Frame) {
         let area = centered_rect(40, 20, f.area());
         f.render_widget(Clear, area);
@@ -3106,7 +3537,7 @@ impl App {
         let paragraph = Paragraph::new(text).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme::current().highlight))
                 .title(" Confirm Quit "),
         );
         f.render_widget(paragraph, area);
@@ -3126,7 +3557,7 @@ impl App {
         let paragraph = Paragraph::new(text).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red))
+                .border_style(Style::default().fg(theme::current().error))
                 .title(" Confirm Delete "),
         );
         f.render_widget(paragraph, area);
@@ -3148,16 +3579,20 @@ impl App {
             })
             .collect();
 
+        let title = match self.moving_entry {
+            Some(ref id) => format!(" Move {} To (n:New | Enter:Select | Esc:Cancel) ", id),
+            None => " Select File (n:New | Enter:Select | Esc:Cancel) ".to_string(),
+        };
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan))
-                    .title(" Select File (n:New | Enter:Select | Esc:Cancel) "),
+                    .border_style(Style::default().fg(theme::current().accent))
+                    .title(title),
             )
             .highlight_style(
                 Style::default()
-                    .bg(Color::DarkGray)
+                    .bg(theme::current().muted)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("> ");
@@ -3174,18 +3609,108 @@ impl App {
             Line::from(""),
             Line::from(Span::styled(
                 format!("{}_", self.new_file_name),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme::current().accent),
             )),
         ];
 
         let paragraph = Paragraph::new(text).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme::current().accent))
                 .title(" New File "),
         );
         f.render_widget(paragraph, area);
     }
+
+    fn render_jump_input(&self, f: &mut Frame) {
+        let area = centered_rect(50, 20, f.area());
+        f.render_widget(Clear, area);
+
+        let target = match self.find_entry(self.text_input.value()) {
+            Some((tab, idx)) => Line::from(format!(
+                "-> {}: {}",
+                tab.as_str(),
+                self.view_state(tab).ids[idx]
+            )),
+            None if self.text_input.value().trim().is_empty() => Line::from(""),
+            None => Line::from(Span::styled(
+                "No match",
+                Style::default().fg(theme::current().error),
+            )),
+        };
+        let text = vec![
+            Line::from("Enter an entry id (partial ids match too):"),
+            Line::from(""),
+            Line::from(Span::styled(
+                self.text_input.display_with_cursor(),
+                Style::default().fg(theme::current().accent),
+            )),
+            target,
+        ];
+
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::current().accent))
+                .title(" Jump to Entry "),
+        );
+        f.render_widget(paragraph, area);
+    }
+}
+
+/// Affixes an item class can roll, for the stats dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassCoverage {
+    pub class: ItemClass,
+    pub prefixes: usize,
+    pub suffixes: usize,
+    /// Sum of tier weights across every eligible affix
+    pub total_weight: u32,
+}
+
+/// Aggregate eligible affixes and their weights for every item class
+pub fn class_coverage(config: &Config) -> Vec<ClassCoverage> {
+    ItemClass::all()
+        .iter()
+        .map(|&class| {
+            let mut coverage = ClassCoverage {
+                class,
+                prefixes: 0,
+                suffixes: 0,
+                total_weight: 0,
+            };
+            for affix in config.affixes.values() {
+                if !affix.allowed_classes.is_empty() && !affix.allowed_classes.contains(&class) {
+                    continue;
+                }
+                match affix.affix_type {
+                    AffixType::Prefix => coverage.prefixes += 1,
+                    AffixType::Suffix => coverage.suffixes += 1,
+                }
+                coverage.total_weight += affix.tiers.iter().map(|t| t.weight).sum::<u32>();
+            }
+            coverage
+        })
+        .collect()
+}
+
+/// Pulls a selection back inside `len` and resets the scroll offset.
+fn reclamp_list_state(state: &mut ListState, len: usize) {
+    if let Some(selected) = state.selected() {
+        if len > 0 && selected >= len {
+            state.select(Some(len - 1));
+        }
+    }
+    *state.offset_mut() = 0;
+}
+
+/// Smallest terminal the editor lays out in; below this only a resize hint is shown
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 12;
+
+/// Whether `area` is too small for the tab bar, lists and help bar to fit
+fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -3207,3 +3732,500 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_affix(id: &str) -> AffixConfig {
+        AffixConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            affix_type: AffixType::Prefix,
+            stat: loot_core::types::StatType::AddedLife,
+            scope: Default::default(),
+            tags: Vec::new(),
+            allowed_classes: Vec::new(),
+            tiers: vec![AffixTierConfig {
+                tier: 1,
+                weight: 100,
+                min: 1,
+                max: 10,
+                max_value: None,
+                min_ilvl: 0,
+                step: None,
+                extra_stats: Vec::new(),
+            }],
+            annul_weight: None,
+            flavor: None,
+            extra: Default::default(),
+        }
+    }
+
+    fn make_pool(id: &str) -> AffixPoolConfig {
+        AffixPoolConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            affixes: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_dirty_entries_across_tabs() {
+        let mut tracker = DirtyTracker::default();
+        tracker.mark_dirty(ConfigTab::Uniques, "u1");
+        tracker.mark_dirty(ConfigTab::Affixes, "b");
+        tracker.mark_dirty(ConfigTab::Affixes, "a");
+        tracker.mark_dirty(ConfigTab::BaseTypes, "sword");
+        tracker.mark_dirty(ConfigTab::AffixPools, "pool");
+        tracker.mark_clean(ConfigTab::AffixPools, "pool");
+
+        assert_eq!(
+            tracker.dirty_entries(),
+            vec![
+                (ConfigTab::BaseTypes, "sword".to_string()),
+                (ConfigTab::Affixes, "a".to_string()),
+                (ConfigTab::Affixes, "b".to_string()),
+                (ConfigTab::Uniques, "u1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_class_coverage_respects_allowed_classes() {
+        let mut config = Config::default();
+        let mut sword_only = make_affix("sword_only");
+        sword_only.allowed_classes = vec![ItemClass::OneHandSword];
+        sword_only.tiers[0].weight = 40;
+        let mut anywhere = make_affix("anywhere");
+        anywhere.affix_type = AffixType::Suffix;
+        anywhere.tiers.push(AffixTierConfig {
+            tier: 2,
+            weight: 60,
+            min: 1,
+            max: 5,
+            max_value: None,
+            min_ilvl: 0,
+            step: None,
+            extra_stats: Vec::new(),
+        });
+        config.affixes.insert("sword_only".to_string(), sword_only);
+        config.affixes.insert("anywhere".to_string(), anywhere);
+
+        let coverage = class_coverage(&config);
+        assert_eq!(coverage.len(), ItemClass::all().len());
+
+        let sword = coverage
+            .iter()
+            .find(|c| c.class == ItemClass::OneHandSword)
+            .unwrap();
+        assert_eq!(
+            (sword.prefixes, sword.suffixes, sword.total_weight),
+            (1, 1, 200)
+        );
+
+        let ring = coverage
+            .iter()
+            .find(|c| c.class == ItemClass::Ring)
+            .unwrap();
+        assert_eq!(
+            (ring.prefixes, ring.suffixes, ring.total_weight),
+            (0, 1, 160)
+        );
+    }
+
+    #[test]
+    fn test_save_all_writes_dirty_entries_and_skips_missing_origins() {
+        let dir =
+            std::env::temp_dir().join(format!("config_editor_save_all_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut app = App::new(&dir);
+
+        let affix_file = dir.join("affixes").join("test.toml");
+        let pool_file = dir.join("affix_pools").join("test.toml");
+        for id in ["first", "second"] {
+            app.config.affixes.insert(id.to_string(), make_affix(id));
+            app.origins
+                .set_origin(ConfigTab::Affixes, id, affix_file.clone());
+            app.dirty.mark_dirty(ConfigTab::Affixes, id);
+        }
+        app.config
+            .affix_pools
+            .insert("pool".to_string(), make_pool("pool"));
+        app.origins
+            .set_origin(ConfigTab::AffixPools, "pool", pool_file.clone());
+        app.dirty.mark_dirty(ConfigTab::AffixPools, "pool");
+
+        // No origin file: should be skipped and stay dirty
+        app.config
+            .affix_pools
+            .insert("orphan".to_string(), make_pool("orphan"));
+        app.dirty.mark_dirty(ConfigTab::AffixPools, "orphan");
+
+        app.save_all();
+
+        let message = app.message.clone().unwrap();
+        assert!(message.starts_with("Saved 3 entries"), "{}", message);
+        assert!(
+            message.contains("skipped 1 with no file: orphan"),
+            "{}",
+            message
+        );
+        assert_eq!(
+            app.dirty.dirty_entries(),
+            vec![(ConfigTab::AffixPools, "orphan".to_string())]
+        );
+
+        let reloaded = Config::load_from_dir(&dir).unwrap();
+        assert!(reloaded.affixes.contains_key("first"));
+        assert!(reloaded.affixes.contains_key("second"));
+        assert!(reloaded.affix_pools.contains_key("pool"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_affix_scope_edit_persists_through_save() {
+        let dir = std::env::temp_dir().join(format!("config_editor_scope_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut app = App::new(&dir);
+        app.current_tab = ConfigTab::Affixes;
+        app.origins.set_origin(
+            ConfigTab::Affixes,
+            "aura",
+            dir.join("affixes").join("test.toml"),
+        );
+
+        app.mode = Mode::Edit;
+        app.editing = Some(EditingEntry::Affix(make_affix("aura")));
+        app.apply_enum_selection(4, "Global");
+        assert_eq!(app.get_field_value(4), "Global");
+
+        app.save_and_exit_edit();
+        app.save_all();
+
+        let reloaded = Config::load_from_dir(&dir).unwrap();
+        assert_eq!(
+            reloaded.affixes["aura"].scope,
+            loot_core::types::AffixScope::Global
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_specific_affix_accepts_fixed_value_in_tier_range() {
+        let mut app = App::new(Path::new("../config"));
+        app.current_tab = ConfigTab::Currencies;
+        app.mode = Mode::Edit;
+        let mut currency = app.config.currencies["imbue_fire"].clone();
+        currency.effects.add_specific_affix.clear();
+        app.editing = Some(EditingEntry::Currency(currency));
+        app.current_view_state_mut().field_index = 5;
+        app.current_view_state_mut().nested_index = 3;
+
+        // Tier 2 of added_fire_damage rolls 9-16, so 40 is rejected
+        app.nested_sub_field_index = usize::MAX;
+        app.text_input = TextInputState::new("added_fire_damage 2 100 40".to_string());
+        app.apply_nested_text_input();
+        assert!(app.message.as_deref().unwrap().contains("outside tier 2"));
+
+        app.text_input = TextInputState::new("added_fire_damage 2 100 12 25".to_string());
+        app.apply_nested_text_input();
+        let Some(EditingEntry::Currency(currency)) = &app.editing else {
+            panic!("still editing the currency");
+        };
+        let specific = &currency.effects.add_specific_affix[0];
+        assert_eq!(specific.tier, Some(2));
+        assert_eq!((specific.value, specific.value_max), (Some(12), Some(25)));
+    }
+
+    #[test]
+    fn test_implicit_edit_takes_optional_weight() {
+        let mut app = App::new(Path::new("../config"));
+        app.current_tab = ConfigTab::BaseTypes;
+        app.mode = Mode::Edit;
+        let base = app.config.base_types["iron_sword"].clone();
+        app.editing = Some(EditingEntry::BaseType(base));
+        app.current_view_state_mut().field_index = 4;
+        app.current_view_state_mut().nested_index = 0;
+
+        let implicit = |app: &App| {
+            let Some(EditingEntry::BaseType(base)) = &app.editing else {
+                panic!("still editing the base type");
+            };
+            base.implicits[0].clone()
+        };
+        app.text_input = TextInputState::new("AddedStrength 5 10 60".to_string());
+        app.apply_nested_text_input();
+        let edited = implicit(&app);
+        assert_eq!(edited.stat, loot_core::types::StatType::AddedStrength);
+        assert_eq!((edited.min, edited.max, edited.weight), (5, 10, Some(60)));
+
+        app.text_input = TextInputState::new("AddedStrength 5 10".to_string());
+        app.apply_nested_text_input();
+        assert_eq!(implicit(&app).weight, None);
+    }
+
+    #[test]
+    fn test_affix_flavor_edit_persists_through_save() {
+        let dir = std::env::temp_dir().join(format!("config_editor_flavor_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut app = App::new(&dir);
+        app.current_tab = ConfigTab::Affixes;
+        app.origins.set_origin(
+            ConfigTab::Affixes,
+            "aura",
+            dir.join("affixes").join("test.toml"),
+        );
+
+        app.mode = Mode::Edit;
+        app.editing = Some(EditingEntry::Affix(make_affix("aura")));
+        app.set_field_value(8, "It hums faintly.".to_string());
+        assert_eq!(app.get_field_value(8), "It hums faintly.");
+
+        app.save_and_exit_edit();
+        app.save_all();
+        let reloaded = Config::load_from_dir(&dir).unwrap();
+        assert_eq!(
+            reloaded.affixes["aura"].flavor.as_deref(),
+            Some("It hums faintly.")
+        );
+        let preview = crate::views::affixes::render_preview(&reloaded, "aura");
+        assert!(preview
+            .iter()
+            .any(|line| line.to_string() == "\"It hums faintly.\""));
+
+        // Clearing the field removes the flavor
+        app.mode = Mode::Edit;
+        app.editing = Some(EditingEntry::Affix(reloaded.affixes["aura"].clone()));
+        app.set_field_value(8, String::new());
+        app.save_and_exit_edit();
+        app.save_all();
+        let reloaded = Config::load_from_dir(&dir).unwrap();
+        assert_eq!(reloaded.affixes["aura"].flavor, None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_move_affix_between_files() {
+        let dir = std::env::temp_dir().join(format!("config_editor_move_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut app = App::new(&dir);
+        app.current_tab = ConfigTab::Affixes;
+
+        let old_file = dir.join("affixes").join("old.toml");
+        let new_file = dir.join("affixes").join("new.toml");
+        for (id, file) in [
+            ("stays", &old_file),
+            ("moves", &old_file),
+            ("other", &new_file),
+        ] {
+            app.config.affixes.insert(id.to_string(), make_affix(id));
+            app.origins.set_origin(ConfigTab::Affixes, id, file.clone());
+            app.dirty.mark_dirty(ConfigTab::Affixes, id);
+        }
+        app.save_all();
+
+        app.move_entry_to_file("moves", new_file.clone());
+        assert!(app.message.as_ref().unwrap().starts_with("Moved moves"));
+
+        let ids_in = |path: &Path| {
+            let content = std::fs::read_to_string(path).unwrap();
+            let value: toml::Value = toml::from_str(&content).unwrap();
+            let mut ids: Vec<String> = value["affixes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|a| a["id"].as_str().unwrap().to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids_in(&old_file), vec!["stays"]);
+        assert_eq!(ids_in(&new_file), vec!["moves", "other"]);
+
+        let origins = ConfigOrigins::load_from_dir(&dir);
+        assert_eq!(origins.affixes["moves"], new_file);
+        assert_eq!(app.origins.affixes["moves"], new_file);
+        assert_eq!(Config::load_from_dir(&dir).unwrap().affixes.len(), 3);
+
+        // Rewriting the old file fails (it's now a directory), so the new file is put back
+        // and the entry stays where it was
+        std::fs::remove_file(&old_file).unwrap();
+        std::fs::create_dir(&old_file).unwrap();
+        app.move_entry_to_file("stays", new_file.clone());
+        assert!(app.message.as_ref().unwrap().starts_with("Move failed"));
+        assert_eq!(app.origins.affixes["stays"], old_file);
+        assert_eq!(ids_in(&new_file), vec!["moves", "other"]);
+
+        // A target file that didn't exist before is removed again
+        let fresh_file = dir.join("affixes").join("fresh.toml");
+        app.move_entry_to_file("stays", fresh_file.clone());
+        assert!(app.message.as_ref().unwrap().starts_with("Move failed"));
+        assert!(!fresh_file.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unknown_fields_survive_save() {
+        let dir = std::env::temp_dir().join(format!("config_editor_extra_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let file = dir.join("affixes").join("custom.toml");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &file,
+            r#"
+[[affixes]]
+id = "custom_life"
+name = "Custom"
+type = "prefix"
+stat = "added_life"
+future_field = "from a newer version"
+
+[[affixes.tiers]]
+tier = 1
+weight = 100
+min = 1
+max = 10
+"#,
+        )
+        .unwrap();
+
+        let mut app = App::new(&dir);
+        assert!(app.config.affixes.contains_key("custom_life"));
+        app.config.affixes.get_mut("custom_life").unwrap().name = "Renamed".to_string();
+        app.dirty.mark_dirty(ConfigTab::Affixes, "custom_life");
+        app.save_all();
+
+        let content = std::fs::read_to_string(&file).unwrap();
+        let value: toml::Value = toml::from_str(&content).unwrap();
+        let affix = &value["affixes"][0];
+        assert_eq!(affix["name"].as_str(), Some("Renamed"));
+        assert_eq!(affix["future_field"].as_str(), Some("from a newer version"));
+        assert_eq!(affix["tiers"][0]["max"].as_integer(), Some(10));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_detail_panel_shows_origin_file() {
+        let config_dir = Path::new("../config");
+        let mut app = App::new(config_dir);
+        app.switch_tab(ConfigTab::Affixes);
+        let id = app.current_view_state().selected_id().unwrap().to_string();
+
+        let loaded = ConfigOrigins::load_from_dir(config_dir);
+        let expected = loaded.affixes[&id].strip_prefix(config_dir).unwrap();
+        let label = app.origin_label(ConfigTab::Affixes, &id);
+        assert_eq!(label, expected.display().to_string());
+        assert!(label.starts_with("affixes"), "{}", label);
+
+        let render = |app: &mut App| {
+            let backend = ratatui::backend::TestBackend::new(160, 50);
+            let mut terminal = ratatui::Terminal::new(backend).unwrap();
+            terminal.draw(|f| app.render(f)).unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            buffer
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+        assert!(render(&mut app).contains(&format!("File: {}", label)));
+
+        app.enter_create_mode();
+        assert!(render(&mut app).contains("File: (unsaved/no origin)"));
+    }
+
+    #[test]
+    fn test_jump_maps_typed_id_to_entry() {
+        let mut app = App::new(Path::new("../config"));
+        assert_eq!(app.current_tab, ConfigTab::BaseTypes);
+
+        // Exact ids win, even in another tab
+        let sword = app
+            .base_types_state
+            .ids
+            .iter()
+            .position(|id| id == "iron_sword");
+        assert_eq!(
+            app.find_entry("iron_sword"),
+            Some((ConfigTab::BaseTypes, sword.unwrap()))
+        );
+
+        // Partial ids select the first match, switching tabs when needed
+        let fire = app
+            .affixes_state
+            .ids
+            .iter()
+            .position(|id| id.contains("fire_damage"))
+            .unwrap();
+        app.open_jump_input();
+        for c in "FIRE_DAMAGE".chars() {
+            app.text_input.insert(c);
+        }
+        app.confirm_jump();
+        assert!(!app.show_jump_input);
+        assert_eq!(app.current_tab, ConfigTab::Affixes);
+        assert_eq!(app.affixes_state.list_state.selected(), Some(fire));
+
+        app.open_jump_input();
+        for c in "no_such_entry".chars() {
+            app.text_input.insert(c);
+        }
+        app.confirm_jump();
+        assert_eq!(app.current_tab, ConfigTab::Affixes);
+        assert_eq!(app.affixes_state.list_state.selected(), Some(fire));
+        assert_eq!(
+            app.message.as_deref(),
+            Some("No entry matching 'no_such_entry'")
+        );
+    }
+
+    #[test]
+    fn test_resize_keeps_selection_visible() {
+        let mut app = App::new(Path::new("../config"));
+        app.switch_tab(ConfigTab::Affixes);
+        let last = app.affixes_state.ids.len() - 1;
+        app.affixes_state.list_state.select(Some(last));
+
+        let render = |app: &mut App, height: u16| {
+            let backend = ratatui::backend::TestBackend::new(120, height);
+            let mut terminal = ratatui::Terminal::new(backend).unwrap();
+            terminal.draw(|f| app.render(f)).unwrap();
+            app.affixes_state.list_state.offset()
+        };
+        let small_offset = render(&mut app, 16);
+        assert!(small_offset > 0);
+
+        app.handle_resize();
+        assert_eq!(render(&mut app, 200), 0);
+        assert_eq!(app.affixes_state.list_state.selected(), Some(last));
+
+        app.handle_resize();
+        assert_eq!(render(&mut app, 16), small_offset);
+        assert_eq!(app.affixes_state.list_state.selected(), Some(last));
+    }
+
+    #[test]
+    fn test_small_terminal_shows_resize_hint() {
+        assert!(!too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT)));
+        assert!(too_small(Rect::new(0, 0, MIN_WIDTH - 1, MIN_HEIGHT)));
+        assert!(too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT - 1)));
+
+        let mut app = App::new(Path::new("../config"));
+        let backend = ratatui::backend::TestBackend::new(30, 8);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| app.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let first_row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, 0)].symbol())
+            .collect();
+        assert_eq!(first_row.trim_end(), "Terminal too small");
+    }
+}
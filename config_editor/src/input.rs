@@ -69,6 +69,7 @@ impl TextInputState {
 
 /// Input validation types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
 pub enum ValidationMode {
     None,
     Integer,
@@ -76,6 +77,7 @@ pub enum ValidationMode {
     Identifier, // alphanumeric + underscore
 }
 
+#[allow(dead_code)]
 impl ValidationMode {
     pub fn validate(&self, c: char) -> bool {
         match self {
@@ -98,6 +100,98 @@ impl ValidationMode {
     }
 }
 
+/// Score how well `query` fuzzy-matches `candidate` (higher is better).
+///
+/// Matching is case-insensitive and expects the query characters to appear in order.
+/// Matches at the start of a PascalCase word or right after the previous match score
+/// higher, and skipped characters cost a little, so "fireres" prefers FireResistance.
+/// Queries of four or more characters tolerate one stray character at a cost, so
+/// "firered" still finds FireResistance. Returns `None` when nothing matches.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    const TYPO: i32 = 10;
+
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+
+    subsequence_score(&query, &candidate).or_else(|| {
+        if query.len() < 4 {
+            return None;
+        }
+        (0..query.len())
+            .filter_map(|skip| {
+                let mut shorter = query.clone();
+                shorter.remove(skip);
+                subsequence_score(&shorter, &candidate)
+            })
+            .max()
+            .map(|score| score - TYPO)
+    })
+}
+
+/// Best score for matching every (lowercase) `query` char in order within `chars`
+fn subsequence_score(query: &[char], chars: &[char]) -> Option<i32> {
+    const MATCH: i32 = 1;
+    const WORD_START: i32 = 8;
+    const CONSECUTIVE: i32 = 5;
+    const GAP: i32 = 1;
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let word_start =
+        |j: usize| j == 0 || chars[j].is_uppercase() || !chars[j - 1].is_alphanumeric();
+    let matches = |i: usize, j: usize| chars[j].to_lowercase().eq(std::iter::once(query[i]));
+    let bonus = |j: usize| MATCH + if word_start(j) { WORD_START } else { 0 };
+
+    // best[j]: best score with the current query char matched at candidate position j
+    let mut best: Vec<Option<i32>> = (0..chars.len())
+        .map(|j| matches(0, j).then(|| bonus(j)))
+        .collect();
+
+    for i in 1..query.len() {
+        best = (0..chars.len())
+            .map(|j| {
+                if !matches(i, j) {
+                    return None;
+                }
+                let from_prev = best[..j]
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(k, score)| {
+                        let score = (*score)?;
+                        Some(if k + 1 == j {
+                            score + CONSECUTIVE
+                        } else {
+                            score - GAP * (j - k - 1) as i32
+                        })
+                    })
+                    .max();
+                from_prev.map(|score| score + bonus(j))
+            })
+            .collect();
+    }
+
+    best.into_iter().flatten().max()
+}
+
+/// Rank `candidates` by fuzzy match against `query`, best first.
+///
+/// Non-matching candidates are dropped. Ties go to the shorter candidate, then to the
+/// original order.
+pub fn fuzzy_rank<'a>(query: &str, candidates: &[&'a str]) -> Vec<&'a str> {
+    let mut scored: Vec<(i32, &str)> = candidates
+        .iter()
+        .filter_map(|c| fuzzy_score(query, c).map(|score| (score, *c)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.len().cmp(&b.1.len())));
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +235,31 @@ mod tests {
         assert!(ValidationMode::Identifier.validate('_'));
         assert!(!ValidationMode::Identifier.validate('-'));
     }
+
+    #[test]
+    fn test_fuzzy_rank_stat_types() {
+        let stats: Vec<&str> = loot_core::types::StatType::all()
+            .iter()
+            .map(|s| s.name())
+            .collect();
+        let top = |query: &str| fuzzy_rank(query, &stats).first().copied();
+
+        assert_eq!(top("firered"), Some("FireResistance"));
+        assert_eq!(top("addfire"), Some("AddedFireDamage"));
+        assert_eq!(top("ias"), Some("IncreasedAttackSpeed"));
+        assert_eq!(top("lifeleech"), Some("LifeLeech"));
+        assert_eq!(top("FIRERES"), Some("FireResistance"));
+        assert!(fuzzy_rank("qqq", &stats).is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_score_requires_order() {
+        assert!(fuzzy_score("fire", "FireResistance").is_some());
+        assert!(fuzzy_score("rif", "FireResistance").is_none());
+        // One stray character is tolerated, at a cost
+        assert!(
+            fuzzy_score("firered", "FireResistance") < fuzzy_score("fireres", "FireResistance")
+        );
+        assert_eq!(fuzzy_score("", "FireResistance"), Some(0));
+    }
 }
@@ -1,6 +1,7 @@
 mod app;
 mod input;
 mod persistence;
+mod theme;
 mod views;
 
 use app::{App, Mode};
@@ -11,11 +12,15 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use loot_core::Config;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::path::Path;
 
 fn main() -> Result<(), io::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config_path = Config::dir_from_arg(args.first().map(String::as_str));
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -23,8 +28,9 @@ fn main() -> Result<(), io::Error> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    theme::load_settings(Path::new(theme::SETTINGS_FILE));
+
     // Load config and create app
-    let config_path = Path::new("config");
     let app = App::new(config_path);
 
     // Run app
@@ -50,7 +56,13 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
     loop {
         terminal.draw(|f| app.render(f))?;
 
-        if let Event::Key(key) = event::read()? {
+        let event = event::read()?;
+        // Redraw from clamped list state so selections stay visible at the new size
+        if let Event::Resize(..) = event {
+            app.handle_resize();
+            continue;
+        }
+        if let Event::Key(key) = event {
             // Only handle key press events
             if key.kind != KeyEventKind::Press {
                 continue;
@@ -59,6 +71,46 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
             // Clear message on any keypress
             app.clear_message();
 
+            // The help overlay swallows keys until it is closed
+            if app.show_help {
+                if matches!(
+                    key.code,
+                    KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q')
+                ) {
+                    app.show_help = false;
+                }
+                continue;
+            }
+
+            // The stats dashboard is read-only, so any close key dismisses it
+            if app.show_stats {
+                if matches!(
+                    key.code,
+                    KeyCode::Esc | KeyCode::Char('D') | KeyCode::Char('q')
+                ) {
+                    app.show_stats = false;
+                }
+                continue;
+            }
+
+            // The jump prompt takes typed text, so it comes before the quit key
+            if app.show_jump_input {
+                match key.code {
+                    KeyCode::Enter => app.confirm_jump(),
+                    KeyCode::Esc => app.show_jump_input = false,
+                    KeyCode::Backspace => app.text_input.backspace(),
+                    KeyCode::Delete => app.text_input.delete(),
+                    KeyCode::Left => app.text_input.move_left(),
+                    KeyCode::Right => app.text_input.move_right(),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    KeyCode::Char(c) => app.text_input.insert(c),
+                    _ => {}
+                }
+                continue;
+            }
+
             // Handle quit
             if key.code == KeyCode::Char('q') && app.mode == Mode::Browse {
                 if app.dirty.is_dirty() {
@@ -121,10 +173,8 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
                     KeyCode::Backspace => {
                         app.new_file_name.pop();
                     }
-                    KeyCode::Char(c) => {
-                        if c.is_alphanumeric() || c == '_' || c == '-' {
-                            app.new_file_name.push(c);
-                        }
+                    KeyCode::Char(c) if c.is_alphanumeric() || c == '_' || c == '-' => {
+                        app.new_file_name.push(c);
                     }
                     _ => {}
                 }
@@ -159,8 +209,15 @@ fn handle_browse_mode(app: &mut App, code: KeyCode) {
         KeyCode::Char('n') => app.enter_create_mode(),
         KeyCode::Char('d') | KeyCode::Delete => app.request_delete(),
         KeyCode::Char('s') => app.save_current(),
+        KeyCode::Char('S') => app.save_all(),
+        KeyCode::Char('I') => app.import_csv(),
+        KeyCode::Char('M') => app.open_move_picker(),
+        KeyCode::Char('/') => app.open_jump_input(),
+        KeyCode::Char('D') => app.show_stats = true,
+        KeyCode::Char('T') => app.toggle_theme(),
+        KeyCode::Char('O') => app.cycle_mod_order(),
+        KeyCode::Char('?') => app.show_help = true,
 
-        // Ctrl+S to save all
         _ => {}
     }
 }
@@ -27,7 +27,7 @@ impl ConfigOrigins {
         if let Ok(entries) = fs::read_dir(config_dir.join("base_types")) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "toml") {
+                if path.extension().is_some_and(|ext| ext == "toml") {
                     if let Ok(content) = fs::read_to_string(&path) {
                         if let Ok(wrapper) = toml::from_str::<BaseTypesWrapper>(&content) {
                             for bt in wrapper.base_types {
@@ -43,7 +43,7 @@ impl ConfigOrigins {
         if let Ok(entries) = fs::read_dir(config_dir.join("affixes")) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "toml") {
+                if path.extension().is_some_and(|ext| ext == "toml") {
                     if let Ok(content) = fs::read_to_string(&path) {
                         if let Ok(wrapper) = toml::from_str::<AffixesWrapper>(&content) {
                             for affix in wrapper.affixes {
@@ -59,7 +59,7 @@ impl ConfigOrigins {
         if let Ok(entries) = fs::read_dir(config_dir.join("affix_pools")) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "toml") {
+                if path.extension().is_some_and(|ext| ext == "toml") {
                     if let Ok(content) = fs::read_to_string(&path) {
                         if let Ok(wrapper) = toml::from_str::<AffixPoolsWrapper>(&content) {
                             for pool in wrapper.pools {
@@ -75,7 +75,7 @@ impl ConfigOrigins {
         if let Ok(entries) = fs::read_dir(config_dir.join("currencies")) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "toml") {
+                if path.extension().is_some_and(|ext| ext == "toml") {
                     if let Ok(content) = fs::read_to_string(&path) {
                         if let Ok(wrapper) = toml::from_str::<CurrenciesWrapper>(&content) {
                             for curr in wrapper.currencies {
@@ -91,7 +91,7 @@ impl ConfigOrigins {
         if let Ok(entries) = fs::read_dir(config_dir.join("uniques")) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "toml") {
+                if path.extension().is_some_and(|ext| ext == "toml") {
                     if let Ok(content) = fs::read_to_string(&path) {
                         if let Ok(file_cfg) = toml::from_str::<UniqueFileConfig>(&content) {
                             origins
@@ -166,6 +166,7 @@ struct CurrenciesWrapper {
 struct UniqueFileConfig {
     unique: UniqueConfig,
     #[serde(default)]
+    #[allow(dead_code)]
     recipe: Option<toml::Value>,
 }
 
@@ -211,6 +212,39 @@ fn is_default_weight(weight: &u32) -> bool {
     *weight == 100
 }
 
+/// A file's contents before a write, so a change spanning several files can be undone
+pub struct FileSnapshot {
+    path: PathBuf,
+    /// None if the file didn't exist
+    contents: Option<Vec<u8>>,
+}
+
+impl FileSnapshot {
+    /// Capture `path` as it is now
+    pub fn take(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read(path) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        Ok(FileSnapshot {
+            path: path.to_path_buf(),
+            contents,
+        })
+    }
+
+    /// Put the file back as it was, removing it if it didn't exist
+    pub fn restore(&self) -> io::Result<()> {
+        match &self.contents {
+            Some(contents) => fs::write(&self.path, contents),
+            None => match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
 /// Save a single entry to its origin file
 pub fn save_entry(
     config: &Config,
@@ -236,9 +270,8 @@ pub fn save_entry(
 fn save_base_types(config: &Config, origins: &ConfigOrigins, path: &Path) -> io::Result<()> {
     // Collect all base types that belong to this file
     let base_types: Vec<&BaseTypeConfig> = config
-        .base_types
-        .values()
-        .filter(|bt| origins.base_types.get(&bt.id).map_or(false, |p| p == path))
+        .base_types_sorted()
+        .filter(|bt| origins.base_types.get(&bt.id).is_some_and(|p| p == path))
         .collect();
 
     let wrapper = BaseTypesWrapperSer { base_types };
@@ -250,9 +283,8 @@ fn save_base_types(config: &Config, origins: &ConfigOrigins, path: &Path) -> io:
 
 fn save_affixes(config: &Config, origins: &ConfigOrigins, path: &Path) -> io::Result<()> {
     let affixes: Vec<&AffixConfig> = config
-        .affixes
-        .values()
-        .filter(|a| origins.affixes.get(&a.id).map_or(false, |p| p == path))
+        .affixes_sorted()
+        .filter(|a| origins.affixes.get(&a.id).is_some_and(|p| p == path))
         .collect();
 
     let wrapper = AffixesWrapperSer { affixes };
@@ -264,14 +296,8 @@ fn save_affixes(config: &Config, origins: &ConfigOrigins, path: &Path) -> io::Re
 
 fn save_affix_pools(config: &Config, origins: &ConfigOrigins, path: &Path) -> io::Result<()> {
     let pools: Vec<&AffixPoolConfig> = config
-        .affix_pools
-        .values()
-        .filter(|p| {
-            origins
-                .affix_pools
-                .get(&p.id)
-                .map_or(false, |op| op == path)
-        })
+        .affix_pools_sorted()
+        .filter(|p| origins.affix_pools.get(&p.id).is_some_and(|op| op == path))
         .collect();
 
     let wrapper = AffixPoolsWrapperSer { pools };
@@ -283,9 +309,8 @@ fn save_affix_pools(config: &Config, origins: &ConfigOrigins, path: &Path) -> io
 
 fn save_currencies(config: &Config, origins: &ConfigOrigins, path: &Path) -> io::Result<()> {
     let currencies: Vec<&CurrencyConfig> = config
-        .currencies
-        .values()
-        .filter(|c| origins.currencies.get(&c.id).map_or(false, |p| p == path))
+        .currencies_sorted()
+        .filter(|c| origins.currencies.get(&c.id).is_some_and(|p| p == path))
         .collect();
 
     let wrapper = CurrenciesWrapperSer { currencies };
@@ -1,8 +1,9 @@
 use super::{preview_line, render_field_line, render_section_header};
 use crate::app::App;
+use crate::theme;
 use loot_core::config::{AffixPoolConfig, Config};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
@@ -19,7 +20,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
                 pool.name.clone()
             },
             Style::default()
-                .fg(Color::White)
+                .fg(theme::current().text)
                 .add_modifier(Modifier::BOLD),
         )),
         preview_line("ID", &pool.id),
@@ -28,7 +29,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
     if !pool.description.is_empty() {
         lines.push(Line::from(Span::styled(
             pool.description.clone(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     }
 
@@ -38,7 +39,18 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         pool.affixes.len()
     )));
 
-    for affix_id in &pool.affixes {
+    // Known affixes follow the display order; unknown ids sort last
+    let mut affix_ids: Vec<&String> = pool.affixes.iter().collect();
+    affix_ids.sort_by(
+        |a, b| match (config.affixes.get(*a), config.affixes.get(*b)) {
+            (Some(a), Some(b)) => {
+                theme::mod_order().compare((a.affix_type, a.stat), (b.affix_type, b.stat))
+            }
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        },
+    );
+
+    for affix_id in affix_ids {
         // Look up the affix to get its name
         let display = config
             .affixes
@@ -56,10 +68,10 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
             .affixes
             .get(affix_id)
             .map(|a| match a.affix_type {
-                loot_core::types::AffixType::Prefix => Color::Cyan,
-                loot_core::types::AffixType::Suffix => Color::Green,
+                loot_core::types::AffixType::Prefix => theme::current().prefix,
+                loot_core::types::AffixType::Suffix => theme::current().suffix,
             })
-            .unwrap_or(Color::Red);
+            .unwrap_or(theme::current().error);
 
         lines.push(Line::from(vec![
             Span::raw("  "),
@@ -116,11 +128,17 @@ pub fn render_edit_form(pool: &AffixPoolConfig, app: &App) -> Vec<Line<'static>>
     let focus_marker = if is_affixes_focused { "> " } else { "  " };
 
     lines.push(Line::from(vec![
-        Span::styled(focus_marker.to_string(), Style::default().fg(Color::Cyan)),
-        Span::styled("Affixes: ".to_string(), Style::default().fg(Color::Gray)),
+        Span::styled(
+            focus_marker.to_string(),
+            Style::default().fg(theme::current().accent),
+        ),
+        Span::styled(
+            "Affixes: ".to_string(),
+            Style::default().fg(theme::current().label),
+        ),
         Span::styled(
             format!("[{} items]", affixes.len()),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         ),
     ]));
 
@@ -128,11 +146,11 @@ pub fn render_edit_form(pool: &AffixPoolConfig, app: &App) -> Vec<Line<'static>>
         // Show input field
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled("     Input: ", Style::default().fg(Color::Gray)),
+            Span::styled("     Input: ", Style::default().fg(theme::current().label)),
             Span::styled(
                 input_display,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme::current().accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
@@ -141,7 +159,7 @@ pub fn render_edit_form(pool: &AffixPoolConfig, app: &App) -> Vec<Line<'static>>
         if affixes.is_empty() {
             lines.push(Line::from(Span::styled(
                 "     (no affixes)",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             for (i, affix_id) in affixes.iter().enumerate() {
@@ -151,27 +169,30 @@ pub fn render_edit_form(pool: &AffixPoolConfig, app: &App) -> Vec<Line<'static>>
                     "     "
                 };
                 lines.push(Line::from(vec![
-                    Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        marker.to_string(),
+                        Style::default().fg(theme::current().success),
+                    ),
                     Span::raw(affix_id.clone()),
                 ]));
             }
         }
         lines.push(Line::from(Span::styled(
             "     [Enter: add, x: remove, Up/Down: select]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
 
         // Show valid affixes from config
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "     Valid affixes (from config):",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
         let all_affixes = app.get_all_affix_ids();
         for chunk in all_affixes.chunks(4) {
             lines.push(Line::from(Span::styled(
                 format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -179,7 +200,7 @@ pub fn render_edit_form(pool: &AffixPoolConfig, app: &App) -> Vec<Line<'static>>
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Tab/Shift+Tab: navigate fields | Ctrl+S: save | Esc: cancel".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
@@ -3,25 +3,106 @@ use super::{
     render_section_header,
 };
 use crate::app::App;
-use loot_core::config::{AffixConfig, Config};
+use crate::theme;
+use loot_core::config::{AffixConfig, AffixTierConfig, Config};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
+/// Item levels sampled by the tier availability table
+const SAMPLE_ILVLS: [u32; 4] = [1, 30, 60, 90];
+
+/// Which tiers can roll at each item level: `result[i][t]` is true when `tiers[t]`
+/// is available at `ilvls[i]`
+pub fn tier_availability(tiers: &[AffixTierConfig], ilvls: &[u32]) -> Vec<Vec<bool>> {
+    ilvls
+        .iter()
+        .map(|&ilvl| tiers.iter().map(|tier| tier.min_ilvl <= ilvl).collect())
+        .collect()
+}
+
+/// Columns used by the tier spread bars
+const SPREAD_WIDTH: usize = 24;
+
+/// One tier's bar in the spread chart: columns `start..=end` out of the chart width
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TierSegment {
+    pub tier: u32,
+    pub start: usize,
+    pub end: usize,
+    /// Whether the tier's value range shares any value with another tier's
+    pub overlaps: bool,
+}
+
+/// Bars for each tier's value range (the low range for damage stats), scaled so the
+/// lowest and highest values across all tiers span `width` columns. Sorted by tier
+/// number, so T1 comes first.
+pub fn tier_spread(tiers: &[AffixTierConfig], width: usize) -> Vec<TierSegment> {
+    let (Some(lowest), Some(highest)) = (
+        tiers.iter().map(|t| t.min).min(),
+        tiers.iter().map(|t| t.max).max(),
+    ) else {
+        return Vec::new();
+    };
+    let last_column = width.saturating_sub(1) as i64;
+    let column = |value: i32| {
+        if highest <= lowest {
+            return 0;
+        }
+        let offset = value.clamp(lowest, highest) as i64 - lowest as i64;
+        (offset * last_column / (highest as i64 - lowest as i64)) as usize
+    };
+
+    let mut segments: Vec<TierSegment> = tiers
+        .iter()
+        .enumerate()
+        .map(|(i, tier)| {
+            // A reversed range (reported by validation) still draws as one column
+            let start = column(tier.min);
+            TierSegment {
+                tier: tier.tier,
+                start,
+                end: column(tier.max).max(start),
+                overlaps: tiers
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| j != i && tier.min <= other.max && other.min <= tier.max),
+            }
+        })
+        .collect();
+    segments.sort_by_key(|segment| segment.tier);
+    segments
+}
+
+/// "step:N " for tiers that round their rolls, empty otherwise
+fn step_label(tier: &AffixTierConfig) -> String {
+    tier.step
+        .map(|step| format!("step:{} ", step))
+        .unwrap_or_default()
+}
+
+/// "+Stat min-max " for each extra stat of a hybrid tier, empty otherwise
+fn extra_stats_label(tier: &AffixTierConfig) -> String {
+    tier.extra_stats
+        .iter()
+        .map(|extra| format!("+{} {}-{} ", extra.stat.name(), extra.min, extra.max))
+        .collect()
+}
+
 pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
     let Some(affix) = config.affixes.get(id) else {
         return vec![Line::from("Affix not found")];
     };
 
     let type_color = match affix.affix_type {
-        loot_core::types::AffixType::Prefix => Color::Cyan,
-        loot_core::types::AffixType::Suffix => Color::Green,
+        loot_core::types::AffixType::Prefix => theme::current().prefix,
+        loot_core::types::AffixType::Suffix => theme::current().suffix,
     };
 
     let scope_color = match affix.scope {
-        loot_core::types::AffixScope::Local => Color::Blue,
-        loot_core::types::AffixScope::Global => Color::Magenta,
+        loot_core::types::AffixScope::Local => theme::current().local,
+        loot_core::types::AffixScope::Global => theme::current().global,
     };
 
     let mut lines = vec![
@@ -31,17 +112,32 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         )),
         preview_line("ID", &affix.id),
         preview_line_colored("Type", &format!("{:?}", affix.affix_type), type_color),
-        preview_line_colored("Stat", &format!("{:?}", affix.stat), Color::Yellow),
+        preview_line_colored(
+            "Stat",
+            &format!("{:?}", affix.stat),
+            theme::current().highlight,
+        ),
         preview_line_colored("Scope", &format!("{:?}", affix.scope), scope_color),
         Line::from(""),
     ];
 
+    // Flavor text
+    if let Some(ref flavor) = affix.flavor {
+        lines.push(Line::from(Span::styled(
+            format!("\"{}\"", flavor),
+            Style::default()
+                .fg(theme::current().muted)
+                .add_modifier(Modifier::ITALIC),
+        )));
+        lines.push(Line::from(""));
+    }
+
     // Tags
     if !affix.tags.is_empty() {
         lines.push(render_section_header("Tags"));
         lines.push(Line::from(Span::styled(
             affix.tags.join(", "),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme::current().highlight),
         )));
         lines.push(Line::from(""));
     }
@@ -72,20 +168,93 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(Line::from(vec![
             Span::styled(
                 format!("  T{} ", tier.tier),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::styled(range_str, Style::default().fg(theme::current().text)),
+            Span::styled(
+                extra_stats_label(tier),
+                Style::default().fg(theme::current().text),
             ),
-            Span::styled(range_str, Style::default().fg(Color::White)),
             Span::styled(
                 format!("ilvl:{} ", tier.min_ilvl),
-                Style::default().fg(Color::Magenta),
+                Style::default().fg(theme::current().special),
+            ),
+            Span::styled(
+                step_label(tier),
+                Style::default().fg(theme::current().muted),
             ),
             Span::styled(
                 format!("w:{}", tier.weight),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             ),
         ]));
     }
 
+    // Tier value ranges side by side, overlaps in the error color
+    if affix.tiers.len() > 1 {
+        lines.push(Line::from(""));
+        lines.push(render_section_header("Tier Spread"));
+        for segment in tier_spread(&affix.tiers, SPREAD_WIDTH) {
+            let color = if segment.overlaps {
+                theme::current().error
+            } else {
+                theme::current().success
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:>3} ", format!("T{}", segment.tier)),
+                    Style::default().fg(theme::current().highlight),
+                ),
+                Span::styled(
+                    "·".repeat(segment.start),
+                    Style::default().fg(theme::current().muted),
+                ),
+                Span::styled(
+                    "█".repeat(segment.end - segment.start + 1),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    "·".repeat(SPREAD_WIDTH - 1 - segment.end),
+                    Style::default().fg(theme::current().muted),
+                ),
+                Span::styled(
+                    if segment.overlaps { " overlaps" } else { "" },
+                    Style::default().fg(theme::current().error),
+                ),
+            ]));
+        }
+    }
+
+    // Tier availability at sample item levels
+    if !affix.tiers.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(render_section_header("Available Tiers"));
+        let header: String = affix
+            .tiers
+            .iter()
+            .map(|tier| format!("{:>4}", format!("T{}", tier.tier)))
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!("  ilvl{}", header),
+            Style::default().fg(theme::current().label),
+        )));
+        let matrix = tier_availability(&affix.tiers, &SAMPLE_ILVLS);
+        for (ilvl, row) in SAMPLE_ILVLS.iter().zip(matrix) {
+            let mut spans = vec![Span::styled(
+                format!("  {:>4}", ilvl),
+                Style::default().fg(theme::current().special),
+            )];
+            for available in row {
+                spans.push(if available {
+                    Span::styled("   x", Style::default().fg(theme::current().success))
+                } else {
+                    Span::styled("   -", Style::default().fg(theme::current().muted))
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
     lines
 }
 
@@ -111,6 +280,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
         .collect::<Vec<_>>()
         .join(", ");
     let tiers_summary = format!("{} tiers", affix.tiers.len());
+    let flavor = affix.flavor.clone().unwrap_or_default();
 
     let mut lines = vec![
         render_section_header("Affix"),
@@ -136,7 +306,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     let type_marker = if is_type_focused { "> " } else { "  " };
     let type_style = if is_type_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -145,22 +315,28 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     if is_type_focused {
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(type_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Type: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                type_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Type: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, type_style),
         ]));
         lines.push(Line::from(Span::styled(
             format!("     Current: {}", type_str),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(Span::styled(
             "     [Enter: set] Valid: Prefix, Suffix",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     } else {
         lines.push(Line::from(vec![
-            Span::styled(type_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Type: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                type_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Type: ", Style::default().fg(theme::current().label)),
             Span::styled(type_str, type_style),
         ]));
     }
@@ -170,7 +346,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     let stat_marker = if is_stat_focused { "> " } else { "  " };
     let stat_style = if is_stat_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -179,34 +355,81 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     if is_stat_focused {
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(stat_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Stat: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                stat_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Stat: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, stat_style),
         ]));
         lines.push(Line::from(Span::styled(
             format!("     Current: {}", stat_str),
-            Style::default().fg(Color::DarkGray),
-        )));
-        lines.push(Line::from(Span::styled(
-            "     [Enter: set]",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "     Valid StatTypes:",
-            Style::default().fg(Color::Gray),
-        )));
-        let all_stats = crate::app::App::get_all_stat_types();
-        for chunk in all_stats.chunks(4) {
+        if app.enum_picker_options.is_empty() {
             lines.push(Line::from(Span::styled(
-                format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                "     [Enter: set] Type to search, e.g. \"firers\" or \"addfire\"",
+                Style::default().fg(theme::current().muted),
+            )));
+            lines.push(Line::from(Span::styled(
+                "     Valid StatTypes:",
+                Style::default().fg(theme::current().label),
+            )));
+            let all_stats: Vec<&str> = loot_core::types::StatType::all()
+                .iter()
+                .map(|s| s.name())
+                .collect();
+            for chunk in all_stats.chunks(4) {
+                lines.push(Line::from(Span::styled(
+                    format!("     {}", chunk.join(", ")),
+                    Style::default().fg(theme::current().muted),
+                )));
+            }
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "     [Up/Down: choose, Enter: set] {} matches",
+                    app.enum_picker_options.len()
+                ),
+                Style::default().fg(theme::current().muted),
             )));
+            const SHOWN_MATCHES: usize = 10;
+            let start = app
+                .enum_picker_index
+                .saturating_sub(SHOWN_MATCHES - 1)
+                .min(app.enum_picker_options.len().saturating_sub(SHOWN_MATCHES));
+            for (i, option) in app
+                .enum_picker_options
+                .iter()
+                .enumerate()
+                .skip(start)
+                .take(SHOWN_MATCHES)
+            {
+                let selected = i == app.enum_picker_index;
+                let (marker, style) = if selected {
+                    (
+                        "     >> ",
+                        Style::default()
+                            .fg(theme::current().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    ("        ", Style::default().fg(theme::current().text))
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme::current().success)),
+                    Span::styled(option.clone(), style),
+                ]));
+            }
         }
     } else {
         lines.push(Line::from(vec![
-            Span::styled(stat_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Stat: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                stat_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Stat: ", Style::default().fg(theme::current().label)),
             Span::styled(stat_str, stat_style),
         ]));
     }
@@ -216,7 +439,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     let scope_marker = if is_scope_focused { "> " } else { "  " };
     let scope_style = if is_scope_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -225,22 +448,28 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     if is_scope_focused {
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(scope_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Scope: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                scope_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Scope: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, scope_style),
         ]));
         lines.push(Line::from(Span::styled(
             format!("     Current: {}", scope_str),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(Span::styled(
             "     [Enter: set] Valid: Local, Global",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     } else {
         lines.push(Line::from(vec![
-            Span::styled(scope_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Scope: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                scope_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Scope: ", Style::default().fg(theme::current().label)),
             Span::styled(scope_str, scope_style),
         ]));
     }
@@ -250,7 +479,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     let tags_marker = if is_tags_focused { "> " } else { "  " };
     let tags_style = if is_tags_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -260,8 +489,11 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
         // Show input field when focused
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(tags_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Tags: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                tags_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Tags: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, tags_style),
         ]));
         // Show existing tags as a list
@@ -272,38 +504,44 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
                 "     "
             };
             lines.push(Line::from(vec![
-                Span::styled(item_marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    item_marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::raw(tag.clone()),
             ]));
         }
         lines.push(Line::from(Span::styled(
             "     [Enter: add, x: remove, Up/Down: select]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "     Valid tags (from config):",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
         // Get tags from config and display in rows
         let all_tags = app.get_all_tags();
         for chunk in all_tags.chunks(6) {
             lines.push(Line::from(Span::styled(
                 format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     } else {
         lines.push(Line::from(vec![
-            Span::styled(tags_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Tags: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                tags_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Tags: ", Style::default().fg(theme::current().label)),
             Span::styled(
                 if tags_str.is_empty() {
                     "(none)".to_string()
                 } else {
                     tags_str
                 },
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             ),
         ]));
     }
@@ -315,7 +553,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
     let classes_marker = if is_classes_focused { "> " } else { "  " };
     let classes_style = if is_classes_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -325,8 +563,14 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
         // Show input field when focused
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(classes_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Allowed Classes: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                classes_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled(
+                "Allowed Classes: ",
+                Style::default().fg(theme::current().label),
+            ),
             Span::styled(input_display, classes_style),
         ]));
         // Show existing classes as a list
@@ -337,37 +581,49 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
                 "     "
             };
             lines.push(Line::from(vec![
-                Span::styled(item_marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    item_marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::raw(format!("{:?}", class)),
             ]));
         }
         lines.push(Line::from(Span::styled(
             "     [Enter: add, x: remove, Up/Down: select]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "     Valid classes (from types.rs):",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
-        let all_classes = crate::app::App::get_all_classes();
+        let all_classes: Vec<&str> = loot_core::types::ItemClass::all()
+            .iter()
+            .map(|c| c.name())
+            .collect();
         for chunk in all_classes.chunks(6) {
             lines.push(Line::from(Span::styled(
                 format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     } else {
         lines.push(Line::from(vec![
-            Span::styled(classes_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Allowed Classes: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                classes_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled(
+                "Allowed Classes: ",
+                Style::default().fg(theme::current().label),
+            ),
             Span::styled(
                 if classes_str.is_empty() {
                     "(none)".to_string()
                 } else {
                     classes_str
                 },
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             ),
         ]));
     }
@@ -385,16 +641,16 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
         if state.nested_depth >= 2 {
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
-                    " (format: tier weight min max [max_min max_max] min_ilvl)",
-                    Style::default().fg(Color::DarkGray),
+                    " (format: tier weight min max [max_min max_max] min_ilvl [step:N])",
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
         }
@@ -403,7 +659,7 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
             let is_selected = i == state.nested_index;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected && state.nested_depth < 2 {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
@@ -418,19 +674,27 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
             };
 
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::styled(
                     format!("T{}: ", tier.tier),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme::current().highlight),
                 ),
                 Span::styled(range_str, style),
+                Span::styled(extra_stats_label(tier), style),
                 Span::styled(
                     format!("ilvl:{} ", tier.min_ilvl),
-                    Style::default().fg(Color::Magenta),
+                    Style::default().fg(theme::current().special),
+                ),
+                Span::styled(
+                    step_label(tier),
+                    Style::default().fg(theme::current().muted),
                 ),
                 Span::styled(
                     format!("w:{}", tier.weight),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
         }
@@ -438,21 +702,105 @@ pub fn render_edit_form(affix: &AffixConfig, app: &App) -> Vec<Line<'static>> {
         if state.nested_depth >= 2 {
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]".to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Ctrl+Del: remove, +: add, Esc: back]".to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
 
+    lines.push(Line::from(""));
+    lines.push(render_field_line(
+        "Flavor",
+        &flavor,
+        8,
+        app,
+        if state.field_index == 8 { cursor } else { None },
+    ));
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Tab/Shift+Tab: navigate fields | Ctrl+S: save | Esc: cancel".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(tier: u32, min_ilvl: u32) -> AffixTierConfig {
+        AffixTierConfig {
+            tier,
+            weight: 100,
+            min: 1,
+            max: 10,
+            max_value: None,
+            min_ilvl,
+            step: None,
+            extra_stats: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tier_availability_matrix() {
+        let tiers = [tier(1, 75), tier(2, 30), tier(3, 1)];
+
+        assert_eq!(
+            tier_availability(&tiers, &SAMPLE_ILVLS),
+            vec![
+                vec![false, false, true],
+                vec![false, true, true],
+                vec![false, true, true],
+                vec![true, true, true],
+            ]
+        );
+    }
+    #[test]
+    fn test_tier_spread_segments() {
+        let ranged = |n: u32, min: i32, max: i32| AffixTierConfig {
+            min,
+            max,
+            ..tier(n, 1)
+        };
+        // Listed worst first, as in most config files; T1 overlaps T2
+        let tiers = [ranged(3, 1, 10), ranged(2, 11, 20), ranged(1, 15, 30)];
+
+        assert_eq!(
+            tier_spread(&tiers, 30),
+            vec![
+                TierSegment {
+                    tier: 1,
+                    start: 14,
+                    end: 29,
+                    overlaps: true,
+                },
+                TierSegment {
+                    tier: 2,
+                    start: 10,
+                    end: 19,
+                    overlaps: true,
+                },
+                TierSegment {
+                    tier: 3,
+                    start: 0,
+                    end: 9,
+                    overlaps: false,
+                },
+            ]
+        );
+
+        // A single fixed value takes one column; no tiers give no bars
+        let fixed = tier_spread(&[ranged(1, 5, 5)], 30);
+        assert_eq!(
+            (fixed[0].start, fixed[0].end, fixed[0].overlaps),
+            (0, 0, false)
+        );
+        assert!(tier_spread(&[], 30).is_empty());
+    }
+}
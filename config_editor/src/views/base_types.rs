@@ -3,9 +3,10 @@ use super::{
     render_section_header,
 };
 use crate::app::App;
-use loot_core::config::{BaseTypeConfig, Config};
+use crate::theme;
+use loot_core::config::{BaseTypeConfig, Config, ImplicitConfig};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
@@ -18,11 +19,18 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         Line::from(Span::styled(
             bt.name.clone(),
             Style::default()
-                .fg(Color::White)
+                .fg(theme::current().text)
                 .add_modifier(Modifier::BOLD),
         )),
         preview_line("ID", &bt.id),
-        preview_line_colored("Class", &format!("{:?}", bt.class), Color::Cyan),
+        preview_line_colored("Class", &format!("{:?}", bt.class), theme::current().accent),
+        preview_line("Group", bt.group.as_deref().unwrap_or("(none)")),
+        preview_line(
+            "Drop Weight",
+            &bt.drop_weight
+                .unwrap_or(BaseTypeConfig::DEFAULT_DROP_WEIGHT)
+                .to_string(),
+        ),
         Line::from(""),
     ];
 
@@ -31,7 +39,15 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(render_section_header("Tags"));
         lines.push(Line::from(Span::styled(
             bt.tags.join(", "),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme::current().highlight),
+        )));
+        lines.push(Line::from(""));
+    }
+    if !bt.blocked_tags.is_empty() {
+        lines.push(render_section_header("Blocked Tags"));
+        lines.push(Line::from(Span::styled(
+            bt.blocked_tags.join(", "),
+            Style::default().fg(theme::current().error),
         )));
         lines.push(Line::from(""));
     }
@@ -64,11 +80,11 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         // Show each damage type with its range
         for entry in &dmg.damages {
             let color = match entry.damage_type {
-                loot_core::types::DamageType::Physical => Color::White,
-                loot_core::types::DamageType::Fire => Color::Red,
-                loot_core::types::DamageType::Cold => Color::Cyan,
-                loot_core::types::DamageType::Lightning => Color::Yellow,
-                loot_core::types::DamageType::Chaos => Color::Magenta,
+                loot_core::types::DamageType::Physical => theme::current().physical,
+                loot_core::types::DamageType::Fire => theme::current().fire,
+                loot_core::types::DamageType::Cold => theme::current().cold,
+                loot_core::types::DamageType::Lightning => theme::current().lightning,
+                loot_core::types::DamageType::Chaos => theme::current().chaos,
             };
             lines.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
@@ -78,7 +94,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
                 ),
                 Span::styled(
                     format!("{}-{}", entry.min, entry.max),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme::current().text),
                 ),
             ]));
         }
@@ -127,13 +143,19 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(Line::from(""));
     }
 
-    // Implicit
-    if let Some(ref imp) = bt.implicit {
-        lines.push(render_section_header("Implicit"));
+    // Implicits
+    if !bt.implicits.is_empty() {
+        lines.push(render_section_header(if bt.quality_affects_implicit {
+            "Implicit (scales with quality)"
+        } else {
+            "Implicit"
+        }));
+    }
+    for imp in &bt.implicits {
         lines.push(preview_line_colored(
             &format!("  {:?}", imp.stat),
-            &format!("{}-{}", imp.min, imp.max),
-            Color::Magenta,
+            &format!("{}-{}{}", imp.min, imp.max, implicit_weight_label(imp)),
+            theme::current().special,
         ));
     }
 
@@ -141,23 +163,23 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "─── Quick Reference ───",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Class: Equipment slot & valid affixes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Tags: Boost matching affix weights",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
-        "Implicit: Always-present bonus",
-        Style::default().fg(Color::DarkGray),
+        "Implicit: Always-present bonus (weighted ones: one of them)",
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Spell Eff: Casting power for wands/staves",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
@@ -177,11 +199,28 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
     let class_str = format!("{:?}", bt.class);
     let tags_str = bt.tags.join(", ");
 
-    let implicit_summary = bt
-        .implicit
-        .as_ref()
-        .map(|i| format!("{:?}: {}-{}", i.stat, i.min, i.max))
-        .unwrap_or_else(|| "None".to_string());
+    let implicit_summary = match bt.implicits.as_slice() {
+        [] => "None".to_string(),
+        [i] => format!(
+            "{:?}: {}-{}{}",
+            i.stat,
+            i.min,
+            i.max,
+            implicit_weight_label(i)
+        ),
+        implicits => {
+            let options = implicits.iter().filter(|i| i.weight.is_some()).count();
+            if options > 0 {
+                format!(
+                    "{} implicits ({} weighted options)",
+                    implicits.len(),
+                    options
+                )
+            } else {
+                format!("{} implicits", implicits.len())
+            }
+        }
+    };
 
     let defenses_summary = bt
         .defenses
@@ -268,7 +307,7 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
     let class_marker = if is_class_focused { "> " } else { "  " };
     let class_style = if is_class_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -277,34 +316,43 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
     if is_class_focused {
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(class_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Class: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                class_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Class: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, class_style),
         ]));
         lines.push(Line::from(Span::styled(
             format!("     Current: {}", class_str),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(Span::styled(
             "     [Enter: set class]",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "     Valid classes (from types.rs):",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
-        let all_classes = crate::app::App::get_all_classes();
+        let all_classes: Vec<&str> = loot_core::types::ItemClass::all()
+            .iter()
+            .map(|c| c.name())
+            .collect();
         for chunk in all_classes.chunks(6) {
             lines.push(Line::from(Span::styled(
                 format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     } else {
         lines.push(Line::from(vec![
-            Span::styled(class_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Class: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                class_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Class: ", Style::default().fg(theme::current().label)),
             Span::styled(class_str, class_style),
         ]));
     }
@@ -314,7 +362,7 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
     let tags_marker = if is_tags_focused { "> " } else { "  " };
     let tags_style = if is_tags_focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -323,8 +371,11 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
     if is_tags_focused {
         let input_display = format!("{}|", app.text_input.value());
         lines.push(Line::from(vec![
-            Span::styled(tags_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Tags: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                tags_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Tags: ", Style::default().fg(theme::current().label)),
             Span::styled(input_display, tags_style),
         ]));
         for (i, tag) in bt.tags.iter().enumerate() {
@@ -334,37 +385,43 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
                 "     "
             };
             lines.push(Line::from(vec![
-                Span::styled(item_marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    item_marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::raw(tag.clone()),
             ]));
         }
         lines.push(Line::from(Span::styled(
             "     [Enter: add, x: remove, Up/Down: select]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "     Valid tags (from config):",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
         let all_tags = app.get_all_tags();
         for chunk in all_tags.chunks(6) {
             lines.push(Line::from(Span::styled(
                 format!("     {}", chunk.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     } else {
         lines.push(Line::from(vec![
-            Span::styled(tags_marker.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled("Tags: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                tags_marker.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::styled("Tags: ", Style::default().fg(theme::current().label)),
             Span::styled(
                 if tags_str.is_empty() {
                     "(none)".to_string()
                 } else {
                     tags_str
                 },
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             ),
         ]));
     }
@@ -382,48 +439,69 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             // Editing mode - show input
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
             lines.push(Line::from(Span::styled(
-                "     Format: StatType min max (or 'none' to clear)",
-                Style::default().fg(Color::DarkGray),
+                "     Format: StatType min max [weight] (or 'none' to remove)",
+                Style::default().fg(theme::current().muted),
             )));
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "     Valid StatTypes:",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme::current().label),
             )));
-            let all_stats = crate::app::App::get_all_stat_types();
+            let all_stats: Vec<&str> = loot_core::types::StatType::all()
+                .iter()
+                .map(|s| s.name())
+                .collect();
             for chunk in all_stats.chunks(4) {
                 lines.push(Line::from(Span::styled(
                     format!("     {}", chunk.join(", ")),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         } else {
-            // Selection mode - show current value
-            let current = bt
-                .implicit
-                .as_ref()
-                .map(|i| format!("{:?}: {}-{}", i.stat, i.min, i.max))
-                .unwrap_or_else(|| "none".to_string());
-            lines.push(Line::from(vec![
-                Span::styled("  >> ", Style::default().fg(Color::Green)),
-                Span::styled(current, Style::default().fg(Color::Cyan)),
-            ]));
+            // Selection mode - list every implicit
+            if bt.implicits.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "     (none)",
+                    Style::default().fg(theme::current().muted),
+                )));
+            }
+            for (i, imp) in bt.implicits.iter().enumerate() {
+                let is_selected = i == state.nested_index;
+                let marker = if is_selected { "  >> " } else { "     " };
+                let style = if is_selected {
+                    Style::default().fg(theme::current().accent)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme::current().success)),
+                    Span::styled(
+                        format!("{:?}: ", imp.stat),
+                        Style::default().fg(theme::current().special),
+                    ),
+                    Span::styled(format!("{}-{}", imp.min, imp.max), style),
+                    Span::styled(
+                        implicit_weight_label(imp),
+                        Style::default().fg(theme::current().muted),
+                    ),
+                ]));
+            }
             lines.push(Line::from(Span::styled(
-                "     [Enter: edit, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                "     [Enter: edit, x: remove, +: add, Esc: back]",
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -437,17 +515,17 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             // Editing mode - show input
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
             lines.push(Line::from(Span::styled(
                 "     Format: min max (or 'none' to clear)",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
 
@@ -471,7 +549,7 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             let is_selected = i == state.nested_index;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected && state.nested_depth < 2 {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
@@ -481,8 +559,14 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
                 .unwrap_or_else(|| "none".to_string());
 
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
+                Span::styled(
+                    format!("{}: ", name),
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(value_str, style),
             ]));
         }
@@ -490,12 +574,12 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
         if state.nested_depth >= 2 {
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -509,11 +593,11 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
         if state.nested_depth >= 2 {
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -521,16 +605,16 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             if state.nested_index < 3 {
                 lines.push(Line::from(Span::styled(
                     "     Enter a number (or 'none' to clear damage entirely)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
                     "     Format: Type min max (e.g., Physical 10 20)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
                 lines.push(Line::from(Span::styled(
                     "     Types: Physical, Fire, Cold, Lightning, Chaos",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         }
@@ -559,13 +643,19 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             let is_selected = i == state.nested_index && state.nested_depth < 2;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
+                Span::styled(
+                    format!("{}: ", name),
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(value.clone(), style),
             ]));
         }
@@ -576,33 +666,39 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
         lines.push(Line::from(vec![
             Span::styled(
                 damages_marker.to_string(),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme::current().success),
+            ),
+            Span::styled(
+                "Damage Types:",
+                Style::default().fg(theme::current().highlight),
             ),
-            Span::styled("Damage Types:", Style::default().fg(Color::Yellow)),
         ]));
 
         // Show damage entries
-        if let Some(ref d) = dmg {
+        if let Some(d) = dmg {
             for (i, entry) in d.damages.iter().enumerate() {
                 let entry_idx = 4 + i; // Offset by 4 (3 stats + header)
                 let is_selected = entry_idx == state.nested_index && state.nested_depth < 2;
                 let marker = if is_selected { "     >> " } else { "        " };
                 let color = match entry.damage_type {
-                    loot_core::types::DamageType::Physical => Color::White,
-                    loot_core::types::DamageType::Fire => Color::Red,
-                    loot_core::types::DamageType::Cold => Color::Cyan,
-                    loot_core::types::DamageType::Lightning => Color::Yellow,
-                    loot_core::types::DamageType::Chaos => Color::Magenta,
+                    loot_core::types::DamageType::Physical => theme::current().physical,
+                    loot_core::types::DamageType::Fire => theme::current().fire,
+                    loot_core::types::DamageType::Cold => theme::current().cold,
+                    loot_core::types::DamageType::Lightning => theme::current().lightning,
+                    loot_core::types::DamageType::Chaos => theme::current().chaos,
                 };
                 let style = if is_selected {
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
                 };
                 lines.push(Line::from(vec![
-                    Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        marker.to_string(),
+                        Style::default().fg(theme::current().success),
+                    ),
                     Span::styled(
                         format!("{:?}: ", entry.damage_type),
                         Style::default().fg(color),
@@ -616,12 +712,12 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
         if state.nested_depth >= 2 {
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, +: add damage type, Ctrl+Del: remove, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -635,138 +731,175 @@ pub fn render_edit_form(bt: &BaseTypeConfig, app: &App) -> Vec<Line<'static>> {
             // Editing mode - show input
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
             lines.push(Line::from(Span::styled(
                 "     Format: level strength dexterity intelligence",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             // Selection mode - show current values
             let req = &bt.requirements;
             lines.push(Line::from(vec![
-                Span::styled("  >> ", Style::default().fg(Color::Green)),
+                Span::styled("  >> ", Style::default().fg(theme::current().success)),
                 Span::styled(
                     format!(
                         "Lv{} Str{} Dex{} Int{}",
                         req.level, req.strength, req.dexterity, req.intelligence
                     ),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme::current().accent),
                 ),
             ]));
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
 
+    lines.push(render_section_header("Generation"));
+    lines.push(render_field_line(
+        "Group",
+        bt.group.as_deref().unwrap_or(""),
+        8,
+        app,
+        if state.field_index == 8 { cursor } else { None },
+    ));
+    let drop_weight = bt.drop_weight.map(|w| w.to_string()).unwrap_or_default();
+    lines.push(render_field_line(
+        "Drop Weight",
+        &drop_weight,
+        9,
+        app,
+        if state.field_index == 9 { cursor } else { None },
+    ));
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Tab/Shift+Tab: navigate fields | Ctrl+S: save | Esc: cancel".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     // Field explanations footnote
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "─── Field Reference ───",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Basic Fields:",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  ID: Unique identifier used in config references",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Name: Display name shown to players",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Class: Item category (determines valid affixes and equipment slot)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Tags: Keywords for affix weighting (matching tags = higher roll chance)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
+    )));
+    lines.push(Line::from(Span::styled(
+        "  Group: Optional family (e.g. int_armour) for generating a random base from it",
+        Style::default().fg(theme::current().muted),
+    )));
+    lines.push(Line::from(Span::styled(
+        "  Drop Weight: Relative chance as a random drop (blank = 100, 0 = never)",
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Implicit:",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Built-in stat bonus that's always present (not from affixes)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Format: StatType min max (e.g., AddedAccuracy 10 20)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
+    )));
+    lines.push(Line::from(Span::styled(
+        "  Add a weight to make it an option: each item gets one weighted implicit",
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Defenses (for armour pieces):",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Armour: Reduces physical damage taken",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Evasion: Chance to avoid attacks entirely",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Energy Shield: Absorbs damage before life, recharges over time",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Damage (for weapons):",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Attack Speed: Attacks per second (higher = faster)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Crit Chance: Base % chance for critical strikes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Spell Efficiency: % effectiveness for spells (0=melee, 80-120=caster)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Damage Types: Each type has its own min-max range (use + to add)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    Physical/Fire/Cold/Lightning/Chaos - format: Type min max",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Requirements:",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Minimum character stats needed to equip the item",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Level: Character level | Str/Dex/Int: Attribute requirements",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
 }
+
+/// " (weight N)" for a weighted implicit option, empty for an always-rolled implicit
+fn implicit_weight_label(implicit: &ImplicitConfig) -> String {
+    implicit
+        .weight
+        .map(|weight| format!(" (weight {})", weight))
+        .unwrap_or_default()
+}
@@ -3,9 +3,11 @@ use super::{
     render_section_header,
 };
 use crate::app::App;
+use crate::theme;
 use loot_core::config::{Config, CurrencyConfig};
+use loot_core::types::AffixType;
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
@@ -18,7 +20,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         Line::from(Span::styled(
             curr.name.clone(),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme::current().accent)
                 .add_modifier(Modifier::BOLD),
         )),
         preview_line("ID", &curr.id),
@@ -28,14 +30,18 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(preview_line_colored(
             "Category",
             &curr.category,
-            Color::Yellow,
+            theme::current().highlight,
         ));
     }
 
+    if let Some(stack_size) = curr.stack_size {
+        lines.push(preview_line("Stack size", &stack_size.to_string()));
+    }
+
     if !curr.description.is_empty() {
         lines.push(Line::from(Span::styled(
             curr.description.clone(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     }
 
@@ -43,7 +49,11 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
 
     // Requirements
     let reqs = &curr.requires;
-    if !reqs.rarities.is_empty() || reqs.has_affix || reqs.has_affix_slot {
+    if !reqs.rarities.is_empty()
+        || reqs.has_affix
+        || reqs.has_affix_slot
+        || !reqs.required_tags.is_empty()
+    {
         lines.push(render_section_header("Requirements"));
 
         if !reqs.rarities.is_empty() {
@@ -51,13 +61,23 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
             lines.push(preview_line("  Rarities", &rarities.join(", ")));
         }
         if reqs.has_affix {
-            lines.push(preview_line_colored("  Has Affix", "true", Color::Green));
+            lines.push(preview_line_colored(
+                "  Has Affix",
+                "true",
+                theme::current().success,
+            ));
         }
         if reqs.has_affix_slot {
             lines.push(preview_line_colored(
                 "  Has Affix Slot",
                 "true",
-                Color::Green,
+                theme::current().success,
+            ));
+        }
+        if !reqs.required_tags.is_empty() {
+            lines.push(preview_line(
+                "  Required Tags",
+                &reqs.required_tags.join(", "),
             ));
         }
         lines.push(Line::from(""));
@@ -71,11 +91,15 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(preview_line_colored(
             "  Set Rarity",
             &format!("{:?}", rarity),
-            Color::Yellow,
+            theme::current().highlight,
         ));
     }
     if effects.clear_affixes {
-        lines.push(preview_line_colored("  Clear Affixes", "true", Color::Red));
+        lines.push(preview_line_colored(
+            "  Clear Affixes",
+            "true",
+            theme::current().error,
+        ));
     }
     if let Some(ref count) = effects.add_affixes {
         let range = if count.min == count.max {
@@ -83,7 +107,11 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         } else {
             format!("{}-{}", count.min, count.max)
         };
-        lines.push(preview_line_colored("  Add Affixes", &range, Color::Green));
+        lines.push(preview_line_colored(
+            "  Add Affixes",
+            &range,
+            theme::current().success,
+        ));
     }
     if !effects.add_specific_affix.is_empty() {
         let total_weight: u32 = effects.add_specific_affix.iter().map(|s| s.weight).sum();
@@ -92,7 +120,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
                 "  Add Specific: {} affix(es)",
                 effects.add_specific_affix.len()
             ),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme::current().success),
         )));
         for specific in &effects.add_specific_affix {
             let chance = if total_weight > 0 {
@@ -107,12 +135,12 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
             lines.push(Line::from(vec![
                 Span::raw("    - "),
                 Span::styled(
-                    format!("{}{}", specific.id, tier_str),
-                    Style::default().fg(Color::White),
+                    format!("{}{}{}", specific.id, tier_str, fixed_value_label(specific)),
+                    Style::default().fg(theme::current().text),
                 ),
                 Span::styled(
                     format!(" ({:.1}%)", chance),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme::current().highlight),
                 ),
             ]));
         }
@@ -121,21 +149,32 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(preview_line_colored(
             "  Remove Affixes",
             &count.to_string(),
-            Color::Red,
+            theme::current().error,
         ));
     }
     if let Some(count) = effects.reroll_affixes {
         lines.push(preview_line_colored(
             "  Reroll Affixes",
             &count.to_string(),
-            Color::Yellow,
+            theme::current().highlight,
         ));
+        if let Some(scope) = effects.reroll_scope() {
+            let group = match scope {
+                AffixType::Prefix => "prefixes",
+                AffixType::Suffix => "suffixes",
+            };
+            lines.push(preview_line_colored(
+                "  Reroll Only",
+                group,
+                theme::current().highlight,
+            ));
+        }
     }
     if effects.try_unique {
         lines.push(preview_line_colored(
             "  Try Unique",
             "true",
-            Color::Rgb(175, 95, 0),
+            theme::current().unique,
         ));
     }
     if !effects.affix_pools.is_empty() {
@@ -144,6 +183,35 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
             &effects.affix_pools.join(", "),
         ));
     }
+    if let Some(ref pool) = effects.enchant_pool {
+        lines.push(preview_line_colored(
+            "  Enchant Pool",
+            pool,
+            theme::current().special,
+        ));
+    }
+    let blocked: Vec<&str> = effects
+        .weighted_tags
+        .iter()
+        .filter(|(_, multiplier)| *multiplier == 0.0)
+        .map(|(tag, _)| tag.as_str())
+        .collect();
+    if !blocked.is_empty() {
+        lines.push(preview_line_colored(
+            "  Blocked Tags",
+            &blocked.join(", "),
+            theme::current().error,
+        ));
+    }
+    let weighted: Vec<String> = effects
+        .weighted_tags
+        .iter()
+        .filter(|(_, multiplier)| *multiplier != 0.0)
+        .map(|(tag, multiplier)| format!("{} x{}", tag, multiplier))
+        .collect();
+    if !weighted.is_empty() {
+        lines.push(preview_line("  Weighted Tags", &weighted.join(", ")));
+    }
 
     lines
 }
@@ -175,6 +243,9 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
         if reqs.has_affix_slot {
             parts.push("has_slot".to_string());
         }
+        if !reqs.required_tags.is_empty() {
+            parts.push(format!("{} tags", reqs.required_tags.len()));
+        }
         if parts.is_empty() {
             "None".to_string()
         } else {
@@ -258,11 +329,11 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             // Rarities list editing mode
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Add: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Add: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -271,19 +342,22 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             if reqs.rarities.is_empty() {
                 lines.push(Line::from(Span::styled(
                     "     (no rarities - press Enter to add)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else {
                 for (i, rarity) in reqs.rarities.iter().enumerate() {
                     let is_selected = i == app.nested_sub_field_index;
                     let marker = if is_selected { "     >> " } else { "        " };
                     let style = if is_selected {
-                        Style::default().fg(Color::Cyan)
+                        Style::default().fg(theme::current().accent)
                     } else {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(theme::current().text)
                     };
                     lines.push(Line::from(vec![
-                        Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                        Span::styled(
+                            marker.to_string(),
+                            Style::default().fg(theme::current().success),
+                        ),
                         Span::styled(format!("{:?}", rarity), style),
                     ]));
                 }
@@ -291,29 +365,35 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
 
             lines.push(Line::from(Span::styled(
                 "     [Enter: add, x: remove, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
             lines.push(Line::from(Span::styled(
                 "     Valid: Normal, Magic, Rare, Unique",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
 
             // Also show the other requirement fields (non-editable in this mode)
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled("     ", Style::default()),
-                Span::styled("Has Affix: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    "Has Affix: ",
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(
                     reqs.has_affix.to_string(),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
             lines.push(Line::from(vec![
                 Span::styled("     ", Style::default()),
-                Span::styled("Has Affix Slot: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    "Has Affix Slot: ",
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(
                     reqs.has_affix_slot.to_string(),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
         } else {
@@ -321,11 +401,11 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             if state.nested_depth >= 2 && state.nested_index != 0 {
                 let input_display = format!("{}|", app.text_input.value());
                 lines.push(Line::from(vec![
-                    Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                    Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                     Span::styled(
                         input_display,
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme::current().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                 ]));
@@ -342,20 +422,33 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
                 }),
                 ("Has Affix", reqs.has_affix.to_string()),
                 ("Has Affix Slot", reqs.has_affix_slot.to_string()),
+                ("Required Tags", {
+                    if reqs.required_tags.is_empty() {
+                        "none".to_string()
+                    } else {
+                        reqs.required_tags.join(", ")
+                    }
+                }),
             ];
 
             for (i, (name, value)) in req_items.iter().enumerate() {
                 let is_selected = i == state.nested_index;
                 let marker = if is_selected { "  >> " } else { "     " };
                 let style = if is_selected && state.nested_depth < 2 {
-                    Style::default().fg(Color::Cyan)
+                    Style::default().fg(theme::current().accent)
                 } else {
                     Style::default()
                 };
 
                 lines.push(Line::from(vec![
-                    Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                    Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        marker.to_string(),
+                        Style::default().fg(theme::current().success),
+                    ),
+                    Span::styled(
+                        format!("{}: ", name),
+                        Style::default().fg(theme::current().highlight),
+                    ),
                     Span::styled(value.clone(), style),
                 ]));
             }
@@ -369,17 +462,17 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
                 if !hint.is_empty() {
                     lines.push(Line::from(Span::styled(
                         format!("     {}", hint),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 }
                 lines.push(Line::from(Span::styled(
                     "     [Enter: save, Esc: cancel]",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
                     "     [Enter: edit, Up/Down: select, Esc: back]",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         }
@@ -396,11 +489,11 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
         if state.nested_depth >= 2 {
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -457,14 +550,20 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             let is_selected = i == state.nested_index;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected && state.nested_depth < 2 {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
 
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
+                Span::styled(
+                    format!("{}: ", name),
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(value.clone(), style),
             ]));
         }
@@ -485,17 +584,17 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
                 };
                 let input_display = format!("{}|", app.text_input.value());
                 lines.push(Line::from(vec![
-                    Span::styled(label, Style::default().fg(Color::Gray)),
+                    Span::styled(label, Style::default().fg(theme::current().label)),
                     Span::styled(
                         input_display,
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme::current().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                 ]));
                 lines.push(Line::from(Span::styled(
-                    "     Format: affix_id [tier] weight",
-                    Style::default().fg(Color::DarkGray),
+                    "     Format: affix_id [tier|-] [weight] [value] [value_max]",
+                    Style::default().fg(theme::current().muted),
                 )));
             }
 
@@ -503,16 +602,16 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             if effects.add_specific_affix.is_empty() {
                 lines.push(Line::from(Span::styled(
                     "     (no specific affixes - press + to add)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else {
                 for (i, specific) in effects.add_specific_affix.iter().enumerate() {
                     let is_selected = i == app.nested_sub_field_index;
                     let marker = if is_selected { "     >> " } else { "        " };
                     let style = if is_selected && state.nested_depth < 3 {
-                        Style::default().fg(Color::Cyan)
+                        Style::default().fg(theme::current().accent)
                     } else {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(theme::current().text)
                     };
 
                     let chance = if total_weight > 0 {
@@ -526,11 +625,17 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
                         .unwrap_or_default();
 
                     lines.push(Line::from(vec![
-                        Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                        Span::styled(format!("{}{}", specific.id, tier_str), style),
+                        Span::styled(
+                            marker.to_string(),
+                            Style::default().fg(theme::current().success),
+                        ),
+                        Span::styled(
+                            format!("{}{}{}", specific.id, tier_str, fixed_value_label(specific)),
+                            style,
+                        ),
                         Span::styled(
                             format!(" - {:.1}% (w:{})", chance, specific.weight),
-                            Style::default().fg(Color::DarkGray),
+                            Style::default().fg(theme::current().muted),
                         ),
                     ]));
                 }
@@ -539,12 +644,12 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             if state.nested_depth >= 3 {
                 lines.push(Line::from(Span::styled(
                     "     [Enter: save, Esc: cancel]",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
                     "     [Enter: edit, +: add, x: remove, Up/Down: select, Esc: back]",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
 
@@ -552,13 +657,13 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "     Valid affixes:",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme::current().label),
             )));
             let all_affixes = app.get_all_affix_ids();
             for chunk in all_affixes.chunks(4) {
                 lines.push(Line::from(Span::styled(
                     format!("     {}", chunk.join(", ")),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         } else if state.nested_depth >= 2 {
@@ -573,37 +678,37 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
             };
             lines.push(Line::from(Span::styled(
                 format!("     {}", hint),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
 
             // Show valid options for certain fields
             if state.nested_index == 0 {
                 lines.push(Line::from(Span::styled(
                     "     Valid: Normal, Magic, Rare, Unique",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             } else if state.nested_index == 7 {
                 lines.push(Line::from(Span::styled(
                     "     Valid pools:",
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme::current().label),
                 )));
                 let all_pools = app.get_all_affix_pool_ids();
                 for chunk in all_pools.chunks(4) {
                     lines.push(Line::from(Span::styled(
                         format!("     {}", chunk.join(", ")),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 }
             }
 
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -611,67 +716,77 @@ pub fn render_edit_form(curr: &CurrencyConfig, app: &App) -> Vec<Line<'static>>
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Tab/Shift+Tab: navigate fields | Ctrl+S: save | Esc: cancel".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     // Field explanations footnote
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "─── Field Reference ───",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Requirements (item must match to use currency):",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Rarities: Item must be one of these rarities",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Has Affix: Item must have at least one affix",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Has Affix Slot: Item must have room for more affixes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Effects (what the currency does):",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Set Rarity: Change item to this rarity",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Clear Affixes: Remove all existing affixes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Add Affixes: Add random affixes (min-max range)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
-        "  Add Specific: Add weighted specific affixes (w/ tier)",
-        Style::default().fg(Color::DarkGray),
+        "  Add Specific: Add weighted specific affixes (w/ tier, fixed value)",
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Remove Affixes: Remove N random affixes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Reroll Affixes: Reroll values of N random affixes",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Try Unique: Chance to upgrade to unique version",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Affix Pools: Limit affixes to these pools only",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
 }
+
+/// " = 12" (or " = 12-25" for damage ranges) when a specific affix has a fixed value
+fn fixed_value_label(specific: &loot_core::config::SpecificAffix) -> String {
+    match (specific.value, specific.value_max) {
+        (Some(value), Some(value_max)) => format!(" = {}-{}", value, value_max),
+        (Some(value), None) => format!(" = {}", value),
+        (None, Some(value_max)) => format!(" = ?-{}", value_max),
+        (None, None) => String::new(),
+    }
+}
@@ -5,6 +5,7 @@ pub mod currencies;
 pub mod uniques;
 
 use crate::app::{App, Focus};
+use crate::theme;
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
@@ -22,10 +23,10 @@ pub fn render_field_line(
     let state = app.current_view_state();
     let is_current = state.field_index == field_idx;
 
-    let label_style = Style::default().fg(Color::Gray);
+    let label_style = Style::default().fg(theme::current().label);
     let value_style = if is_current {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -49,7 +50,10 @@ pub fn render_field_line(
     };
 
     Line::from(vec![
-        Span::styled(focus_marker.to_string(), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            focus_marker.to_string(),
+            Style::default().fg(theme::current().accent),
+        ),
         Span::styled(format!("{}: ", label), label_style),
         Span::styled(display_value, value_style),
     ])
@@ -60,7 +64,7 @@ pub fn render_section_header(title: &str) -> Line<'static> {
     Line::from(Span::styled(
         title.to_string(),
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme::current().highlight)
             .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
     ))
 }
@@ -75,29 +79,33 @@ pub fn render_nested_field(
     let state = app.current_view_state();
     let is_current = state.field_index == field_idx;
 
-    let label_style = Style::default().fg(Color::Gray);
+    let label_style = Style::default().fg(theme::current().label);
     let value_style = if is_current {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme::current().accent)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme::current().muted)
     };
 
     let focus_marker = if is_current { "> " } else { "  " };
 
     Line::from(vec![
-        Span::styled(focus_marker.to_string(), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            focus_marker.to_string(),
+            Style::default().fg(theme::current().accent),
+        ),
         Span::styled(format!("{}: ", label), label_style),
         Span::styled(summary.to_string(), value_style),
         Span::styled(
             " [Enter to edit]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         ),
     ])
 }
 
 /// Helper to render a list field
+#[allow(dead_code)]
 pub fn render_list_field(
     label: &str,
     items: &[String],
@@ -107,15 +115,18 @@ pub fn render_list_field(
     let state = app.current_view_state();
     let is_current = state.field_index == field_idx;
 
-    let label_style = Style::default().fg(Color::Gray);
+    let label_style = Style::default().fg(theme::current().label);
     let focus_marker = if is_current { "> " } else { "  " };
 
     let mut lines = vec![Line::from(vec![
-        Span::styled(focus_marker.to_string(), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            focus_marker.to_string(),
+            Style::default().fg(theme::current().accent),
+        ),
         Span::styled(format!("{}: ", label), label_style),
         Span::styled(
             format!("[{} items]", items.len()),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         ),
     ])];
 
@@ -127,13 +138,16 @@ pub fn render_list_field(
                 "     "
             };
             lines.push(Line::from(vec![
-                Span::styled(item_marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    item_marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::raw(item.clone()),
             ]));
         }
         lines.push(Line::from(Span::styled(
             "     [Enter: add, Ctrl+Del: remove]".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     }
 
@@ -143,7 +157,10 @@ pub fn render_list_field(
 /// Helper for preview key-value line
 pub fn preview_line(label: &str, value: &str) -> Line<'static> {
     Line::from(vec![
-        Span::styled(format!("{}: ", label), Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{}: ", label),
+            Style::default().fg(theme::current().label),
+        ),
         Span::raw(value.to_string()),
     ])
 }
@@ -151,7 +168,10 @@ pub fn preview_line(label: &str, value: &str) -> Line<'static> {
 /// Helper for preview key-value line with colored value
 pub fn preview_line_colored(label: &str, value: &str, color: Color) -> Line<'static> {
     Line::from(vec![
-        Span::styled(format!("{}: ", label), Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{}: ", label),
+            Style::default().fg(theme::current().label),
+        ),
         Span::styled(value.to_string(), Style::default().fg(color)),
     ])
 }
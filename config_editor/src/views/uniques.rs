@@ -3,9 +3,10 @@ use super::{
     render_section_header,
 };
 use crate::app::App;
+use crate::theme;
 use loot_core::config::{Config, UniqueConfig, UniqueRecipeConfig};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
@@ -18,7 +19,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         Line::from(Span::styled(
             uniq.name.clone(),
             Style::default()
-                .fg(Color::Rgb(175, 95, 0))
+                .fg(theme::current().unique)
                 .add_modifier(Modifier::BOLD),
         )),
         preview_line("ID", &uniq.id),
@@ -30,7 +31,20 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         .get(&uniq.base_type)
         .map(|bt| bt.name.clone())
         .unwrap_or_else(|| format!("{} (not found)", uniq.base_type));
-    lines.push(preview_line_colored("Base Type", &base_name, Color::White));
+    lines.push(preview_line_colored(
+        "Base Type",
+        &base_name,
+        theme::current().text,
+    ));
+    if uniq.required_level > 0 {
+        lines.push(preview_line(
+            "Required Level",
+            &uniq.required_level.to_string(),
+        ));
+    }
+    if uniq.drop_level > 0 {
+        lines.push(preview_line("Drop Level", &uniq.drop_level.to_string()));
+    }
 
     // Flavor text
     if let Some(ref flavor) = uniq.flavor {
@@ -38,7 +52,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(Line::from(Span::styled(
             format!("\"{}\"", flavor),
             Style::default()
-                .fg(Color::DarkGray)
+                .fg(theme::current().muted)
                 .add_modifier(Modifier::ITALIC),
         )));
     }
@@ -51,11 +65,11 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         lines.push(Line::from(vec![
             Span::styled(
                 format!("  {:?}: ", mod_cfg.stat),
-                Style::default().fg(Color::Magenta),
+                Style::default().fg(theme::current().special),
             ),
             Span::styled(
                 format!("{}-{}", mod_cfg.min, mod_cfg.max),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme::current().text),
             ),
         ]));
     }
@@ -73,7 +87,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         if !recipe.required_affixes.is_empty() {
             lines.push(Line::from(Span::styled(
                 format!("  Required Affixes: {}", recipe.required_affixes.len()),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme::current().highlight),
             )));
             for req in &recipe.required_affixes {
                 let affix_type_str = req
@@ -87,7 +101,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
                 };
                 lines.push(Line::from(Span::styled(
                     format!("    {:?}{}{}", req.stat, affix_type_str, tier_str),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme::current().text),
                 )));
             }
         }
@@ -95,7 +109,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
         if !recipe.mappings.is_empty() {
             lines.push(Line::from(Span::styled(
                 format!("  Mappings: {}", recipe.mappings.len()),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme::current().highlight),
             )));
             for mapping in &recipe.mappings {
                 lines.push(Line::from(Span::styled(
@@ -106,7 +120,7 @@ pub fn render_preview(config: &Config, id: &str) -> Vec<Line<'static>> {
                         mapping.mode,
                         mapping.influence * 100.0
                     ),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme::current().text),
                 )));
             }
         }
@@ -179,16 +193,16 @@ pub fn render_edit_form_with_recipe(
         if state.nested_depth >= 2 {
             let input_display = format!("{}|", app.text_input.value());
             lines.push(Line::from(vec![
-                Span::styled("     Edit: ", Style::default().fg(Color::Gray)),
+                Span::styled("     Edit: ", Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     " (format: StatType min max)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
         }
@@ -197,16 +211,19 @@ pub fn render_edit_form_with_recipe(
             let is_selected = i == state.nested_index;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected && state.nested_depth < 2 {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
 
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
                 Span::styled(
                     format!("{:?}: ", mod_cfg.stat),
-                    Style::default().fg(Color::Magenta),
+                    Style::default().fg(theme::current().special),
                 ),
                 Span::styled(format!("{}-{}", mod_cfg.min, mod_cfg.max), style),
             ]));
@@ -215,12 +232,12 @@ pub fn render_edit_form_with_recipe(
         if state.nested_depth >= 2 {
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]".to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Ctrl+Del: remove, +: add, Esc: back]".to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
@@ -276,11 +293,11 @@ pub fn render_edit_form_with_recipe(
                 _ => "     Input: ",
             };
             lines.push(Line::from(vec![
-                Span::styled(label, Style::default().fg(Color::Gray)),
+                Span::styled(label, Style::default().fg(theme::current().label)),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme::current().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -295,7 +312,7 @@ pub fn render_edit_form_with_recipe(
             if !hint.is_empty() {
                 lines.push(Line::from(Span::styled(
                     format!("     {}", hint),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         }
@@ -304,14 +321,20 @@ pub fn render_edit_form_with_recipe(
             let is_selected = i == state.nested_index;
             let marker = if is_selected { "  >> " } else { "     " };
             let style = if is_selected && state.nested_depth < 2 {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme::current().accent)
             } else {
                 Style::default()
             };
 
             lines.push(Line::from(vec![
-                Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
-                Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    marker.to_string(),
+                    Style::default().fg(theme::current().success),
+                ),
+                Span::styled(
+                    format!("{}: ", name),
+                    Style::default().fg(theme::current().highlight),
+                ),
                 Span::styled(value.clone(), style),
             ]));
         }
@@ -323,23 +346,26 @@ pub fn render_edit_form_with_recipe(
                 if r.required_affixes.is_empty() {
                     lines.push(Line::from(Span::styled(
                         "     (no required affixes - press Enter to add)",
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 } else {
                     for (i, req) in r.required_affixes.iter().enumerate() {
                         let is_selected = i == app.nested_sub_field_index;
                         let marker = if is_selected { "     >> " } else { "        " };
                         let style = if is_selected {
-                            Style::default().fg(Color::Cyan)
+                            Style::default().fg(theme::current().accent)
                         } else {
-                            Style::default().fg(Color::White)
+                            Style::default().fg(theme::current().text)
                         };
                         let affix_type_str = req
                             .affix_type
                             .map(|t| format!(" {:?}", t))
                             .unwrap_or_default();
                         lines.push(Line::from(vec![
-                            Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                            Span::styled(
+                                marker.to_string(),
+                                Style::default().fg(theme::current().success),
+                            ),
                             Span::styled(
                                 format!(
                                     "{:?}{} T{}-{}",
@@ -353,20 +379,23 @@ pub fn render_edit_form_with_recipe(
             }
             lines.push(Line::from(Span::styled(
                 "     [Enter: add, x: remove, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
 
             // Show valid stat types
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "     Valid StatTypes:",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme::current().label),
             )));
-            let all_stats = crate::app::App::get_all_stat_types();
+            let all_stats: Vec<&str> = loot_core::types::StatType::all()
+                .iter()
+                .map(|s| s.name())
+                .collect();
             for chunk in all_stats.chunks(4) {
                 lines.push(Line::from(Span::styled(
                     format!("     {}", chunk.join(", ")),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         } else if state.nested_index == 2 && state.nested_depth >= 2 {
@@ -375,19 +404,22 @@ pub fn render_edit_form_with_recipe(
                 if r.mappings.is_empty() {
                     lines.push(Line::from(Span::styled(
                         "     (no mappings - press Enter to add)",
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 } else {
                     for (i, mapping) in r.mappings.iter().enumerate() {
                         let is_selected = i == app.nested_sub_field_index;
                         let marker = if is_selected { "     >> " } else { "        " };
                         let style = if is_selected {
-                            Style::default().fg(Color::Cyan)
+                            Style::default().fg(theme::current().accent)
                         } else {
-                            Style::default().fg(Color::White)
+                            Style::default().fg(theme::current().text)
                         };
                         lines.push(Line::from(vec![
-                            Span::styled(marker.to_string(), Style::default().fg(Color::Green)),
+                            Span::styled(
+                                marker.to_string(),
+                                Style::default().fg(theme::current().success),
+                            ),
                             Span::styled(
                                 format!(
                                     "{:?} -> [{}] {:?} {:.0}%",
@@ -407,17 +439,17 @@ pub fn render_edit_form_with_recipe(
                 if r.required_affixes.is_empty() {
                     lines.push(Line::from(Span::styled(
                         "     ⚠ Add Required Affixes first to map from them",
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(theme::current().highlight),
                     )));
                 } else {
                     lines.push(Line::from(Span::styled(
                         "     Available stats to map from (Required Affixes):",
-                        Style::default().fg(Color::Gray),
+                        Style::default().fg(theme::current().label),
                     )));
                     for req in &r.required_affixes {
                         lines.push(Line::from(Span::styled(
                             format!("       {:?}", req.stat),
-                            Style::default().fg(Color::DarkGray),
+                            Style::default().fg(theme::current().muted),
                         )));
                     }
                 }
@@ -426,7 +458,7 @@ pub fn render_edit_form_with_recipe(
                 lines.push(Line::from(""));
                 lines.push(Line::from(Span::styled(
                     "     Unique mods to map to (by index):",
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme::current().label),
                 )));
                 for (i, mod_cfg) in uniq.mods.iter().enumerate() {
                     lines.push(Line::from(Span::styled(
@@ -434,98 +466,115 @@ pub fn render_edit_form_with_recipe(
                             "       [{}] {:?} ({}-{})",
                             i, mod_cfg.stat, mod_cfg.min, mod_cfg.max
                         ),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 }
             }
             lines.push(Line::from(Span::styled(
                 "     [Enter: add, x: remove, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else if state.nested_depth >= 2 {
             lines.push(Line::from(Span::styled(
                 "     [Enter: save, Esc: cancel]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         } else {
             lines.push(Line::from(Span::styled(
                 "     [Enter: edit, Up/Down: select, Esc: back]",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
     }
 
+    lines.push(Line::from(""));
+    lines.push(render_section_header("Levels"));
+    lines.push(render_field_line(
+        "Drop Level",
+        &uniq.drop_level.to_string(),
+        6,
+        app,
+        if state.field_index == 6 { cursor } else { None },
+    ));
+    lines.push(render_field_line(
+        "Required Level",
+        &uniq.required_level.to_string(),
+        7,
+        app,
+        if state.field_index == 7 { cursor } else { None },
+    ));
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Tab/Shift+Tab: navigate fields | Ctrl+S: save | Esc: cancel".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     // Field reference footnote
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "─── Recipe Reference ───",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "Recipe allows crafting this unique from a matching rare/magic item.",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "  Weight: Selection weight when multiple recipes match (higher = more likely)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Required Affixes: Stats the input item must have to trigger the recipe",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    - Prefix/Suffix: Optional, restricts to that affix type only",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    - Tier range: T1=best tier, higher=worse. Default 1-99 (any tier)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Mappings: Transfer affix values from input item to unique mod values",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    - from_stat: Which required affix stat to read from",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    - mod_index: Which unique mod to write to (0-based index)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    Mapping Modes:",
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme::current().label),
     )));
     lines.push(Line::from(Span::styled(
         "      percentage: If affix rolled 75% of its range, unique mod gets 75% of its range",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "                  Example: Affix 5-15 rolled 12 (70%) -> Unique 10-30 gets 24 (70%)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "      direct: Copy the exact number (clamped to unique's range)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "              Example: Affix rolled 12 -> Unique 10-30 gets 12",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "      random: Ignore input value, roll fresh random within unique's range",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
     lines.push(Line::from(Span::styled(
         "    Influence (0.0-1.0): Blends mapped value with random. 1.0=fully mapped, 0.5=half random",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
 
     lines
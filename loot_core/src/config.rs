@@ -1,9 +1,14 @@
+use crate::generator::Generator;
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
 /// Complete game configuration loaded from TOML files
+///
+/// Base types, affixes, affix pools, currencies and uniques keep any fields this version
+/// doesn't know (e.g. written by a newer tool) in their `extra` table, so an editor that
+/// loads an entry and saves it back doesn't drop them.
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     pub base_types: HashMap<String, BaseTypeConfig>,
@@ -12,9 +17,15 @@ pub struct Config {
     pub currencies: HashMap<String, CurrencyConfig>,
     pub uniques: HashMap<String, UniqueConfig>,
     pub unique_recipes: Vec<UniqueRecipeConfig>,
+    pub generation: GenerationConfig,
 }
 
 impl Config {
+    /// The config directory named on a tool's command line, or `config` when none was given
+    pub fn dir_from_arg(arg: Option<&str>) -> &Path {
+        Path::new(arg.unwrap_or("config"))
+    }
+
     /// Load configuration from a directory containing subdirectories for each config type
     /// Expected structure:
     ///   config/
@@ -23,12 +34,14 @@ impl Config {
     ///     affix_pools/   - .toml files containing [[pools]] arrays
     ///     currencies/    - .toml files containing [[currencies]] arrays
     ///     uniques/       - .toml files each containing [unique] and optional [recipe]
+    ///     generation.toml - optional generation settings (affix counts per rarity)
     pub fn load_from_dir(dir: &Path) -> Result<Self, ConfigError> {
         let base_types = Self::load_base_types_dir(&dir.join("base_types"))?;
         let affixes = Self::load_affixes_dir(&dir.join("affixes"))?;
         let affix_pools = Self::load_affix_pools_dir(&dir.join("affix_pools"))?;
         let currencies = Self::load_currencies_dir(&dir.join("currencies"))?;
         let (uniques, unique_recipes) = Self::load_uniques_dir(&dir.join("uniques"))?;
+        let generation = Self::load_generation_file(&dir.join("generation.toml"))?;
 
         Ok(Config {
             base_types,
@@ -37,13 +50,77 @@ impl Config {
             currencies,
             uniques,
             unique_recipes,
+            generation,
+        })
+    }
+
+    /// Serialize the whole config into a single TOML document for distribution.
+    ///
+    /// Entries are written sorted by id so the same config always produces the same bundle.
+    /// Load it back with `Config::load_bundle`.
+    pub fn to_bundle(&self) -> String {
+        let mut unique_recipes = self.unique_recipes.clone();
+        unique_recipes.sort_by(|a, b| a.unique_id.cmp(&b.unique_id));
+
+        let bundle = ConfigBundle {
+            generation: self.generation.clone(),
+            base_types: self.base_types_sorted().cloned().collect(),
+            affixes: self.affixes_sorted().cloned().collect(),
+            pools: self.affix_pools_sorted().cloned().collect(),
+            currencies: self.currencies_sorted().cloned().collect(),
+            uniques: self.uniques_sorted().cloned().collect(),
+            unique_recipes,
+        };
+        toml::to_string(&bundle).expect("config is always representable as TOML")
+    }
+
+    /// Load a config from a single bundle file written by `Config::to_bundle`
+    pub fn load_bundle(path: &Path) -> Result<Self, ConfigError> {
+        let content = Self::read_file_with_context(path)?;
+        let bundle: ConfigBundle = Self::parse_toml_with_context(&content, path)?;
+
+        fn index<T>(
+            entries: Vec<T>,
+            kind: &'static str,
+            id: impl Fn(&T) -> &str,
+            path: &Path,
+        ) -> Result<HashMap<String, T>, ConfigError> {
+            let mut origins = HashMap::new();
+            let mut result = HashMap::new();
+            for entry in entries {
+                let entry_id = id(&entry).to_string();
+                check_duplicate_id(&mut origins, kind, &entry_id, path)?;
+                result.insert(entry_id, entry);
+            }
+            Ok(result)
+        }
+
+        Ok(Config {
+            base_types: index(bundle.base_types, "base_type", |bt| &bt.id, path)?,
+            affixes: index(bundle.affixes, "affix", |a| &a.id, path)?,
+            affix_pools: index(bundle.pools, "affix_pool", |p| &p.id, path)?,
+            currencies: index(bundle.currencies, "currency", |c| &c.id, path)?,
+            uniques: index(bundle.uniques, "unique", |u| &u.id, path)?,
+            unique_recipes: bundle.unique_recipes,
+            generation: bundle.generation,
         })
     }
 
+    /// Load generation settings, falling back to defaults if the file is absent
+    fn load_generation_file(path: &Path) -> Result<GenerationConfig, ConfigError> {
+        if !path.exists() {
+            return Ok(GenerationConfig::default());
+        }
+
+        let content = Self::read_file_with_context(path)?;
+        Self::parse_toml_with_context(&content, path)
+    }
+
     /// Load all base type files from a directory
     /// Each file can contain one or more [[base_types]] entries
     fn load_base_types_dir(dir: &Path) -> Result<HashMap<String, BaseTypeConfig>, ConfigError> {
         let mut result = HashMap::new();
+        let mut origins = HashMap::new();
 
         if !dir.exists() {
             return Ok(result);
@@ -53,10 +130,11 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: BaseTypesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for bt in wrapper.base_types {
+                    check_duplicate_id(&mut origins, "base_type", &bt.id, &path)?;
                     result.insert(bt.id.clone(), bt);
                 }
             }
@@ -69,6 +147,7 @@ impl Config {
     /// Each file can contain one or more [[affixes]] entries
     fn load_affixes_dir(dir: &Path) -> Result<HashMap<String, AffixConfig>, ConfigError> {
         let mut result = HashMap::new();
+        let mut origins = HashMap::new();
 
         if !dir.exists() {
             return Ok(result);
@@ -78,10 +157,11 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: AffixesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for affix in wrapper.affixes {
+                    check_duplicate_id(&mut origins, "affix", &affix.id, &path)?;
                     result.insert(affix.id.clone(), affix);
                 }
             }
@@ -94,6 +174,7 @@ impl Config {
     /// Each file can contain one or more [[pools]] entries
     fn load_affix_pools_dir(dir: &Path) -> Result<HashMap<String, AffixPoolConfig>, ConfigError> {
         let mut result = HashMap::new();
+        let mut origins = HashMap::new();
 
         if !dir.exists() {
             return Ok(result);
@@ -103,10 +184,11 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: AffixPoolsWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for pool in wrapper.pools {
+                    check_duplicate_id(&mut origins, "affix_pool", &pool.id, &path)?;
                     result.insert(pool.id.clone(), pool);
                 }
             }
@@ -119,6 +201,7 @@ impl Config {
     /// Each file can contain one or more [[currencies]] entries
     fn load_currencies_dir(dir: &Path) -> Result<HashMap<String, CurrencyConfig>, ConfigError> {
         let mut result = HashMap::new();
+        let mut origins = HashMap::new();
 
         if !dir.exists() {
             return Ok(result);
@@ -128,10 +211,11 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: CurrenciesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for currency in wrapper.currencies {
+                    check_duplicate_id(&mut origins, "currency", &currency.id, &path)?;
                     result.insert(currency.id.clone(), currency);
                 }
             }
@@ -147,6 +231,7 @@ impl Config {
     ) -> Result<(HashMap<String, UniqueConfig>, Vec<UniqueRecipeConfig>), ConfigError> {
         let mut uniques = HashMap::new();
         let mut recipes = Vec::new();
+        let mut origins = HashMap::new();
 
         if !dir.exists() {
             return Ok((uniques, recipes));
@@ -156,13 +241,14 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let file_config: UniqueFileConfig = Self::parse_toml_with_context(&content, &path)?;
 
                 let unique_id = file_config.unique.id.clone();
                 let base_type = file_config.unique.base_type.clone();
 
+                check_duplicate_id(&mut origins, "unique", &unique_id, &path)?;
                 uniques.insert(unique_id.clone(), file_config.unique);
 
                 // If there's a recipe, add it with the unique_id and base_type filled in
@@ -177,6 +263,324 @@ impl Config {
         Ok((uniques, recipes))
     }
 
+    /// Check the loaded config for internal consistency
+    ///
+    /// Returns every problem found rather than stopping at the first one.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        for bt in self.base_types_sorted() {
+            let entry = format!("base_type '{}'", bt.id);
+            for implicit in &bt.implicits {
+                check_range(&mut errors, &entry, "implicit", implicit.min, implicit.max);
+            }
+            let mut option_weights = bt.implicits.iter().filter_map(|i| i.weight).peekable();
+            if option_weights.peek().is_some() && option_weights.sum::<u32>() == 0 {
+                errors.push(ValidationError::new(
+                    &entry,
+                    "implicit options all have zero weight",
+                ));
+            }
+            if let Some(ref def) = bt.defenses {
+                for (label, range) in [
+                    ("armour", def.armour),
+                    ("evasion", def.evasion),
+                    ("energy_shield", def.energy_shield),
+                ] {
+                    if let Some(range) = range {
+                        check_range(&mut errors, &entry, label, range.min, range.max);
+                    }
+                }
+            }
+            if let Some(ref dmg) = bt.damage {
+                for d in &dmg.damages {
+                    let label = format!("{:?} damage", d.damage_type);
+                    check_range(&mut errors, &entry, &label, d.min, d.max);
+                }
+            }
+            if !bt.blocked_tags.is_empty() {
+                let rollable = |blocked: &[Tag]| {
+                    self.affixes.values().any(|affix| {
+                        (affix.allowed_classes.is_empty()
+                            || affix.allowed_classes.contains(&bt.class))
+                            && Generator::has_matching_tag(affix, &bt.tags)
+                            && !Generator::has_blocked_tag(affix, blocked)
+                    })
+                };
+                if rollable(&[]) && !rollable(&bt.blocked_tags) {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!(
+                            "blocked_tags ({}) leave no affix that can roll on it",
+                            bt.blocked_tags.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for affix in self.affixes_sorted() {
+            let entry = format!("affix '{}'", affix.id);
+            if affix.tiers.is_empty() {
+                errors.push(ValidationError::new(&entry, "has no tiers"));
+            } else if affix.tiers.iter().all(|t| t.weight == 0) {
+                errors.push(ValidationError::new(&entry, "all tiers have zero weight"));
+            }
+            for tier in &affix.tiers {
+                let label = format!("tier {}", tier.tier);
+                check_range(&mut errors, &entry, &label, tier.min, tier.max);
+                if let Some(max_value) = tier.max_value {
+                    let label = format!("tier {} max_value", tier.tier);
+                    check_range(&mut errors, &entry, &label, max_value.min, max_value.max);
+                    if max_value.min < tier.min || max_value.max < tier.max {
+                        errors.push(ValidationError::new(
+                            &entry,
+                            format!(
+                                "{} range {}-{} lies below the low range {}-{}",
+                                label, max_value.min, max_value.max, tier.min, tier.max
+                            ),
+                        ));
+                    }
+                }
+                for extra in &tier.extra_stats {
+                    let label = format!("tier {} {}", tier.tier, extra.stat.name());
+                    check_range(&mut errors, &entry, &label, extra.min, extra.max);
+                }
+                let extra_stats: Vec<StatType> = tier.extra_stats.iter().map(|e| e.stat).collect();
+                let first_extra_stats: Vec<StatType> =
+                    affix.tiers[0].extra_stats.iter().map(|e| e.stat).collect();
+                if extra_stats.contains(&affix.stat) {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!("{} lists its own stat as an extra stat", label),
+                    ));
+                } else if extra_stats != first_extra_stats {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!(
+                            "{} extra stats differ from tier {}",
+                            label, affix.tiers[0].tier
+                        ),
+                    ));
+                }
+                if let Some(step) = tier.step {
+                    let ranges = std::iter::once(tier.low_range())
+                        .chain(tier.high_range())
+                        .chain(tier.extra_stats.iter().map(StatRange::range));
+                    if step <= 0 {
+                        errors.push(ValidationError::new(
+                            &entry,
+                            format!("{} step must be positive, got {}", label, step),
+                        ));
+                    } else if let Some(range) = ranges
+                        .into_iter()
+                        .find(|range| range.step_bounds(step).is_none())
+                    {
+                        errors.push(ValidationError::new(
+                            &entry,
+                            format!(
+                                "{} range {}-{} holds no multiple of step {}",
+                                label, range.min, range.max, step
+                            ),
+                        ));
+                    }
+                }
+                if affix.stat == StatType::ReducedRequirements && tier.max > 100 {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!(
+                            "{} reduces requirements by more than 100% ({})",
+                            label, tier.max
+                        ),
+                    ));
+                }
+            }
+            // A config without base types is partial; there's nothing to check against
+            if !self.base_types.is_empty() && affix.tiers.iter().any(|t| t.weight > 0) {
+                if let Some(reason) = self.unreachable_reason(affix) {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!("can never roll: {}", reason),
+                    ));
+                }
+            }
+        }
+
+        for pool in self.affix_pools_sorted() {
+            let entry = format!("affix_pool '{}'", pool.id);
+            for affix_id in &pool.affixes {
+                if !self.affixes.contains_key(affix_id) {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!("references unknown affix '{}'", affix_id),
+                    ));
+                }
+            }
+        }
+
+        for currency in self.currencies_sorted() {
+            let entry = format!("currency '{}'", currency.id);
+            for specific in &currency.effects.add_specific_affix {
+                match self.affixes.get(&specific.id) {
+                    None => errors.push(ValidationError::new(
+                        &entry,
+                        format!("references unknown affix '{}'", specific.id),
+                    )),
+                    Some(affix) => {
+                        if let Some(problem) = specific.fixed_value_problem(affix) {
+                            errors.push(ValidationError::new(&entry, problem));
+                        }
+                    }
+                }
+            }
+            for pool_id in currency
+                .effects
+                .affix_pools
+                .iter()
+                .chain(currency.effects.enchant_pool.iter())
+            {
+                if !self.affix_pools.contains_key(pool_id) {
+                    errors.push(ValidationError::new(
+                        &entry,
+                        format!("references unknown affix pool '{}'", pool_id),
+                    ));
+                }
+            }
+            check_currency_effects(&mut errors, &entry, &currency.effects);
+        }
+
+        for unique in self.uniques_sorted() {
+            let entry = format!("unique '{}'", unique.id);
+            if !self.base_types.contains_key(&unique.base_type) {
+                errors.push(ValidationError::new(
+                    &entry,
+                    format!("references unknown base type '{}'", unique.base_type),
+                ));
+            }
+            for (i, m) in unique.mods.iter().enumerate() {
+                let label = format!("mod {}", i + 1);
+                check_range(&mut errors, &entry, &label, m.min, m.max);
+            }
+        }
+
+        for (label, words) in [
+            ("rare_name_prefixes", &self.generation.rare_name_prefixes),
+            ("rare_name_suffixes", &self.generation.rare_name_suffixes),
+            ("rare_name_epithets", &self.generation.rare_name_epithets),
+        ] {
+            if let Some(word) = words
+                .iter()
+                .find(|w| w.is_empty() || w.contains(char::is_whitespace))
+            {
+                errors.push(ValidationError::new(
+                    "generation",
+                    format!("{} entry '{}' must be a single word", label, word),
+                ));
+            }
+        }
+
+        for (label, count, rarity) in [
+            (
+                "magic_affix_count",
+                &self.generation.magic_affix_count,
+                Rarity::Magic,
+            ),
+            (
+                "rare_affix_count",
+                &self.generation.rare_affix_count,
+                Rarity::Rare,
+            ),
+        ] {
+            let cap = (rarity.max_prefixes() + rarity.max_suffixes()) as u32;
+            if count.min > count.max {
+                errors.push(ValidationError::new(
+                    "generation",
+                    format!(
+                        "{} range {}-{} has min greater than max",
+                        label, count.min, count.max
+                    ),
+                ));
+            } else if count.max > cap {
+                errors.push(ValidationError::new(
+                    "generation",
+                    format!(
+                        "{} max {} exceeds the {} affix slots of a {:?} item",
+                        label, count.max, cap, rarity
+                    ),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check for entries that load and generate but probably aren't what the author meant.
+    ///
+    /// Unlike `validate`, nothing here stops the config from being used. A unique's
+    /// defense mod is flagged when affixes roll that stat but none of them on the unique's
+    /// base class (e.g. added armour on a sword). Other categories aren't judged: damage,
+    /// attribute and resource mods are at home on any slot once they're global. Affix
+    /// tiers are flagged when they roll outside the stat's `StatType::sane_range`.
+    pub fn validation_warnings(&self) -> Vec<ValidationError> {
+        let mut warnings = Vec::new();
+
+        for affix in self.affixes_sorted() {
+            let entry = format!("affix '{}'", affix.id);
+            for tier in &affix.tiers {
+                let rolls = std::iter::once((affix.stat, tier.min, tier.max))
+                    .chain(tier.extra_stats.iter().map(|e| (e.stat, e.min, e.max)));
+                for (stat, min, max) in rolls {
+                    let Some((low, high)) = stat.sane_range() else {
+                        continue;
+                    };
+                    if min < low || max > high {
+                        warnings.push(ValidationError::new(
+                            &entry,
+                            format!(
+                                "tier {} rolls {} {}-{}, outside the usual {} to {}",
+                                tier.tier, stat, min, max, low, high
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for unique in self.uniques_sorted() {
+            let Some(base) = self.base_types.get(&unique.base_type) else {
+                // Reported as an error by `validate`
+                continue;
+            };
+            let entry = format!("unique '{}'", unique.id);
+            for m in unique
+                .mods
+                .iter()
+                .filter(|m| m.stat.category() == StatCategory::Defense)
+            {
+                let mut stat_affixes = self.affixes.values().filter(|a| a.stat == m.stat);
+                let rolled = stat_affixes.clone().next().is_some();
+                let fits_class = stat_affixes.any(|a| {
+                    a.allowed_classes.is_empty() || a.allowed_classes.contains(&base.class)
+                });
+                if rolled && !fits_class {
+                    warnings.push(ValidationError::new(
+                        &entry,
+                        format!(
+                            "mod {:?} doesn't roll on {:?} items (base type '{}')",
+                            m.stat, base.class, base.id
+                        ),
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
+
     // Helper functions for error context
 
     fn read_dir_with_context(dir: &Path) -> Result<std::fs::ReadDir, ConfigError> {
@@ -204,6 +608,334 @@ impl Config {
     }
 }
 
+/// Record where an id was loaded from, failing if it was already defined elsewhere
+fn check_duplicate_id(
+    origins: &mut HashMap<String, std::path::PathBuf>,
+    kind: &'static str,
+    id: &str,
+    path: &Path,
+) -> Result<(), ConfigError> {
+    if let Some(first) = origins.insert(id.to_string(), path.to_path_buf()) {
+        let mut files = vec![first, path.to_path_buf()];
+        files.sort();
+        return Err(ConfigError::DuplicateId {
+            kind,
+            id: id.to_string(),
+            files,
+        });
+    }
+    Ok(())
+}
+
+impl Config {
+    /// Base types in id order
+    pub fn base_types_sorted(&self) -> impl Iterator<Item = &BaseTypeConfig> {
+        sorted_values(&self.base_types).into_iter()
+    }
+
+    /// Affixes in id order
+    pub fn affixes_sorted(&self) -> impl Iterator<Item = &AffixConfig> {
+        sorted_values(&self.affixes).into_iter()
+    }
+
+    /// Affix pools in id order
+    pub fn affix_pools_sorted(&self) -> impl Iterator<Item = &AffixPoolConfig> {
+        sorted_values(&self.affix_pools).into_iter()
+    }
+
+    /// Currencies in id order
+    pub fn currencies_sorted(&self) -> impl Iterator<Item = &CurrencyConfig> {
+        sorted_values(&self.currencies).into_iter()
+    }
+
+    /// Uniques in id order
+    pub fn uniques_sorted(&self) -> impl Iterator<Item = &UniqueConfig> {
+        sorted_values(&self.uniques).into_iter()
+    }
+
+    /// Currencies whose display name matches `name`, ignoring case, in id order.
+    /// Names aren't unique, so callers decide what to do with several matches.
+    pub fn find_currency_by_name(&self, name: &str) -> Vec<&CurrencyConfig> {
+        self.currencies_sorted()
+            .filter(|c| c.name.eq_ignore_ascii_case(name))
+            .collect()
+    }
+
+    /// Affixes whose display name matches `name`, ignoring case, in id order.
+    /// Names aren't unique (the same name can roll on several item classes), so every
+    /// match is returned.
+    pub fn find_affix_by_name(&self, name: &str) -> Vec<&AffixConfig> {
+        self.affixes_sorted()
+            .filter(|a| a.name.eq_ignore_ascii_case(name))
+            .collect()
+    }
+
+    /// Every affix tier as a tab-separated table, one row per tier, for pasting into a
+    /// spreadsheet. Affixes are in id order; enum values use their config spelling and
+    /// `allowed_classes` is `;`-separated (empty means all classes).
+    pub fn export_affix_table(&self) -> String {
+        const COLUMNS: [&str; 11] = [
+            "id",
+            "name",
+            "type",
+            "stat",
+            "scope",
+            "tier",
+            "weight",
+            "min",
+            "max",
+            "min_ilvl",
+            "allowed_classes",
+        ];
+
+        let mut table = COLUMNS.join("\t");
+        table.push('\n');
+        for affix in self.affixes_sorted() {
+            let classes: Vec<String> = affix.allowed_classes.iter().map(config_name).collect();
+            for tier in &affix.tiers {
+                let row = [
+                    tsv_escape(&affix.id),
+                    tsv_escape(&affix.name),
+                    config_name(&affix.affix_type),
+                    config_name(&affix.stat),
+                    config_name(&affix.scope),
+                    tier.tier.to_string(),
+                    tier.weight.to_string(),
+                    tier.min.to_string(),
+                    tier.max.to_string(),
+                    tier.min_ilvl.to_string(),
+                    classes.join(";"),
+                ];
+                table.push_str(&row.join("\t"));
+                table.push('\n');
+            }
+        }
+        table
+    }
+
+    /// Why random rolls can never pick `affix`, if they can't: no base type has an
+    /// allowed class, or none of those is high enough level for any weighted tier.
+    fn unreachable_reason(&self, affix: &AffixConfig) -> Option<String> {
+        let class_bases: Vec<&BaseTypeConfig> = self
+            .base_types
+            .values()
+            .filter(|bt| {
+                affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&bt.class)
+            })
+            .collect();
+        if class_bases.is_empty() {
+            return Some(format!(
+                "no base type has an allowed class ({:?})",
+                affix.allowed_classes
+            ));
+        }
+
+        let max_level = class_bases
+            .iter()
+            .map(|bt| bt.requirements.level)
+            .max()
+            .unwrap_or(0);
+        let lowest_ilvl = affix
+            .tiers
+            .iter()
+            .filter(|t| t.weight > 0)
+            .map(|t| t.min_ilvl)
+            .min()
+            .unwrap_or(0);
+        if lowest_ilvl > max_level {
+            return Some(format!(
+                "lowest tier needs item level {} but bases of its classes reach at most {}",
+                lowest_ilvl, max_level
+            ));
+        }
+
+        None
+    }
+}
+
+/// Values of a config map in id order, so validation output is stable
+fn sorted_values<T>(map: &HashMap<String, T>) -> Vec<&T> {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+    keys.into_iter().map(|k| &map[k]).collect()
+}
+
+/// Values of a config map ordered by `key`, with ties in id order (e.g. currencies by
+/// display name: `sorted_by_key(&config.currencies, |c| c.name.clone())`)
+pub fn sorted_by_key<T, K: Ord>(
+    map: &HashMap<String, T>,
+    key: impl FnMut(&&T) -> K,
+) -> impl Iterator<Item = &T> {
+    let mut values = sorted_values(map);
+    values.sort_by_key(key);
+    values.into_iter()
+}
+
+/// An enum value as spelled in config files (e.g. "added_life")
+fn config_name<T: Serialize>(value: &T) -> String {
+    match toml::Value::try_from(value) {
+        Ok(toml::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// Escape the characters that would break a TSV cell (backslash, tab and line breaks)
+fn tsv_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn check_range(errors: &mut Vec<ValidationError>, entry: &str, label: &str, min: i32, max: i32) {
+    if min > max {
+        errors.push(ValidationError::new(
+            entry,
+            format!("{} range {}-{} has min greater than max", label, min, max),
+        ));
+    }
+}
+
+/// Flag effect combinations that can never do what they say.
+///
+/// Effects apply in the order listed on `CurrencyEffects`, so these are judged
+/// against that order.
+fn check_currency_effects(
+    errors: &mut Vec<ValidationError>,
+    entry: &str,
+    effects: &CurrencyEffects,
+) {
+    let adds_affixes = effects.add_affixes.is_some() || !effects.add_specific_affix.is_empty();
+    if effects.set_rarity == Some(Rarity::Normal)
+        && (adds_affixes || effects.reroll_affixes.is_some())
+    {
+        errors.push(ValidationError::new(
+            entry,
+            "sets Normal rarity (no affix slots) but also adds or rerolls affixes",
+        ));
+    }
+
+    if effects.clear_affixes {
+        for (label, count) in [
+            ("remove_affixes", effects.remove_affixes),
+            ("reroll_affixes", effects.reroll_affixes),
+        ] {
+            if count.is_some() {
+                errors.push(ValidationError::new(
+                    entry,
+                    format!(
+                        "clears affixes and also sets {}; clearing runs first, so only locked \
+                         modifiers would be left to act on",
+                        label
+                    ),
+                ));
+            }
+        }
+    }
+
+    if effects.reroll_prefixes_only && effects.reroll_suffixes_only {
+        errors.push(ValidationError::new(
+            entry,
+            "sets both reroll_prefixes_only and reroll_suffixes_only",
+        ));
+    } else if effects.reroll_scope().is_some() && effects.reroll_affixes.is_none() {
+        errors.push(ValidationError::new(
+            entry,
+            "limits rerolls to prefixes or suffixes but sets no reroll_affixes",
+        ));
+    }
+
+    if let Some(ref count) = effects.add_affixes {
+        if count.min > count.max {
+            errors.push(ValidationError::new(
+                entry,
+                format!(
+                    "add_affixes range {}-{} has min greater than max",
+                    count.min, count.max
+                ),
+            ));
+        }
+    }
+
+    let max_multiplier = CurrencyEffects::MAX_TAG_MULTIPLIER;
+    for (tag, multiplier) in &effects.weighted_tags {
+        if !multiplier.is_finite() || *multiplier < 0.0 || *multiplier > max_multiplier {
+            errors.push(ValidationError::new(
+                entry,
+                format!(
+                    "weighted_tags multiplier {} for '{}' must be a number from 0 to {}",
+                    multiplier, tag, max_multiplier
+                ),
+            ));
+        }
+    }
+
+    let max_bias = CurrencyEffects::MAX_TIER_BIAS;
+    if !effects.tier_bias.is_finite() || effects.tier_bias.abs() > max_bias {
+        errors.push(ValidationError::new(
+            entry,
+            format!(
+                "tier_bias {} must be a number from -{} to {}",
+                effects.tier_bias, max_bias, max_bias
+            ),
+        ));
+    }
+
+    if let Some(ref conversion) = effects.convert_damage {
+        if conversion.from == conversion.to {
+            errors.push(ValidationError::new(
+                entry,
+                format!(
+                    "convert_damage converts {:?} damage to itself",
+                    conversion.from
+                ),
+            ));
+        }
+        if !(1..=100).contains(&conversion.percent) {
+            errors.push(ValidationError::new(
+                entry,
+                format!(
+                    "convert_damage percent {} must be between 1 and 100",
+                    conversion.percent
+                ),
+            ));
+        }
+    }
+
+    let rolls_from_pools = effects.add_affixes.is_some() || effects.reroll_affixes.is_some();
+    if rolls_from_pools && effects.affix_pools.is_empty() {
+        errors.push(ValidationError::new(
+            entry,
+            "adds or rerolls random affixes but lists no affix_pools to draw from",
+        ));
+    }
+}
+
+/// A consistency problem found by `Config::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The entry the problem was found in (e.g. "affix 'added_life'")
+    pub entry: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(entry: impl Into<String>, message: impl Into<String>) -> Self {
+        ValidationError {
+            entry: entry.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.entry, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 #[derive(Debug)]
 pub enum ConfigError {
     /// IO error with optional file path
@@ -216,6 +948,14 @@ pub enum ConfigError {
         error: toml::de::Error,
         path: std::path::PathBuf,
     },
+    /// The same id is defined more than once (usually a copy-paste mistake across files)
+    DuplicateId {
+        /// Kind of entry, e.g. "affix" or "base_type"
+        kind: &'static str,
+        id: String,
+        /// The files defining the id (the same file twice for an in-file duplicate)
+        files: Vec<std::path::PathBuf>,
+    },
 }
 
 impl ConfigError {
@@ -224,6 +964,7 @@ impl ConfigError {
         match self {
             ConfigError::Io { path, .. } => path.as_deref(),
             ConfigError::Parse { path, .. } => Some(path),
+            ConfigError::DuplicateId { files, .. } => files.last().map(|p| p.as_path()),
         }
     }
 
@@ -241,6 +982,10 @@ impl ConfigError {
                 }
                 desc
             }
+            ConfigError::DuplicateId { files, .. } => {
+                let files: Vec<String> = files.iter().map(|p| p.display().to_string()).collect();
+                format!("Files: {}", files.join(", "))
+            }
         }
     }
 
@@ -253,6 +998,9 @@ impl ConfigError {
                 let msg = error.message();
                 msg.to_string()
             }
+            ConfigError::DuplicateId { kind, id, .. } => {
+                format!("{} '{}' is defined more than once", kind, id)
+            }
         }
     }
 }
@@ -281,6 +1029,16 @@ impl std::fmt::Display for ConfigError {
             ConfigError::Parse { error, path } => {
                 write!(f, "Parse error in '{}': {}", path.display(), error)
             }
+            ConfigError::DuplicateId { kind, id, files } => {
+                let files: Vec<String> = files.iter().map(|p| p.display().to_string()).collect();
+                write!(
+                    f,
+                    "Duplicate {} id '{}' in {}",
+                    kind,
+                    id,
+                    files.join(" and ")
+                )
+            }
         }
     }
 }
@@ -290,6 +1048,7 @@ impl std::error::Error for ConfigError {
         match self {
             ConfigError::Io { error, .. } => Some(error),
             ConfigError::Parse { error, .. } => Some(error),
+            ConfigError::DuplicateId { .. } => None,
         }
     }
 }
@@ -320,6 +1079,25 @@ struct CurrenciesWrapper {
     currencies: Vec<CurrencyConfig>,
 }
 
+/// Every config section in one document, as written by `Config::to_bundle`
+#[derive(Serialize, Deserialize)]
+struct ConfigBundle {
+    #[serde(default)]
+    generation: GenerationConfig,
+    #[serde(default)]
+    base_types: Vec<BaseTypeConfig>,
+    #[serde(default)]
+    affixes: Vec<AffixConfig>,
+    #[serde(default)]
+    pools: Vec<AffixPoolConfig>,
+    #[serde(default)]
+    currencies: Vec<CurrencyConfig>,
+    #[serde(default)]
+    uniques: Vec<UniqueConfig>,
+    #[serde(default)]
+    unique_recipes: Vec<UniqueRecipeConfig>,
+}
+
 /// Config structure for individual unique files
 /// Each file contains the unique definition and optionally a recipe
 #[derive(Deserialize)]
@@ -335,16 +1113,80 @@ pub struct BaseTypeConfig {
     pub id: String,
     pub name: String,
     pub class: ItemClass,
+    /// Family of interchangeable bases (e.g. "int_armour") for pooled generation
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Relative chance of this base being picked as a random drop
+    /// (`DEFAULT_DROP_WEIGHT` when unset, 0 never drops)
+    #[serde(default)]
+    pub drop_weight: Option<u32>,
     #[serde(default)]
     pub tags: Vec<Tag>,
+    /// Affixes carrying any of these tags never roll on this base, whatever its class
+    /// allows (e.g. "caster" on an attack-only weapon)
     #[serde(default)]
-    pub implicit: Option<ImplicitConfig>,
+    pub blocked_tags: Vec<Tag>,
+    /// Implicit modifiers, rolled in order. TOML accepts a single `[base_types.implicit]`
+    /// table or an array of them. Implicits with a `weight` are alternatives: each item
+    /// gets exactly one of them, picked by weight, alongside every unweighted implicit.
+    #[serde(
+        rename = "implicit",
+        default,
+        deserialize_with = "one_or_many",
+        serialize_with = "serialize_one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub implicits: Vec<ImplicitConfig>,
+    /// Let base quality scale the implicits as well as the base's damage or defenses
+    #[serde(default)]
+    pub quality_affects_implicit: bool,
     #[serde(default)]
     pub defenses: Option<DefensesConfig>,
     #[serde(default)]
     pub damage: Option<DamageConfig>,
     #[serde(default)]
     pub requirements: Requirements,
+    /// Unknown fields, kept for round-tripping
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl BaseTypeConfig {
+    /// Default weight for random drops when `drop_weight` is unset
+    pub const DEFAULT_DROP_WEIGHT: u32 = 100;
+}
+
+/// Deserialize a list that may also be written as a single value (or `null`), for fields
+/// that used to hold at most one entry
+pub(crate) fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::Many(values)) => values,
+        Some(OneOrMany::One(value)) => vec![value],
+    })
+}
+
+/// Serialize a single entry as a plain value so existing files keep their layout
+fn serialize_one_or_many<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: Serialize,
+{
+    match values {
+        [value] => value.serialize(serializer),
+        _ => values.serialize(serializer),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -352,6 +1194,9 @@ pub struct ImplicitConfig {
     pub stat: StatType,
     pub min: i32,
     pub max: i32,
+    /// Makes this implicit one of the base's weighted options rather than always rolled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -393,6 +1238,30 @@ pub struct RollRange {
     pub max: i32,
 }
 
+impl RollRange {
+    /// Multiples of `step` inside the range, as (lowest, highest), or None if none fit
+    pub fn step_bounds(&self, step: i32) -> Option<(i32, i32)> {
+        if step <= 0 {
+            return None;
+        }
+        let lowest = (self.min + step - 1).div_euclid(step) * step;
+        let highest = self.max.div_euclid(step) * step;
+        (lowest <= highest).then_some((lowest, highest))
+    }
+
+    /// Move `value` to the nearest multiple of `step` inside the range (halves round up).
+    /// Values are left alone when the step is unset or no multiple fits.
+    pub fn snap(&self, value: i32, step: Option<i32>) -> i32 {
+        let Some(step) = step.filter(|&s| s > 1) else {
+            return value;
+        };
+        let Some((lowest, highest)) = self.step_bounds(step) else {
+            return value;
+        };
+        ((value + step / 2).div_euclid(step) * step).clamp(lowest, highest)
+    }
+}
+
 /// Affix configuration with tiers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AffixConfig {
@@ -409,6 +1278,21 @@ pub struct AffixConfig {
     #[serde(default)]
     pub allowed_classes: Vec<ItemClass>,
     pub tiers: Vec<AffixTierConfig>,
+    /// Relative chance of being picked when a currency removes a random affix
+    /// (default 100, so removal is uniform unless an affix overrides it)
+    #[serde(default)]
+    pub annul_weight: Option<u32>,
+    /// Short descriptive text shown dimmed under the modifier in item details
+    #[serde(default)]
+    pub flavor: Option<String>,
+    /// Unknown fields, kept for round-tripping
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl AffixConfig {
+    /// Default weight for random removal when `annul_weight` is unset
+    pub const DEFAULT_ANNUL_WEIGHT: u32 = 100;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -426,6 +1310,51 @@ pub struct AffixTierConfig {
     /// Minimum item level required for this tier to roll
     #[serde(default)]
     pub min_ilvl: u32,
+    /// Rolled values snap to the nearest multiple of this within their range
+    /// (e.g. 5 for life in steps of 5)
+    #[serde(default)]
+    pub step: Option<i32>,
+    /// Further stats a hybrid affix grants alongside `stat`, each with its own range.
+    /// Every tier of an affix lists the same stats in the same order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_stats: Vec<StatRange>,
+}
+
+/// One extra stat of a hybrid affix tier (e.g. the mana half of "+life and +mana")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatRange {
+    pub stat: StatType,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl StatRange {
+    pub fn range(&self) -> RollRange {
+        RollRange {
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+impl AffixTierConfig {
+    /// Range `Modifier::value` rolls from (the low end for damage range stats)
+    pub fn low_range(&self) -> RollRange {
+        RollRange {
+            min: self.min,
+            max: self.max,
+        }
+    }
+
+    /// Range `Modifier::value_max` rolls from, for "Adds X to Y" stats
+    pub fn high_range(&self) -> Option<RollRange> {
+        self.max_value
+    }
+
+    /// Whether this tier rolls both a low and a high bound
+    pub fn is_dual_range(&self) -> bool {
+        self.max_value.is_some()
+    }
 }
 
 /// Affix pool configuration - groups of affixes that can be referenced by currencies
@@ -438,6 +1367,9 @@ pub struct AffixPoolConfig {
     pub description: String,
     /// List of affix IDs in this pool
     pub affixes: Vec<String>,
+    /// Unknown fields, kept for round-tripping
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 /// Currency configuration - generic and data-driven
@@ -456,6 +1388,13 @@ pub struct CurrencyConfig {
     /// Effects when currency is applied
     #[serde(default)]
     pub effects: CurrencyEffects,
+    /// How many times the currency can be used per session in front ends that track
+    /// uses (None for unlimited). The generator itself never limits uses.
+    #[serde(default)]
+    pub stack_size: Option<u32>,
+    /// Unknown fields, kept for round-tripping
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 /// Requirements for using a currency
@@ -470,9 +1409,17 @@ pub struct CurrencyRequirements {
     /// Item must have room for at least one more affix
     #[serde(default)]
     pub has_affix_slot: bool,
+    /// Item's base type must have all of these tags
+    #[serde(default)]
+    pub required_tags: Vec<Tag>,
 }
 
-/// Effects when a currency is applied
+/// Effects when a currency is applied.
+///
+/// Effects apply in a fixed order: `set_rarity`, `clear_affixes`, `remove_affixes`,
+/// `reroll_affixes`, `add_affixes`, `add_specific_affix`, `try_unique`,
+/// `quality_affects` and `add_quality`, `convert_damage`, then `enchant_pool`.
+/// `Config::validate` rejects combinations that contradict each other under this order.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CurrencyEffects {
     /// Set the item's rarity
@@ -493,12 +1440,67 @@ pub struct CurrencyEffects {
     /// Reroll this many random affixes (remove and re-add)
     #[serde(default)]
     pub reroll_affixes: Option<u32>,
+    /// Only reroll prefixes, leaving suffixes untouched
+    #[serde(default)]
+    pub reroll_prefixes_only: bool,
+    /// Only reroll suffixes, leaving prefixes untouched
+    #[serde(default)]
+    pub reroll_suffixes_only: bool,
     /// Try to transform into a unique based on recipes
     #[serde(default)]
     pub try_unique: bool,
     /// Affix pools to draw from when adding random affixes (if empty, uses all affixes)
     #[serde(default)]
     pub affix_pools: Vec<String>,
+    /// Multiply the weight of randomly added or rerolled affixes carrying these tags,
+    /// e.g. `[["caster", 3.0]]` to favor caster mods
+    #[serde(default)]
+    pub weighted_tags: Vec<(Tag, f32)>,
+    /// Skew the tiers of randomly added or rerolled affixes: positive favors better tiers,
+    /// negative worse ones, 0 keeps the configured tier weights
+    #[serde(default)]
+    pub tier_bias: f32,
+    /// Add this much quality, capped at `Item::MAX_QUALITY`
+    #[serde(default)]
+    pub add_quality: Option<u32>,
+    /// Point the item's quality at this target. Switching to a different target resets
+    /// quality to 0 before `add_quality` is applied.
+    #[serde(default)]
+    pub quality_affects: Option<QualityTarget>,
+    /// Move part of a weapon's base damage from one type to another
+    #[serde(default)]
+    pub convert_damage: Option<DamageConversion>,
+    /// Replace the item's enchant with an affix rolled from this pool. The enchant has
+    /// its own slot, so prefix and suffix limits don't apply to it.
+    #[serde(default)]
+    pub enchant_pool: Option<String>,
+}
+
+impl CurrencyEffects {
+    /// Largest `tier_bias` in either direction. Past this the favored tier takes almost
+    /// every roll anyway.
+    pub const MAX_TIER_BIAS: f32 = 8.0;
+
+    /// Largest single `weighted_tags` multiplier
+    pub const MAX_TAG_MULTIPLIER: f32 = 1000.0;
+
+    /// The only affix type `reroll_affixes` may pick from, if the reroll is scoped
+    pub fn reroll_scope(&self) -> Option<AffixType> {
+        match (self.reroll_prefixes_only, self.reroll_suffixes_only) {
+            (true, false) => Some(AffixType::Prefix),
+            (false, true) => Some(AffixType::Suffix),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a share of one base damage type into another, e.g. 50% physical to fire
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageConversion {
+    pub from: DamageType,
+    pub to: DamageType,
+    /// Share of the `from` damage moved, 1-100
+    pub percent: u32,
 }
 
 /// Specifies a specific affix to add
@@ -512,6 +1514,49 @@ pub struct SpecificAffix {
     /// Weight for random selection (default 100)
     #[serde(default = "default_affix_weight")]
     pub weight: u32,
+    /// Exact value to give the modifier instead of rolling it (needs `tier`, and must lie
+    /// in that tier's range), for deterministic bench crafts
+    #[serde(default)]
+    pub value: Option<i32>,
+    /// Exact high end for damage range stats, within the tier's `max_value` range
+    #[serde(default)]
+    pub value_max: Option<i32>,
+}
+
+impl SpecificAffix {
+    /// Why the fixed `value`/`value_max` can't be used with this affix, if they can't
+    pub fn fixed_value_problem(&self, affix: &AffixConfig) -> Option<String> {
+        if self.value.is_none() && self.value_max.is_none() {
+            return None;
+        }
+        let Some(tier) = self.tier else {
+            return Some(format!("fixed value for '{}' needs a tier", self.id));
+        };
+        let tier_cfg = affix.tiers.iter().find(|t| t.tier == tier)?;
+        if let Some(value) = self.value {
+            if value < tier_cfg.min || value > tier_cfg.max {
+                return Some(format!(
+                    "fixed value {} for '{}' is outside tier {} ({}-{})",
+                    value, self.id, tier, tier_cfg.min, tier_cfg.max
+                ));
+            }
+        }
+        if let Some(value_max) = self.value_max {
+            let Some(range) = tier_cfg.max_value else {
+                return Some(format!(
+                    "fixed value_max for '{}' but tier {} has no max_value range",
+                    self.id, tier
+                ));
+            };
+            if value_max < range.min || value_max > range.max {
+                return Some(format!(
+                    "fixed value_max {} for '{}' is outside tier {} ({}-{})",
+                    value_max, self.id, tier, range.min, range.max
+                ));
+            }
+        }
+        None
+    }
 }
 
 fn default_affix_weight() -> u32 {
@@ -519,12 +1564,65 @@ fn default_affix_weight() -> u32 {
 }
 
 /// Specifies how many affixes to add
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AffixCount {
     pub min: u32,
     pub max: u32,
 }
 
+/// Settings for generating items directly at a rarity (loaded from generation.toml)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationConfig {
+    /// Total affixes rolled on a magic item
+    #[serde(default = "default_magic_affix_count")]
+    pub magic_affix_count: AffixCount,
+    /// Total affixes rolled on a rare item
+    #[serde(default = "default_rare_affix_count")]
+    pub rare_affix_count: AffixCount,
+    /// How affix values are rolled within their tier range
+    #[serde(default)]
+    pub value_distribution: ValueDistribution,
+    /// How rare items are named
+    #[serde(default)]
+    pub rare_name_mode: RareNameMode,
+    /// Which name parts a rare name is built from
+    #[serde(default)]
+    pub rare_name_structure: RareNameStructure,
+    /// Words for the first part of rare names (built-in list when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rare_name_prefixes: Vec<String>,
+    /// Words for the second part of rare names (built-in list when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rare_name_suffixes: Vec<String>,
+    /// Words for the "of the X" ending of `prefix_suffix_epithet` names (built-in list
+    /// when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rare_name_epithets: Vec<String>,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        GenerationConfig {
+            magic_affix_count: default_magic_affix_count(),
+            rare_affix_count: default_rare_affix_count(),
+            value_distribution: ValueDistribution::default(),
+            rare_name_mode: RareNameMode::default(),
+            rare_name_structure: RareNameStructure::default(),
+            rare_name_prefixes: Vec::new(),
+            rare_name_suffixes: Vec::new(),
+            rare_name_epithets: Vec::new(),
+        }
+    }
+}
+
+fn default_magic_affix_count() -> AffixCount {
+    AffixCount { min: 1, max: 2 }
+}
+
+fn default_rare_affix_count() -> AffixCount {
+    AffixCount { min: 4, max: 6 }
+}
+
 /// Unique item template configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UniqueConfig {
@@ -533,7 +1631,16 @@ pub struct UniqueConfig {
     pub base_type: String,
     #[serde(default)]
     pub flavor: Option<String>,
+    /// Minimum area level for this unique to drop (for drop tables; 0 = any level)
+    #[serde(default)]
+    pub drop_level: u32,
+    /// Character level needed to equip; raises the base type's level requirement
+    #[serde(default)]
+    pub required_level: u32,
     pub mods: Vec<UniqueModConfig>,
+    /// Unknown fields, kept for round-tripping
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -566,8 +1673,8 @@ fn default_weight() -> u32 {
     100
 }
 
-/// Requirement for an affix in a unique recipe
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Requirement for an affix in a unique recipe (also used for crafting targets)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RecipeAffixRequirement {
     /// The stat type required
     pub stat: StatType,
@@ -1,17 +1,24 @@
 use crate::config::{
-    CurrencyConfig, MappingMode, RecipeAffixRequirement, SpecificAffix, UniqueRecipeConfig,
+    AffixConfig, CurrencyConfig, CurrencyEffects, DamageConversion, MappingMode,
+    RecipeAffixRequirement, SpecificAffix, UniqueRecipeConfig,
 };
-use crate::generator::Generator;
-use crate::item::{Item, Modifier};
+use crate::generator::{AffixRoll, Generator};
+use crate::item::{DamageValue, Item, Modifier};
 use crate::types::*;
 use rand::Rng;
 use rand_chacha::ChaCha8Rng;
 
-/// Apply a currency to an item using the generic config-driven system
+/// Apply a currency to an item using the generic config-driven system.
+///
+/// `set_rarity` only changes the rarity: existing modifiers stay unless the currency
+/// also clears, removes or rerolls them, so `set_rarity` with `add_affixes` upgrades
+/// an item on top of what it already has. Modifiers whose affix id is in `locked`
+/// survive clearing, removal and rerolls.
 pub fn apply_currency(
     generator: &Generator,
     item: &mut Item,
     currency: &CurrencyConfig,
+    locked: &[String],
     rng: &mut ChaCha8Rng,
 ) -> Result<(), CurrencyError> {
     // Check requirements
@@ -21,17 +28,20 @@ pub fn apply_currency(
     let effects = &currency.effects;
 
     // 1. Set rarity (if specified)
+    let mut renamed = false;
     if let Some(new_rarity) = effects.set_rarity {
+        let was_magic = item.rarity == Rarity::Magic;
         item.rarity = new_rarity;
-        if new_rarity == Rarity::Rare && item.name == item.base_name {
+        if new_rarity == Rarity::Rare && (was_magic || item.name == item.base_name) {
             item.name = generator.generate_rare_name(rng);
+            renamed = true;
         }
     }
 
     // 2. Clear affixes (if specified)
     if effects.clear_affixes {
-        item.prefixes.clear();
-        item.suffixes.clear();
+        item.prefixes.retain(|m| locked.contains(&m.affix_id));
+        item.suffixes.retain(|m| locked.contains(&m.affix_id));
         // Reset name to base name if becoming normal
         if item.rarity == Rarity::Normal {
             item.name = item.base_name.clone();
@@ -41,14 +51,21 @@ pub fn apply_currency(
     // 3. Remove random affixes (if specified)
     if let Some(count) = effects.remove_affixes {
         for _ in 0..count {
-            remove_random_affix(item, rng)?;
+            remove_random_affix(generator, item, locked, rng)?;
         }
     }
 
     // 4. Reroll random affixes (if specified)
     if let Some(count) = effects.reroll_affixes {
         for _ in 0..count {
-            reroll_random_affix(generator, item, &effects.affix_pools, rng)?;
+            reroll_random_affix(
+                generator,
+                item,
+                &AffixRoll::from_effects(effects),
+                effects.reroll_scope(),
+                locked,
+                rng,
+            )?;
         }
     }
 
@@ -61,7 +78,7 @@ pub fn apply_currency(
         };
 
         for _ in 0..count {
-            if !add_random_affix(generator, item, &effects.affix_pools, rng) {
+            if !add_random_affix(generator, item, &AffixRoll::from_effects(effects), rng) {
                 break; // No more valid affixes or slots
             }
         }
@@ -72,16 +89,201 @@ pub fn apply_currency(
         add_specific_affix_from_set(generator, item, &effects.add_specific_affix, rng)?;
     }
 
+    // A new rare name is themed once the affixes are settled
+    if renamed {
+        generator.theme_rare_name(item, rng);
+    }
+
     // 7. Try unique transformation (if specified)
     if effects.try_unique {
         try_unique_transformation(generator, item, rng)?;
     }
 
+    // 8. Retarget and add quality (if specified)
+    if let Some(ref target) = effects.quality_affects {
+        if item.quality_target != *target {
+            item.quality_target = target.clone();
+            item.quality = 0;
+        }
+    }
+    if let Some(amount) = effects.add_quality {
+        item.quality = (item.quality + amount).min(Item::MAX_QUALITY);
+    }
+
+    // 9. Convert base damage (if specified)
+    if let Some(ref conversion) = effects.convert_damage {
+        convert_damage(item, conversion);
+    }
+
+    // 10. Set the enchant (if specified)
+    if let Some(ref pool) = effects.enchant_pool {
+        item.enchant = Some(roll_enchant(generator, item, pool, rng)?);
+    }
+
+    if item.rarity == Rarity::Magic {
+        item.name = item.magic_name();
+    }
+
+    Ok(())
+}
+
+/// Affixes in `pool` that can roll as an enchant on the item, with their spawn weights,
+/// in pool order. Prefixes and suffixes both qualify.
+fn enchant_candidates<'a>(
+    generator: &'a Generator,
+    item: &Item,
+    pool: &str,
+) -> Vec<(&'a AffixConfig, u32)> {
+    let Some(pool) = generator.config().affix_pools.get(pool) else {
+        return Vec::new();
+    };
+    pool.affixes
+        .iter()
+        .filter_map(|id| generator.config().affixes.get(id))
+        .filter(|affix| {
+            affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&item.class)
+        })
+        .filter(|affix| Generator::has_matching_tag(affix, &item.tags))
+        .filter(|affix| !Generator::has_blocked_tag(affix, generator.blocked_tags(item)))
+        .filter(|affix| {
+            affix
+                .tiers
+                .iter()
+                .any(|t| t.min_ilvl <= item.requirements.level && t.weight > 0)
+        })
+        .map(|affix| (affix, generator.calculate_weight(affix, &item.tags)))
+        .filter(|&(_, weight)| weight > 0)
+        .collect()
+}
+
+/// Roll an enchant from `pool`: an affix by spawn weight, then one of its tiers the
+/// item's level allows
+fn roll_enchant(
+    generator: &Generator,
+    item: &Item,
+    pool: &str,
+    rng: &mut ChaCha8Rng,
+) -> Result<Modifier, CurrencyError> {
+    let candidates = enchant_candidates(generator, item, pool);
+    let total: u32 = candidates.iter().map(|&(_, weight)| weight).sum();
+    if total == 0 {
+        return Err(CurrencyError::NoValidAffixes);
+    }
+    let mut roll = rng.gen_range(0..total);
+    let mut chosen = candidates[0].0;
+    for &(affix, weight) in &candidates {
+        if roll < weight {
+            chosen = affix;
+            break;
+        }
+        roll -= weight;
+    }
+
+    let tiers: Vec<_> = chosen
+        .tiers
+        .iter()
+        .filter(|t| t.min_ilvl <= item.requirements.level)
+        .collect();
+    let tier_total: u32 = tiers.iter().map(|t| t.weight).sum();
+    let mut roll = rng.gen_range(0..tier_total);
+    let mut tier = tiers[0];
+    for t in &tiers {
+        if roll < t.weight {
+            tier = t;
+            break;
+        }
+        roll -= t.weight;
+    }
+
+    let (value, value_max) = generator.roll_tier_values(tier, rng);
+    let extra_stats = generator.roll_extra_stats(tier, rng);
+    Ok(Modifier::from_affix(
+        chosen,
+        tier,
+        value,
+        value_max,
+        extra_stats,
+    ))
+}
+
+/// Checks `apply_add_affix` makes before adding an affix; shared with
+/// `Generator::eligible_affixes` so the two can't diverge.
+pub(crate) fn check_affix_addable(item: &Item, affix: &AffixConfig) -> Result<(), CurrencyError> {
+    if item.rarity == Rarity::Unique {
+        return Err(CurrencyError::InvalidRarity {
+            expected: vec![Rarity::Normal, Rarity::Magic, Rarity::Rare],
+            got: item.rarity,
+        });
+    }
+
+    let already_present = item
+        .prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .any(|m| m.affix_id == affix.id);
+    if already_present {
+        return Err(CurrencyError::AffixAlreadyPresent(affix.id.clone()));
+    }
+
+    if !affix.allowed_classes.is_empty() && !affix.allowed_classes.contains(&item.class) {
+        return Err(CurrencyError::AffixNotAllowed(affix.id.clone()));
+    }
+
+    let has_slot = match affix.affix_type {
+        AffixType::Prefix => item.prefixes.len() < Rarity::Rare.max_prefixes(),
+        AffixType::Suffix => item.suffixes.len() < Rarity::Rare.max_suffixes(),
+    };
+    if !has_slot {
+        return Err(CurrencyError::NoAffixSlots);
+    }
+
     Ok(())
 }
 
+/// Add a specific affix at a specific tier (manual crafting).
+///
+/// Slots are limited by rare-item capacity regardless of current rarity; the item's
+/// rarity is upgraded to fit the new affix count afterwards.
+pub(crate) fn apply_add_affix(
+    generator: &Generator,
+    item: &mut Item,
+    affix_id: &str,
+    tier: u32,
+    rng: &mut ChaCha8Rng,
+) -> Result<(), CurrencyError> {
+    let affix = generator
+        .config()
+        .affixes
+        .get(affix_id)
+        .ok_or_else(|| CurrencyError::AffixNotFound(affix_id.to_string()))?;
+
+    check_affix_addable(item, affix)?;
+
+    add_affix_by_id(generator, item, affix_id, Some(tier), None, rng)?;
+
+    // Upgrade rarity based on total affix count
+    let total_affixes = item.affix_count();
+    if total_affixes >= 3 && item.rarity != Rarity::Rare {
+        item.rarity = Rarity::Rare;
+        item.name = generator.generate_rare_name(rng);
+        generator.theme_rare_name(item, rng);
+    } else if total_affixes >= 1 && item.rarity == Rarity::Normal {
+        item.rarity = Rarity::Magic;
+    }
+    if item.rarity == Rarity::Magic {
+        item.name = item.magic_name();
+    }
+
+    Ok(())
+}
+
+/// First of the required tags the item does not have, if any
+fn missing_required_tag<'a>(item: &Item, required_tags: &'a [Tag]) -> Option<&'a Tag> {
+    required_tags.iter().find(|tag| !item.tags.contains(tag))
+}
+
 /// Check if currency requirements are met
-fn check_requirements(
+pub(crate) fn check_requirements(
     generator: &Generator,
     item: &Item,
     currency: &CurrencyConfig,
@@ -102,6 +304,14 @@ fn check_requirements(
         return Err(CurrencyError::NoAffixesToRemove);
     }
 
+    // Check required_tags requirement
+    if let Some(tag) = missing_required_tag(item, &reqs.required_tags) {
+        return Err(CurrencyError::RequirementNotMet(format!(
+            "item is missing tag '{}'",
+            tag
+        )));
+    }
+
     // Check has_affix_slot requirement
     // If the currency will change rarity, check against target rarity's limits
     if reqs.has_affix_slot {
@@ -140,6 +350,32 @@ fn check_requirements(
         }
     }
 
+    // Damage conversion only applies to weapons with damage of the source type
+    if let Some(ref conversion) = effects.convert_damage {
+        let Some(ref damage) = item.damage else {
+            return Err(CurrencyError::RequirementNotMet(
+                "only weapons have damage to convert".to_string(),
+            ));
+        };
+        if !damage
+            .damages
+            .iter()
+            .any(|d| d.damage_type == conversion.from)
+        {
+            return Err(CurrencyError::RequirementNotMet(format!(
+                "weapon has no {:?} damage to convert",
+                conversion.from
+            )));
+        }
+    }
+
+    // An enchant pool needs something this item can roll
+    if let Some(ref pool) = effects.enchant_pool {
+        if enchant_candidates(generator, item, pool).is_empty() {
+            return Err(CurrencyError::NoValidAffixes);
+        }
+    }
+
     // Check that affix_pools is specified when adding or rerolling random affixes
     let needs_pools = effects.add_affixes.is_some() || effects.reroll_affixes.is_some();
     if needs_pools && effects.affix_pools.is_empty() {
@@ -208,9 +444,53 @@ fn can_add_any_specific_affix(
     })
 }
 
+/// Move `percent` of the weapon's base `from` damage into `to` (rounded down), merging
+/// with an existing `to` entry. A `from` entry left at 0-0 is removed.
+fn convert_damage(item: &mut Item, conversion: &DamageConversion) {
+    let Some(ref mut damage) = item.damage else {
+        return;
+    };
+    let Some(source) = damage
+        .damages
+        .iter_mut()
+        .find(|d| d.damage_type == conversion.from)
+    else {
+        return;
+    };
+
+    let percent = conversion.percent.min(100) as i32;
+    let moved_min = source.min * percent / 100;
+    let moved_max = source.max * percent / 100;
+    if moved_max == 0 {
+        return;
+    }
+    source.min -= moved_min;
+    source.max -= moved_max;
+    damage.damages.retain(|d| d.min > 0 || d.max > 0);
+
+    match damage
+        .damages
+        .iter_mut()
+        .find(|d| d.damage_type == conversion.to)
+    {
+        Some(target) => {
+            target.min += moved_min;
+            target.max += moved_max;
+        }
+        None => damage.damages.push(DamageValue {
+            damage_type: conversion.to,
+            min: moved_min,
+            max: moved_max,
+        }),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum CurrencyError {
-    InvalidRarity { expected: Vec<Rarity>, got: Rarity },
+    InvalidRarity {
+        expected: Vec<Rarity>,
+        got: Rarity,
+    },
     NoAffixSlots,
     NoAffixesToRemove,
     NoValidAffixes,
@@ -218,35 +498,81 @@ pub enum CurrencyError {
     AffixNotFound(String),
     AffixAlreadyPresent(String),
     AffixNotAllowed(String),
-    TierNotFound { affix_id: String, tier: u32 },
+    TierNotFound {
+        affix_id: String,
+        tier: u32,
+    },
     NoAffixPoolsSpecified,
     UnknownCurrency(String),
+    RequirementNotMet(String),
+    /// Mirrored items can't be modified
+    Mirrored,
+    /// Every modifier the currency could touch is locked
+    AllAffixesLocked,
+    /// A fixed `add_specific_affix` value doesn't fit the affix tier
+    FixedValueOutOfRange(String),
 }
 
 impl std::fmt::Display for CurrencyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            CurrencyError::InvalidRarity { expected, got }
+                if *got == Rarity::Unique && !expected.contains(&Rarity::Unique) =>
+            {
+                write!(f, "This item is Unique and cannot be modified")
+            }
             CurrencyError::InvalidRarity { expected, got } => {
-                write!(f, "Invalid rarity: expected {:?}, got {:?}", expected, got)
+                let names: Vec<&str> = expected.iter().map(|r| r.name()).collect();
+                write!(
+                    f,
+                    "Requires a {} item, but this item is {}",
+                    names.join(" or "),
+                    got
+                )
+            }
+            CurrencyError::NoAffixSlots => {
+                write!(f, "This item has no open prefix or suffix slots")
+            }
+            CurrencyError::NoAffixesToRemove => write!(f, "This item has no modifiers to remove"),
+            CurrencyError::NoValidAffixes => {
+                write!(f, "No modifier can roll on this item with this currency")
+            }
+            CurrencyError::NoMatchingRecipe => {
+                write!(
+                    f,
+                    "No unique recipe matches this base type and its modifiers"
+                )
+            }
+            CurrencyError::AffixNotFound(id) => {
+                write!(f, "Modifier '{}' is not defined in the config", id)
+            }
+            CurrencyError::AffixAlreadyPresent(id) => {
+                write!(f, "This item already has modifier '{}'", id)
             }
-            CurrencyError::NoAffixSlots => write!(f, "No affix slots available"),
-            CurrencyError::NoAffixesToRemove => write!(f, "No affixes to remove"),
-            CurrencyError::NoValidAffixes => write!(f, "No valid affixes to add"),
-            CurrencyError::NoMatchingRecipe => write!(f, "No matching unique recipe"),
-            CurrencyError::AffixNotFound(id) => write!(f, "Affix not found: {}", id),
-            CurrencyError::AffixAlreadyPresent(id) => write!(f, "Affix already on item: {}", id),
             CurrencyError::AffixNotAllowed(id) => {
-                write!(f, "Affix not allowed on this item: {}", id)
+                write!(f, "Modifier '{}' cannot roll on this item class", id)
             }
             CurrencyError::TierNotFound { affix_id, tier } => {
-                write!(f, "Tier {} not found for affix {}", tier, affix_id)
+                write!(f, "Modifier '{}' has no tier {}", affix_id, tier)
             }
             CurrencyError::NoAffixPoolsSpecified => {
-                write!(f, "No affix pools specified for currency")
+                write!(f, "This currency has no affix pools to roll from")
             }
             CurrencyError::UnknownCurrency(id) => {
                 write!(f, "Unknown currency: {}", id)
             }
+            CurrencyError::RequirementNotMet(reason) => {
+                write!(f, "Can't use this currency here: {}", reason)
+            }
+            CurrencyError::Mirrored => {
+                write!(f, "This item is mirrored and cannot be modified")
+            }
+            CurrencyError::AllAffixesLocked => {
+                write!(f, "Every modifier this currency would change is locked")
+            }
+            CurrencyError::FixedValueOutOfRange(problem) => {
+                write!(f, "Can't craft the fixed value: {}", problem)
+            }
         }
     }
 }
@@ -254,20 +580,12 @@ impl std::fmt::Display for CurrencyError {
 impl std::error::Error for CurrencyError {}
 
 /// Add a random affix to the item, returns false if no valid affix/slot available
-/// If pools is non-empty, only affixes from those pools will be considered
 fn add_random_affix(
     generator: &Generator,
     item: &mut Item,
-    pools: &[String],
+    options: &AffixRoll,
     rng: &mut ChaCha8Rng,
 ) -> bool {
-    let existing: Vec<String> = item
-        .prefixes
-        .iter()
-        .chain(item.suffixes.iter())
-        .map(|m| m.affix_id.clone())
-        .collect();
-
     let can_prefix = item.can_add_prefix();
     let can_suffix = item.can_add_suffix();
 
@@ -289,10 +607,7 @@ fn add_random_affix(
         (false, false) => return false,
     };
 
-    let item_level = item.requirements.level as u32;
-    if let Some(modifier) = generator.roll_affix_from_pools(
-        item.class, &item.tags, affix_type, &existing, pools, item_level, rng,
-    ) {
+    if let Some(modifier) = generator.roll_affix(item, affix_type, options, rng) {
         match affix_type {
             AffixType::Prefix => item.prefixes.push(modifier),
             AffixType::Suffix => item.suffixes.push(modifier),
@@ -311,9 +626,7 @@ fn add_random_affix(
         };
 
         if can_other {
-            if let Some(modifier) = generator.roll_affix_from_pools(
-                item.class, &item.tags, other_type, &existing, pools, item_level, rng,
-            ) {
+            if let Some(modifier) = generator.roll_affix(item, other_type, options, rng) {
                 match other_type {
                     AffixType::Prefix => item.prefixes.push(modifier),
                     AffixType::Suffix => item.suffixes.push(modifier),
@@ -329,7 +642,7 @@ fn add_random_affix(
 fn add_specific_affix_from_set(
     generator: &Generator,
     item: &mut Item,
-    candidates: &[crate::config::SpecificAffix],
+    candidates: &[SpecificAffix],
     rng: &mut ChaCha8Rng,
 ) -> Result<(), CurrencyError> {
     // Get existing affix IDs
@@ -389,15 +702,24 @@ fn add_specific_affix_from_set(
     };
 
     // Now add the selected affix
-    add_affix_by_id(generator, item, &selected.id, selected.tier, rng)
+    add_affix_by_id(
+        generator,
+        item,
+        &selected.id,
+        selected.tier,
+        Some(selected),
+        rng,
+    )
 }
 
-/// Add a specific affix to the item by ID
+/// Add a specific affix to the item by ID, using the fixed values of `fixed` (if any) in
+/// place of the rolled ones
 fn add_affix_by_id(
     generator: &Generator,
     item: &mut Item,
     affix_id: &str,
     tier: Option<u32>,
+    fixed: Option<&SpecificAffix>,
     rng: &mut ChaCha8Rng,
 ) -> Result<(), CurrencyError> {
     // Get the affix config
@@ -407,7 +729,7 @@ fn add_affix_by_id(
         .get(affix_id)
         .ok_or_else(|| CurrencyError::AffixNotFound(affix_id.to_string()))?;
 
-    let item_level = item.requirements.level as u32;
+    let item_level = item.requirements.level;
 
     // Select tier
     let selected_tier = if let Some(specific_tier) = tier {
@@ -451,27 +773,17 @@ fn add_affix_by_id(
         selected.ok_or(CurrencyError::NoValidAffixes)?
     };
 
-    // Roll value within tier range
-    let value = rng.gen_range(selected_tier.min..=selected_tier.max);
-
-    // Roll max value if this is a damage range stat
-    let value_max = selected_tier
-        .max_value
-        .map(|range| rng.gen_range(range.min..=range.max));
-
-    // Create the modifier
-    let modifier = Modifier {
-        affix_id: affix.id.clone(),
-        name: affix.name.clone(),
-        stat: affix.stat,
-        scope: affix.scope,
-        tier: selected_tier.tier,
-        value,
-        value_max,
-        tier_min: selected_tier.min,
-        tier_max: selected_tier.max,
-        tier_max_value: selected_tier.max_value.map(|r| (r.min, r.max)),
-    };
+    // Fixed values still roll first, so the rest of the RNG stream doesn't depend on them
+    let (mut value, mut value_max) = generator.roll_tier_values(selected_tier, rng);
+    if let Some(fixed) = fixed {
+        if let Some(problem) = fixed.fixed_value_problem(affix) {
+            return Err(CurrencyError::FixedValueOutOfRange(problem));
+        }
+        value = fixed.value.unwrap_or(value);
+        value_max = fixed.value_max.or(value_max);
+    }
+    let extra_stats = generator.roll_extra_stats(selected_tier, rng);
+    let modifier = Modifier::from_affix(affix, selected_tier, value, value_max, extra_stats);
 
     // Add to appropriate list
     match affix.affix_type {
@@ -482,14 +794,56 @@ fn add_affix_by_id(
     Ok(())
 }
 
-/// Remove a random affix from the item
-fn remove_random_affix(item: &mut Item, rng: &mut ChaCha8Rng) -> Result<(), CurrencyError> {
-    let total_affixes = item.prefixes.len() + item.suffixes.len();
-    if total_affixes == 0 {
-        return Err(CurrencyError::NoAffixesToRemove);
+/// Indices (prefixes first, then suffixes) of the explicit modifiers not in `locked`
+fn unlocked_indices(item: &Item, locked: &[String]) -> Result<Vec<usize>, CurrencyError> {
+    let indices: Vec<usize> = item
+        .prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .enumerate()
+        .filter(|(_, m)| !locked.contains(&m.affix_id))
+        .map(|(i, _)| i)
+        .collect();
+    if indices.is_empty() {
+        return Err(if item.affix_count() == 0 {
+            CurrencyError::NoAffixesToRemove
+        } else {
+            CurrencyError::AllAffixesLocked
+        });
     }
+    Ok(indices)
+}
 
-    let idx = rng.gen_range(0..total_affixes);
+/// Remove a random unlocked affix from the item, weighted by each affix's `annul_weight`
+fn remove_random_affix(
+    generator: &Generator,
+    item: &mut Item,
+    locked: &[String],
+    rng: &mut ChaCha8Rng,
+) -> Result<(), CurrencyError> {
+    let candidates = unlocked_indices(item, locked)?;
+    let all_weights = annul_weights(generator, item);
+    let weights: Vec<u32> = candidates.iter().map(|&i| all_weights[i]).collect();
+
+    // Uniform odds keep the original single roll so existing items replay unchanged
+    let pick = if weights.iter().all(|&w| w == weights[0]) {
+        rng.gen_range(0..weights.len())
+    } else {
+        let total_weight: u32 = weights.iter().sum();
+        let mut roll = rng.gen_range(0..total_weight);
+        weights
+            .iter()
+            .position(|&w| {
+                if roll < w {
+                    true
+                } else {
+                    roll -= w;
+                    false
+                }
+            })
+            .unwrap_or(weights.len() - 1)
+    };
+    let idx = candidates[pick];
 
     if idx < item.prefixes.len() {
         item.prefixes.remove(idx);
@@ -500,72 +854,372 @@ fn remove_random_affix(item: &mut Item, rng: &mut ChaCha8Rng) -> Result<(), Curr
     Ok(())
 }
 
-/// Reroll a random affix (remove it and add a new one of the same type)
-/// If pools is non-empty, only affixes from those pools will be considered
+/// Removal weight of each explicit modifier, prefixes first then suffixes
+pub(crate) fn annul_weights(generator: &Generator, item: &Item) -> Vec<u32> {
+    item.prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .map(|m| {
+            generator
+                .config()
+                .affixes
+                .get(&m.affix_id)
+                .and_then(|a| a.annul_weight)
+                .unwrap_or(AffixConfig::DEFAULT_ANNUL_WEIGHT)
+        })
+        .collect()
+}
+
+/// Reroll a random unlocked affix (remove it and add a new one of the same type, drawn
+/// as `options` ask). With a `scope`, only modifiers of that type are candidates.
 fn reroll_random_affix(
     generator: &Generator,
     item: &mut Item,
-    pools: &[String],
+    options: &AffixRoll,
+    scope: Option<AffixType>,
+    locked: &[String],
     rng: &mut ChaCha8Rng,
 ) -> Result<(), CurrencyError> {
     let prefix_count = item.prefixes.len();
-    let suffix_count = item.suffixes.len();
-    let total = prefix_count + suffix_count;
-
-    if total == 0 {
-        return Err(CurrencyError::NoAffixesToRemove);
+    let mut candidates = unlocked_indices(item, locked)?;
+    if let Some(scope) = scope {
+        candidates.retain(|&i| (i < prefix_count) == (scope == AffixType::Prefix));
+        if candidates.is_empty() {
+            let scoped_count = match scope {
+                AffixType::Prefix => prefix_count,
+                AffixType::Suffix => item.suffixes.len(),
+            };
+            return Err(if scoped_count == 0 {
+                CurrencyError::NoAffixesToRemove
+            } else {
+                CurrencyError::AllAffixesLocked
+            });
+        }
     }
 
-    let idx = rng.gen_range(0..total);
+    let idx = candidates[rng.gen_range(0..candidates.len())];
     let is_prefix = idx < prefix_count;
-    let item_level = item.requirements.level as u32;
 
     if is_prefix {
         item.prefixes.remove(idx);
-
-        let existing_ids: Vec<String> = item
-            .prefixes
-            .iter()
-            .chain(item.suffixes.iter())
-            .map(|m| m.affix_id.clone())
-            .collect();
-
-        if let Some(modifier) = generator.roll_affix_from_pools(
-            item.class,
-            &item.tags,
-            AffixType::Prefix,
-            &existing_ids,
-            pools,
-            item_level,
-            rng,
-        ) {
+        if let Some(modifier) = generator.roll_affix(item, AffixType::Prefix, options, rng) {
             item.prefixes.push(modifier);
         }
     } else {
         let removed_idx = idx - prefix_count;
         item.suffixes.remove(removed_idx);
+        if let Some(modifier) = generator.roll_affix(item, AffixType::Suffix, options, rng) {
+            item.suffixes.push(modifier);
+        }
+    }
 
-        let existing_ids: Vec<String> = item
-            .prefixes
-            .iter()
-            .chain(item.suffixes.iter())
-            .map(|m| m.affix_id.clone())
-            .collect();
+    Ok(())
+}
+
+/// Chance of one affix coming out of a currency, broken down by tier
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffixOdds {
+    pub affix_id: String,
+    /// Chance this affix is the one added
+    pub chance: f64,
+    /// Chance of each reachable tier, sorted by tier; these sum to `chance`
+    pub tiers: Vec<(u32, f64)>,
+}
+
+/// What a currency could do to an item, as probabilities.
+///
+/// Each random effect is enumerated against the item after `set_rarity` and
+/// `clear_affixes`, using the same weighted candidates `apply_currency` rolls from.
+/// A list may sum to less than 1 when a roll can come up empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutcomeOdds {
+    /// Chance each explicit modifier (prefixes first, then suffixes) is the first one
+    /// removed or rerolled, by affix id
+    pub removed: Vec<(String, f64)>,
+    /// Chance of each affix being the first random affix added, or the replacement
+    /// for the first reroll, sorted by affix id
+    pub added: Vec<AffixOdds>,
+    /// Chance of each affix picked from `add_specific_affix`, sorted by affix id
+    pub specific: Vec<AffixOdds>,
+}
+
+/// Accumulates chances per affix and tier so overlapping paths can be merged
+#[derive(Default)]
+struct OddsTable(std::collections::BTreeMap<String, std::collections::BTreeMap<u32, f64>>);
+
+impl OddsTable {
+    fn add(&mut self, affix_id: &str, tier: u32, chance: f64) {
+        *self
+            .0
+            .entry(affix_id.to_string())
+            .or_default()
+            .entry(tier)
+            .or_default() += chance;
+    }
+
+    fn into_odds(self) -> Vec<AffixOdds> {
+        self.0
+            .into_iter()
+            .map(|(affix_id, tiers)| AffixOdds {
+                affix_id,
+                chance: tiers.values().sum(),
+                tiers: tiers.into_iter().collect(),
+            })
+            .collect()
+    }
+}
 
-        if let Some(modifier) = generator.roll_affix_from_pools(
-            item.class,
-            &item.tags,
-            AffixType::Suffix,
-            &existing_ids,
-            pools,
-            item_level,
-            rng,
+/// Compute the odds of each outcome of a currency on an item without applying it
+pub fn outcome_odds(
+    generator: &Generator,
+    item: &Item,
+    currency: &CurrencyConfig,
+) -> Result<OutcomeOdds, CurrencyError> {
+    check_requirements(generator, item, currency)?;
+
+    let effects = &currency.effects;
+    let mut item = item.clone();
+    if let Some(rarity) = effects.set_rarity {
+        item.rarity = rarity;
+    }
+    if effects.clear_affixes {
+        item.prefixes.clear();
+        item.suffixes.clear();
+    }
+
+    let mut odds = OutcomeOdds::default();
+    let mut added = OddsTable::default();
+
+    if effects.remove_affixes.is_some() {
+        let weights = annul_weights(generator, &item);
+        let total: u32 = weights.iter().sum();
+        if total > 0 {
+            odds.removed = item
+                .prefixes
+                .iter()
+                .chain(item.suffixes.iter())
+                .zip(weights)
+                .map(|(m, w)| (m.affix_id.clone(), w as f64 / total as f64))
+                .collect();
+        }
+    }
+
+    if effects.reroll_affixes.is_some() {
+        // The rerolled modifier is picked uniformly (from its group, when scoped) and
+        // replaced by one of the same type
+        let scope = effects.reroll_scope();
+        let in_scope = |idx: usize| {
+            let affix_type = if idx < item.prefixes.len() {
+                AffixType::Prefix
+            } else {
+                AffixType::Suffix
+            };
+            scope.is_none_or(|s| s == affix_type)
+        };
+        let total = (0..item.affix_count()).filter(|&i| in_scope(i)).count();
+        for idx in (0..item.affix_count()).filter(|&i| in_scope(i)) {
+            let chance = 1.0 / total as f64;
+            let mut rerolled = item.clone();
+            let (removed, affix_type) = if idx < item.prefixes.len() {
+                (rerolled.prefixes.remove(idx), AffixType::Prefix)
+            } else {
+                (
+                    rerolled.suffixes.remove(idx - item.prefixes.len()),
+                    AffixType::Suffix,
+                )
+            };
+            if effects.remove_affixes.is_none() {
+                odds.removed.push((removed.affix_id, chance));
+            }
+            roll_odds(
+                generator, &rerolled, affix_type, effects, chance, &mut added,
+            );
+        }
+    }
+
+    if effects.add_affixes.is_some() {
+        add_random_affix_odds(generator, &item, effects, &mut added);
+    }
+    odds.added = added.into_odds();
+
+    if !effects.add_specific_affix.is_empty() {
+        odds.specific = specific_affix_odds(generator, &item, &effects.add_specific_affix);
+    }
+
+    Ok(odds)
+}
+
+/// Odds for `add_random_affix`: a coin flip between open slot types, falling back to
+/// the other type when the first roll comes up empty
+fn add_random_affix_odds(
+    generator: &Generator,
+    item: &Item,
+    effects: &CurrencyEffects,
+    table: &mut OddsTable,
+) {
+    let can_prefix = item.can_add_prefix();
+    let can_suffix = item.can_add_suffix();
+
+    let first_types: &[(AffixType, f64)] = match (can_prefix, can_suffix) {
+        (true, true) => &[(AffixType::Prefix, 0.5), (AffixType::Suffix, 0.5)],
+        (true, false) => &[(AffixType::Prefix, 1.0)],
+        (false, true) => &[(AffixType::Suffix, 1.0)],
+        (false, false) => return,
+    };
+
+    for &(affix_type, chance) in first_types {
+        let missed = roll_odds(generator, item, affix_type, effects, chance, table);
+        let other_type = match affix_type {
+            AffixType::Prefix => AffixType::Suffix,
+            AffixType::Suffix => AffixType::Prefix,
+        };
+        let can_other = match other_type {
+            AffixType::Prefix => can_prefix,
+            AffixType::Suffix => can_suffix,
+        };
+        if can_other && missed > 0.0 {
+            roll_odds(generator, item, other_type, effects, missed, table);
+        }
+    }
+}
+
+/// Odds for `Generator::roll_affix_weighted` with a currency's pools and tag weights,
+/// scaled by `chance`.
+///
+/// Returns the share of `chance` where the roll comes up empty.
+fn roll_odds(
+    generator: &Generator,
+    item: &Item,
+    affix_type: AffixType,
+    effects: &CurrencyEffects,
+    chance: f64,
+    table: &mut OddsTable,
+) -> f64 {
+    let existing: Vec<&str> = item
+        .prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .map(|m| m.affix_id.as_str())
+        .collect();
+    let candidates: Vec<_> = generator
+        .get_valid_affixes_from_pools(item.class, affix_type, &effects.affix_pools)
+        .into_iter()
+        .filter(|a| !existing.contains(&a.id.as_str()))
+        .filter(|a| Generator::has_matching_tag(a, &item.tags))
+        .filter(|a| !Generator::has_blocked_tag(a, generator.blocked_tags(item)))
+        .collect();
+    let weights: Vec<u32> = candidates
+        .iter()
+        .map(|a| generator.tag_weighted(a, &item.tags, &effects.weighted_tags))
+        .collect();
+    let weights = Generator::fit_weights(weights);
+
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return chance;
+    }
+
+    let mut missed = 0.0;
+    for (affix, weight) in candidates.into_iter().zip(weights) {
+        let affix_chance = chance * weight as f64 / total as f64;
+        if !add_tier_odds(
+            affix,
+            None,
+            item.requirements.level,
+            effects.tier_bias,
+            affix_chance,
+            table,
         ) {
-            item.suffixes.push(modifier);
+            missed += affix_chance;
         }
     }
+    missed
+}
 
-    Ok(())
+/// Odds for `add_specific_affix_from_set`
+fn specific_affix_odds(
+    generator: &Generator,
+    item: &Item,
+    candidates: &[SpecificAffix],
+) -> Vec<AffixOdds> {
+    let existing: Vec<&str> = item
+        .prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .map(|m| m.affix_id.as_str())
+        .collect();
+    let valid: Vec<(&SpecificAffix, &AffixConfig)> = candidates
+        .iter()
+        .filter_map(|c| {
+            let affix = generator.config().affixes.get(&c.id)?;
+            let allowed =
+                affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&item.class);
+            let has_slot = match affix.affix_type {
+                AffixType::Prefix => item.can_add_prefix(),
+                AffixType::Suffix => item.can_add_suffix(),
+            };
+            (!existing.contains(&c.id.as_str()) && allowed && has_slot).then_some((c, affix))
+        })
+        .collect();
+
+    let mut table = OddsTable::default();
+    let total: u32 = valid.iter().map(|(c, _)| c.weight).sum();
+    if total == 0 || valid.len() == 1 {
+        // The first valid candidate is taken without a roll
+        if let Some((c, affix)) = valid.first() {
+            add_tier_odds(affix, c.tier, item.requirements.level, 0.0, 1.0, &mut table);
+        }
+    } else {
+        for (c, affix) in valid {
+            let chance = c.weight as f64 / total as f64;
+            add_tier_odds(
+                affix,
+                c.tier,
+                item.requirements.level,
+                0.0,
+                chance,
+                &mut table,
+            );
+        }
+    }
+    table.into_odds()
+}
+
+/// Split an affix's chance across the tiers it can roll at, or all of it onto a fixed
+/// tier. Returns false if no tier can roll.
+fn add_tier_odds(
+    affix: &AffixConfig,
+    fixed_tier: Option<u32>,
+    item_level: u32,
+    tier_bias: f32,
+    chance: f64,
+    table: &mut OddsTable,
+) -> bool {
+    if chance <= 0.0 {
+        return true;
+    }
+    if let Some(tier) = fixed_tier {
+        if !affix.tiers.iter().any(|t| t.tier == tier) {
+            return false;
+        }
+        table.add(&affix.id, tier, chance);
+        return true;
+    }
+
+    let eligible: Vec<_> = affix
+        .tiers
+        .iter()
+        .filter(|t| t.min_ilvl <= item_level && t.weight > 0)
+        .collect();
+    let weights = Generator::biased_tier_weights(&eligible, tier_bias);
+    let total: f64 = weights.iter().map(|&w| w as f64).sum();
+    if total == 0.0 {
+        return false;
+    }
+    for (tier, weight) in eligible.iter().zip(weights) {
+        table.add(&affix.id, tier.tier, chance * weight as f64 / total);
+    }
+    true
 }
 
 /// Try to transform item into a unique based on recipes
@@ -574,19 +1228,22 @@ fn try_unique_transformation(
     item: &mut Item,
     rng: &mut ChaCha8Rng,
 ) -> Result<(), CurrencyError> {
-    // Find all matching recipes
-    let matching_recipes: Vec<_> = generator
+    // Find all matching recipes. A zero weight disables a recipe.
+    let mut matching_recipes: Vec<_> = generator
         .config()
         .unique_recipes
         .iter()
-        .filter(|recipe| recipe_matches(recipe, item))
+        .filter(|recipe| recipe.weight > 0 && recipe_matches(recipe, item))
         .collect();
 
     if matching_recipes.is_empty() {
         return Err(CurrencyError::NoMatchingRecipe);
     }
 
-    // Weighted random selection
+    // Weighted random selection: each recipe wins with weight / total weight. The roll is
+    // laid out over the recipes in unique id order, so a seed picks the same unique no
+    // matter what order the recipe files were read in, and equal weights stay equally likely.
+    matching_recipes.sort_by(|a, b| a.unique_id.cmp(&b.unique_id));
     let total_weight: u32 = matching_recipes.iter().map(|r| r.weight).sum();
     let mut roll = rng.gen_range(0..total_weight);
     let mut selected_recipe = None;
@@ -617,6 +1274,7 @@ fn try_unique_transformation(
     // Transform the item into the unique
     item.rarity = Rarity::Unique;
     item.name = unique.name.clone();
+    item.requirements.level = item.requirements.level.max(unique.required_level);
     item.prefixes.clear();
     item.suffixes.clear();
 
@@ -691,12 +1349,14 @@ fn try_unique_transformation(
             name: unique.name.clone(),
             stat: mod_cfg.stat,
             scope: AffixScope::Global,
+            tags: Vec::new(),
             tier: 0,
             value,
             value_max: None,
             tier_min: mod_cfg.min,
             tier_max: mod_cfg.max,
             tier_max_value: None,
+            extra_stats: Vec::new(),
         };
         item.prefixes.push(modifier);
     }
@@ -722,7 +1382,7 @@ fn recipe_matches(recipe: &UniqueRecipeConfig, item: &Item) -> bool {
 }
 
 /// Check if a single affix requirement is met by the item
-fn affix_requirement_met(req: &RecipeAffixRequirement, item: &Item) -> bool {
+pub(crate) fn affix_requirement_met(req: &RecipeAffixRequirement, item: &Item) -> bool {
     let prefixes_match = item.prefixes.iter().any(|m| {
         m.stat == req.stat
             && m.tier >= req.min_tier
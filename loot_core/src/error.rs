@@ -0,0 +1,104 @@
+use crate::config::ConfigError;
+use crate::currency::CurrencyError;
+use crate::storage::DecodeError;
+
+/// Any error the crate returns, for applications that want a single error type.
+///
+/// Each variant wraps the specific error unchanged, so callers can still match on it.
+/// Display and `source` pass straight through to the wrapped error.
+#[derive(Debug)]
+pub enum LootError {
+    Config(ConfigError),
+    Currency(CurrencyError),
+    Decode(DecodeError),
+}
+
+impl std::fmt::Display for LootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LootError::Config(e) => e.fmt(f),
+            LootError::Currency(e) => e.fmt(f),
+            LootError::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LootError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LootError::Config(e) => e.source(),
+            LootError::Currency(e) => e.source(),
+            LootError::Decode(e) => e.source(),
+        }
+    }
+}
+
+impl From<ConfigError> for LootError {
+    fn from(e: ConfigError) -> Self {
+        LootError::Config(e)
+    }
+}
+
+impl From<CurrencyError> for LootError {
+    fn from(e: CurrencyError) -> Self {
+        LootError::Currency(e)
+    }
+}
+
+impl From<DecodeError> for LootError {
+    fn from(e: DecodeError) -> Self {
+        LootError::Decode(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BinaryDecode, Config, Generator, ItemCollection};
+    use std::error::Error;
+    use std::path::Path;
+
+    #[test]
+    fn test_conversions_keep_the_message() {
+        let config = Config::load_bundle(Path::new("no_such_bundle.toml")).unwrap_err();
+        let message = config.to_string();
+        let error = LootError::from(config);
+        assert!(matches!(error, LootError::Config(ConfigError::Io { .. })));
+        assert_eq!(error.to_string(), message);
+        assert!(error.source().is_some());
+
+        let currency = CurrencyError::AffixNotFound("no_such_affix".to_string());
+        let message = currency.to_string();
+        let error: LootError = currency.into();
+        assert!(matches!(error, LootError::Currency(_)));
+        assert_eq!(error.to_string(), message);
+
+        let error: LootError = DecodeError::InvalidVersion(99).into();
+        assert_eq!(error.to_string(), "Invalid version: 99");
+    }
+
+    #[test]
+    fn test_question_mark_across_operations() {
+        fn load_and_craft(currency_id: Option<&str>, saved: &[u8]) -> Result<usize, LootError> {
+            let config = Config::load_from_dir(Path::new("../config"))?;
+            let generator = Generator::new(config);
+            if let Some(currency_id) = currency_id {
+                let item = generator
+                    .generate("iron_sword", 1)
+                    .expect("known base type");
+                generator.apply_currency(&item, currency_id)?;
+            }
+            Ok(ItemCollection::decode_from_slice(saved, &generator)?
+                .items
+                .len())
+        }
+
+        let error = load_and_craft(Some("no_such_currency"), &[]).unwrap_err();
+        assert!(matches!(
+            error,
+            LootError::Currency(CurrencyError::UnknownCurrency(_))
+        ));
+        let error = load_and_craft(None, b"not an item collection").unwrap_err();
+        assert!(matches!(error, LootError::Decode(_)));
+    }
+}
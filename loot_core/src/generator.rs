@@ -1,6 +1,12 @@
-use crate::config::{AffixConfig, BaseTypeConfig, Config, CurrencyConfig, UniqueConfig};
-use crate::currency::{apply_currency, CurrencyError};
-use crate::item::{Item, Modifier};
+use crate::config::{
+    AffixConfig, AffixTierConfig, BaseTypeConfig, Config, CurrencyConfig, CurrencyEffects,
+    RecipeAffixRequirement, UniqueConfig,
+};
+use crate::currency::{
+    annul_weights, apply_add_affix, apply_currency, check_affix_addable, check_requirements,
+    outcome_odds, AffixOdds, CurrencyError, OutcomeOdds,
+};
+use crate::item::{Item, Modifier, StatRoll};
 use crate::storage::Operation;
 use crate::types::*;
 use rand::prelude::*;
@@ -11,6 +17,67 @@ pub struct Generator {
     config: Config,
 }
 
+/// A non-fatal problem met while rolling affixes. The roll carries on with the slot left
+/// empty; these explain why an expected affix didn't appear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationWarning {
+    /// The picked affix has no weighted tier at or below the item level, so it was skipped
+    NoEligibleTier { affix_id: String, item_level: u32 },
+    /// No affix of this type can roll on the item (none allowed, or all weights are zero)
+    NoAffixAvailable {
+        affix_type: AffixType,
+        class: ItemClass,
+    },
+    /// No affix at all allows the item's class, so it stays bare at any rarity. Reported
+    /// once in place of a `NoAffixAvailable` for every roll.
+    NoAffixesForClass { class: ItemClass },
+}
+
+impl std::fmt::Display for GenerationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationWarning::NoEligibleTier {
+                affix_id,
+                item_level,
+            } => write!(
+                f,
+                "affix {} had no eligible tier at ilvl {} and was skipped",
+                affix_id, item_level
+            ),
+            GenerationWarning::NoAffixAvailable { affix_type, class } => {
+                write!(f, "no {:?} can roll on {:?} items", affix_type, class)
+            }
+            GenerationWarning::NoAffixesForClass { class } => {
+                write!(f, "no affixes available for {:?} items", class)
+            }
+        }
+    }
+}
+
+/// How a random affix roll is narrowed and skewed, usually taken from a currency's
+/// effects. The default draws from every affix at the configured weights.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AffixRoll<'a> {
+    /// Only draw from these affix pools (every affix if empty)
+    pub pools: &'a [String],
+    /// Multiply the weight of affixes carrying these tags
+    pub tag_weights: &'a [(Tag, f32)],
+    /// Skew the tier toward better (positive) or worse (negative) ones; see
+    /// `Generator::biased_tier_weights`
+    pub tier_bias: f32,
+}
+
+impl<'a> AffixRoll<'a> {
+    /// The roll a currency's random affix effects ask for
+    pub fn from_effects(effects: &'a CurrencyEffects) -> Self {
+        AffixRoll {
+            pools: &effects.affix_pools,
+            tag_weights: &effects.weighted_tags,
+            tier_bias: effects.tier_bias,
+        }
+    }
+}
+
 impl Generator {
     pub fn new(config: Config) -> Self {
         Generator { config }
@@ -31,22 +98,8 @@ impl Generator {
         let mut rng = Self::make_rng(seed);
         let mut item = Item::new_normal(base, seed);
 
-        // Roll implicit if present
-        if let Some(ref implicit_cfg) = base.implicit {
-            let value = rng.gen_range(implicit_cfg.min..=implicit_cfg.max);
-            item.implicit = Some(Modifier {
-                affix_id: "implicit".to_string(),
-                name: "Implicit".to_string(),
-                stat: implicit_cfg.stat,
-                scope: AffixScope::Local,
-                tier: 0,
-                value,
-                value_max: None,
-                tier_min: implicit_cfg.min,
-                tier_max: implicit_cfg.max,
-                tier_max_value: None,
-            });
-        }
+        // Roll implicits if present
+        item.implicits = Self::roll_implicits(base, &mut rng);
 
         // Roll base defenses
         if let Some(ref def_cfg) = base.defenses {
@@ -64,59 +117,290 @@ impl Generator {
         Some(item)
     }
 
+    /// Generate an item directly at a rarity, rolling its affixes from the base's seed.
+    ///
+    /// Magic and rare items get an affix count within the configured range, and the
+    /// upgrade is recorded as an `Operation::Rarity` so the item replays. Uniques are
+    /// not supported here (use `generate_unique`).
+    pub fn generate_at_rarity(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        rarity: Rarity,
+    ) -> Option<Item> {
+        self.generate_at_rarity_inner(base_type_id, seed, rarity, None)
+    }
+
+    /// `generate_at_rarity`, also collecting a warning for each affix roll that came up
+    /// empty. Rolls are identical to `generate_at_rarity` for the same arguments.
+    pub fn generate_at_rarity_with_warnings(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        rarity: Rarity,
+        warnings: &mut Vec<GenerationWarning>,
+    ) -> Option<Item> {
+        self.generate_at_rarity_inner(base_type_id, seed, rarity, Some(warnings))
+    }
+
+    fn generate_at_rarity_inner(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        rarity: Rarity,
+        warnings: Option<&mut Vec<GenerationWarning>>,
+    ) -> Option<Item> {
+        if rarity == Rarity::Unique {
+            return None;
+        }
+        let mut item = self.generate(base_type_id, seed)?;
+        if rarity == Rarity::Normal {
+            return Some(item);
+        }
+
+        let mut rng = self.replay_rng(&item);
+        self.roll_at_rarity(&mut item, rarity, &mut rng, warnings);
+        item.record_rarity(rarity);
+
+        Some(item)
+    }
+
+    /// Upgrade a freshly generated item to a rarity and roll its affixes, as recorded by
+    /// `Operation::Rarity`. Normal and unique leave the item unchanged.
+    fn roll_at_rarity(
+        &self,
+        item: &mut Item,
+        rarity: Rarity,
+        rng: &mut ChaCha8Rng,
+        warnings: Option<&mut Vec<GenerationWarning>>,
+    ) {
+        match rarity {
+            Rarity::Magic => self.make_magic_inner(item, rng, warnings),
+            Rarity::Rare => self.make_rare_inner(item, rng, warnings),
+            Rarity::Normal | Rarity::Unique => {}
+        }
+    }
+
+    /// Generate a normal item from a random base type in a group.
+    ///
+    /// The base is picked deterministically from the seed, and the item itself is
+    /// generated from that base with the same seed so it can be reconstructed by id.
+    pub fn generate_from_group(&self, group: &str, seed: u64) -> Option<Item> {
+        let bases = self.base_types_in_group(group);
+        let mut rng = Self::make_rng(seed);
+        let base = bases.choose(&mut rng)?;
+        self.generate(&base.id, seed)
+    }
+
+    /// Generate `n` items from a seed sequence and return the highest-scoring one with
+    /// its seed.
+    ///
+    /// Candidate seeds are drawn from an RNG seeded with `seed`, so the same arguments
+    /// always pick the same item. Ties go to the earliest candidate. Returns `None` if
+    /// the base type is unknown or `n` is zero.
+    pub fn best_of(
+        &self,
+        base_type_id: &str,
+        n: usize,
+        score: impl Fn(&Item) -> f64,
+        seed: u64,
+    ) -> Option<(u64, Item)> {
+        let mut rng = Self::make_rng(seed);
+        let mut best: Option<(f64, u64, Item)> = None;
+        for _ in 0..n {
+            let candidate_seed = rng.gen::<u64>();
+            let item = self.generate(base_type_id, candidate_seed)?;
+            let candidate_score = score(&item);
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _, _)| candidate_score > *best_score)
+            {
+                best = Some((candidate_score, candidate_seed, item));
+            }
+        }
+        best.map(|(_, seed, item)| (seed, item))
+    }
+
     /// Apply a currency to an item by currency ID.
     ///
     /// Returns a new item with the currency applied. The original item is not modified.
     /// Uses the item's seed and operation history to maintain deterministic RNG state.
     pub fn apply_currency(&self, item: &Item, currency_id: &str) -> Result<Item, CurrencyError> {
+        self.apply_currency_locked(item, currency_id, &[])
+    }
+
+    /// Apply a currency while keeping the modifiers with the given affix ids untouched.
+    ///
+    /// Locked modifiers survive clearing, removal and rerolls. Locks are recorded with
+    /// the operation so the result reproduces; with no locks this is `apply_currency`.
+    pub fn apply_currency_locked(
+        &self,
+        item: &Item,
+        currency_id: &str,
+        locked: &[String],
+    ) -> Result<Item, CurrencyError> {
         let currency = self
             .config
             .currencies
             .get(currency_id)
             .ok_or_else(|| CurrencyError::UnknownCurrency(currency_id.to_string()))?;
+        if item.mirrored {
+            return Err(CurrencyError::Mirrored);
+        }
 
         // Clone the item and replay to get correct RNG state
         let mut new_item = item.clone();
         let mut rng = self.replay_rng(&new_item);
 
         // Apply the currency
-        apply_currency(self, &mut new_item, currency, &mut rng)?;
+        apply_currency(self, &mut new_item, currency, locked, &mut rng)?;
 
         // Record operation
-        new_item.record_currency(currency_id);
+        if locked.is_empty() {
+            new_item.record_currency(currency_id);
+        } else {
+            new_item.record_locked_currency(currency_id, locked.to_vec());
+        }
 
         Ok(new_item)
     }
 
-    /// Check if a currency can be applied to an item
-    pub fn can_apply_currency(&self, item: &Item, currency_id: &str) -> bool {
-        let Some(currency) = self.config.currencies.get(currency_id) else {
-            return false;
-        };
+    /// Add a specific affix at a specific tier to an item.
+    ///
+    /// Returns a new item with the affix added. The value is rolled from the item's
+    /// replayed RNG and the addition is recorded, so the result is reproducible.
+    pub fn add_affix(&self, item: &Item, affix_id: &str, tier: u32) -> Result<Item, CurrencyError> {
+        if item.mirrored {
+            return Err(CurrencyError::Mirrored);
+        }
+        let mut new_item = item.clone();
+        let mut rng = self.replay_rng(&new_item);
+
+        apply_add_affix(self, &mut new_item, affix_id, tier, &mut rng)?;
+
+        new_item.record_add_affix(affix_id, tier);
 
-        let reqs = &currency.requires;
+        Ok(new_item)
+    }
 
-        // Check rarity requirement
-        if !reqs.rarities.is_empty() && !reqs.rarities.contains(&item.rarity) {
-            return false;
+    /// Duplicate an item with identical rolls, as a separate mirrored copy.
+    ///
+    /// The copy records a `Mirror` operation so it reconstructs as mirrored, and rejects
+    /// further currencies and affix additions. The original is left modifiable.
+    pub fn mirror(&self, item: &Item) -> Result<Item, CurrencyError> {
+        if item.mirrored {
+            return Err(CurrencyError::Mirrored);
         }
+        let mut copy = item.clone();
+        copy.mirrored = true;
+        copy.record_mirror();
+        Ok(copy)
+    }
 
-        // Check has_affix requirement
-        if reqs.has_affix && item.prefixes.is_empty() && item.suffixes.is_empty() {
-            return false;
+    /// Odds of each outcome of a currency on an item, without applying it.
+    ///
+    /// Fails with the same error `apply_currency` would for unmet requirements.
+    pub fn currency_outcome_odds(
+        &self,
+        item: &Item,
+        currency_id: &str,
+    ) -> Result<OutcomeOdds, CurrencyError> {
+        let currency = self
+            .config
+            .currencies
+            .get(currency_id)
+            .ok_or_else(|| CurrencyError::UnknownCurrency(currency_id.to_string()))?;
+        if item.mirrored {
+            return Err(CurrencyError::Mirrored);
         }
+        outcome_odds(self, item, currency)
+    }
 
-        // Check has_affix_slot requirement
-        if reqs.has_affix_slot && !item.can_add_prefix() && !item.can_add_suffix() {
-            return false;
+    /// The currency most likely to add an affix the item's target still needs, and that
+    /// chance. Looks one application ahead from `currency_outcome_odds`, ignoring what the
+    /// currency might remove; ties go to the lower currency id. None when the target is met
+    /// or nothing applicable can help.
+    pub fn suggest_target_currency(&self, item: &Item) -> Option<(String, f64)> {
+        let unmet: Vec<&RecipeAffixRequirement> = item
+            .target_progress()
+            .into_iter()
+            .filter(|(_, met)| !met)
+            .map(|(req, _)| req)
+            .collect();
+        if unmet.is_empty() {
+            return None;
         }
 
-        true
+        let mut best: Option<(String, f64)> = None;
+        for currency_id in self.config.currencies_sorted().map(|c| &c.id) {
+            let Ok(odds) = self.currency_outcome_odds(item, currency_id) else {
+                continue;
+            };
+            let chance: f64 = odds
+                .added
+                .iter()
+                .chain(odds.specific.iter())
+                .map(|affix_odds| self.target_chance(affix_odds, &unmet))
+                .sum();
+            let chance = chance.min(1.0);
+            if chance > 0.0 && best.as_ref().is_none_or(|(_, b)| chance > *b) {
+                best = Some((currency_id.clone(), chance));
+            }
+        }
+        best
     }
 
-    /// Reconstruct an item from its base type, seed, and operations
-    pub fn reconstruct(&self, base_type_id: &str, seed: u64, operations: &[Operation]) -> Option<Item> {
-        let mut item = self.generate(base_type_id, seed)?;
+    /// Share of an affix's odds landing on a tier that satisfies one of `unmet`
+    fn target_chance(&self, odds: &AffixOdds, unmet: &[&RecipeAffixRequirement]) -> f64 {
+        let Some(affix) = self.config.affixes.get(&odds.affix_id) else {
+            return 0.0;
+        };
+        odds.tiers
+            .iter()
+            .filter(|(tier, _)| {
+                unmet.iter().any(|req| {
+                    req.stat == affix.stat
+                        && (req.min_tier..=req.max_tier).contains(tier)
+                        && req.affix_type.is_none_or(|t| t == affix.affix_type)
+                })
+            })
+            .map(|(_, chance)| chance)
+            .sum()
+    }
+
+    /// Run every check `apply_currency` makes before changing anything: the currency's
+    /// requirements and whether its effects have something to work with on this item.
+    ///
+    /// An `Ok` can still fail at apply time only when `try_unique` finds no recipe.
+    pub fn check_currency(&self, item: &Item, currency_id: &str) -> Result<(), CurrencyError> {
+        let currency = self
+            .config
+            .currencies
+            .get(currency_id)
+            .ok_or_else(|| CurrencyError::UnknownCurrency(currency_id.to_string()))?;
+        if item.mirrored {
+            return Err(CurrencyError::Mirrored);
+        }
+        check_requirements(self, item, currency)
+    }
+
+    /// Check if a currency can be applied to an item (`check_currency` without the reason)
+    pub fn can_apply_currency(&self, item: &Item, currency_id: &str) -> bool {
+        self.check_currency(item, currency_id).is_ok()
+    }
+
+    /// Reconstruct an item from its base type, seed, and operations. A leading
+    /// `Operation::Unique` starts from that unique instead of a normal item.
+    pub fn reconstruct(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        operations: &[Operation],
+    ) -> Option<Item> {
+        let mut item = match operations.first() {
+            Some(Operation::Unique(unique_id)) => self.generate_unique(unique_id, seed)?,
+            _ => self.generate(base_type_id, seed)?,
+        };
 
         // Replay operations (but don't record them again)
         let mut rng = self.replay_rng(&item);
@@ -125,18 +409,84 @@ impl Generator {
             match op {
                 Operation::Currency(currency_id) => {
                     if let Some(currency) = self.config.currencies.get(currency_id) {
-                        let _ = apply_currency(self, &mut item, currency, &mut rng);
+                        let _ = apply_currency(self, &mut item, currency, &[], &mut rng);
+                    }
+                }
+                Operation::LockedCurrency {
+                    currency_id,
+                    locked,
+                } => {
+                    if let Some(currency) = self.config.currencies.get(currency_id) {
+                        let _ = apply_currency(self, &mut item, currency, locked, &mut rng);
                     }
                 }
+                Operation::AddAffix { affix_id, tier } => {
+                    let _ = apply_add_affix(self, &mut item, affix_id, *tier, &mut rng);
+                }
+                Operation::Mirror => item.mirrored = true,
+                Operation::Rarity(rarity) => {
+                    self.roll_at_rarity(&mut item, *rarity, &mut rng, None);
+                }
+                // Already applied as the starting item
+                Operation::Unique(_) => {}
             }
         }
 
-        // Set the operations on the reconstructed item
+        // Set the operations on the reconstructed item (timestamps are restored by the caller)
         item.operations = operations.to_vec();
+        item.operation_times = vec![None; operations.len()];
 
         Some(item)
     }
 
+    /// Roll a base type's implicit modifiers in order. When the base has weighted implicit
+    /// options, one is picked first and rolled in its place among the others.
+    fn roll_implicits(base: &BaseTypeConfig, rng: &mut ChaCha8Rng) -> Vec<Modifier> {
+        let option = Self::pick_implicit_option(base, rng);
+        base.implicits
+            .iter()
+            .enumerate()
+            .filter(|&(i, implicit_cfg)| implicit_cfg.weight.is_none() || option == Some(i))
+            .map(|(_, implicit_cfg)| Modifier {
+                affix_id: "implicit".to_string(),
+                name: "Implicit".to_string(),
+                stat: implicit_cfg.stat,
+                scope: AffixScope::Local,
+                tags: Vec::new(),
+                tier: 0,
+                value: rng.gen_range(implicit_cfg.min..=implicit_cfg.max),
+                value_max: None,
+                tier_min: implicit_cfg.min,
+                tier_max: implicit_cfg.max,
+                tier_max_value: None,
+                extra_stats: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Index of the weighted implicit option an item rolls, if the base has any. Bases
+    /// without options draw nothing from the RNG.
+    fn pick_implicit_option(base: &BaseTypeConfig, rng: &mut ChaCha8Rng) -> Option<usize> {
+        let options: Vec<(usize, u32)> = base
+            .implicits
+            .iter()
+            .enumerate()
+            .filter_map(|(i, implicit_cfg)| Some((i, implicit_cfg.weight?)))
+            .collect();
+        let total: u32 = options.iter().map(|&(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.gen_range(0..total);
+        for &(i, weight) in &options {
+            if roll < weight {
+                return Some(i);
+            }
+            roll -= weight;
+        }
+        None
+    }
+
     /// Replay the RNG to the current state based on item's seed and operations
     fn replay_rng(&self, item: &Item) -> ChaCha8Rng {
         let base = self.config.base_types.get(&item.base_type_id);
@@ -144,7 +494,11 @@ impl Generator {
 
         // Advance RNG past initial item generation
         if let Some(base) = base {
-            if base.implicit.is_some() {
+            if base.implicits.iter().any(|i| i.weight.is_some()) {
+                let _ = rng.gen::<u32>(); // implicit option
+                let _ = rng.gen::<u32>(); // its value
+            }
+            for _ in base.implicits.iter().filter(|i| i.weight.is_none()) {
                 let _ = rng.gen::<u32>(); // implicit value
             }
             if let Some(ref def) = base.defenses {
@@ -166,21 +520,7 @@ impl Generator {
             let mut replay_item = Item::new_normal(base, item.seed);
 
             // Re-roll initial values
-            if let Some(ref implicit_cfg) = base.implicit {
-                let value = rng.gen_range(implicit_cfg.min..=implicit_cfg.max);
-                replay_item.implicit = Some(Modifier {
-                    affix_id: "implicit".to_string(),
-                    name: "Implicit".to_string(),
-                    stat: implicit_cfg.stat,
-                    scope: AffixScope::Local,
-                    tier: 0,
-                    value,
-                    value_max: None,
-                    tier_min: implicit_cfg.min,
-                    tier_max: implicit_cfg.max,
-                    tier_max_value: None,
-                });
-            }
+            replay_item.implicits = Self::roll_implicits(base, &mut rng);
             if let Some(ref def_cfg) = base.defenses {
                 if let Some(range) = def_cfg.armour {
                     replay_item.defenses.armour = Some(rng.gen_range(range.min..=range.max));
@@ -193,13 +533,37 @@ impl Generator {
                 }
             }
 
+            // Uniques roll their mods from a separate RNG, so only the starting item changes
+            if let Some(Operation::Unique(unique_id)) = item.operations.first() {
+                if let Some(unique) = self.generate_unique(unique_id, item.seed) {
+                    replay_item = unique;
+                }
+            }
+
             for op in &item.operations {
                 match op {
                     Operation::Currency(currency_id) => {
                         if let Some(currency) = self.config.currencies.get(currency_id) {
-                            let _ = apply_currency(self, &mut replay_item, currency, &mut rng);
+                            let _ = apply_currency(self, &mut replay_item, currency, &[], &mut rng);
+                        }
+                    }
+                    Operation::LockedCurrency {
+                        currency_id,
+                        locked,
+                    } => {
+                        if let Some(currency) = self.config.currencies.get(currency_id) {
+                            let _ =
+                                apply_currency(self, &mut replay_item, currency, locked, &mut rng);
                         }
                     }
+                    Operation::AddAffix { affix_id, tier } => {
+                        let _ = apply_add_affix(self, &mut replay_item, affix_id, *tier, &mut rng);
+                    }
+                    Operation::Rarity(rarity) => {
+                        self.roll_at_rarity(&mut replay_item, *rarity, &mut rng, None);
+                    }
+                    // Mirroring consumes no randomness
+                    Operation::Mirror | Operation::Unique(_) => {}
                 }
             }
         }
@@ -207,11 +571,67 @@ impl Generator {
         rng
     }
 
-    /// Get affixes valid for an item class
-    pub fn get_valid_affixes(&self, class: ItemClass, affix_type: AffixType) -> Vec<&AffixConfig> {
+    /// Get every affix that `add_affix` would currently accept for an item, sorted by id.
+    ///
+    /// Applies the same checks as `add_affix`: rarity, class restrictions, duplicates and
+    /// free prefix/suffix slots. Tier item level is not checked since explicit tiers
+    /// may override it.
+    pub fn eligible_affixes(&self, item: &Item) -> Vec<&AffixConfig> {
+        self.config
+            .affixes_sorted()
+            .filter(|affix| check_affix_addable(item, affix).is_ok())
+            .collect()
+    }
+
+    /// Every affix tier that can roll randomly on a base type at an item level, in affix id
+    /// order with each affix's tiers in config order. Empty for an unknown base.
+    ///
+    /// Uses the same filters as random rolls: allowed classes, the base's tags and blocked
+    /// tags, and tier item level. Tiers with no weight are left out since they never roll.
+    pub fn possible_modifiers(
+        &self,
+        base_type_id: &str,
+        item_level: u32,
+    ) -> Vec<(&AffixConfig, &AffixTierConfig)> {
+        let Some(base) = self.config.base_types.get(base_type_id) else {
+            return Vec::new();
+        };
+        self.config
+            .affixes_sorted()
+            .filter(|affix| {
+                (affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&base.class))
+                    && Self::has_matching_tag(affix, &base.tags)
+                    && !Self::has_blocked_tag(affix, &base.blocked_tags)
+            })
+            .flat_map(|affix| {
+                affix
+                    .tiers
+                    .iter()
+                    .filter(move |tier| tier.weight > 0 && tier.min_ilvl <= item_level)
+                    .map(move |tier| (affix, tier))
+            })
+            .collect()
+    }
+
+    /// Weight of each explicit modifier (prefixes first, then suffixes) when a currency
+    /// removes a random affix. Divide by the sum for each modifier's removal chance.
+    pub fn annul_weights(&self, item: &Item) -> Vec<u32> {
+        annul_weights(self, item)
+    }
+
+    /// Whether any affix allows `class`, ignoring tags and affix type
+    pub fn has_affixes_for_class(&self, class: ItemClass) -> bool {
         self.config
             .affixes
             .values()
+            .any(|affix| affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&class))
+    }
+
+    /// Get affixes valid for an item class, in id order so seeded rolls don't depend on
+    /// map iteration order
+    pub fn get_valid_affixes(&self, class: ItemClass, affix_type: AffixType) -> Vec<&AffixConfig> {
+        self.config
+            .affixes_sorted()
             .filter(|affix| {
                 affix.affix_type == affix_type
                     && (affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&class))
@@ -237,8 +657,7 @@ impl Generator {
             .collect();
 
         self.config
-            .affixes
-            .values()
+            .affixes_sorted()
             .filter(|affix| {
                 affix.affix_type == affix_type
                     && (affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&class))
@@ -248,7 +667,7 @@ impl Generator {
     }
 
     /// Calculate spawn weight for an affix based on tag matching
-    fn calculate_weight(&self, affix: &AffixConfig, item_tags: &[Tag]) -> u32 {
+    pub(crate) fn calculate_weight(&self, affix: &AffixConfig, item_tags: &[Tag]) -> u32 {
         let base_weight: u32 = affix.tiers.iter().map(|t| t.weight).sum();
 
         let matching_tags = affix
@@ -261,63 +680,141 @@ impl Generator {
         (base_weight as f32 * multiplier) as u32
     }
 
-    /// Roll a random affix for an item
-    pub fn roll_affix(
+    /// Spawn weight scaled by a currency's `weighted_tags`; each listed tag the affix
+    /// carries multiplies the weight
+    pub(crate) fn tag_weighted(
         &self,
-        class: ItemClass,
+        affix: &AffixConfig,
         item_tags: &[Tag],
+        tag_weights: &[(Tag, f32)],
+    ) -> u32 {
+        let weight = self.calculate_weight(affix, item_tags);
+        if tag_weights.is_empty() {
+            return weight;
+        }
+        let multiplier: f32 = tag_weights
+            .iter()
+            .filter(|(tag, _)| affix.tags.contains(tag))
+            .map(|(_, multiplier)| multiplier)
+            .product();
+        (weight as f32 * multiplier) as u32
+    }
+
+    /// Roll a random affix of `affix_type` for an item.
+    ///
+    /// The item's class, tags and level decide what can roll, affixes already on it and
+    /// those with a tag its base blocks are skipped, and `options` narrow and skew the draw.
+    pub fn roll_affix(
+        &self,
+        item: &Item,
         affix_type: AffixType,
-        existing_affix_ids: &[String],
-        item_level: u32,
+        options: &AffixRoll,
         rng: &mut ChaCha8Rng,
     ) -> Option<Modifier> {
-        self.roll_affix_from_pools(
-            class,
-            item_tags,
-            affix_type,
-            existing_affix_ids,
-            &[],
-            item_level,
-            rng,
-        )
+        self.roll_affix_reporting(item, affix_type, options, rng, None)
     }
 
-    fn has_matching_tag(affix: &AffixConfig, item_tags: &[Tag]) -> bool {
+    pub(crate) fn has_matching_tag(affix: &AffixConfig, item_tags: &[Tag]) -> bool {
         if affix.tags.is_empty() {
             return true;
         }
         affix.tags.iter().any(|tag| item_tags.contains(tag))
     }
 
-    /// Roll a random affix for an item, filtered by affix pools
-    pub fn roll_affix_from_pools(
+    pub(crate) fn has_blocked_tag(affix: &AffixConfig, blocked_tags: &[Tag]) -> bool {
+        affix.tags.iter().any(|tag| blocked_tags.contains(tag))
+    }
+
+    /// Tags the item's base type blocks from random rolls (empty if the base is unknown)
+    pub fn blocked_tags(&self, item: &Item) -> &[Tag] {
+        self.config
+            .base_types
+            .get(&item.base_type_id)
+            .map_or(&[], |base| &base.blocked_tags)
+    }
+
+    /// Weights scaled down together if their total would pass `u32::MAX`, so callers can
+    /// always total them as a `u32`. Weights that already fit are returned unchanged.
+    pub(crate) fn fit_weights(weights: Vec<u32>) -> Vec<u32> {
+        let total: u64 = weights.iter().map(|&w| w as u64).sum();
+        if total <= u32::MAX as u64 {
+            return weights;
+        }
+        let scale = u32::MAX as f64 / total as f64;
+        weights
+            .into_iter()
+            .map(|w| (w as f64 * scale) as u32)
+            .collect()
+    }
+
+    /// Tier weights skewed toward better tiers (lower tier numbers) for a positive `bias`,
+    /// or worse tiers for a negative one.
+    ///
+    /// Each tier step away from the disfavored end doubles a tier's weight per 1.0 of bias.
+    /// A bias of 0 returns the plain weights. If the weights would sum past `u32::MAX`
+    /// they are scaled down together, so callers can always total them as a `u32`.
+    pub fn biased_tier_weights(tiers: &[&AffixTierConfig], bias: f32) -> Vec<u32> {
+        let worst = tiers.iter().map(|t| t.tier).max().unwrap_or(0);
+        let best = tiers.iter().map(|t| t.tier).min().unwrap_or(0);
+        let weights: Vec<f64> = tiers
+            .iter()
+            .map(|t| {
+                let steps = if bias > 0.0 {
+                    worst - t.tier
+                } else {
+                    t.tier - best
+                };
+                t.weight as f64 * 2f64.powf(bias.abs() as f64 * steps as f64)
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let scale = (u32::MAX as f64 / total).min(1.0);
+        weights.iter().map(|w| (w * scale) as u32).collect()
+    }
+
+    /// `roll_affix`, pushing a warning to `warnings` when the roll comes up empty
+    pub(crate) fn roll_affix_reporting(
         &self,
-        class: ItemClass,
-        item_tags: &[Tag],
+        item: &Item,
         affix_type: AffixType,
-        existing_affix_ids: &[String],
-        pools: &[String],
-        item_level: u32,
+        options: &AffixRoll,
         rng: &mut ChaCha8Rng,
+        warnings: Option<&mut Vec<GenerationWarning>>,
     ) -> Option<Modifier> {
+        let warn = |warning: GenerationWarning| {
+            if let Some(warnings) = warnings {
+                warnings.push(warning);
+            }
+        };
+
+        let class = item.class;
+        let item_level = item.requirements.level;
+        let blocked_tags = self.blocked_tags(item);
+        let on_item = |affix: &AffixConfig| {
+            item.prefixes
+                .iter()
+                .chain(item.suffixes.iter())
+                .any(|m| m.affix_id == affix.id)
+        };
+
         let valid_affixes: Vec<_> = self
-            .get_valid_affixes_from_pools(class, affix_type, pools)
+            .get_valid_affixes_from_pools(class, affix_type, options.pools)
             .into_iter()
-            .filter(|a| !existing_affix_ids.contains(&a.id))
-            .filter(|a| Self::has_matching_tag(a, item_tags))
+            .filter(|a| !on_item(a))
+            .filter(|a| Self::has_matching_tag(a, &item.tags))
+            .filter(|a| !Self::has_blocked_tag(a, blocked_tags))
             .collect();
 
-        if valid_affixes.is_empty() {
-            return None;
-        }
-
         let weights: Vec<u32> = valid_affixes
             .iter()
-            .map(|a| self.calculate_weight(a, item_tags))
+            .map(|a| self.tag_weighted(a, &item.tags, options.tag_weights))
             .collect();
+        // Large `weighted_tags` multipliers could otherwise overflow the total
+        let weights = Self::fit_weights(weights);
 
         let total_weight: u32 = weights.iter().sum();
         if total_weight == 0 {
+            warn(GenerationWarning::NoAffixAvailable { affix_type, class });
             return None;
         }
 
@@ -339,95 +836,161 @@ impl Generator {
             .filter(|t| t.min_ilvl <= item_level)
             .collect();
 
-        if eligible_tiers.is_empty() {
-            return None;
-        }
-
-        let tier_total: u32 = eligible_tiers.iter().map(|t| t.weight).sum();
+        // Can't overflow: biased weights are scaled to keep their total within u32
+        let tier_weights = Self::biased_tier_weights(&eligible_tiers, options.tier_bias);
+        let tier_total: u32 = tier_weights.iter().sum();
         if tier_total == 0 {
+            warn(GenerationWarning::NoEligibleTier {
+                affix_id: affix.id.clone(),
+                item_level,
+            });
             return None;
         }
 
         let mut tier_roll = rng.gen_range(0..tier_total);
         let mut selected_tier = None;
-        for tier in &eligible_tiers {
-            if tier_roll < tier.weight {
+        for (tier, &weight) in eligible_tiers.iter().zip(tier_weights.iter()) {
+            if tier_roll < weight {
                 selected_tier = Some(*tier);
                 break;
             }
-            tier_roll -= tier.weight;
+            tier_roll -= weight;
         }
 
         let tier = selected_tier?;
-        let value = rng.gen_range(tier.min..=tier.max);
+        let (value, value_max) = self.roll_tier_values(tier, rng);
+        let extra_stats = self.roll_extra_stats(tier, rng);
+
+        Some(Modifier::from_affix(
+            affix,
+            tier,
+            value,
+            value_max,
+            extra_stats,
+        ))
+    }
+
+    /// Roll a tier's values: the low range, then the high range for dual-range stats.
+    ///
+    /// Each bound rolls independently from its own range; the order is fixed so
+    /// replays consume the RNG identically. With a `step`, each value then snaps to the
+    /// nearest multiple of it inside its range.
+    pub fn roll_tier_values(
+        &self,
+        tier: &AffixTierConfig,
+        rng: &mut ChaCha8Rng,
+    ) -> (i32, Option<i32>) {
+        let low = tier.low_range();
+        let value = low.snap(self.roll_value(low.min, low.max, rng), tier.step);
         let value_max = tier
-            .max_value
-            .map(|range| rng.gen_range(range.min..=range.max));
+            .high_range()
+            .map(|range| range.snap(self.roll_value(range.min, range.max, rng), tier.step));
+        (value, value_max)
+    }
+
+    /// Roll a hybrid tier's extra stats, in listed order, after its main values.
+    /// Single-stat tiers have none, so this consumes no randomness for them.
+    pub fn roll_extra_stats(&self, tier: &AffixTierConfig, rng: &mut ChaCha8Rng) -> Vec<StatRoll> {
+        tier.extra_stats
+            .iter()
+            .map(|extra| {
+                let range = extra.range();
+                StatRoll {
+                    stat: extra.stat,
+                    value: range.snap(self.roll_value(range.min, range.max, rng), tier.step),
+                    tier_min: extra.min,
+                    tier_max: extra.max,
+                }
+            })
+            .collect()
+    }
 
-        Some(Modifier::from_affix(affix, tier, value, value_max))
+    /// Roll an affix value in `min..=max` using the configured value distribution
+    pub fn roll_value(&self, min: i32, max: i32, rng: &mut ChaCha8Rng) -> i32 {
+        // ExpLow falls off to 1/e^3 (about 5%) of its peak at the top of the range
+        const EXP_LOW_RATE: f64 = 3.0;
+
+        // Misconfigured ranges are reported by Config::validate; don't panic on them here
+        if min > max {
+            return min;
+        }
+
+        match self.config.generation.value_distribution {
+            ValueDistribution::Uniform => rng.gen_range(min..=max),
+            ValueDistribution::Triangular => {
+                // The lower of two uniform rolls
+                let a = rng.gen_range(min..=max);
+                let b = rng.gen_range(min..=max);
+                a.min(b)
+            }
+            ValueDistribution::ExpLow => {
+                // Inverse CDF of an exponential truncated to [0, 1)
+                let u: f64 = rng.gen();
+                let x = -(1.0 - u * (1.0 - (-EXP_LOW_RATE).exp())).ln() / EXP_LOW_RATE;
+                let span = (max - min) as f64 + 1.0;
+                (min + (x * span) as i32).min(max)
+            }
+        }
     }
 
-    /// Add affixes to make an item magic (1-2 affixes)
+    /// Add affixes to make an item magic (count from `generation.magic_affix_count`)
     pub fn make_magic(&self, item: &mut Item, rng: &mut ChaCha8Rng) {
+        self.make_magic_inner(item, rng, None);
+    }
+
+    fn make_magic_inner(
+        &self,
+        item: &mut Item,
+        rng: &mut ChaCha8Rng,
+        warnings: Option<&mut Vec<GenerationWarning>>,
+    ) {
         item.rarity = Rarity::Magic;
         item.prefixes.clear();
         item.suffixes.clear();
 
-        let affix_count = rng.gen_range(1..=2);
-
-        for _ in 0..affix_count {
-            let existing: Vec<String> = item
-                .prefixes
-                .iter()
-                .chain(item.suffixes.iter())
-                .map(|m| m.affix_id.clone())
-                .collect();
+        let count = &self.config.generation.magic_affix_count;
+        let affix_count = rng.gen_range(count.min..=count.max.max(count.min));
+        self.roll_random_affixes(item, affix_count, rng, warnings);
+        item.name = item.magic_name();
+    }
 
-            let can_prefix = item.can_add_prefix();
-            let can_suffix = item.can_add_suffix();
+    /// Add affixes to make an item rare (count from `generation.rare_affix_count`)
+    pub fn make_rare(&self, item: &mut Item, rng: &mut ChaCha8Rng) {
+        self.make_rare_inner(item, rng, None);
+    }
 
-            let affix_type = match (can_prefix, can_suffix) {
-                (true, true) => {
-                    if rng.gen_bool(0.5) {
-                        AffixType::Prefix
-                    } else {
-                        AffixType::Suffix
-                    }
-                }
-                (true, false) => AffixType::Prefix,
-                (false, true) => AffixType::Suffix,
-                (false, false) => break,
-            };
-
-            let item_level = item.requirements.level;
-            if let Some(modifier) = self.roll_affix(
-                item.class, &item.tags, affix_type, &existing, item_level, rng,
-            ) {
-                match affix_type {
-                    AffixType::Prefix => item.prefixes.push(modifier),
-                    AffixType::Suffix => item.suffixes.push(modifier),
-                }
-            }
-        }
-    }
-
-    /// Add affixes to make an item rare (4-6 affixes)
-    pub fn make_rare(&self, item: &mut Item, rng: &mut ChaCha8Rng) {
+    fn make_rare_inner(
+        &self,
+        item: &mut Item,
+        rng: &mut ChaCha8Rng,
+        warnings: Option<&mut Vec<GenerationWarning>>,
+    ) {
         item.rarity = Rarity::Rare;
         item.prefixes.clear();
         item.suffixes.clear();
         item.name = self.generate_rare_name(rng);
 
-        let affix_count = rng.gen_range(4..=6);
+        let count = &self.config.generation.rare_affix_count;
+        let affix_count = rng.gen_range(count.min..=count.max.max(count.min));
+        self.roll_random_affixes(item, affix_count, rng, warnings);
+        self.theme_rare_name(item, rng);
+    }
 
-        for _ in 0..affix_count {
-            let existing: Vec<String> = item
-                .prefixes
-                .iter()
-                .chain(item.suffixes.iter())
-                .map(|m| m.affix_id.clone())
-                .collect();
+    /// Roll up to `count` affixes, picking prefix or suffix at random among open slots
+    fn roll_random_affixes(
+        &self,
+        item: &mut Item,
+        count: u32,
+        rng: &mut ChaCha8Rng,
+        mut warnings: Option<&mut Vec<GenerationWarning>>,
+    ) {
+        if count > 0 && !self.has_affixes_for_class(item.class) {
+            if let Some(warnings) = warnings.take() {
+                warnings.push(GenerationWarning::NoAffixesForClass { class: item.class });
+            }
+        }
 
+        for _ in 0..count {
             let can_prefix = item.can_add_prefix();
             let can_suffix = item.can_add_suffix();
 
@@ -444,9 +1007,12 @@ impl Generator {
                 (false, false) => break,
             };
 
-            let item_level = item.requirements.level;
-            if let Some(modifier) = self.roll_affix(
-                item.class, &item.tags, affix_type, &existing, item_level, rng,
+            if let Some(modifier) = self.roll_affix_reporting(
+                item,
+                affix_type,
+                &AffixRoll::default(),
+                rng,
+                warnings.as_deref_mut(),
             ) {
                 match affix_type {
                     AffixType::Prefix => item.prefixes.push(modifier),
@@ -456,22 +1022,69 @@ impl Generator {
         }
     }
 
-    /// Generate a random rare item name
+    /// Generate a random rare item name from the configured word pools, in the configured
+    /// `rare_name_structure`
     pub fn generate_rare_name(&self, rng: &mut ChaCha8Rng) -> String {
-        const PREFIXES: &[&str] = &[
-            "Doom", "Wrath", "Storm", "Dread", "Soul", "Death", "Blood", "Shadow", "Grim", "Hate",
-            "Plague", "Blight", "Rune", "Spirit", "Mind", "Skull", "Bone", "Venom", "Foe", "Pain",
-        ];
+        let generation = &self.config.generation;
+        let prefixes = name_pool(&generation.rare_name_prefixes, RARE_NAME_PREFIXES);
+        let suffixes = name_pool(&generation.rare_name_suffixes, RARE_NAME_SUFFIXES);
+        let prefix = prefixes[rng.gen_range(0..prefixes.len())];
+        let suffix = suffixes[rng.gen_range(0..suffixes.len())];
 
-        const SUFFIXES: &[&str] = &[
-            "Bane", "Edge", "Fang", "Bite", "Roar", "Song", "Call", "Cry", "Grasp", "Touch",
-            "Strike", "Blow", "Mark", "Brand", "Scar", "Ward", "Guard", "Veil", "Shroud", "Mantle",
-        ];
+        self.compose_rare_name(prefix, suffix, rng)
+    }
 
-        let prefix = PREFIXES[rng.gen_range(0..PREFIXES.len())];
-        let suffix = SUFFIXES[rng.gen_range(0..SUFFIXES.len())];
+    /// Put a rare name's parts together in the configured structure. Only the epithet
+    /// structure draws from the RNG, after both parts are picked.
+    fn compose_rare_name(&self, prefix: &str, suffix: &str, rng: &mut ChaCha8Rng) -> String {
+        match self.config.generation.rare_name_structure {
+            RareNameStructure::SingleWord => format!("{}{}", prefix, suffix.to_lowercase()),
+            RareNameStructure::PrefixSuffix => format!("{} {}", prefix, suffix),
+            RareNameStructure::PrefixSuffixEpithet => {
+                let epithets = name_pool(
+                    &self.config.generation.rare_name_epithets,
+                    RARE_NAME_EPITHETS,
+                );
+                let epithet = epithets[rng.gen_range(0..epithets.len())];
+                format!("{} {} of the {}", prefix, suffix, epithet)
+            }
+        }
+    }
+
+    /// Generate a rare name whose first part hints at the item's best prefix and whose
+    /// second part hints at its best suffix (lowest tier number wins). Sides without a
+    /// themed modifier fall back to the generic pools.
+    pub fn generate_themed_rare_name(&self, item: &Item, rng: &mut ChaCha8Rng) -> String {
+        let best_theme = |mods: &[Modifier]| {
+            mods.iter()
+                .min_by_key(|m| m.tier)
+                .and_then(|m| name_theme(m.stat))
+        };
 
-        format!("{} {}", prefix, suffix)
+        let generation = &self.config.generation;
+        let prefixes = best_theme(&item.prefixes).map_or_else(
+            || name_pool(&generation.rare_name_prefixes, RARE_NAME_PREFIXES),
+            |t| t.prefixes.to_vec(),
+        );
+        let suffixes = best_theme(&item.suffixes).map_or_else(
+            || name_pool(&generation.rare_name_suffixes, RARE_NAME_SUFFIXES),
+            |t| t.suffixes.to_vec(),
+        );
+
+        let prefix = prefixes[rng.gen_range(0..prefixes.len())];
+        let suffix = suffixes[rng.gen_range(0..suffixes.len())];
+
+        self.compose_rare_name(prefix, suffix, rng)
+    }
+
+    /// Rename a freshly named rare item after its affixes when `rare_name_mode` is
+    /// `AffixThemed`. Random mode leaves the name (and the RNG) untouched.
+    pub(crate) fn theme_rare_name(&self, item: &mut Item, rng: &mut ChaCha8Rng) {
+        if self.config.generation.rare_name_mode == RareNameMode::AffixThemed
+            && item.rarity == Rarity::Rare
+        {
+            item.name = self.generate_themed_rare_name(item, rng);
+        }
     }
 
     /// Get a base type by ID
@@ -479,9 +1092,48 @@ impl Generator {
         self.config.base_types.get(id)
     }
 
-    /// List all base type IDs
+    /// List all base type IDs, sorted
     pub fn base_type_ids(&self) -> Vec<&String> {
-        self.config.base_types.keys().collect()
+        self.config.base_types_sorted().map(|bt| &bt.id).collect()
+    }
+
+    /// Get every base type in a group, sorted by id
+    pub fn base_types_in_group(&self, group: &str) -> Vec<&BaseTypeConfig> {
+        self.config
+            .base_types_sorted()
+            .filter(|bt| bt.group.as_deref() == Some(group))
+            .collect()
+    }
+
+    /// Pick a random base type weighted by `drop_weight`, optionally limited to one class.
+    ///
+    /// The pick is deterministic from `seed` (via `make_rng`), so generating the returned
+    /// base with the same seed reproduces the whole drop.
+    pub fn pick_base_type(&self, class: Option<ItemClass>, seed: u64) -> Option<&BaseTypeConfig> {
+        let bases: Vec<&BaseTypeConfig> = self
+            .config
+            .base_types_sorted()
+            .filter(|bt| class.is_none_or(|class| bt.class == class))
+            .collect();
+
+        let weight = |bt: &BaseTypeConfig| {
+            bt.drop_weight
+                .unwrap_or(BaseTypeConfig::DEFAULT_DROP_WEIGHT)
+        };
+        let total: u32 = bases.iter().map(|bt| weight(bt)).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut rng = Self::make_rng(seed);
+        let mut roll = rng.gen_range(0..total);
+        for base in bases {
+            if roll < weight(base) {
+                return Some(base);
+            }
+            roll -= weight(base);
+        }
+        None
     }
 
     /// Get a unique by ID
@@ -489,9 +1141,9 @@ impl Generator {
         self.config.uniques.get(id)
     }
 
-    /// List all unique IDs
+    /// List all unique IDs, sorted
     pub fn unique_ids(&self) -> Vec<&String> {
-        self.config.uniques.keys().collect()
+        self.config.uniques_sorted().map(|u| &u.id).collect()
     }
 
     /// Generate a unique item
@@ -503,23 +1155,10 @@ impl Generator {
         let mut item = Item::new_normal(base, seed);
         item.rarity = Rarity::Unique;
         item.name = unique.name.clone();
+        item.requirements.level = item.requirements.level.max(unique.required_level);
 
-        // Roll implicit if present
-        if let Some(ref implicit_cfg) = base.implicit {
-            let value = rng.gen_range(implicit_cfg.min..=implicit_cfg.max);
-            item.implicit = Some(Modifier {
-                affix_id: "implicit".to_string(),
-                name: "Implicit".to_string(),
-                stat: implicit_cfg.stat,
-                scope: AffixScope::Local,
-                tier: 0,
-                value,
-                value_max: None,
-                tier_min: implicit_cfg.min,
-                tier_max: implicit_cfg.max,
-                tier_max_value: None,
-            });
-        }
+        // Roll implicits if present
+        item.implicits = Self::roll_implicits(base, &mut rng);
 
         // Roll base defenses
         if let Some(ref def_cfg) = base.defenses {
@@ -542,16 +1181,19 @@ impl Generator {
                 name: unique.name.clone(),
                 stat: mod_cfg.stat,
                 scope: AffixScope::Global,
+                tags: Vec::new(),
                 tier: 0,
                 value,
                 value_max: None,
                 tier_min: mod_cfg.min,
                 tier_max: mod_cfg.max,
                 tier_max_value: None,
+                extra_stats: Vec::new(),
             };
             item.prefixes.push(modifier);
         }
 
+        item.record_unique(unique_id);
         Some(item)
     }
 
@@ -560,3 +1202,1636 @@ impl Generator {
         self.config.currencies.get(id)
     }
 }
+
+/// Generic name parts for rare items
+const RARE_NAME_PREFIXES: &[&str] = &[
+    "Doom", "Wrath", "Storm", "Dread", "Soul", "Death", "Blood", "Shadow", "Grim", "Hate",
+    "Plague", "Blight", "Rune", "Spirit", "Mind", "Skull", "Bone", "Venom", "Foe", "Pain",
+];
+
+const RARE_NAME_SUFFIXES: &[&str] = &[
+    "Bane", "Edge", "Fang", "Bite", "Roar", "Song", "Call", "Cry", "Grasp", "Touch", "Strike",
+    "Blow", "Mark", "Brand", "Scar", "Ward", "Guard", "Veil", "Shroud", "Mantle",
+];
+
+/// Endings for `prefix_suffix_epithet` rare names ("of the Wolf")
+const RARE_NAME_EPITHETS: &[&str] = &[
+    "Wolf", "Raven", "Serpent", "Storm", "Void", "Ancients", "Deep", "Dawn", "Crow", "Hollow",
+    "Tide", "Ash",
+];
+
+/// The configured words, or the built-in ones when none are configured
+fn name_pool<'a>(configured: &'a [String], default: &[&'static str]) -> Vec<&'a str> {
+    if configured.is_empty() {
+        default.to_vec()
+    } else {
+        configured.iter().map(String::as_str).collect()
+    }
+}
+
+/// Name parts for affix-themed rare names
+struct NameTheme {
+    /// Words matched against stat names; the first theme with a match wins
+    keywords: &'static [&'static str],
+    prefixes: &'static [&'static str],
+    suffixes: &'static [&'static str],
+}
+
+const NAME_THEMES: &[NameTheme] = &[
+    NameTheme {
+        keywords: &["Fire", "Burn"],
+        prefixes: &["Ember", "Blaze", "Cinder", "Pyre", "Flame"],
+        suffixes: &["Brand", "Scorch", "Pyre", "Flare", "Kindle"],
+    },
+    NameTheme {
+        keywords: &["Cold", "Freeze", "Chill"],
+        prefixes: &["Frost", "Rime", "Ice", "Winter", "Glacier"],
+        suffixes: &["Bite", "Shard", "Chill", "Veil", "Fang"],
+    },
+    NameTheme {
+        keywords: &["Lightning", "Static"],
+        prefixes: &["Storm", "Thunder", "Spark", "Tempest", "Volt"],
+        suffixes: &["Strike", "Bolt", "Crash", "Arc", "Call"],
+    },
+    NameTheme {
+        keywords: &["Chaos", "Poison"],
+        prefixes: &["Plague", "Blight", "Venom", "Rot", "Void"],
+        suffixes: &["Touch", "Grasp", "Sting", "Bane", "Mire"],
+    },
+    NameTheme {
+        keywords: &["Physical", "Bleed", "Critical", "AttackSpeed"],
+        prefixes: &["Blood", "Wrath", "Grim", "Skull", "Foe"],
+        suffixes: &["Edge", "Fang", "Scar", "Blow", "Cleaver"],
+    },
+    NameTheme {
+        keywords: &["Life"],
+        prefixes: &["Soul", "Heart", "Vigor", "Spirit", "Blood"],
+        suffixes: &["Song", "Heart", "Mantle", "Bloom", "Pulse"],
+    },
+    NameTheme {
+        keywords: &["Mana", "Intelligence", "Wisdom"],
+        prefixes: &["Mind", "Rune", "Sage", "Dream", "Spirit"],
+        suffixes: &["Song", "Call", "Whisper", "Glyph", "Mark"],
+    },
+    NameTheme {
+        keywords: &["Armour", "Evasion", "EnergyShield", "Resistance"],
+        prefixes: &["Bastion", "Iron", "Stone", "Bulwark", "Aegis"],
+        suffixes: &["Ward", "Guard", "Shroud", "Veil", "Mantle"],
+    },
+];
+
+/// The name theme for a stat, if any. Conversions are themed by what they convert to.
+fn name_theme(stat: StatType) -> Option<&'static NameTheme> {
+    let name = stat.name();
+    let name = name
+        .strip_prefix("Convert")
+        .and_then(|n| n.split_once("To"))
+        .map_or(name, |(_, target)| target);
+    NAME_THEMES
+        .iter()
+        .find(|theme| theme.keywords.iter().any(|k| name.contains(k)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::sync::OnceLock;
+
+    fn generator() -> &'static Generator {
+        static GENERATOR: OnceLock<Generator> = OnceLock::new();
+        GENERATOR.get_or_init(|| {
+            let config = Config::load_from_dir(Path::new("../config")).unwrap();
+            Generator::new(config)
+        })
+    }
+
+    /// Generate and craft an item twice from the same seed and assert full equality
+    fn assert_deterministic(base_type_id: &str, seed: u64) {
+        let generator = generator();
+
+        let first = generator.generate(base_type_id, seed).unwrap();
+        let second = generator.generate(base_type_id, seed).unwrap();
+        assert_eq!(first, second, "{} diverged for seed {}", base_type_id, seed);
+
+        // Crafting history must replay to the same item
+        let crafted = ["transmute", "augment", "regal", "exalt", "chaos"]
+            .iter()
+            .fold(first, |item, id| {
+                generator.apply_currency(&item, id).unwrap_or(item)
+            });
+        let rebuilt = generator
+            .reconstruct(base_type_id, seed, &crafted.operations)
+            .unwrap();
+        assert_eq!(
+            crafted, rebuilt,
+            "{} crafting diverged for seed {}",
+            base_type_id, seed
+        );
+    }
+
+    #[test]
+    fn test_generated_affix_counts_stay_in_range() {
+        let gen = generator();
+        let settings = &gen.config().generation;
+
+        for (rarity, count) in [
+            (Rarity::Magic, &settings.magic_affix_count),
+            (Rarity::Rare, &settings.rare_affix_count),
+        ] {
+            for seed in 0..64 {
+                let item = gen.generate_at_rarity("iron_sword", seed, rarity).unwrap();
+                assert_eq!(item.rarity, rarity);
+                assert!(item.prefixes.len() <= rarity.max_prefixes());
+                assert!(item.suffixes.len() <= rarity.max_suffixes());
+                assert!(item.affix_count() >= count.min as usize, "seed {}", seed);
+                assert!(item.affix_count() <= count.max as usize, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_magic_name_reflects_affixes() {
+        let gen = generator();
+        for seed in 0..32 {
+            let item = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Magic)
+                .unwrap();
+            let mut expected = Vec::new();
+            if let Some(prefix) = item.prefixes.first() {
+                expected.push(prefix.name.clone());
+            }
+            expected.push(item.base_name.clone());
+            if let Some(suffix) = item.suffixes.first() {
+                expected.push(suffix.name.clone());
+            }
+            assert_eq!(item.name, expected.join(" "), "seed {}", seed);
+        }
+
+        let mut sword = gen.generate("iron_sword", 7).unwrap();
+        assert_eq!(sword.name, sword.base_name);
+        sword = gen.apply_currency(&sword, "transmute").unwrap();
+        assert_eq!(sword.name, sword.magic_name());
+        assert_ne!(sword.name, sword.base_name);
+
+        let regal = gen.apply_currency(&sword, "regal").unwrap();
+        assert_eq!(regal.rarity, Rarity::Rare);
+        assert_ne!(regal.name, sword.name);
+    }
+
+    #[test]
+    fn test_regal_and_exalt_keep_existing_affixes() {
+        let gen = generator();
+        let kept = |before: &Item, after: &Item| {
+            before.prefixes.iter().all(|m| after.prefixes.contains(m))
+                && before.suffixes.iter().all(|m| after.suffixes.contains(m))
+        };
+
+        for seed in 0..50 {
+            let item = gen.generate("iron_sword", seed).unwrap();
+            let magic = gen.apply_currency(&item, "transmute").unwrap();
+            let magic = gen.apply_currency(&magic, "augment").unwrap_or(magic);
+
+            let mut item = gen.apply_currency(&magic, "regal").unwrap();
+            assert_eq!(item.rarity, Rarity::Rare);
+            assert!(kept(&magic, &item), "seed {}", seed);
+            assert_eq!(item.affix_count(), magic.affix_count() + 1, "seed {}", seed);
+
+            // Exalts add one affix at a time until the rare caps are reached
+            while item.can_add_prefix() || item.can_add_suffix() {
+                let exalted = gen.apply_currency(&item, "exalt").unwrap();
+                assert!(kept(&item, &exalted), "seed {}", seed);
+                if exalted.affix_count() == item.affix_count() {
+                    break; // nothing left in the pool for the open slots
+                }
+                assert_eq!(exalted.affix_count(), item.affix_count() + 1);
+                item = exalted;
+            }
+            assert!(item.prefixes.len() <= Rarity::Rare.max_prefixes());
+            assert!(item.suffixes.len() <= Rarity::Rare.max_suffixes());
+            if !item.can_add_prefix() && !item.can_add_suffix() {
+                assert!(matches!(
+                    gen.apply_currency(&item, "exalt"),
+                    Err(CurrencyError::NoAffixSlots)
+                ));
+            }
+        }
+
+        // Neither applies outside its rarity
+        let normal = gen.generate("iron_sword", 1).unwrap();
+        for currency in ["regal", "exalt"] {
+            assert!(matches!(
+                gen.apply_currency(&normal, currency),
+                Err(CurrencyError::InvalidRarity { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_added_fire_damage_rolls_both_bounds() {
+        let gen = generator();
+        let affix = &gen.config().affixes["added_fire_damage"];
+        let sword = gen.generate("iron_sword", 1).unwrap();
+
+        for tier in &affix.tiers {
+            assert!(tier.is_dual_range());
+            let low = tier.low_range();
+            let high = tier.high_range().unwrap();
+            for seed in 0..32 {
+                let mut item = sword.clone();
+                item.seed = seed;
+                let item = gen.add_affix(&item, &affix.id, tier.tier).unwrap();
+                let modifier = item.prefixes.last().unwrap();
+                assert!((low.min..=low.max).contains(&modifier.value));
+                let value_max = modifier.value_max.unwrap();
+                assert!((high.min..=high.max).contains(&value_max));
+                assert_eq!(modifier.tier_max_value, Some((high.min, high.max)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_base_type_respects_drop_weights() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.base_types.get_mut("plate_vest").unwrap().drop_weight = Some(300);
+        config.base_types.get_mut("silk_robe").unwrap().drop_weight = Some(0);
+        let gen = Generator::new(config);
+
+        let draws = 4000;
+        let mut plate = 0;
+        for seed in 0..draws {
+            let base = gen
+                .pick_base_type(Some(ItemClass::BodyArmour), seed)
+                .unwrap();
+            assert_eq!(base.class, ItemClass::BodyArmour);
+            assert_ne!(base.id, "silk_robe");
+            if base.id == "plate_vest" {
+                plate += 1;
+            }
+        }
+        // plate_vest has 300 of the 400 total weight
+        let share = plate as f64 / draws as f64;
+        assert!((0.72..0.78).contains(&share), "plate_vest share {}", share);
+
+        assert_eq!(
+            gen.pick_base_type(None, 7).map(|b| &b.id),
+            gen.pick_base_type(None, 7).map(|b| &b.id)
+        );
+        assert!(gen.pick_base_type(Some(ItemClass::Claw), 0).is_none());
+    }
+
+    #[test]
+    fn test_affix_count_is_clamped_to_slots() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.generation.rare_affix_count = crate::config::AffixCount { min: 10, max: 12 };
+        assert!(config.validate().is_err());
+
+        let gen = Generator::new(config);
+        for seed in 0..16 {
+            let item = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            assert!(item.prefixes.len() <= Rarity::Rare.max_prefixes());
+            assert!(item.suffixes.len() <= Rarity::Rare.max_suffixes());
+        }
+    }
+
+    #[test]
+    fn test_tag_gated_currency() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut currency = config.currencies["transmute"].clone();
+        currency.id = "caster_transmute".to_string();
+        currency.requires.required_tags = vec!["caster".to_string()];
+        config.currencies.insert(currency.id.clone(), currency);
+        let gen = Generator::new(config);
+
+        // silk_robe is tagged "caster", iron_sword is not
+        let robe = gen.generate("silk_robe", 7).unwrap();
+        assert!(gen.can_apply_currency(&robe, "caster_transmute"));
+        let crafted = gen.apply_currency(&robe, "caster_transmute").unwrap();
+        assert_eq!(crafted.rarity, Rarity::Magic);
+
+        let sword = gen.generate("iron_sword", 7).unwrap();
+        assert!(!gen.can_apply_currency(&sword, "caster_transmute"));
+        assert!(matches!(
+            gen.apply_currency(&sword, "caster_transmute"),
+            Err(CurrencyError::RequirementNotMet(_))
+        ));
+    }
+
+    #[test]
+    fn test_weighted_annul_respects_weights() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config
+            .affixes
+            .get_mut("added_fire_damage")
+            .unwrap()
+            .annul_weight = Some(300);
+        let gen = Generator::new(config);
+
+        let trials = 2000;
+        let mut fire_removed = 0;
+        for seed in 0..trials {
+            let mut item = gen.generate("iron_sword", seed).unwrap();
+            item.prefixes.clear();
+            item.suffixes.clear();
+            item.rarity = Rarity::Normal;
+            let item = gen.add_affix(&item, "added_fire_damage", 1).unwrap();
+            let item = gen.add_affix(&item, "increased_attack_speed", 1).unwrap();
+            assert_eq!(gen.annul_weights(&item), vec![300, 100]);
+
+            let annulled = gen.apply_currency(&item, "annul").unwrap();
+            if annulled.prefixes.is_empty() {
+                fire_removed += 1;
+            }
+        }
+
+        // Expected 3/4 of removals hit the fire affix
+        let ratio = fire_removed as f64 / trials as f64;
+        assert!(
+            (ratio - 0.75).abs() < 0.05,
+            "fire removed {:.3} of the time",
+            ratio
+        );
+    }
+
+    #[test]
+    fn test_base_types_in_group() {
+        let gen = generator();
+        let ids = |group: &str| -> Vec<&str> {
+            gen.base_types_in_group(group)
+                .iter()
+                .map(|bt| bt.id.as_str())
+                .collect()
+        };
+
+        assert_eq!(
+            ids("str_armour"),
+            vec!["iron_gauntlets", "iron_shield", "plate_vest"]
+        );
+        assert_eq!(ids("int_armour"), vec!["silk_robe"]);
+        assert!(ids("no_such_group").is_empty());
+
+        let item = gen.generate_from_group("dex_armour", 7).unwrap();
+        assert!(["leather_boots", "leather_vest"].contains(&item.base_type_id.as_str()));
+        assert!(gen.generate_from_group("no_such_group", 7).is_none());
+    }
+
+    /// Mean of all affix values rolled with the given distribution over many seeds
+    fn mean_affix_value(distribution: ValueDistribution) -> f64 {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.generation.value_distribution = distribution;
+        let gen = Generator::new(config);
+
+        let mut total = 0.0;
+        let mut count = 0;
+        for seed in 0..2000 {
+            let mut rng = Generator::make_rng(seed);
+            let value = gen.roll_value(0, 99, &mut rng);
+            assert!((0..=99).contains(&value));
+            total += value as f64;
+            count += 1;
+        }
+        total / count as f64
+    }
+
+    #[test]
+    fn test_value_distribution_means() {
+        let uniform = mean_affix_value(ValueDistribution::Uniform);
+        let triangular = mean_affix_value(ValueDistribution::Triangular);
+        let exp_low = mean_affix_value(ValueDistribution::ExpLow);
+
+        // Expected means: uniform ~49.5, triangular ~33, exp_low ~28
+        assert!((uniform - 49.5).abs() < 3.0, "uniform mean {}", uniform);
+        assert!(
+            triangular < uniform - 10.0,
+            "triangular mean {}",
+            triangular
+        );
+        assert!(exp_low < uniform - 10.0, "exp_low mean {}", exp_low);
+    }
+
+    #[test]
+    fn test_value_distribution_is_deterministic() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.generation.value_distribution = ValueDistribution::ExpLow;
+        let gen = Generator::new(config);
+
+        for seed in 0..16 {
+            let a = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            let b = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            assert_eq!(a, b);
+            for m in a.prefixes.iter().chain(a.suffixes.iter()) {
+                assert!(m.value >= m.tier_min && m.value <= m.tier_max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unique_required_level() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let unique = config.uniques.get_mut("titans_grip").unwrap();
+        unique.required_level = 60;
+        unique.drop_level = 55;
+        let base_level = config.base_types["iron_gauntlets"].requirements.level;
+        assert!(base_level < 60);
+        let gen = Generator::new(config);
+
+        let item = gen.generate_unique("titans_grip", 3).unwrap();
+        assert_eq!(item.requirements.level, 60);
+
+        // A required level below the base's requirement doesn't lower it
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config
+            .uniques
+            .get_mut("titans_grip")
+            .unwrap()
+            .required_level = 0;
+        let gen = Generator::new(config);
+        let item = gen.generate_unique("titans_grip", 3).unwrap();
+        assert_eq!(item.requirements.level, base_level);
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        assert_deterministic("iron_sword", 12345);
+        assert_deterministic("leather_boots", 0);
+        assert_deterministic("iron_sword", u64::MAX);
+    }
+
+    #[test]
+    fn test_generation_is_deterministic_across_seeds() {
+        let mut seeds = Generator::make_rng(0xD1CE);
+        let base_type_ids: Vec<String> = generator().base_type_ids().into_iter().cloned().collect();
+
+        for _ in 0..32 {
+            let seed: u64 = seeds.gen();
+            for base_type_id in &base_type_ids {
+                assert_deterministic(base_type_id, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_affix_is_recorded_and_reproducible() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 42).unwrap();
+
+        let first = generator.add_affix(&item, "added_fire_damage", 1).unwrap();
+        let second = generator.add_affix(&item, "added_fire_damage", 1).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            first.operations,
+            vec![Operation::AddAffix {
+                affix_id: "added_fire_damage".to_string(),
+                tier: 1,
+            }]
+        );
+        assert_eq!(first.rarity, Rarity::Magic);
+
+        let rebuilt = generator
+            .reconstruct("iron_sword", 42, &first.operations)
+            .unwrap();
+        assert_eq!(first, rebuilt);
+    }
+
+    #[test]
+    fn test_add_affix_rejects_duplicate() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 42).unwrap();
+        let item = generator.add_affix(&item, "added_fire_damage", 1).unwrap();
+
+        assert!(matches!(
+            generator.add_affix(&item, "added_fire_damage", 2),
+            Err(CurrencyError::AffixAlreadyPresent(_))
+        ));
+    }
+
+    #[test]
+    fn test_modifiers_carry_their_affix_tags() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 42).unwrap();
+        let item = generator.apply_currency(&item, "alchemy").unwrap();
+
+        assert!(item.affix_count() > 0);
+        for modifier in item.prefixes.iter().chain(item.suffixes.iter()) {
+            let affix = &generator.config().affixes[&modifier.affix_id];
+            assert_eq!(modifier.tags, affix.tags, "{}", modifier.affix_id);
+            assert_eq!(modifier.scope, affix.scope, "{}", modifier.affix_id);
+        }
+        assert!(item.implicits.iter().all(|m| m.tags.is_empty()));
+
+        // The tags travel with the item, so JSON readers don't need the config
+        #[cfg(feature = "json")]
+        {
+            let loaded = Item::from_json(&item.to_json().unwrap()).unwrap();
+            assert_eq!(loaded.prefixes, item.prefixes);
+            assert_eq!(loaded.suffixes, item.suffixes);
+        }
+    }
+
+    #[test]
+    fn test_eligible_affixes_respect_class() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 42).unwrap();
+        let eligible = generator.eligible_affixes(&item);
+
+        assert!(!eligible.is_empty());
+        for affix in &eligible {
+            assert!(
+                affix.allowed_classes.is_empty() || affix.allowed_classes.contains(&item.class),
+                "{} is not allowed on {:?}",
+                affix.id,
+                item.class
+            );
+        }
+        // Armour-only affixes exist but must be filtered out for a sword
+        assert!(generator
+            .config()
+            .affixes
+            .values()
+            .any(|a| !a.allowed_classes.is_empty() && !a.allowed_classes.contains(&item.class)));
+    }
+
+    #[test]
+    fn test_eligible_affixes_exclude_full_slots() {
+        let generator = generator();
+        let mut item = generator.generate("iron_sword", 42).unwrap();
+
+        while item.prefixes.len() < Rarity::Rare.max_prefixes() {
+            let prefix = generator
+                .eligible_affixes(&item)
+                .into_iter()
+                .find(|a| a.affix_type == AffixType::Prefix)
+                .expect("sword should have enough prefixes to fill every slot");
+            let (id, tier) = (prefix.id.clone(), prefix.tiers[0].tier);
+            item = generator.add_affix(&item, &id, tier).unwrap();
+        }
+
+        let eligible = generator.eligible_affixes(&item);
+        assert!(eligible.iter().all(|a| a.affix_type == AffixType::Suffix));
+        assert!(!eligible.is_empty());
+        for affix in eligible {
+            assert!(generator
+                .add_affix(&item, &affix.id, affix.tiers[0].tier)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_mirror_duplicates_rolls_and_locks_copy() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 2024).unwrap();
+        let item = generator.apply_currency(&item, "alchemy").unwrap();
+
+        let copy = generator.mirror(&item).unwrap();
+        assert!(copy.mirrored);
+        assert!(!item.mirrored);
+        assert_eq!(copy.prefixes, item.prefixes);
+        assert_eq!(copy.suffixes, item.suffixes);
+        assert_eq!(copy.implicits, item.implicits);
+        assert_eq!(copy.name, item.name);
+
+        assert!(matches!(
+            generator.apply_currency(&copy, "chaos"),
+            Err(CurrencyError::Mirrored)
+        ));
+        assert!(!generator.can_apply_currency(&copy, "chaos"));
+        assert!(matches!(
+            generator.mirror(&copy),
+            Err(CurrencyError::Mirrored)
+        ));
+
+        // The original stays craftable, and the copy replays as mirrored
+        assert!(generator.apply_currency(&item, "chaos").is_ok());
+        let replayed = generator
+            .reconstruct(&copy.base_type_id, copy.seed, &copy.operations)
+            .unwrap();
+        assert_eq!(replayed, copy);
+    }
+
+    fn odds_affix(id: &str, affix_type: AffixType, tiers: &[(u32, u32, u32)]) -> AffixConfig {
+        AffixConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            affix_type,
+            stat: StatType::AddedLife,
+            scope: AffixScope::Global,
+            tags: Vec::new(),
+            allowed_classes: Vec::new(),
+            tiers: tiers
+                .iter()
+                .map(|&(tier, weight, min_ilvl)| AffixTierConfig {
+                    tier,
+                    weight,
+                    min: 1,
+                    max: 10,
+                    max_value: None,
+                    min_ilvl,
+                    step: None,
+                    extra_stats: Vec::new(),
+                })
+                .collect(),
+            annul_weight: None,
+            flavor: None,
+            extra: Default::default(),
+        }
+    }
+
+    /// Generator with two prefixes and a suffix, plus currencies drawing from them
+    fn odds_generator() -> Generator {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.affixes.clear();
+        for affix in [
+            odds_affix("big", AffixType::Prefix, &[(1, 300, 0), (2, 100, 0)]),
+            odds_affix("gated", AffixType::Prefix, &[(1, 100, 50)]),
+            odds_affix("suffix", AffixType::Suffix, &[(1, 50, 0), (2, 50, 0)]),
+        ] {
+            config.affixes.insert(affix.id.clone(), affix);
+        }
+        config.affix_pools.insert(
+            "odds".to_string(),
+            crate::config::AffixPoolConfig {
+                id: "odds".to_string(),
+                name: String::new(),
+                description: String::new(),
+                affixes: vec!["big".into(), "gated".into(), "suffix".into()],
+                extra: Default::default(),
+            },
+        );
+
+        let mut transmute = config.currencies["transmute"].clone();
+        transmute.effects.affix_pools = vec!["odds".to_string()];
+        config.currencies.insert("odds_add".to_string(), transmute);
+
+        let mut imbue = config.currencies["transmute"].clone();
+        imbue.requires = Default::default();
+        imbue.effects = Default::default();
+        imbue.effects.add_specific_affix = vec![
+            crate::config::SpecificAffix {
+                id: "big".to_string(),
+                tier: Some(2),
+                weight: 300,
+                value: None,
+                value_max: None,
+            },
+            crate::config::SpecificAffix {
+                id: "suffix".to_string(),
+                tier: None,
+                weight: 100,
+                value: None,
+                value_max: None,
+            },
+        ];
+        config.currencies.insert("odds_specific".to_string(), imbue);
+
+        Generator::new(config)
+    }
+
+    #[test]
+    fn test_possible_modifiers_follow_class_tags_and_item_level() {
+        let mut config = odds_generator().config().clone();
+        let mut armour_only = odds_affix("armour_only", AffixType::Prefix, &[(1, 100, 0)]);
+        armour_only.allowed_classes = vec![ItemClass::BodyArmour];
+        let mut caster = odds_affix("caster", AffixType::Suffix, &[(1, 100, 0)]);
+        caster.tags = vec!["caster".to_string()];
+        let mut unweighted = odds_affix("unweighted", AffixType::Suffix, &[(1, 0, 0)]);
+        unweighted.tags = vec!["melee".to_string()];
+        for affix in [armour_only, caster, unweighted] {
+            config.affixes.insert(affix.id.clone(), affix);
+        }
+        let generator = Generator::new(config);
+
+        let listed = |item_level| -> Vec<(String, u32)> {
+            generator
+                .possible_modifiers("iron_sword", item_level)
+                .into_iter()
+                .map(|(affix, tier)| (affix.id.clone(), tier.tier))
+                .collect()
+        };
+        let expected = |ids: &[(&str, u32)]| -> Vec<(String, u32)> {
+            ids.iter()
+                .map(|&(id, tier)| (id.to_string(), tier))
+                .collect()
+        };
+
+        assert_eq!(
+            listed(10),
+            expected(&[("big", 1), ("big", 2), ("suffix", 1), ("suffix", 2)])
+        );
+        // The gated tier joins once the item level reaches it
+        assert_eq!(
+            listed(50),
+            expected(&[
+                ("big", 1),
+                ("big", 2),
+                ("gated", 1),
+                ("suffix", 1),
+                ("suffix", 2)
+            ])
+        );
+        assert!(generator.possible_modifiers("no_such_base", 50).is_empty());
+    }
+
+    fn odds_item(generator: &Generator) -> Item {
+        let mut item = generator.generate("iron_sword", 1).unwrap();
+        item.rarity = Rarity::Normal;
+        item.prefixes.clear();
+        item.suffixes.clear();
+        item.requirements.level = 10;
+        item
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_outcome_odds_for_random_affix() {
+        let generator = odds_generator();
+        let item = odds_item(&generator);
+
+        let odds = generator.currency_outcome_odds(&item, "odds_add").unwrap();
+        assert!(odds.removed.is_empty());
+        assert!(odds.specific.is_empty());
+
+        let ids: Vec<&str> = odds.added.iter().map(|a| a.affix_id.as_str()).collect();
+        assert_eq!(ids, vec!["big", "suffix"]);
+
+        // Half the time a prefix: "big" wins 400 of 500 weight, the gated prefix's
+        // 100 can't roll at this item level and falls back to the suffix
+        let big = &odds.added[0];
+        assert_close(big.chance, 0.4);
+        assert_eq!(big.tiers.len(), 2);
+        assert_close(big.tiers[0].1, 0.3);
+        assert_close(big.tiers[1].1, 0.1);
+
+        let suffix = &odds.added[1];
+        assert_close(suffix.chance, 0.6);
+        assert_close(suffix.tiers[0].1, 0.3);
+        assert_close(suffix.tiers[1].1, 0.3);
+    }
+
+    #[test]
+    fn test_outcome_odds_for_specific_affix_set() {
+        let generator = odds_generator();
+        let mut item = odds_item(&generator);
+        item.rarity = Rarity::Magic;
+
+        let odds = generator
+            .currency_outcome_odds(&item, "odds_specific")
+            .unwrap();
+        assert!(odds.added.is_empty());
+        assert_eq!(odds.specific.len(), 2);
+        assert_eq!(odds.specific[0].affix_id, "big");
+        assert_close(odds.specific[0].chance, 0.75);
+        assert_eq!(odds.specific[0].tiers, vec![(2, 0.75)]);
+        assert_eq!(odds.specific[1].affix_id, "suffix");
+        assert_close(odds.specific[1].chance, 0.25);
+
+        // Once the suffix is taken, the fixed-tier prefix is the only candidate
+        let item = generator.add_affix(&item, "suffix", 1).unwrap();
+        let odds = generator
+            .currency_outcome_odds(&item, "odds_specific")
+            .unwrap();
+        assert_eq!(odds.specific.len(), 1);
+        assert_close(odds.specific[0].chance, 1.0);
+    }
+
+    #[test]
+    fn test_locked_modifiers_survive_rerolls() {
+        let generator = generator();
+        let mut others_changed = false;
+
+        for seed in 0..20 {
+            let item = generator.generate("iron_sword", seed).unwrap();
+            let item = generator.apply_currency(&item, "alchemy").unwrap();
+            let kept = item
+                .prefixes
+                .first()
+                .or(item.suffixes.first())
+                .unwrap()
+                .clone();
+            let locked = vec![kept.affix_id.clone()];
+
+            for currency in ["chaos", "divine", "annul"] {
+                let Ok(crafted) = generator.apply_currency_locked(&item, currency, &locked) else {
+                    continue;
+                };
+                let survivor = crafted
+                    .prefixes
+                    .iter()
+                    .chain(crafted.suffixes.iter())
+                    .find(|m| m.affix_id == kept.affix_id);
+                assert_eq!(survivor, Some(&kept), "{} on seed {}", currency, seed);
+
+                others_changed |=
+                    crafted.prefixes != item.prefixes || crafted.suffixes != item.suffixes;
+
+                let replayed = generator
+                    .reconstruct(&crafted.base_type_id, crafted.seed, &crafted.operations)
+                    .unwrap();
+                assert_eq!(replayed, crafted);
+            }
+        }
+        assert!(others_changed);
+    }
+
+    #[test]
+    fn test_rerolling_with_everything_locked_fails() {
+        let generator = generator();
+        let item = generator.generate("iron_sword", 77).unwrap();
+        let item = generator.apply_currency(&item, "alchemy").unwrap();
+        let locked: Vec<String> = item
+            .prefixes
+            .iter()
+            .chain(item.suffixes.iter())
+            .map(|m| m.affix_id.clone())
+            .collect();
+
+        for currency in ["divine", "annul"] {
+            assert!(matches!(
+                generator.apply_currency_locked(&item, currency, &locked),
+                Err(CurrencyError::AllAffixesLocked)
+            ));
+        }
+
+        // Unlocked crafting records the plain currency operation
+        let crafted = generator
+            .apply_currency_locked(&item, "divine", &[])
+            .unwrap();
+        assert_eq!(
+            crafted.operations.last(),
+            Some(&Operation::Currency("divine".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crafting_target_progress_and_suggestion() {
+        let gen = generator();
+        let sword = gen.generate("iron_sword", 5).unwrap();
+        let mut item = gen.add_affix(&sword, "added_physical_damage", 3).unwrap();
+        let req = |stat, max_tier| RecipeAffixRequirement {
+            stat,
+            affix_type: None,
+            min_tier: 1,
+            max_tier,
+        };
+        item.target = vec![
+            req(StatType::AddedPhysicalDamage, 3),
+            req(StatType::AddedPhysicalDamage, 1),
+            req(StatType::IncreasedAttackSpeed, 99),
+        ];
+
+        let met: Vec<bool> = item.target_progress().iter().map(|(_, met)| *met).collect();
+        assert_eq!(met, vec![true, false, false]);
+
+        // Some applicable currency can add attack speed
+        let (currency_id, chance) = gen.suggest_target_currency(&item).unwrap();
+        assert!(gen.can_apply_currency(&item, &currency_id));
+        assert!(chance > 0.0 && chance <= 1.0, "{}", chance);
+
+        item.target.truncate(1);
+        assert_eq!(gen.suggest_target_currency(&item), None);
+    }
+
+    #[test]
+    fn test_weighted_tags_favor_tagged_affixes() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut favored = config.currencies["transmute"].clone();
+        favored.id = "caster_transmute".to_string();
+        favored.effects.weighted_tags = vec![("caster".to_string(), 5.0)];
+        config.currencies.insert(favored.id.clone(), favored);
+        let gen = Generator::new(config);
+
+        // Energy shield is the only caster mod a plate vest can roll
+        let caster_mods = |item: &Item| {
+            item.prefixes
+                .iter()
+                .chain(item.suffixes.iter())
+                .filter(|m| {
+                    gen.config().affixes[&m.affix_id]
+                        .tags
+                        .contains(&"caster".to_string())
+                })
+                .count()
+        };
+
+        let mut plain = 0;
+        let mut weighted = 0;
+        for seed in 0..300 {
+            let item = gen.generate("plate_vest", seed).unwrap();
+            plain += caster_mods(&gen.apply_currency(&item, "transmute").unwrap());
+            weighted += caster_mods(&gen.apply_currency(&item, "caster_transmute").unwrap());
+        }
+
+        assert!(
+            weighted as f64 > plain as f64 * 2.0,
+            "caster mods: {} weighted vs {} plain",
+            weighted,
+            plain
+        );
+    }
+
+    #[test]
+    fn test_tier_bias_favors_better_tiers() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        for (id, bias) in [("lucky_exalt", 2.0), ("unlucky_exalt", -2.0)] {
+            let mut currency = config.currencies["exalt"].clone();
+            currency.id = id.to_string();
+            currency.effects.tier_bias = bias;
+            config.currencies.insert(currency.id.clone(), currency);
+        }
+        let gen = Generator::new(config);
+
+        // Average tier number of the added affix; lower is better
+        let average_tier = |currency_id: &str| {
+            let mut total = 0;
+            for seed in 0..300 {
+                // An empty rare, high enough level for every tier so the bias has
+                // tiers to choose from
+                let mut item = gen.generate("iron_sword", seed).unwrap();
+                item.rarity = Rarity::Rare;
+                item.requirements.level = 100;
+                let crafted = gen.apply_currency(&item, currency_id).unwrap();
+                let added = crafted
+                    .prefixes
+                    .iter()
+                    .chain(crafted.suffixes.iter())
+                    .next()
+                    .unwrap();
+                total += added.tier;
+            }
+            total as f64 / 300.0
+        };
+
+        let neutral = average_tier("exalt");
+        let lucky = average_tier("lucky_exalt");
+        let unlucky = average_tier("unlucky_exalt");
+        assert!(
+            lucky < neutral - 0.3,
+            "lucky {} vs neutral {}",
+            lucky,
+            neutral
+        );
+        assert!(
+            unlucky > neutral,
+            "unlucky {} vs neutral {}",
+            unlucky,
+            neutral
+        );
+    }
+
+    #[test]
+    fn test_best_of_picks_highest_score_reproducibly() {
+        let generator = generator();
+        let armour = |item: &Item| item.defenses.armour.unwrap_or(0) as f64;
+
+        let (seed, best) = generator.best_of("plate_vest", 25, armour, 99).unwrap();
+        assert_eq!(best, generator.generate("plate_vest", seed).unwrap());
+
+        // Every candidate in the sequence scores at most the winner
+        let mut rng = Generator::make_rng(99);
+        let candidates: Vec<u64> = (0..25).map(|_| rng.gen::<u64>()).collect();
+        assert!(candidates.contains(&seed));
+        for candidate in candidates {
+            let item = generator.generate("plate_vest", candidate).unwrap();
+            assert!(armour(&item) <= armour(&best));
+        }
+
+        assert_eq!(
+            generator.best_of("plate_vest", 25, armour, 99),
+            Some((seed, best))
+        );
+        assert!(generator.best_of("plate_vest", 0, armour, 99).is_none());
+        assert!(generator.best_of("missing", 5, armour, 99).is_none());
+    }
+
+    #[test]
+    fn test_currency_error_messages_are_user_facing() {
+        let cases = [
+            (
+                CurrencyError::InvalidRarity {
+                    expected: vec![Rarity::Normal, Rarity::Magic],
+                    got: Rarity::Unique,
+                },
+                "This item is Unique and cannot be modified",
+            ),
+            (
+                CurrencyError::InvalidRarity {
+                    expected: vec![Rarity::Normal, Rarity::Magic],
+                    got: Rarity::Rare,
+                },
+                "Requires a Normal or Magic item, but this item is Rare",
+            ),
+            (
+                CurrencyError::NoAffixSlots,
+                "This item has no open prefix or suffix slots",
+            ),
+            (
+                CurrencyError::NoAffixesToRemove,
+                "This item has no modifiers to remove",
+            ),
+            (
+                CurrencyError::NoValidAffixes,
+                "No modifier can roll on this item with this currency",
+            ),
+            (
+                CurrencyError::NoMatchingRecipe,
+                "No unique recipe matches this base type and its modifiers",
+            ),
+            (
+                CurrencyError::AffixNotFound("fire_res".to_string()),
+                "Modifier 'fire_res' is not defined in the config",
+            ),
+            (
+                CurrencyError::AffixAlreadyPresent("fire_res".to_string()),
+                "This item already has modifier 'fire_res'",
+            ),
+            (
+                CurrencyError::AffixNotAllowed("fire_res".to_string()),
+                "Modifier 'fire_res' cannot roll on this item class",
+            ),
+            (
+                CurrencyError::TierNotFound {
+                    affix_id: "fire_res".to_string(),
+                    tier: 9,
+                },
+                "Modifier 'fire_res' has no tier 9",
+            ),
+            (
+                CurrencyError::NoAffixPoolsSpecified,
+                "This currency has no affix pools to roll from",
+            ),
+            (
+                CurrencyError::UnknownCurrency("nope".to_string()),
+                "Unknown currency: nope",
+            ),
+            (
+                CurrencyError::RequirementNotMet("item is missing tag 'weapon'".to_string()),
+                "Can't use this currency here: item is missing tag 'weapon'",
+            ),
+            (
+                CurrencyError::Mirrored,
+                "This item is mirrored and cannot be modified",
+            ),
+            (
+                CurrencyError::AllAffixesLocked,
+                "Every modifier this currency would change is locked",
+            ),
+        ];
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_themed_rare_names_follow_best_affixes() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.generation.rare_name_mode = RareNameMode::AffixThemed;
+        let gen = Generator::new(config);
+        let fire = name_theme(StatType::AddedFireDamage).unwrap();
+        assert_eq!(
+            name_theme(StatType::IncreasedFireDamage).unwrap().prefixes,
+            fire.prefixes
+        );
+
+        let mut fire_named = 0;
+        for seed in 0..200 {
+            let item = gen
+                .generate_at_rarity("crystal_wand", seed, Rarity::Rare)
+                .unwrap();
+            let best_prefix = item.prefixes.iter().min_by_key(|m| m.tier);
+            let fire_damage = [StatType::AddedFireDamage, StatType::IncreasedFireDamage];
+            if best_prefix.is_some_and(|m| fire_damage.contains(&m.stat)) {
+                let first = item.name.split(' ').next().unwrap();
+                assert!(fire.prefixes.contains(&first), "{}", item.name);
+                fire_named += 1;
+            }
+        }
+        assert!(fire_named > 0);
+
+        // Crafting the third affix makes the item rare and names it after its mods
+        let mut item = gen.generate("iron_sword", 3).unwrap();
+        for affix_id in [
+            "added_fire_damage",
+            "increased_attack_speed",
+            "added_accuracy",
+        ] {
+            item = gen.add_affix(&item, affix_id, 1).unwrap();
+        }
+        assert_eq!(item.rarity, Rarity::Rare);
+        let (first, second) = item.name.split_once(' ').unwrap();
+        assert!(fire.prefixes.contains(&first), "{}", item.name);
+        let attack = name_theme(StatType::IncreasedAttackSpeed).unwrap();
+        assert!(attack.suffixes.contains(&second), "{}", item.name);
+    }
+
+    #[test]
+    fn test_rare_name_structure_and_pools_from_config() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.generation.rare_name_structure = RareNameStructure::SingleWord;
+        let gen = Generator::new(config.clone());
+        for seed in 0..200 {
+            let item = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            assert!(!item.name.contains(' '), "{}", item.name);
+        }
+
+        config.generation.rare_name_structure = RareNameStructure::PrefixSuffixEpithet;
+        config.generation.rare_name_prefixes = vec!["Gloom".to_string()];
+        config.generation.rare_name_epithets = vec!["Moth".to_string()];
+        let gen = Generator::new(config);
+        let item = gen
+            .generate_at_rarity("iron_sword", 7, Rarity::Rare)
+            .unwrap();
+        assert!(item.name.starts_with("Gloom "), "{}", item.name);
+        assert!(item.name.ends_with(" of the Moth"), "{}", item.name);
+    }
+
+    #[test]
+    fn test_implicit_options_roll_by_weight() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let base = config.base_types.get_mut("iron_sword").unwrap();
+        let fixed = base.implicits.clone();
+        for (stat, weight) in [(StatType::AddedStrength, 3), (StatType::AddedDexterity, 1)] {
+            base.implicits.push(crate::config::ImplicitConfig {
+                stat,
+                min: 5,
+                max: 10,
+                weight: Some(weight),
+            });
+        }
+        assert!(config.validate().is_ok());
+        let gen = Generator::new(config);
+
+        let mut strength = 0;
+        for seed in 0..2000 {
+            let item = gen.generate("iron_sword", seed).unwrap();
+            assert_eq!(item.implicits.len(), fixed.len() + 1);
+            assert_eq!(item.implicits[0].stat, fixed[0].stat);
+            match item.implicits[fixed.len()].stat {
+                StatType::AddedStrength => strength += 1,
+                stat => assert_eq!(stat, StatType::AddedDexterity),
+            }
+        }
+        // 3:1 weights, so about 1500 of 2000
+        assert!((1400..=1600).contains(&strength), "{}", strength);
+
+        // The chosen implicit comes back on replay
+        let item = gen.generate("iron_sword", 5).unwrap();
+        let item = gen.apply_currency(&item, "alchemy").unwrap();
+        let replayed = gen
+            .reconstruct(&item.base_type_id, item.seed, &item.operations)
+            .unwrap();
+        assert_eq!(replayed, item);
+    }
+
+    #[test]
+    fn test_convert_damage_moves_base_damage() {
+        let generator = generator();
+        let sword = generator.generate("iron_sword", 11).unwrap();
+        let physical = sword.damage.as_ref().unwrap().damages[0].clone();
+        assert_eq!(physical.damage_type, DamageType::Physical);
+
+        let converted = generator.apply_currency(&sword, "ember_infusion").unwrap();
+        let damages = &converted.damage.as_ref().unwrap().damages;
+        let fire = crate::item::DamageValue {
+            damage_type: DamageType::Fire,
+            min: physical.min / 2,
+            max: physical.max / 2,
+        };
+        let remaining = crate::item::DamageValue {
+            damage_type: DamageType::Physical,
+            min: physical.min - fire.min,
+            max: physical.max - fire.max,
+        };
+        assert_eq!(damages, &vec![remaining, fire]);
+
+        // Converting again merges into the existing fire entry
+        let twice = generator
+            .apply_currency(&converted, "ember_infusion")
+            .unwrap();
+        let twice = &twice.damage.as_ref().unwrap().damages;
+        assert_eq!(twice.len(), 2);
+        assert_eq!(
+            twice[0].min + twice[1].min,
+            physical.min,
+            "total damage is conserved"
+        );
+
+        let vest = generator.generate("plate_vest", 11).unwrap();
+        assert!(matches!(
+            generator.apply_currency(&vest, "ember_infusion"),
+            Err(CurrencyError::RequirementNotMet(_))
+        ));
+    }
+
+    #[test]
+    fn test_reduced_requirements_clamp_at_zero() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        for (id, value) in [("lightweight", 40), ("featherweight", 80)] {
+            let mut affix = odds_affix(id, AffixType::Suffix, &[(1, 100, 0)]);
+            affix.stat = StatType::ReducedRequirements;
+            affix.scope = AffixScope::Local;
+            affix.tiers[0].min = value;
+            affix.tiers[0].max = value;
+            config.affixes.insert(affix.id.clone(), affix);
+        }
+        assert!(config.validate().is_ok());
+        let gen = Generator::new(config);
+
+        let vest = gen.generate("plate_vest", 5).unwrap();
+        let base = vest.requirements.clone();
+        assert_eq!(vest.computed_requirements(), base);
+
+        let light = gen.add_affix(&vest, "lightweight", 1).unwrap();
+        let reqs = light.computed_requirements();
+        assert_eq!(reqs.level, base.level * 60 / 100);
+        assert_eq!(reqs.strength, base.strength * 60 / 100);
+        assert_eq!(light.requirements, base, "base requirements are untouched");
+        let markdown = light.to_markdown();
+        assert!(markdown.contains(&format!("Requires: Level {}", reqs.level)));
+
+        // 120% total reduction bottoms out at zero
+        let both = gen.add_affix(&light, "featherweight", 1).unwrap();
+        let reqs = both.computed_requirements();
+        assert_eq!((reqs.level, reqs.strength), (0, 0));
+
+        let mut config = gen.config().clone();
+        config.affixes.get_mut("featherweight").unwrap().tiers[0].max = 150;
+        let errors = config.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.to_string().contains("more than 100%")));
+    }
+
+    #[test]
+    fn test_unique_recipes_are_picked_by_weight() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut unique = config.uniques["titans_grip"].clone();
+        unique.id = "giants_grasp".to_string();
+        unique.name = "Giant's Grasp".to_string();
+        config.uniques.insert(unique.id.clone(), unique);
+        let mut recipe = config.unique_recipes[0].clone();
+        recipe.unique_id = "giants_grasp".to_string();
+        recipe.weight = 300;
+        config.unique_recipes.push(recipe.clone());
+        recipe.unique_id = "never".to_string();
+        recipe.weight = 0;
+        config.unique_recipes.push(recipe);
+        let gen = Generator::new(config);
+
+        let draws = 2000;
+        let mut giants = 0;
+        for seed in 0..draws {
+            let mut item = gen
+                .generate_at_rarity("iron_gauntlets", seed, Rarity::Normal)
+                .unwrap();
+            item = gen.add_affix(&item, "added_life", 1).unwrap();
+            item = gen.add_affix(&item, "added_strength", 1).unwrap();
+            let unique = gen.apply_currency(&item, "chance").unwrap();
+            match unique.name.as_str() {
+                "Giant's Grasp" => giants += 1,
+                name => assert_eq!(name, "Titan's Grip"),
+            }
+        }
+
+        // 300 of 400 weight, give or take sampling noise
+        let share = giants as f64 / draws as f64;
+        assert!((0.70..0.80).contains(&share), "share {}", share);
+    }
+
+    #[test]
+    fn test_stepped_tier_rolls_multiples_only() {
+        let mut affix = odds_affix("stepped_life", AffixType::Prefix, &[(1, 100, 0)]);
+        affix.tiers[0].min = 3;
+        affix.tiers[0].max = 27;
+        affix.tiers[0].step = Some(5);
+        affix.tiers[0].max_value = Some(crate::config::RollRange { min: 31, max: 44 });
+        let tier = &affix.tiers[0];
+
+        let gen = generator();
+        let mut seen = std::collections::BTreeSet::new();
+        for seed in 0..500 {
+            let mut rng = Generator::make_rng(seed);
+            let (value, value_max) = gen.roll_tier_values(tier, &mut rng);
+            assert_eq!(value % 5, 0, "{}", value);
+            assert!((5..=25).contains(&value), "{}", value);
+            let value_max = value_max.unwrap();
+            assert!([35, 40].contains(&value_max), "{}", value_max);
+            seen.insert(value);
+        }
+        assert_eq!(seen.into_iter().collect::<Vec<_>>(), [5, 10, 15, 20, 25]);
+
+        // A step with no multiple inside the range is a config error
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        affix.tiers[0].min = 11;
+        affix.tiers[0].max = 14;
+        config.affixes.insert(affix.id.clone(), affix);
+        let errors = config.validate().unwrap_err();
+        assert!(
+            errors[0].message.contains("no multiple of step 5"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_skipped_affix_records_warning() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.affixes.clear();
+        for affix in [
+            odds_affix("out_of_reach", AffixType::Prefix, &[(1, 100, 999)]),
+            odds_affix("suffix", AffixType::Suffix, &[(1, 100, 0)]),
+        ] {
+            config.affixes.insert(affix.id.clone(), affix);
+        }
+        let gen = Generator::new(config);
+        let level = gen.get_base_type("iron_sword").unwrap().requirements.level;
+
+        let mut warnings = Vec::new();
+        for seed in 0..20 {
+            let item = gen
+                .generate_at_rarity_with_warnings("iron_sword", seed, Rarity::Rare, &mut warnings)
+                .unwrap();
+            let silent = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            assert_eq!(item, silent, "collecting warnings doesn't change rolls");
+            assert!(item.prefixes.is_empty());
+        }
+
+        let skipped = GenerationWarning::NoEligibleTier {
+            affix_id: "out_of_reach".to_string(),
+            item_level: level,
+        };
+        assert!(warnings.contains(&skipped), "{:?}", warnings);
+        assert_eq!(
+            skipped.to_string(),
+            format!(
+                "affix out_of_reach had no eligible tier at ilvl {} and was skipped",
+                level
+            )
+        );
+    }
+
+    #[test]
+    fn test_rare_on_class_without_affixes_warns_once() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.affixes.clear();
+        let mut ring_only = odds_affix("ring_only", AffixType::Prefix, &[(1, 100, 0)]);
+        ring_only.allowed_classes = vec![ItemClass::Ring];
+        config.affixes.insert(ring_only.id.clone(), ring_only);
+        let gen = Generator::new(config);
+        let class = gen.get_base_type("iron_sword").unwrap().class;
+        assert!(!gen.has_affixes_for_class(class));
+        assert!(gen.has_affixes_for_class(ItemClass::Ring));
+
+        let mut warnings = Vec::new();
+        let item = gen
+            .generate_at_rarity_with_warnings("iron_sword", 7, Rarity::Rare, &mut warnings)
+            .unwrap();
+        assert_eq!(item.rarity, Rarity::Rare);
+        assert!(item.prefixes.is_empty() && item.suffixes.is_empty());
+        assert_eq!(
+            warnings,
+            vec![GenerationWarning::NoAffixesForClass { class }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            format!("no affixes available for {:?} items", class)
+        );
+        let silent = gen
+            .generate_at_rarity("iron_sword", 7, Rarity::Rare)
+            .unwrap();
+        assert_eq!(item, silent);
+    }
+
+    #[test]
+    fn test_blocked_tag_affix_never_rolls() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config
+            .base_types
+            .get_mut("iron_sword")
+            .unwrap()
+            .blocked_tags = vec!["speed".to_string()];
+        assert!(config.validate().is_ok());
+        let blocked = Generator::new(config);
+
+        let rolls_speed = |gen: &Generator| {
+            (0..100).any(|seed| {
+                let item = gen
+                    .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                    .unwrap();
+                let item = gen.apply_currency(&item, "chaos").unwrap();
+                item.prefixes.iter().chain(item.suffixes.iter()).any(|m| {
+                    gen.config().affixes[&m.affix_id]
+                        .tags
+                        .contains(&"speed".to_string())
+                })
+            })
+        };
+        assert!(rolls_speed(generator()));
+        assert!(!rolls_speed(&blocked));
+
+        // Rolling an affix directly respects the base's blocked tags too
+        let item = blocked.generate("iron_sword", 1).unwrap();
+        let mut rng = Generator::make_rng(1);
+        for affix_type in [AffixType::Prefix, AffixType::Suffix].repeat(100) {
+            let modifier = blocked
+                .roll_affix(&item, affix_type, &AffixRoll::default(), &mut rng)
+                .unwrap();
+            assert!(!modifier.tags.contains(&"speed".to_string()));
+        }
+
+        // Blocking every tag the base could roll leaves nothing, which validation reports
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let sword = config.base_types.get_mut("iron_sword").unwrap();
+        sword.blocked_tags = sword.tags.clone();
+        let errors = config.validate().unwrap_err();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.entry == "base_type 'iron_sword'" && e.message.contains("blocked_tags")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_hybrid_affix_grants_both_stats() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut hybrid = odds_affix("life_and_mana", AffixType::Prefix, &[(1, 100, 0)]);
+        hybrid.tiers[0].extra_stats = vec![crate::config::StatRange {
+            stat: StatType::AddedMana,
+            min: 5,
+            max: 8,
+        }];
+        config.affixes.insert(hybrid.id.clone(), hybrid.clone());
+        assert!(config.validate().is_ok());
+
+        let gen = Generator::new(config.clone());
+        let mut item = gen.generate("iron_sword", 7).unwrap();
+        item.rarity = Rarity::Rare;
+        let life_before = item.stat_total(StatType::AddedLife);
+        let mana_before = item.stat_total(StatType::AddedMana);
+
+        let crafted = gen.add_affix(&item, "life_and_mana", 1).unwrap();
+        let modifier = &crafted.prefixes[0];
+        assert_eq!(modifier.extra_stats.len(), 1);
+        let mana = modifier.extra_stats[0].value;
+        assert!((5..=8).contains(&mana));
+        assert_eq!(
+            crafted.stat_total(StatType::AddedLife),
+            life_before + modifier.value
+        );
+        assert_eq!(crafted.stat_total(StatType::AddedMana), mana_before + mana);
+        assert_eq!(
+            modifier.display(),
+            format!("+{} Added Life and +{} Added Mana", modifier.value, mana)
+        );
+
+        // Every tier must list the same extra stats
+        let mut tier_2 = hybrid.tiers[0].clone();
+        tier_2.tier = 2;
+        tier_2.extra_stats.clear();
+        hybrid.tiers.push(tier_2);
+        config.affixes.insert(hybrid.id.clone(), hybrid);
+        let errors = config.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.to_string().contains("extra stats differ from tier 1")));
+    }
+
+    #[test]
+    fn test_prefixes_only_reroll_leaves_suffixes() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut currency = config.currencies["divine"].clone();
+        currency.id = "prefix_divine".to_string();
+        currency.effects.reroll_prefixes_only = true;
+        config
+            .currencies
+            .insert(currency.id.clone(), currency.clone());
+        assert!(config.validate().is_ok());
+        let gen = Generator::new(config.clone());
+
+        let mut prefixes_changed = 0;
+        for seed in 0..30 {
+            let item = gen
+                .generate_at_rarity("iron_sword", seed, Rarity::Rare)
+                .unwrap();
+            if item.prefixes.is_empty() {
+                let result = gen.apply_currency(&item, "prefix_divine");
+                assert!(matches!(result, Err(CurrencyError::NoAffixesToRemove)));
+                continue;
+            }
+            let crafted = gen.apply_currency(&item, "prefix_divine").unwrap();
+            assert_eq!(crafted.suffixes, item.suffixes);
+            assert_eq!(crafted.prefixes.len(), item.prefixes.len());
+            if crafted.prefixes != item.prefixes {
+                prefixes_changed += 1;
+            }
+        }
+        assert!(prefixes_changed > 0);
+
+        // Both scopes at once can't be satisfied
+        currency.effects.reroll_suffixes_only = true;
+        config.currencies.insert(currency.id.clone(), currency);
+        let errors = config.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e
+            .message
+            .contains("reroll_prefixes_only and reroll_suffixes_only")));
+    }
+
+    #[test]
+    fn test_fossil_blocks_and_boosts_tags() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut plain = config.currencies["frigid_fossil"].clone();
+        plain.id = "plain_reforge".to_string();
+        plain.effects.weighted_tags.clear();
+        config.currencies.insert(plain.id.clone(), plain);
+        let gen = Generator::new(config);
+
+        // Affixes carrying `tag` across many rerolls of the same items (wands roll both
+        // fire and cold affixes)
+        let count_tagged = |currency_id: &str, tag: &str| {
+            let mut count = 0;
+            for seed in 0..200 {
+                let item = gen
+                    .generate_at_rarity("crystal_wand", seed, Rarity::Rare)
+                    .unwrap();
+                let crafted = gen.apply_currency(&item, currency_id).unwrap();
+                count += crafted
+                    .prefixes
+                    .iter()
+                    .chain(crafted.suffixes.iter())
+                    .filter(|m| {
+                        gen.config().affixes[&m.affix_id]
+                            .tags
+                            .contains(&tag.to_string())
+                    })
+                    .count();
+            }
+            count
+        };
+
+        assert!(count_tagged("plain_reforge", "fire") > 0);
+        assert_eq!(count_tagged("frigid_fossil", "fire"), 0);
+        assert!(count_tagged("frigid_fossil", "cold") > count_tagged("plain_reforge", "cold"));
+    }
+
+    #[test]
+    fn test_enchant_ignores_affix_limits() {
+        let gen = generator();
+        let mut item = gen
+            .generate_at_rarity("plate_vest", 3, Rarity::Rare)
+            .unwrap();
+        while let Ok(exalted) = gen.apply_currency(&item, "exalt") {
+            item = exalted;
+        }
+        assert!(!item.can_add_prefix() && !item.can_add_suffix());
+        assert!(gen.can_apply_currency(&item, "enchanting_orb"));
+
+        let enchanted = gen.apply_currency(&item, "enchanting_orb").unwrap();
+        let enchant = enchanted.enchant.clone().unwrap();
+        assert!(gen.config().affix_pools["enchants"]
+            .affixes
+            .contains(&enchant.affix_id));
+        assert_eq!(enchanted.prefixes, item.prefixes);
+        assert_eq!(enchanted.suffixes, item.suffixes);
+        assert_eq!(
+            enchanted.stat_total(enchant.stat),
+            item.stat_total(enchant.stat) + enchant.value
+        );
+
+        // Affix rerolls leave the enchant alone, and replay restores it
+        let rerolled = gen.apply_currency(&enchanted, "chaos").unwrap();
+        assert_ne!(
+            (&rerolled.prefixes, &rerolled.suffixes),
+            (&enchanted.prefixes, &enchanted.suffixes)
+        );
+        assert_eq!(rerolled.enchant, Some(enchant));
+        let replayed = gen
+            .reconstruct(&rerolled.base_type_id, rerolled.seed, &rerolled.operations)
+            .unwrap();
+        assert_eq!(replayed.enchant, rerolled.enchant);
+
+        // Enchant pools only hold armour affixes
+        let sword = gen.generate("iron_sword", 3).unwrap();
+        assert!(matches!(
+            gen.apply_currency(&sword, "enchanting_orb"),
+            Err(CurrencyError::NoValidAffixes)
+        ));
+    }
+}
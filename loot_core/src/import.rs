@@ -0,0 +1,534 @@
+use crate::config::{
+    AffixConfig, AffixTierConfig, BaseTypeConfig, Config, DamageConfig, DamageEntry,
+    DefensesConfig, ImplicitConfig, RollRange,
+};
+use crate::types::*;
+use serde::de::{value::StrDeserializer, DeserializeOwned, IntoDeserializer};
+use std::collections::HashMap;
+
+/// A problem with one row of an imported CSV file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    /// 1-based line number in the source file
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Config entries read from spreadsheet exports, ready to merge into a `Config`
+#[derive(Debug, Clone, Default)]
+pub struct ImportedEntries {
+    pub base_types: Vec<BaseTypeConfig>,
+    pub affixes: Vec<AffixConfig>,
+}
+
+impl ImportedEntries {
+    /// Insert all entries into the config, replacing any with the same ID
+    pub fn merge_into(self, config: &mut Config) {
+        for bt in self.base_types {
+            config.base_types.insert(bt.id.clone(), bt);
+        }
+        for affix in self.affixes {
+            config.affixes.insert(affix.id.clone(), affix);
+        }
+    }
+}
+
+/// Parse affixes from CSV.
+///
+/// Each row is one tier. Columns (by header name): `id, name, type, stat, tier, weight,
+/// min, max` are required; `scope, tags, allowed_classes, max_min, max_max, min_ilvl, step`
+/// are optional. Rows with an empty `id` (or the same `id` as the row above) add another tier
+/// to the previous affix. List columns use `;` as a separator.
+pub fn parse_affixes_csv(text: &str) -> Result<Vec<AffixConfig>, Vec<ImportError>> {
+    let (header, rows) = read_csv(text).map_err(|e| vec![e])?;
+    header.require(&["id", "name", "type", "stat", "tier", "weight", "min", "max"])?;
+
+    let mut affixes: Vec<AffixConfig> = Vec::new();
+    let mut errors = Vec::new();
+
+    for row in &rows {
+        let line = row.line;
+        let id = header.get(row, "id");
+        let continues_previous = affixes.last().is_some_and(|a| id.is_empty() || a.id == id);
+
+        let result = (|| -> Result<(), String> {
+            let tier = parse_tier(&header, row)?;
+
+            if continues_previous {
+                affixes.last_mut().unwrap().tiers.push(tier);
+                return Ok(());
+            }
+
+            if id.is_empty() {
+                return Err("tier row has no affix above it".to_string());
+            }
+            if affixes.iter().any(|a| a.id == id) {
+                return Err(format!("duplicate affix id '{}'", id));
+            }
+
+            let scope = match header.get(row, "scope") {
+                "" => AffixScope::default(),
+                s => parse_enum(s, "scope")?,
+            };
+
+            affixes.push(AffixConfig {
+                id: id.to_string(),
+                name: required(header.get(row, "name"), "name")?.to_string(),
+                affix_type: parse_enum(header.get(row, "type"), "affix type")?,
+                stat: parse_enum(header.get(row, "stat"), "stat")?,
+                scope,
+                tags: parse_list(header.get(row, "tags")),
+                allowed_classes: parse_list(header.get(row, "allowed_classes"))
+                    .iter()
+                    .map(|c| parse_enum(c, "item class"))
+                    .collect::<Result<_, _>>()?,
+                tiers: vec![tier],
+                annul_weight: None,
+                flavor: None,
+                extra: Default::default(),
+            });
+            Ok(())
+        })();
+
+        if let Err(message) = result {
+            errors.push(ImportError { line, message });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(affixes)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse base types from CSV.
+///
+/// Columns (by header name): `id, name, class` are required; `tags, blocked_tags, level,
+/// strength, dexterity, constitution, intelligence, wisdom, charisma, implicit_stat, implicit_min,
+/// implicit_max, armour, evasion, energy_shield, damage, attack_speed, critical_chance,
+/// spell_efficiency` are optional. Defense columns are `min-max` ranges and `damage` is a
+/// `;`-separated list of `type:min-max` entries.
+pub fn parse_base_types_csv(text: &str) -> Result<Vec<BaseTypeConfig>, Vec<ImportError>> {
+    let (header, rows) = read_csv(text).map_err(|e| vec![e])?;
+    header.require(&["id", "name", "class"])?;
+
+    let mut base_types: Vec<BaseTypeConfig> = Vec::new();
+    let mut errors = Vec::new();
+
+    for row in &rows {
+        let result = (|| -> Result<BaseTypeConfig, String> {
+            let id = required(header.get(row, "id"), "id")?;
+            if base_types.iter().any(|b| b.id == id) {
+                return Err(format!("duplicate base type id '{}'", id));
+            }
+
+            let implicits = match header.get(row, "implicit_stat") {
+                "" => Vec::new(),
+                stat => vec![ImplicitConfig {
+                    stat: parse_enum(stat, "stat")?,
+                    min: parse_number(header.get(row, "implicit_min"), "implicit_min")?,
+                    max: parse_number(header.get(row, "implicit_max"), "implicit_max")?,
+                    weight: None,
+                }],
+            };
+
+            let armour = parse_optional_range(header.get(row, "armour"), "armour")?;
+            let evasion = parse_optional_range(header.get(row, "evasion"), "evasion")?;
+            let energy_shield =
+                parse_optional_range(header.get(row, "energy_shield"), "energy_shield")?;
+            let defenses = if armour.is_some() || evasion.is_some() || energy_shield.is_some() {
+                Some(DefensesConfig {
+                    armour,
+                    evasion,
+                    energy_shield,
+                })
+            } else {
+                None
+            };
+
+            let damage = match header.get(row, "damage") {
+                "" => None,
+                damages => Some(DamageConfig {
+                    damages: parse_list(damages)
+                        .iter()
+                        .map(|d| parse_damage_entry(d))
+                        .collect::<Result<_, _>>()?,
+                    attack_speed: parse_optional_number(
+                        header.get(row, "attack_speed"),
+                        "attack_speed",
+                    )?,
+                    critical_chance: parse_optional_number(
+                        header.get(row, "critical_chance"),
+                        "critical_chance",
+                    )?,
+                    spell_efficiency: parse_optional_number(
+                        header.get(row, "spell_efficiency"),
+                        "spell_efficiency",
+                    )?,
+                }),
+            };
+
+            let mut requirements = Requirements::default();
+            for (column, field) in [
+                ("level", &mut requirements.level),
+                ("strength", &mut requirements.strength),
+                ("dexterity", &mut requirements.dexterity),
+                ("constitution", &mut requirements.constitution),
+                ("intelligence", &mut requirements.intelligence),
+                ("wisdom", &mut requirements.wisdom),
+                ("charisma", &mut requirements.charisma),
+            ] {
+                *field = parse_optional_number(header.get(row, column), column)?;
+            }
+
+            Ok(BaseTypeConfig {
+                id: id.to_string(),
+                name: required(header.get(row, "name"), "name")?.to_string(),
+                class: parse_enum(header.get(row, "class"), "item class")?,
+                group: match header.get(row, "group") {
+                    "" => None,
+                    group => Some(group.to_string()),
+                },
+                drop_weight: match header.get(row, "drop_weight") {
+                    "" => None,
+                    weight => Some(parse_number(weight, "drop_weight")?),
+                },
+                tags: parse_list(header.get(row, "tags")),
+                blocked_tags: parse_list(header.get(row, "blocked_tags")),
+                implicits,
+                quality_affects_implicit: false,
+                defenses,
+                damage,
+                requirements,
+                extra: Default::default(),
+            })
+        })();
+
+        match result {
+            Ok(bt) => base_types.push(bt),
+            Err(message) => errors.push(ImportError {
+                line: row.line,
+                message,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(base_types)
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_tier(header: &Header, row: &Row) -> Result<AffixTierConfig, String> {
+    let max_min = header.get(row, "max_min");
+    let max_max = header.get(row, "max_max");
+    let max_value = match (max_min.is_empty(), max_max.is_empty()) {
+        (true, true) => None,
+        (false, false) => Some(RollRange {
+            min: parse_number(max_min, "max_min")?,
+            max: parse_number(max_max, "max_max")?,
+        }),
+        _ => return Err("max_min and max_max must be given together".to_string()),
+    };
+
+    Ok(AffixTierConfig {
+        tier: parse_number(header.get(row, "tier"), "tier")?,
+        weight: parse_number(header.get(row, "weight"), "weight")?,
+        min: parse_number(header.get(row, "min"), "min")?,
+        max: parse_number(header.get(row, "max"), "max")?,
+        max_value,
+        min_ilvl: parse_optional_number(header.get(row, "min_ilvl"), "min_ilvl")?,
+        step: match header.get(row, "step") {
+            "" => None,
+            step => Some(parse_number(step, "step")?),
+        },
+        extra_stats: Vec::new(),
+    })
+}
+
+fn parse_damage_entry(value: &str) -> Result<DamageEntry, String> {
+    let (damage_type, range) = value
+        .split_once(':')
+        .ok_or_else(|| format!("damage entry '{}' should be type:min-max", value))?;
+    let range = parse_range(range, "damage")?;
+    Ok(DamageEntry {
+        damage_type: parse_enum(damage_type, "damage type")?,
+        min: range.min,
+        max: range.max,
+    })
+}
+
+fn parse_optional_range(value: &str, column: &str) -> Result<Option<RollRange>, String> {
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        parse_range(value, column).map(Some)
+    }
+}
+
+fn parse_range(value: &str, column: &str) -> Result<RollRange, String> {
+    let (min, max) = value
+        .trim()
+        .split_once('-')
+        .ok_or_else(|| format!("{} '{}' should be a min-max range", column, value))?;
+    Ok(RollRange {
+        min: parse_number(min, column)?,
+        max: parse_number(max, column)?,
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, column: &str) -> Result<T, String> {
+    let value = required(value, column)?;
+    value
+        .parse()
+        .map_err(|_| format!("{} '{}' is not a valid number", column, value))
+}
+
+fn parse_optional_number<T: std::str::FromStr + Default>(
+    value: &str,
+    column: &str,
+) -> Result<T, String> {
+    if value.is_empty() {
+        Ok(T::default())
+    } else {
+        parse_number(value, column)
+    }
+}
+
+/// Parse a snake_case enum value the same way the TOML loader does
+fn parse_enum<T: DeserializeOwned>(value: &str, what: &str) -> Result<T, String> {
+    let value = required(value, what)?;
+    let deserializer: StrDeserializer<serde::de::value::Error> = value.into_deserializer();
+    T::deserialize(deserializer).map_err(|_| format!("unknown {} '{}'", what, value))
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn required<'a>(value: &'a str, column: &str) -> Result<&'a str, String> {
+    if value.is_empty() {
+        Err(format!("missing {}", column))
+    } else {
+        Ok(value)
+    }
+}
+
+// Minimal CSV reader: quoted fields may contain commas and "" escapes, but not newlines.
+// Blank lines and lines starting with '#' are skipped.
+
+struct Header {
+    line: usize,
+    columns: HashMap<String, usize>,
+}
+
+struct Row {
+    line: usize,
+    fields: Vec<String>,
+}
+
+impl Header {
+    fn require(&self, columns: &[&str]) -> Result<(), Vec<ImportError>> {
+        let missing: Vec<ImportError> = columns
+            .iter()
+            .filter(|c| !self.columns.contains_key(**c))
+            .map(|c| ImportError {
+                line: self.line,
+                message: format!("missing column '{}'", c),
+            })
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Get a trimmed field by column name (empty if the column or field is absent)
+    fn get<'a>(&self, row: &'a Row, column: &str) -> &'a str {
+        self.columns
+            .get(column)
+            .and_then(|&i| row.fields.get(i))
+            .map(|s| s.trim())
+            .unwrap_or("")
+    }
+}
+
+fn read_csv(text: &str) -> Result<(Header, Vec<Row>), ImportError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+
+    let (header_line, header_text) = lines.next().ok_or(ImportError {
+        line: 1,
+        message: "file has no header row".to_string(),
+    })?;
+
+    let columns = split_csv_line(header_text)
+        .map_err(|message| ImportError {
+            line: header_line,
+            message,
+        })?
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim().to_lowercase(), i))
+        .collect();
+
+    let mut rows = Vec::new();
+    for (line, text) in lines {
+        let fields = split_csv_line(text).map_err(|message| ImportError { line, message })?;
+        rows.push(Row { line, fields });
+    }
+
+    Ok((
+        Header {
+            line: header_line,
+            columns,
+        },
+        rows,
+    ))
+}
+
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const AFFIXES_CSV: &str = "\
+id,name,type,stat,scope,tags,allowed_classes,tier,weight,min,max,max_min,max_max,min_ilvl
+# Designer sheet export
+csv_added_life,Hale,suffix,added_life,global,life;defense,,1,100,10,19,,,1
+,,,,,,,2,50,20,29,,,10
+csv_frost,Icy,prefix,added_cold_damage,local,cold,\"one_hand_sword;bow\",1,100,2,4,6,9,1
+";
+
+    const BASE_TYPES_CSV: &str = "\
+id,name,class,tags,level,strength,armour,damage,attack_speed,critical_chance
+csv_blade,Sheet Blade,one_hand_sword,melee;sword,5,12,,physical:4-9;fire:1-2,1.4,5
+csv_vest,Sheet Vest,body_armour,armour,3,,20-30,,,
+";
+
+    #[test]
+    fn test_parse_affixes_csv() {
+        let affixes = parse_affixes_csv(AFFIXES_CSV).unwrap();
+        assert_eq!(affixes.len(), 2);
+
+        let life = &affixes[0];
+        assert_eq!(life.id, "csv_added_life");
+        assert_eq!(life.affix_type, AffixType::Suffix);
+        assert_eq!(life.stat, StatType::AddedLife);
+        assert_eq!(life.scope, AffixScope::Global);
+        assert_eq!(life.tags, vec!["life", "defense"]);
+        assert_eq!(life.tiers.len(), 2);
+        assert_eq!(life.tiers[1].min_ilvl, 10);
+
+        let frost = &affixes[1];
+        assert_eq!(
+            frost.allowed_classes,
+            vec![ItemClass::OneHandSword, ItemClass::Bow]
+        );
+        assert_eq!(
+            frost.tiers[0].max_value.map(|r| (r.min, r.max)),
+            Some((6, 9))
+        );
+    }
+
+    #[test]
+    fn test_parse_base_types_csv() {
+        let base_types = parse_base_types_csv(BASE_TYPES_CSV).unwrap();
+        assert_eq!(base_types.len(), 2);
+
+        let blade = &base_types[0];
+        assert_eq!(blade.class, ItemClass::OneHandSword);
+        assert_eq!(blade.requirements.strength, 12);
+        let damage = blade.damage.as_ref().unwrap();
+        assert_eq!(damage.damages.len(), 2);
+        assert_eq!(damage.damages[1].damage_type, DamageType::Fire);
+
+        let vest = &base_types[1];
+        assert!(vest.damage.is_none());
+        let armour = vest.defenses.as_ref().unwrap().armour.unwrap();
+        assert_eq!((armour.min, armour.max), (20, 30));
+    }
+
+    #[test]
+    fn test_imported_config_validates() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        ImportedEntries {
+            base_types: parse_base_types_csv(BASE_TYPES_CSV).unwrap(),
+            affixes: parse_affixes_csv(AFFIXES_CSV).unwrap(),
+        }
+        .merge_into(&mut config);
+
+        assert!(config.affixes.contains_key("csv_frost"));
+        assert!(config.base_types.contains_key("csv_vest"));
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_row_errors_report_line_numbers() {
+        let csv = "\
+id,name,type,stat,tier,weight,min,max
+ok_affix,Fine,prefix,added_life,1,100,1,5
+bad_stat,Broken,prefix,not_a_stat,1,100,1,5
+
+bad_number,Broken,suffix,added_mana,1,lots,1,5
+";
+        let errors = parse_affixes_csv(csv).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, 3);
+        assert!(errors[0].message.contains("not_a_stat"));
+        assert_eq!(errors[1].line, 5);
+        assert!(errors[1].message.contains("weight"));
+    }
+
+    #[test]
+    fn test_missing_columns_are_reported() {
+        let errors = parse_base_types_csv("id,name\nfoo,Foo\n").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 1);
+        assert!(errors[0].message.contains("class"));
+    }
+}
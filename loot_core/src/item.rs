@@ -1,16 +1,20 @@
-use crate::config::{AffixConfig, AffixTierConfig, BaseTypeConfig};
+use crate::config::{AffixConfig, AffixTierConfig, BaseTypeConfig, RecipeAffixRequirement};
 use crate::storage::Operation;
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 
 /// A fully realized item with all stats computed
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     // === Storage fields (for serialization) ===
     /// RNG seed used to generate this item
     pub seed: u64,
     /// Operations applied to this item (for deterministic reconstruction)
     pub operations: Vec<Operation>,
+    /// Optional wall-clock time (Unix seconds) of each operation, parallel to `operations`.
+    /// For audit trails only: replay ignores it.
+    #[serde(default)]
+    pub operation_times: Vec<Option<u64>>,
 
     // === Computed fields ===
     /// Reference to the base type ID
@@ -27,19 +31,50 @@ pub struct Item {
     pub tags: Vec<Tag>,
     /// Requirements to equip
     pub requirements: Requirements,
-    /// Implicit modifier (if any)
-    pub implicit: Option<Modifier>,
+    /// Implicit modifiers from the base type (older saves stored a single `implicit`)
+    #[serde(
+        default,
+        alias = "implicit",
+        deserialize_with = "crate::config::one_or_many"
+    )]
+    pub implicits: Vec<Modifier>,
     /// Rolled prefix modifiers
     pub prefixes: Vec<Modifier>,
     /// Rolled suffix modifiers
     pub suffixes: Vec<Modifier>,
+    /// Enchant from an `enchant_pool` currency. It doesn't take a prefix or suffix slot,
+    /// and currencies that change affixes leave it alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enchant: Option<Modifier>,
     /// Base defenses (for armour)
     pub defenses: Defenses,
     /// Base damage (for weapons)
     pub damage: Option<WeaponDamage>,
+    /// Quality percentage, up to `Item::MAX_QUALITY`
+    #[serde(default)]
+    pub quality: u32,
+    /// What the quality boosts
+    #[serde(default)]
+    pub quality_target: QualityTarget,
+    /// Copied from the base type: base quality also scales the implicits
+    #[serde(default)]
+    pub quality_affects_implicit: bool,
+    /// Set on copies made by `Generator::mirror`; mirrored items can't be modified
+    #[serde(default)]
+    pub mirrored: bool,
+    /// Affixes the user is crafting toward. Guidance only: replay ignores it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target: Vec<RecipeAffixRequirement>,
+    /// When the item entered its inventory, counting up from 1 (0 if never numbered), so
+    /// views can reorder items and still tell them apart. Replay ignores it.
+    #[serde(default)]
+    pub created_index: u64,
 }
 
 impl Item {
+    /// Highest quality currencies can raise an item to
+    pub const MAX_QUALITY: u32 = 20;
+
     /// Create a new normal (white) item from a base type with a seed
     pub(crate) fn new_normal(base: &BaseTypeConfig, seed: u64) -> Self {
         let defenses = if let Some(ref def) = base.defenses {
@@ -70,6 +105,7 @@ impl Item {
         Item {
             seed,
             operations: Vec::new(),
+            operation_times: Vec::new(),
             base_type_id: base.id.clone(),
             name: base.name.clone(),
             base_name: base.name.clone(),
@@ -77,17 +113,120 @@ impl Item {
             rarity: Rarity::Normal,
             tags: base.tags.clone(),
             requirements: base.requirements.clone(),
-            implicit: None, // Will be rolled with seed
+            implicits: Vec::new(), // Will be rolled with seed
             prefixes: Vec::new(),
             suffixes: Vec::new(),
+            enchant: None,
             defenses,
             damage,
+            quality: 0,
+            quality_target: QualityTarget::default(),
+            quality_affects_implicit: base.quality_affects_implicit,
+            mirrored: false,
+            target: Vec::new(),
+            created_index: 0,
         }
     }
 
     /// Record that a currency was applied to this item
     pub(crate) fn record_currency(&mut self, currency_id: impl Into<String>) {
-        self.operations.push(Operation::Currency(currency_id.into()));
+        self.operations
+            .push(Operation::Currency(currency_id.into()));
+        self.operation_times.push(None);
+    }
+
+    /// Record that a currency was applied with some modifiers locked
+    pub(crate) fn record_locked_currency(
+        &mut self,
+        currency_id: impl Into<String>,
+        locked: Vec<String>,
+    ) {
+        self.operations.push(Operation::LockedCurrency {
+            currency_id: currency_id.into(),
+            locked,
+        });
+        self.operation_times.push(None);
+    }
+
+    /// Record that a specific affix was added to this item
+    pub(crate) fn record_add_affix(&mut self, affix_id: impl Into<String>, tier: u32) {
+        self.operations.push(Operation::AddAffix {
+            affix_id: affix_id.into(),
+            tier,
+        });
+        self.operation_times.push(None);
+    }
+
+    /// Record that this item was mirrored
+    pub(crate) fn record_mirror(&mut self) {
+        self.operations.push(Operation::Mirror);
+        self.operation_times.push(None);
+    }
+
+    /// Record that this item was generated as a unique
+    pub(crate) fn record_unique(&mut self, unique_id: impl Into<String>) {
+        self.operations.push(Operation::Unique(unique_id.into()));
+        self.operation_times.push(None);
+    }
+
+    /// Record that this item was generated directly at a rarity
+    pub(crate) fn record_rarity(&mut self, rarity: Rarity) {
+        self.operations.push(Operation::Rarity(rarity));
+        self.operation_times.push(None);
+    }
+
+    /// Operations applied after the item dropped, i.e. all but a leading
+    /// `Operation::Unique` or `Operation::Rarity`
+    pub fn crafting_operations(&self) -> &[Operation] {
+        match self.operations.first() {
+            Some(Operation::Unique(_) | Operation::Rarity(_)) => &self.operations[1..],
+            _ => &self.operations,
+        }
+    }
+
+    /// Timestamp the most recent operation (Unix seconds)
+    pub fn stamp_last_operation(&mut self, unix_secs: u64) {
+        self.operation_times.resize(self.operations.len(), None);
+        if let Some(time) = self.operation_times.last_mut() {
+            *time = Some(unix_secs);
+        }
+    }
+
+    /// Timestamp of the operation at `index`, if one was recorded
+    pub fn operation_time(&self, index: usize) -> Option<u64> {
+        self.operation_times.get(index).copied().flatten()
+    }
+
+    /// Magic-style name: "<Prefix> <Base> <Suffix>" from the first prefix and suffix
+    pub fn magic_name(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        if let Some(prefix) = self.prefixes.first() {
+            parts.push(&prefix.name);
+        }
+        parts.push(&self.base_name);
+        if let Some(suffix) = self.suffixes.first() {
+            parts.push(&suffix.name);
+        }
+        parts.join(" ")
+    }
+
+    /// Explicit modifiers in display order, each with its affix type and its index
+    /// within `prefixes` or `suffixes`
+    pub fn ordered_explicits(&self, order: ModOrder) -> Vec<(AffixType, usize, &Modifier)> {
+        let mut mods: Vec<(AffixType, usize, &Modifier)> = self
+            .prefixes
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (AffixType::Prefix, i, m))
+            .chain(
+                self.suffixes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| (AffixType::Suffix, i, m)),
+            )
+            .collect();
+        mods.sort_by(|a, b| order.compare((a.0, a.2.stat), (b.0, b.2.stat)));
+        mods
     }
 
     /// Count total affixes
@@ -95,6 +234,14 @@ impl Item {
         self.prefixes.len() + self.suffixes.len()
     }
 
+    /// Each requirement of the crafting target, paired with whether the item meets it
+    pub fn target_progress(&self) -> Vec<(&RecipeAffixRequirement, bool)> {
+        self.target
+            .iter()
+            .map(|req| (req, crate::currency::affix_requirement_met(req, self)))
+            .collect()
+    }
+
     /// Check if item can have more prefixes
     pub fn can_add_prefix(&self) -> bool {
         self.prefixes.len() < self.rarity.max_prefixes()
@@ -105,6 +252,297 @@ impl Item {
         self.suffixes.len() < self.rarity.max_suffixes()
     }
 
+    /// Modifiers (implicit, explicit and enchant) that apply to the item itself rather than
+    /// the character
+    pub fn local_modifiers(&self) -> impl Iterator<Item = &Modifier> {
+        self.implicits
+            .iter()
+            .chain(self.prefixes.iter())
+            .chain(self.suffixes.iter())
+            .chain(self.enchant.iter())
+            .filter(|m| m.scope == AffixScope::Local)
+    }
+
+    /// A modifier's rolled values (min and optional max) after quality, when the quality
+    /// target covers its stat
+    pub fn quality_scaled(&self, modifier: &Modifier) -> (i32, Option<i32>) {
+        self.quality_scaled_stat(modifier.stat, modifier.value, modifier.value_max)
+    }
+
+    /// One stat's rolled values after quality, when the quality target covers the stat
+    pub fn quality_scaled_stat(
+        &self,
+        stat: StatType,
+        value: i32,
+        value_max: Option<i32>,
+    ) -> (i32, Option<i32>) {
+        self.scale_by_quality(value, value_max, self.quality_target.boosts_stat(stat))
+    }
+
+    /// An implicit's rolled values after quality. Base quality scales them when the base
+    /// type sets `quality_affects_implicit`; otherwise they scale like any other modifier.
+    pub fn quality_scaled_implicit(&self, modifier: &Modifier) -> (i32, Option<i32>) {
+        self.quality_scaled_implicit_stat(modifier.stat, modifier.value, modifier.value_max)
+    }
+
+    fn quality_scaled_implicit_stat(
+        &self,
+        stat: StatType,
+        value: i32,
+        value_max: Option<i32>,
+    ) -> (i32, Option<i32>) {
+        let boosted = (self.quality_affects_implicit && self.quality_target == QualityTarget::Base)
+            || self.quality_target.boosts_stat(stat);
+        self.scale_by_quality(value, value_max, boosted)
+    }
+
+    fn scale_by_quality(
+        &self,
+        value: i32,
+        value_max: Option<i32>,
+        boosted: bool,
+    ) -> (i32, Option<i32>) {
+        if self.quality == 0 || !boosted {
+            return (value, value_max);
+        }
+        let percent = self.quality as i32;
+        (
+            apply_increase(value, percent),
+            value_max.map(|v| apply_increase(v, percent)),
+        )
+    }
+
+    /// Sum of every modifier (implicit, explicit and enchant, local and global) granting
+    /// `stat`, after quality. Hybrid modifiers count towards each stat they grant.
+    pub fn stat_total(&self, stat: StatType) -> i32 {
+        self.stat_range_total(stat).0
+    }
+
+    /// Every stat the item's modifiers grant, in modifier order, with its total as in
+    /// [`Item::stat_total`]. Ranged stats (added damage) also total their upper rolls.
+    pub fn total_stats(&self) -> Vec<(StatType, i32, Option<i32>)> {
+        let mut stats: Vec<StatType> = Vec::new();
+        for (stat, _, _) in self.all_stat_rolls() {
+            if !stats.contains(&stat) {
+                stats.push(stat);
+            }
+        }
+        stats
+            .into_iter()
+            .map(|stat| {
+                let (total, total_max) = self.stat_range_total(stat);
+                (stat, total, total_max)
+            })
+            .collect()
+    }
+
+    /// Sum of the (value, value_max) rolls granting `stat`, after quality. The max is
+    /// only set when some roll has one; rolls without a max count their value towards it.
+    fn stat_range_total(&self, stat: StatType) -> (i32, Option<i32>) {
+        let mut total = 0;
+        let mut total_max = None;
+        for (_, value, value_max) in self.all_stat_rolls().filter(|&(s, _, _)| s == stat) {
+            if total_max.is_some() || value_max.is_some() {
+                total_max = Some(total_max.unwrap_or(total) + value_max.unwrap_or(value));
+            }
+            total += value;
+        }
+        (total, total_max)
+    }
+
+    /// Every stat granted by any modifier (implicit, explicit and enchant), after quality
+    fn all_stat_rolls(&self) -> impl Iterator<Item = (StatType, i32, Option<i32>)> + '_ {
+        let implicits = self.implicits.iter().flat_map(|m| m.stat_rolls());
+        let implicits = implicits.map(|(stat, value, value_max)| {
+            let (value, value_max) = self.quality_scaled_implicit_stat(stat, value, value_max);
+            (stat, value, value_max)
+        });
+        let explicits = self
+            .prefixes
+            .iter()
+            .chain(self.suffixes.iter())
+            .chain(self.enchant.iter())
+            .flat_map(|m| m.stat_rolls())
+            .map(|(stat, value, value_max)| {
+                let (value, value_max) = self.quality_scaled_stat(stat, value, value_max);
+                (stat, value, value_max)
+            });
+        implicits.chain(explicits)
+    }
+
+    /// Every stat granted by local modifiers, hybrid extras included, after quality
+    fn local_stat_rolls(&self) -> impl Iterator<Item = (StatType, i32, Option<i32>)> + '_ {
+        let local = |m: &&Modifier| m.scope == AffixScope::Local;
+        let implicits = self
+            .implicits
+            .iter()
+            .filter(local)
+            .flat_map(|m| m.stat_rolls())
+            .map(|(stat, value, value_max)| {
+                let (value, value_max) = self.quality_scaled_implicit_stat(stat, value, value_max);
+                (stat, value, value_max)
+            });
+        let explicits = self
+            .prefixes
+            .iter()
+            .chain(self.suffixes.iter())
+            .chain(self.enchant.iter())
+            .filter(local)
+            .flat_map(|m| m.stat_rolls())
+            .map(|(stat, value, value_max)| {
+                let (value, value_max) = self.quality_scaled_stat(stat, value, value_max);
+                (stat, value, value_max)
+            });
+        implicits.chain(explicits)
+    }
+
+    /// Weapon damage with local modifiers applied (global modifiers are ignored)
+    pub fn computed_damage(&self) -> Option<WeaponDamage> {
+        let mut damage = self.damage.clone()?;
+
+        // Increased damage per type, so hybrid weapons scale each portion separately
+        let mut increased: Vec<(DamageType, i32)> = Vec::new();
+        let mut increased_attack_speed = 0;
+        let mut increased_crit = 0;
+
+        for (stat, value, value_max) in self.local_stat_rolls() {
+            let added_type = match stat {
+                StatType::AddedPhysicalDamage => Some(DamageType::Physical),
+                StatType::AddedFireDamage => Some(DamageType::Fire),
+                StatType::AddedColdDamage => Some(DamageType::Cold),
+                StatType::AddedLightningDamage => Some(DamageType::Lightning),
+                StatType::AddedChaosDamage => Some(DamageType::Chaos),
+                _ => None,
+            };
+
+            if let Some(damage_type) = added_type {
+                let min = value;
+                let max = value_max.unwrap_or(value);
+                match damage
+                    .damages
+                    .iter_mut()
+                    .find(|d| d.damage_type == damage_type)
+                {
+                    Some(entry) => {
+                        entry.min += min;
+                        entry.max += max;
+                    }
+                    None => damage.damages.push(DamageValue {
+                        damage_type,
+                        min,
+                        max,
+                    }),
+                }
+                continue;
+            }
+
+            for &damage_type in stat.increased_damage_types() {
+                add_increase(&mut increased, damage_type, value);
+            }
+            match stat {
+                StatType::IncreasedAttackSpeed => increased_attack_speed += value,
+                StatType::IncreasedCriticalChance => increased_crit += value,
+                _ => {}
+            }
+        }
+        if self.quality_target.boosts_damage() {
+            add_increase(&mut increased, DamageType::Physical, self.quality as i32);
+        }
+
+        for (damage_type, percent) in increased {
+            for entry in damage
+                .damages
+                .iter_mut()
+                .filter(|d| d.damage_type == damage_type)
+            {
+                entry.min = apply_increase(entry.min, percent);
+                entry.max = apply_increase(entry.max, percent);
+            }
+        }
+        damage.attack_speed *= 1.0 + increased_attack_speed as f32 / 100.0;
+        damage.critical_chance *= 1.0 + increased_crit as f32 / 100.0;
+
+        Some(damage)
+    }
+
+    /// Damage per second from local stats (average hit times attack speed)
+    pub fn dps(&self) -> Option<f32> {
+        let damage = self.computed_damage()?;
+        let average_hit: f32 = damage
+            .damages
+            .iter()
+            .map(|d| (d.min + d.max) as f32 / 2.0)
+            .sum();
+        Some(average_hit * damage.attack_speed)
+    }
+
+    /// Defenses with local modifiers applied (global modifiers are ignored)
+    pub fn computed_defenses(&self) -> Defenses {
+        let mut defenses = self.defenses.clone();
+
+        let mut increased_armour = 0;
+        let mut increased_evasion = 0;
+        let mut increased_es = 0;
+
+        for (stat, value, _) in self.local_stat_rolls() {
+            match stat {
+                StatType::AddedArmour => {
+                    *defenses.armour.get_or_insert(0) += value;
+                }
+                StatType::AddedEvasion => {
+                    *defenses.evasion.get_or_insert(0) += value;
+                }
+                StatType::AddedEnergyShield => {
+                    *defenses.energy_shield.get_or_insert(0) += value;
+                }
+                StatType::IncreasedArmour => increased_armour += value,
+                StatType::IncreasedEvasion => increased_evasion += value,
+                StatType::IncreasedEnergyShield => increased_es += value,
+                _ => {}
+            }
+        }
+        if self.quality_target.boosts_defenses() {
+            increased_armour += self.quality as i32;
+            increased_evasion += self.quality as i32;
+            increased_es += self.quality as i32;
+        }
+
+        defenses.armour = defenses.armour.map(|v| apply_increase(v, increased_armour));
+        defenses.evasion = defenses
+            .evasion
+            .map(|v| apply_increase(v, increased_evasion));
+        defenses.energy_shield = defenses
+            .energy_shield
+            .map(|v| apply_increase(v, increased_es));
+
+        defenses
+    }
+
+    /// Requirements after local "reduced requirements" modifiers. Reductions add up;
+    /// negative values raise requirements instead, and results never drop below zero.
+    pub fn computed_requirements(&self) -> Requirements {
+        let reduced: i64 = self
+            .local_stat_rolls()
+            .filter(|&(stat, _, _)| stat == StatType::ReducedRequirements)
+            .map(|(_, value, _)| value as i64)
+            .sum();
+        if reduced == 0 {
+            return self.requirements.clone();
+        }
+
+        let scale = |value: u32| (value as i64 * (100 - reduced) / 100).max(0) as u32;
+        let reqs = &self.requirements;
+        Requirements {
+            level: scale(reqs.level),
+            strength: scale(reqs.strength),
+            dexterity: scale(reqs.dexterity),
+            constitution: scale(reqs.constitution),
+            intelligence: scale(reqs.intelligence),
+            wisdom: scale(reqs.wisdom),
+            charisma: scale(reqs.charisma),
+        }
+    }
+
     /// Export item to markdown format
     pub fn to_markdown(&self) -> String {
         let mut md = String::new();
@@ -112,6 +550,16 @@ impl Item {
         // Header with name
         md.push_str(&format!("## {}\n", self.name));
         md.push_str(&format!("**{}** ({:?})\n\n", self.base_name, self.rarity));
+        if self.quality > 0 {
+            md.push_str(&format!(
+                "Quality: +{}% ({})\n\n",
+                self.quality,
+                self.quality_target.describe()
+            ));
+        }
+        if self.mirrored {
+            md.push_str("*Mirrored*\n\n");
+        }
 
         // Defenses (for armour)
         if self.defenses.has_any() {
@@ -153,9 +601,12 @@ impl Item {
         }
 
         // Implicit
-        if let Some(ref imp) = self.implicit {
+        if !self.implicits.is_empty() {
             md.push_str("### Implicit\n");
-            md.push_str(&format!("- {}\n\n", imp.display()));
+            for imp in &self.implicits {
+                md.push_str(&format!("- {}\n", imp.display()));
+            }
+            md.push('\n');
         }
 
         // Explicit mods
@@ -170,24 +621,32 @@ impl Item {
             md.push('\n');
         }
 
+        // Enchant
+        if let Some(ref enchant) = self.enchant {
+            md.push_str("### Enchant\n");
+            md.push_str(&format!("- {}\n", enchant.display()));
+            md.push('\n');
+        }
+
         // Requirements
-        if self.requirements.level > 0
-            || self.requirements.strength > 0
-            || self.requirements.dexterity > 0
-            || self.requirements.intelligence > 0
+        let requirements = self.computed_requirements();
+        if requirements.level > 0
+            || requirements.strength > 0
+            || requirements.dexterity > 0
+            || requirements.intelligence > 0
         {
             let mut reqs = Vec::new();
-            if self.requirements.level > 0 {
-                reqs.push(format!("Level {}", self.requirements.level));
+            if requirements.level > 0 {
+                reqs.push(format!("Level {}", requirements.level));
             }
-            if self.requirements.strength > 0 {
-                reqs.push(format!("{} Str", self.requirements.strength));
+            if requirements.strength > 0 {
+                reqs.push(format!("{} Str", requirements.strength));
             }
-            if self.requirements.dexterity > 0 {
-                reqs.push(format!("{} Dex", self.requirements.dexterity));
+            if requirements.dexterity > 0 {
+                reqs.push(format!("{} Dex", requirements.dexterity));
             }
-            if self.requirements.intelligence > 0 {
-                reqs.push(format!("{} Int", self.requirements.intelligence));
+            if requirements.intelligence > 0 {
+                reqs.push(format!("{} Int", requirements.intelligence));
             }
             md.push_str(&format!("*Requires: {}*\n", reqs.join(", ")));
         }
@@ -196,8 +655,21 @@ impl Item {
     }
 }
 
+/// Add `percent` to the running increase for `damage_type`
+fn add_increase(increased: &mut Vec<(DamageType, i32)>, damage_type: DamageType, percent: i32) {
+    match increased.iter_mut().find(|(t, _)| *t == damage_type) {
+        Some((_, total)) => *total += percent,
+        None => increased.push((damage_type, percent)),
+    }
+}
+
+/// Scale a base value by a percentage increase, rounding to the nearest integer
+fn apply_increase(value: i32, percent: i32) -> i32 {
+    (value as f32 * (1.0 + percent as f32 / 100.0)).round() as i32
+}
+
 /// Defense values on an armour piece
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Defenses {
     pub armour: Option<i32>,
     pub evasion: Option<i32>,
@@ -211,7 +683,7 @@ impl Defenses {
 }
 
 /// Individual damage entry with type and range
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DamageValue {
     pub damage_type: DamageType,
     pub min: i32,
@@ -219,7 +691,7 @@ pub struct DamageValue {
 }
 
 /// Weapon damage values
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WeaponDamage {
     pub damages: Vec<DamageValue>,
     pub attack_speed: f32,
@@ -228,7 +700,7 @@ pub struct WeaponDamage {
 }
 
 /// A rolled modifier instance
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Modifier {
     /// Reference to the affix ID
     pub affix_id: String,
@@ -239,6 +711,10 @@ pub struct Modifier {
     /// Whether this modifier applies locally to the item or globally to the character
     #[serde(default)]
     pub scope: AffixScope,
+    /// Tags of the affix it rolled from, so the item can be filtered without the config
+    /// (empty for implicits and unique mods)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
     /// The rolled tier
     pub tier: u32,
     /// The rolled value within the tier's range (or min value for damage ranges)
@@ -253,6 +729,20 @@ pub struct Modifier {
     /// For damage range stats: the tier range for the max value
     #[serde(default)]
     pub tier_max_value: Option<(i32, i32)>,
+    /// For hybrid affixes: the further stats rolled together with `stat`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_stats: Vec<StatRoll>,
+}
+
+/// A rolled extra stat of a hybrid modifier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatRoll {
+    pub stat: StatType,
+    pub value: i32,
+    /// Minimum value for this stat in the tier
+    pub tier_min: i32,
+    /// Maximum value for this stat in the tier
+    pub tier_max: i32,
 }
 
 impl Modifier {
@@ -262,26 +752,85 @@ impl Modifier {
         tier: &AffixTierConfig,
         value: i32,
         value_max: Option<i32>,
+        extra_stats: Vec<StatRoll>,
     ) -> Self {
         Modifier {
             affix_id: affix.id.clone(),
             name: affix.name.clone(),
             stat: affix.stat,
             scope: affix.scope,
+            tags: affix.tags.clone(),
             tier: tier.tier,
             value,
             value_max,
             tier_min: tier.min,
             tier_max: tier.max,
             tier_max_value: tier.max_value.map(|r| (r.min, r.max)),
+            extra_stats,
         }
     }
 
-    /// Display the modifier as a human-readable string
+    /// Each stat this modifier grants with its rolled value (and max for damage ranges),
+    /// the main stat first
+    pub fn stat_rolls(&self) -> impl Iterator<Item = (StatType, i32, Option<i32>)> + '_ {
+        std::iter::once((self.stat, self.value, self.value_max))
+            .chain(self.extra_stats.iter().map(|e| (e.stat, e.value, None)))
+    }
+
+    /// Stable identity of this roll (affix, tier and rolled values).
+    ///
+    /// Uses FNV-1a rather than `std`'s hasher so the result is identical across builds
+    /// and platforms.
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let value_max = match self.value_max {
+            Some(v) => [&[1u8][..], &v.to_le_bytes()].concat(),
+            None => vec![0u8],
+        };
+
+        // Only hybrids hash their extra values, so single-stat fingerprints are unchanged
+        let extra_values: Vec<u8> = self
+            .extra_stats
+            .iter()
+            .flat_map(|e| e.value.to_le_bytes())
+            .collect();
+
+        // The affix ID is length-prefixed so adjacent fields can't run together
+        [
+            &(self.affix_id.len() as u32).to_le_bytes()[..],
+            self.affix_id.as_bytes(),
+            &self.tier.to_le_bytes(),
+            &self.value.to_le_bytes(),
+            &value_max,
+            &extra_values,
+        ]
+        .iter()
+        .flat_map(|bytes| bytes.iter())
+        .fold(FNV_OFFSET, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Display the modifier as a human-readable string. A hybrid modifier joins its
+    /// stats with "and" (e.g. "+20 Added Life and +10 Added Mana").
     pub fn display(&self) -> String {
+        std::iter::once(Self::stat_display(self.stat, self.value, self.value_max))
+            .chain(
+                self.extra_stats
+                    .iter()
+                    .map(|e| Self::stat_display(e.stat, e.value, None)),
+            )
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+
+    /// One stat's rolled value as shown on an item (e.g. "+12% Increased Armour")
+    pub fn stat_display(stat: StatType, value: i32, value_max: Option<i32>) -> String {
         // Check if this is a flat damage stat with a range
-        if let Some(max_val) = self.value_max {
-            let damage_type = match self.stat {
+        if let Some(max_val) = value_max {
+            let damage_type = match stat {
                 StatType::AddedPhysicalDamage => Some("Physical"),
                 StatType::AddedFireDamage => Some("Fire"),
                 StatType::AddedColdDamage => Some("Cold"),
@@ -291,11 +840,16 @@ impl Modifier {
             };
 
             if let Some(dmg_type) = damage_type {
-                return format!("Adds {} to {} {} Damage", self.value, max_val, dmg_type);
+                return format!("Adds {} to {} {} Damage", value, max_val, dmg_type);
             }
         }
 
-        let stat_name = format!("{:?}", self.stat)
+        if stat == StatType::ReducedRequirements {
+            let direction = if value < 0 { "Increased" } else { "Reduced" };
+            return format!("{}% {} Requirements", value.abs(), direction);
+        }
+
+        let stat_name = format!("{:?}", stat)
             .chars()
             .fold(String::new(), |mut acc, c| {
                 if c.is_uppercase() && !acc.is_empty() {
@@ -307,8 +861,11 @@ impl Modifier {
 
         // Determine if this is a percentage or flat value based on stat type
         let is_percent = matches!(
-            self.stat,
+            stat,
             StatType::IncreasedPhysicalDamage
+                | StatType::IncreasedFireDamage
+                | StatType::IncreasedColdDamage
+                | StatType::IncreasedLightningDamage
                 | StatType::IncreasedElementalDamage
                 | StatType::IncreasedChaosDamage
                 | StatType::IncreasedAttackSpeed
@@ -392,9 +949,9 @@ impl Modifier {
         );
 
         if is_percent {
-            format!("+{}% {}", self.value, stat_name)
+            format!("+{}% {}", value, stat_name)
         } else {
-            format!("+{} {}", self.value, stat_name)
+            format!("+{} {}", value, stat_name)
         }
     }
 }
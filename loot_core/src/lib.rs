@@ -1,21 +1,583 @@
+//! Seeded loot generation, data-driven crafting and compact item storage.
+//!
+//! The crate has no UI dependencies. JSON import/export sits behind the default `json`
+//! feature; with `default-features = false` generation, crafting and binary storage
+//! still work:
+//!
+//! ```
+//! use loot_core::{BinaryDecode, BinaryEncode, Config, Generator, Item};
+//! use std::path::Path;
+//!
+//! let config = Config::load_from_dir(Path::new("../config")).unwrap();
+//! let generator = Generator::new(config);
+//! let item = generator.generate("iron_sword", 12345).unwrap();
+//! let item = generator.apply_currency(&item, "transmute").unwrap();
+//!
+//! let bytes = item.encode_to_vec();
+//! assert_eq!(Item::decode_from_slice(&bytes, &generator).unwrap(), item);
+//! ```
+
 pub mod config;
 pub mod currency;
+pub mod error;
 pub mod generator;
+pub mod import;
 pub mod item;
 pub mod storage;
 pub mod types;
 
 pub use config::Config;
 pub use currency::CurrencyError;
-pub use generator::Generator;
+pub use error::LootError;
+pub use generator::{AffixRoll, GenerationWarning, Generator};
 pub use item::Item;
-pub use storage::{BinaryDecode, BinaryEncode, DecodeError, ItemCollection, Operation};
+pub use storage::{
+    BinaryDecode, BinaryEncode, DecodeError, ItemCollection, Operation, ReplayMismatch,
+};
 pub use types::*;
 
 #[cfg(test)]
 mod tests {
+    use super::config::UniqueModConfig;
     use super::item::Modifier;
-    use super::types::{AffixScope, StatType};
+    use super::types::{AffixScope, DamageType, ItemClass, Rarity, StatType};
+    use super::{Config, Generator};
+    use std::path::Path;
+
+    fn make_generator() -> Generator {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+        Generator::new(config)
+    }
+
+    fn make_modifier(stat: StatType, scope: AffixScope, value: i32) -> Modifier {
+        Modifier {
+            affix_id: "test".to_string(),
+            name: "Test".to_string(),
+            stat,
+            scope,
+            tags: Vec::new(),
+            tier: 1,
+            value,
+            value_max: None,
+            tier_min: value,
+            tier_max: value,
+            tier_max_value: None,
+            extra_stats: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_modifier_fingerprint() {
+        let a = make_modifier(StatType::AddedLife, AffixScope::Global, 10);
+        let same = make_modifier(StatType::AddedLife, AffixScope::Global, 10);
+        assert_eq!(a.fingerprint(), same.fingerprint());
+
+        // Distinct rolls of the same affix
+        let other_value = make_modifier(StatType::AddedLife, AffixScope::Global, 11);
+        assert_ne!(a.fingerprint(), other_value.fingerprint());
+
+        let mut other_tier = a.clone();
+        other_tier.tier = 2;
+        assert_ne!(a.fingerprint(), other_tier.fingerprint());
+
+        let mut with_max = a.clone();
+        with_max.value_max = Some(10);
+        assert_ne!(a.fingerprint(), with_max.fingerprint());
+
+        // The old "affix_id:value" keys collided here
+        let mut left = a.clone();
+        left.affix_id = "life:1".to_string();
+        left.value = 0;
+        let mut right = a.clone();
+        right.affix_id = "life".to_string();
+        right.value = 1;
+        assert_ne!(left.fingerprint(), right.fingerprint());
+    }
+
+    #[test]
+    fn test_config_dir_defaults_to_config() {
+        assert_eq!(Config::dir_from_arg(None), Path::new("config"));
+        assert_eq!(
+            Config::dir_from_arg(Some("rulesets/hardcore")),
+            Path::new("rulesets/hardcore")
+        );
+    }
+
+    #[test]
+    fn test_shipped_config_validates() {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_unique_base_type_checks() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        assert_eq!(config.validation_warnings(), Vec::new());
+
+        let mut unique = config.uniques["titans_grip"].clone();
+        unique.id = "lost_grip".to_string();
+        unique.base_type = "no_such_base".to_string();
+        config.uniques.insert(unique.id.clone(), unique.clone());
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].entry, "unique 'lost_grip'");
+        assert!(errors[0].message.contains("unknown base type"));
+
+        // Armour on a sword still generates, so it's only a warning
+        unique.base_type = "iron_sword".to_string();
+        unique.mods = vec![UniqueModConfig {
+            stat: StatType::IncreasedArmour,
+            min: 10,
+            max: 20,
+        }];
+        config.uniques.insert(unique.id.clone(), unique);
+        assert_eq!(config.validate(), Ok(()));
+        let warnings = config.validation_warnings();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert_eq!(warnings[0].entry, "unique 'lost_grip'");
+        assert!(warnings[0].message.contains("IncreasedArmour"));
+    }
+
+    #[test]
+    fn test_out_of_bounds_tier_is_flagged() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut affix = config
+            .affixes
+            .values()
+            .find(|a| a.stat == StatType::IncreasedAttackSpeed)
+            .unwrap()
+            .clone();
+        affix.id = "frantic".to_string();
+        affix.tiers[0].min = 20;
+        affix.tiers[0].max = 50;
+        config.affixes.insert(affix.id.clone(), affix.clone());
+        assert_eq!(config.validation_warnings(), Vec::new());
+
+        affix.tiers[0].max = 500;
+        config.affixes.insert(affix.id.clone(), affix.clone());
+        let warnings = config.validation_warnings();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert_eq!(warnings[0].entry, "affix 'frantic'");
+        assert!(warnings[0].message.contains("IncreasedAttackSpeed 20-500"));
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_unreachable_affix_is_reported() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut affix = config.affixes["added_fire_damage"].clone();
+        affix.id = "claw_only_fire".to_string();
+        affix.allowed_classes = vec![ItemClass::Claw];
+        config.affixes.insert(affix.id.clone(), affix.clone());
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].entry, "affix 'claw_only_fire'");
+        assert!(errors[0]
+            .message
+            .contains("no base type has an allowed class"));
+
+        // Reachable by class, but every tier is above the highest sword level
+        affix.allowed_classes = vec![ItemClass::OneHandSword];
+        for tier in &mut affix.tiers {
+            tier.min_ilvl = 90;
+        }
+        config.affixes.insert(affix.id.clone(), affix);
+        let errors = config.validate().unwrap_err();
+        assert!(errors[0].message.contains("needs item level 90"));
+    }
+
+    #[test]
+    fn test_contradictory_currency_effects_are_reported() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+
+        // Scouring to Normal leaves no slots for the added affix
+        let mut currency = config.currencies["transmute"].clone();
+        currency.id = "scour_and_add".to_string();
+        currency.effects.set_rarity = Some(Rarity::Normal);
+        config.currencies.insert(currency.id.clone(), currency);
+
+        // Clearing first leaves nothing for the reroll
+        let mut currency = config.currencies["divine"].clone();
+        currency.id = "clear_and_reroll".to_string();
+        currency.effects.clear_affixes = true;
+        config.currencies.insert(currency.id.clone(), currency);
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(errors[0].entry, "currency 'clear_and_reroll'");
+        assert!(errors[0].message.contains("reroll_affixes"));
+        assert_eq!(errors[1].entry, "currency 'scour_and_add'");
+        assert!(errors[1].message.contains("Normal"));
+    }
+
+    #[test]
+    fn test_extreme_tier_bias() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut currency = config.currencies["exalt"].clone();
+        currency.id = "very_lucky_exalt".to_string();
+        currency.effects.tier_bias = 16.0;
+        config.currencies.insert(currency.id.clone(), currency);
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].message.contains("tier_bias 16"));
+
+        // Even unvalidated, the biased weights can't overflow when totalled
+        let gen = Generator::new(config);
+        for seed in 0..20 {
+            let mut item = gen.generate("iron_sword", seed).unwrap();
+            item.rarity = Rarity::Rare;
+            item.requirements.level = 100;
+            let crafted = gen.apply_currency(&item, "very_lucky_exalt").unwrap();
+            assert_eq!(crafted.prefixes.len() + crafted.suffixes.len(), 1);
+            let odds = gen
+                .currency_outcome_odds(&item, "very_lucky_exalt")
+                .unwrap();
+            assert!(!odds.added.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_huge_tag_multiplier() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut tags: Vec<_> = config
+            .affixes
+            .values()
+            .flat_map(|a| a.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        let mut currency = config.currencies["exalt"].clone();
+        currency.id = "heavy_exalt".to_string();
+        currency.effects.weighted_tags = tags.into_iter().map(|tag| (tag, 1e7)).collect();
+        config.currencies.insert(currency.id.clone(), currency);
+
+        let errors = config.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .all(|e| e.message.contains("weighted_tags multiplier 10000000")));
+
+        // Even unvalidated, every affix capping at u32::MAX can't overflow the total
+        let gen = Generator::new(config);
+        for seed in 0..20 {
+            let mut item = gen.generate("iron_sword", seed).unwrap();
+            item.rarity = Rarity::Rare;
+            let crafted = gen.apply_currency(&item, "heavy_exalt").unwrap();
+            assert_eq!(crafted.prefixes.len() + crafted.suffixes.len(), 1);
+            let odds = gen.currency_outcome_odds(&item, "heavy_exalt").unwrap();
+            assert!(!odds.added.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_bench_craft_sets_fixed_value() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut bench = config.currencies["imbue_fire"].clone();
+        bench.id = "bench_fire".to_string();
+        bench.effects.add_specific_affix[0].tier = Some(2);
+        bench.effects.add_specific_affix[0].value = Some(12);
+        bench.effects.add_specific_affix[0].value_max = Some(25);
+        config.currencies.insert(bench.id.clone(), bench.clone());
+
+        let gen = Generator::new(config.clone());
+        let sword = gen.generate("iron_sword", 5).unwrap();
+        for seed in [5, 6, 7] {
+            let sword = gen.generate("iron_sword", seed).unwrap();
+            let crafted = gen.apply_currency(&sword, "bench_fire").unwrap();
+            assert_eq!(crafted.prefixes[0].affix_id, "added_fire_damage");
+            assert_eq!(crafted.prefixes[0].tier, 2);
+            assert_eq!(crafted.prefixes[0].value, 12);
+            assert_eq!(crafted.prefixes[0].value_max, Some(25));
+            let replayed = gen
+                .reconstruct(&crafted.base_type_id, crafted.seed, &crafted.operations)
+                .unwrap();
+            assert_eq!(replayed, crafted);
+        }
+
+        // Tier 2 rolls 9-16, so 40 can't be crafted and the config reports it
+        bench.effects.add_specific_affix[0].value = Some(40);
+        config.currencies.insert(bench.id.clone(), bench);
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors[0].entry, "currency 'bench_fire'");
+        assert!(errors[0].message.contains("outside tier 2 (9-16)"));
+        let gen = Generator::new(config);
+        assert!(matches!(
+            gen.apply_currency(&sword, "bench_fire"),
+            Err(super::CurrencyError::FixedValueOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+
+        let exact = config.find_currency_by_name("Chaos Orb");
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].id, "chaos");
+
+        let case_insensitive = config.find_currency_by_name("orb of ALCHEMY");
+        assert_eq!(case_insensitive.len(), 1);
+        assert_eq!(case_insensitive[0].id, "alchemy");
+        assert!(config.find_currency_by_name("Chaos").is_empty());
+
+        // Two affixes share this name; both come back in id order
+        let heavy: Vec<&str> = config
+            .find_affix_by_name("heavy")
+            .into_iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert_eq!(heavy, ["acc_added_physical", "added_physical_damage"]);
+    }
+
+    #[test]
+    fn test_sorted_iterators_follow_id_order() {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+
+        let is_sorted = |ids: Vec<&str>| ids.windows(2).all(|pair| pair[0] < pair[1]);
+        let affix_ids: Vec<&str> = config.affixes_sorted().map(|a| a.id.as_str()).collect();
+        assert_eq!(affix_ids.len(), config.affixes.len());
+        assert!(is_sorted(affix_ids));
+        assert!(is_sorted(
+            config.base_types_sorted().map(|b| b.id.as_str()).collect()
+        ));
+        assert!(is_sorted(
+            config.affix_pools_sorted().map(|p| p.id.as_str()).collect()
+        ));
+        assert!(is_sorted(
+            config.currencies_sorted().map(|c| c.id.as_str()).collect()
+        ));
+        assert!(is_sorted(
+            config.uniques_sorted().map(|u| u.id.as_str()).collect()
+        ));
+
+        // A custom key orders by it, with ties falling back to id order
+        let heavy_first: Vec<&str> = super::config::sorted_by_key(&config.affixes, |a| {
+            !a.name.eq_ignore_ascii_case("heavy")
+        })
+        .take(2)
+        .map(|a| a.id.as_str())
+        .collect();
+        assert_eq!(heavy_first, ["acc_added_physical", "added_physical_damage"]);
+
+        // Affix candidates for seeded rolls come back in the same order every time
+        let generator = Generator::new(config);
+        let suffixes: Vec<&str> = generator
+            .get_valid_affixes(ItemClass::OneHandSword, super::AffixType::Suffix)
+            .into_iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert!(!suffixes.is_empty());
+        assert!(is_sorted(suffixes));
+    }
+
+    #[test]
+    fn test_bundle_round_trips_directory_config() {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let bundle = config.to_bundle();
+
+        let path =
+            std::env::temp_dir().join(format!("loot_core_bundle_{}.toml", std::process::id()));
+        std::fs::write(&path, &bundle).unwrap();
+        let loaded = Config::load_bundle(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.to_bundle(), bundle);
+        assert_eq!(loaded.base_types.len(), config.base_types.len());
+        assert_eq!(loaded.affixes.len(), config.affixes.len());
+        assert_eq!(loaded.unique_recipes.len(), config.unique_recipes.len());
+        assert_eq!(loaded.generation, config.generation);
+        assert!(loaded.validate().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_affix_id_across_files() {
+        let dir = std::env::temp_dir().join(format!("loot_core_dup_ids_{}", std::process::id()));
+        let affixes_dir = dir.join("affixes");
+        std::fs::create_dir_all(&affixes_dir).unwrap();
+        let affix = r#"
+[[affixes]]
+id = "added_life"
+name = "Healthy"
+type = "prefix"
+stat = "added_life"
+
+[[affixes.tiers]]
+tier = 1
+weight = 100
+min = 10
+max = 20
+"#;
+        std::fs::write(affixes_dir.join("a.toml"), affix).unwrap();
+        std::fs::write(affixes_dir.join("b.toml"), affix).unwrap();
+
+        let result = Config::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        match result {
+            Err(super::config::ConfigError::DuplicateId { kind, id, files }) => {
+                assert_eq!(kind, "affix");
+                assert_eq!(id, "added_life");
+                assert_eq!(
+                    files,
+                    vec![affixes_dir.join("a.toml"), affixes_dir.join("b.toml")]
+                );
+            }
+            other => panic!("expected DuplicateId, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_base_with_two_implicits_displays_both() {
+        let dir = std::env::temp_dir().join(format!("loot_core_implicits_{}", std::process::id()));
+        let base_types_dir = dir.join("base_types");
+        std::fs::create_dir_all(&base_types_dir).unwrap();
+        let bases = r#"
+[[base_types]]
+id = "plain_ring"
+name = "Plain Ring"
+class = "ring"
+
+[base_types.implicit]
+stat = "added_life"
+min = 5
+max = 5
+
+[[base_types]]
+id = "twin_ring"
+name = "Twin Ring"
+class = "ring"
+
+[[base_types.implicit]]
+stat = "fire_resistance"
+min = 10
+max = 10
+
+[[base_types.implicit]]
+stat = "cold_resistance"
+min = 12
+max = 12
+"#;
+        std::fs::write(base_types_dir.join("rings.toml"), bases).unwrap();
+        let config = Config::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let generator = Generator::new(config.unwrap());
+
+        // A single table still parses as one implicit
+        let plain = generator.generate("plain_ring", 1).unwrap();
+        assert_eq!(plain.implicits.len(), 1);
+        assert_eq!(plain.stat_total(StatType::AddedLife), 5);
+
+        let twin = generator.generate("twin_ring", 1).unwrap();
+        let stats: Vec<StatType> = twin.implicits.iter().map(|m| m.stat).collect();
+        assert_eq!(
+            stats,
+            vec![StatType::FireResistance, StatType::ColdResistance]
+        );
+        let totals = twin.total_stats();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].0, StatType::FireResistance);
+        assert_eq!(totals[0].1, twin.stat_total(StatType::FireResistance));
+
+        let markdown = twin.to_markdown();
+        for implicit in &twin.implicits {
+            assert!(markdown.contains(&implicit.display()), "{}", markdown);
+        }
+
+        // Both implicits survive replay
+        let replayed = generator
+            .reconstruct("twin_ring", 1, &twin.operations)
+            .unwrap();
+        assert_eq!(replayed.implicits, twin.implicits);
+    }
+
+    #[test]
+    fn test_quality_scales_implicit_only_when_flagged() {
+        let dir =
+            std::env::temp_dir().join(format!("loot_core_quality_implicit_{}", std::process::id()));
+        let base_types_dir = dir.join("base_types");
+        std::fs::create_dir_all(&base_types_dir).unwrap();
+        let bases = r#"
+[[base_types]]
+id = "plain_belt"
+name = "Plain Belt"
+class = "belt"
+
+[base_types.implicit]
+stat = "added_life"
+min = 50
+max = 50
+
+[[base_types]]
+id = "woven_belt"
+name = "Woven Belt"
+class = "belt"
+quality_affects_implicit = true
+
+[base_types.implicit]
+stat = "added_life"
+min = 50
+max = 50
+"#;
+        std::fs::write(base_types_dir.join("belts.toml"), bases).unwrap();
+        let config = Config::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let generator = Generator::new(config.unwrap());
+
+        let mut plain = generator.generate("plain_belt", 1).unwrap();
+        let mut woven = generator.generate("woven_belt", 1).unwrap();
+        assert_eq!(woven.stat_total(StatType::AddedLife), 50);
+        plain.quality = 20;
+        woven.quality = 20;
+
+        assert_eq!(
+            plain.quality_scaled_implicit(&plain.implicits[0]),
+            (50, None)
+        );
+        assert_eq!(plain.stat_total(StatType::AddedLife), 50);
+        assert_eq!(
+            woven.quality_scaled_implicit(&woven.implicits[0]),
+            (60, None)
+        );
+        assert_eq!(woven.stat_total(StatType::AddedLife), 60);
+
+        // Only base quality carries over; a catalyst aimed elsewhere leaves it alone
+        woven.quality_target = super::QualityTarget::Damage;
+        assert_eq!(woven.stat_total(StatType::AddedLife), 50);
+    }
+
+    #[test]
+    fn test_resistance_catalyst_boosts_only_resistances() {
+        let gen = make_generator();
+        let mut sword = gen.generate("iron_sword", 3).unwrap();
+        sword.rarity = super::Rarity::Magic;
+        let mut fire = make_modifier(StatType::AddedFireDamage, AffixScope::Local, 10);
+        fire.value_max = Some(20);
+        sword.prefixes.push(fire);
+        sword.suffixes.push(make_modifier(
+            StatType::FireResistance,
+            AffixScope::Global,
+            40,
+        ));
+
+        let catalysed = gen.apply_currency(&sword, "prismatic_catalyst").unwrap();
+        assert_eq!(catalysed.quality, 5);
+        assert_eq!(sword.stat_total(StatType::FireResistance), 40);
+        assert_eq!(catalysed.stat_total(StatType::FireResistance), 42);
+        assert_eq!(catalysed.computed_damage(), sword.computed_damage());
+
+        // Flat quality boosts physical damage instead, and switching target resets quality
+        let honed = gen.apply_currency(&catalysed, "whetstone").unwrap();
+        assert_eq!(honed.quality, 5);
+        assert_eq!(honed.stat_total(StatType::FireResistance), 40);
+        assert!(honed.dps().unwrap() > sword.dps().unwrap());
+
+        let capped = (0..5).fold(honed, |item, _| {
+            gen.apply_currency(&item, "whetstone").unwrap()
+        });
+        assert_eq!(capped.quality, super::Item::MAX_QUALITY);
+    }
 
     #[test]
     fn test_damage_range_display() {
@@ -24,12 +586,14 @@ mod tests {
             name: "Flaming".to_string(),
             stat: StatType::AddedFireDamage,
             scope: AffixScope::Local,
+            tags: Vec::new(),
             tier: 1,
             value: 20,
             value_max: Some(35),
             tier_min: 18,
             tier_max: 28,
             tier_max_value: Some((32, 48)),
+            extra_stats: Vec::new(),
         };
 
         assert_eq!(modifier.display(), "Adds 20 to 35 Fire Damage");
@@ -42,14 +606,148 @@ mod tests {
             name: "Robust".to_string(),
             stat: StatType::AddedLife,
             scope: AffixScope::Global,
+            tags: Vec::new(),
             tier: 1,
             value: 50,
             value_max: None,
             tier_min: 40,
             tier_max: 60,
             tier_max_value: None,
+            extra_stats: Vec::new(),
         };
 
         assert_eq!(modifier.display(), "+50 Added Life");
     }
+
+    #[test]
+    fn test_global_increased_physical_does_not_change_dps() {
+        let generator = make_generator();
+        let base = generator.generate("iron_sword", 1).unwrap();
+        let base_dps = base.dps().unwrap();
+
+        let mut global = base.clone();
+        global.prefixes.push(make_modifier(
+            StatType::IncreasedPhysicalDamage,
+            AffixScope::Global,
+            100,
+        ));
+        assert_eq!(global.dps().unwrap(), base_dps);
+
+        let mut local = base.clone();
+        local.prefixes.push(make_modifier(
+            StatType::IncreasedPhysicalDamage,
+            AffixScope::Local,
+            100,
+        ));
+        assert!(local.dps().unwrap() > base_dps);
+    }
+
+    #[test]
+    fn test_fire_increase_scales_only_fire_portion() {
+        let generator = make_generator();
+        let mut hybrid = generator.generate("iron_sword", 1).unwrap();
+        let mut added_fire = make_modifier(StatType::AddedFireDamage, AffixScope::Local, 10);
+        added_fire.value_max = Some(20);
+        hybrid.prefixes.push(added_fire);
+
+        let portion = |item: &super::Item, damage_type: DamageType| {
+            let damage = item.computed_damage().unwrap();
+            let entry = damage
+                .damages
+                .iter()
+                .find(|d| d.damage_type == damage_type)
+                .unwrap();
+            (entry.min, entry.max)
+        };
+        let physical = portion(&hybrid, DamageType::Physical);
+        assert_eq!(portion(&hybrid, DamageType::Fire), (10, 20));
+        let base_dps = hybrid.dps().unwrap();
+
+        hybrid.suffixes.push(make_modifier(
+            StatType::IncreasedFireDamage,
+            AffixScope::Local,
+            100,
+        ));
+        assert_eq!(portion(&hybrid, DamageType::Physical), physical);
+        assert_eq!(portion(&hybrid, DamageType::Fire), (20, 40));
+
+        // Only the fire portion's average hit (15) was doubled
+        let attack_speed = hybrid.computed_damage().unwrap().attack_speed;
+        let expected = base_dps + 15.0 * attack_speed;
+        assert!((hybrid.dps().unwrap() - expected).abs() < 0.01);
+
+        // Elemental increases cover fire too, but never physical
+        hybrid.suffixes.push(make_modifier(
+            StatType::IncreasedElementalDamage,
+            AffixScope::Local,
+            50,
+        ));
+        assert_eq!(portion(&hybrid, DamageType::Physical), physical);
+        assert_eq!(portion(&hybrid, DamageType::Fire), (25, 50));
+    }
+
+    #[test]
+    fn test_local_defense_modifiers_apply_to_item() {
+        let generator = make_generator();
+        let mut item = generator.generate("plate_vest", 1).unwrap();
+        let base_armour = item.defenses.armour.unwrap();
+
+        item.suffixes
+            .push(make_modifier(StatType::AddedArmour, AffixScope::Global, 50));
+        assert_eq!(item.computed_defenses().armour, Some(base_armour));
+
+        item.suffixes
+            .push(make_modifier(StatType::AddedArmour, AffixScope::Local, 50));
+        assert_eq!(item.computed_defenses().armour, Some(base_armour + 50));
+    }
+
+    #[test]
+    fn test_export_affix_table() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut affix = config.affixes["added_fire_damage"].clone();
+        affix.id = "aaa_tabbed".to_string();
+        affix.name = "Fire\tand\nLines".to_string();
+        affix.allowed_classes = vec![ItemClass::OneHandSword, ItemClass::Bow];
+        affix.tiers.truncate(2);
+        config.affixes.insert(affix.id.clone(), affix.clone());
+
+        let table = config.export_affix_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id\tname\ttype\tstat\tscope\ttier\tweight\tmin\tmax\tmin_ilvl\tallowed_classes"
+        );
+        let tier_rows: usize = config.affixes.values().map(|a| a.tiers.len()).sum();
+        assert_eq!(lines.len(), 1 + tier_rows);
+
+        // Sorted by id, so the added affix comes first with one row per tier
+        for (line, tier) in lines[1..3].iter().zip(&affix.tiers) {
+            assert_eq!(
+                *line,
+                format!(
+                    "aaa_tabbed\tFire\\tand\\nLines\tprefix\tadded_fire_damage\tlocal\t{}\t{}\t{}\t{}\t{}\tone_hand_sword;bow",
+                    tier.tier, tier.weight, tier.min, tier.max, tier.min_ilvl
+                )
+            );
+        }
+        assert!(lines.iter().all(|line| line.split('\t').count() == 11));
+    }
+
+    #[test]
+    #[cfg(not(feature = "json"))]
+    fn test_binary_storage_without_json() {
+        use super::{BinaryDecode, BinaryEncode, ItemCollection};
+
+        let generator = make_generator();
+        let item = generator
+            .generate_at_rarity("iron_sword", 9, Rarity::Rare)
+            .unwrap();
+        let item = generator.apply_currency(&item, "chaos").unwrap();
+
+        let mut collection = ItemCollection::new();
+        collection.add(item.clone());
+        let decoded =
+            ItemCollection::decode_from_slice(&collection.encode_to_vec(), &generator).unwrap();
+        assert_eq!(decoded.items, vec![item]);
+    }
 }
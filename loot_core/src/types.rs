@@ -59,6 +59,21 @@ pub enum Rarity {
 }
 
 impl Rarity {
+    /// Every rarity, in declaration order
+    pub fn all() -> &'static [Rarity] {
+        &[Rarity::Normal, Rarity::Magic, Rarity::Rare, Rarity::Unique]
+    }
+
+    /// Variant name as written in the editor and error messages (e.g. "Normal")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rarity::Normal => "Normal",
+            Rarity::Magic => "Magic",
+            Rarity::Rare => "Rare",
+            Rarity::Unique => "Unique",
+        }
+    }
+
     pub fn max_prefixes(&self) -> usize {
         match self {
             Rarity::Normal => 0,
@@ -78,6 +93,24 @@ impl Rarity {
     }
 }
 
+impl std::fmt::Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Rarity {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rarity::all()
+            .iter()
+            .copied()
+            .find(|v| v.name() == s)
+            .ok_or_else(|| ParseEnumError::new("Rarity", s))
+    }
+}
+
 /// Granular item class categories
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -109,6 +142,56 @@ pub enum ItemClass {
 }
 
 impl ItemClass {
+    /// Every item class, in declaration order
+    pub fn all() -> &'static [ItemClass] {
+        &[
+            ItemClass::OneHandSword,
+            ItemClass::OneHandAxe,
+            ItemClass::OneHandMace,
+            ItemClass::Dagger,
+            ItemClass::Claw,
+            ItemClass::Wand,
+            ItemClass::TwoHandSword,
+            ItemClass::TwoHandAxe,
+            ItemClass::TwoHandMace,
+            ItemClass::Bow,
+            ItemClass::Staff,
+            ItemClass::Shield,
+            ItemClass::Helmet,
+            ItemClass::BodyArmour,
+            ItemClass::Gloves,
+            ItemClass::Boots,
+            ItemClass::Ring,
+            ItemClass::Amulet,
+            ItemClass::Belt,
+        ]
+    }
+
+    /// Variant name as written in the editor and error messages (e.g. "OneHandSword")
+    pub fn name(&self) -> &'static str {
+        match self {
+            ItemClass::OneHandSword => "OneHandSword",
+            ItemClass::OneHandAxe => "OneHandAxe",
+            ItemClass::OneHandMace => "OneHandMace",
+            ItemClass::Dagger => "Dagger",
+            ItemClass::Claw => "Claw",
+            ItemClass::Wand => "Wand",
+            ItemClass::TwoHandSword => "TwoHandSword",
+            ItemClass::TwoHandAxe => "TwoHandAxe",
+            ItemClass::TwoHandMace => "TwoHandMace",
+            ItemClass::Bow => "Bow",
+            ItemClass::Staff => "Staff",
+            ItemClass::Shield => "Shield",
+            ItemClass::Helmet => "Helmet",
+            ItemClass::BodyArmour => "BodyArmour",
+            ItemClass::Gloves => "Gloves",
+            ItemClass::Boots => "Boots",
+            ItemClass::Ring => "Ring",
+            ItemClass::Amulet => "Amulet",
+            ItemClass::Belt => "Belt",
+        }
+    }
+
     pub fn is_weapon(&self) -> bool {
         matches!(
             self,
@@ -142,6 +225,24 @@ impl ItemClass {
     }
 }
 
+impl std::fmt::Display for ItemClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ItemClass {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ItemClass::all()
+            .iter()
+            .copied()
+            .find(|v| v.name() == s)
+            .ok_or_else(|| ParseEnumError::new("ItemClass", s))
+    }
+}
+
 /// Affix type: prefix or suffix
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -161,6 +262,169 @@ pub enum AffixScope {
     Global,
 }
 
+/// How affix values are rolled within a tier's min-max range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueDistribution {
+    /// Every value in the range is equally likely
+    #[default]
+    Uniform,
+    /// Likelihood falls off linearly from min to max
+    Triangular,
+    /// Likelihood falls off exponentially from min, so top rolls are rare
+    ExpLow,
+}
+
+/// How rare items are named
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RareNameMode {
+    /// Two name parts drawn from the generic pools
+    #[default]
+    Random,
+    /// Name parts hint at the item's best prefix and suffix
+    AffixThemed,
+}
+
+/// Which name parts make up a rare item's name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RareNameStructure {
+    /// Prefix and suffix joined into one word (e.g. "Doomfang")
+    SingleWord,
+    /// Prefix and suffix as two words (e.g. "Doom Fang")
+    #[default]
+    PrefixSuffix,
+    /// Prefix and suffix followed by an epithet (e.g. "Doom Fang of the Wolf")
+    PrefixSuffixEpithet,
+}
+
+/// Broad grouping of stats, used to order modifiers for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StatCategory {
+    Damage,
+    Ailment,
+    Defense,
+    Attribute,
+    Resource,
+    Resistance,
+    Utility,
+}
+
+/// How explicit modifiers are ordered when an item is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ModOrder {
+    /// Prefixes, then suffixes, each in the order they were rolled
+    #[default]
+    PrefixesFirst,
+    /// Suffixes, then prefixes
+    SuffixesFirst,
+    /// By stat category, prefixes before suffixes within a category
+    Category,
+}
+
+impl ModOrder {
+    /// Every order, in declaration order
+    pub fn all() -> &'static [ModOrder] {
+        &[
+            ModOrder::PrefixesFirst,
+            ModOrder::SuffixesFirst,
+            ModOrder::Category,
+        ]
+    }
+
+    /// Name as written in the settings file (e.g. "prefixes_first")
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModOrder::PrefixesFirst => "prefixes_first",
+            ModOrder::SuffixesFirst => "suffixes_first",
+            ModOrder::Category => "category",
+        }
+    }
+
+    /// The order after this one, wrapping around
+    pub fn next(&self) -> ModOrder {
+        let all = ModOrder::all();
+        all[(*self as usize + 1) % all.len()]
+    }
+
+    /// Compare two explicit modifiers, given as (affix type, stat), for display.
+    /// Modifiers that compare equal keep their rolled order under a stable sort.
+    pub fn compare(
+        &self,
+        a: (AffixType, StatType),
+        b: (AffixType, StatType),
+    ) -> std::cmp::Ordering {
+        let is_suffix = |affix_type: AffixType| affix_type == AffixType::Suffix;
+        match self {
+            ModOrder::PrefixesFirst => is_suffix(a.0).cmp(&is_suffix(b.0)),
+            ModOrder::SuffixesFirst => is_suffix(b.0).cmp(&is_suffix(a.0)),
+            ModOrder::Category => {
+                (a.1.category(), is_suffix(a.0)).cmp(&(b.1.category(), is_suffix(b.0)))
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ModOrder {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModOrder::all()
+            .iter()
+            .copied()
+            .find(|v| v.name() == s)
+            .ok_or_else(|| ParseEnumError::new("ModOrder", s))
+    }
+}
+
+/// What an item's quality boosts, chosen by the last quality currency applied
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityTarget {
+    /// The base's own stats: damage on weapons, defenses on armour
+    #[default]
+    Base,
+    /// Local physical damage only
+    Damage,
+    /// Local defenses only
+    Defense,
+    /// Modifiers granting one of these stats (e.g. a resistance catalyst)
+    Stats(Vec<StatType>),
+}
+
+impl QualityTarget {
+    /// Whether quality adds increased physical damage
+    pub fn boosts_damage(&self) -> bool {
+        matches!(self, QualityTarget::Base | QualityTarget::Damage)
+    }
+
+    /// Whether quality adds increased armour, evasion and energy shield
+    pub fn boosts_defenses(&self) -> bool {
+        matches!(self, QualityTarget::Base | QualityTarget::Defense)
+    }
+
+    /// Short label for display (e.g. "base", "FireResistance, ColdResistance")
+    pub fn describe(&self) -> String {
+        match self {
+            QualityTarget::Base => "base".to_string(),
+            QualityTarget::Damage => "damage".to_string(),
+            QualityTarget::Defense => "defense".to_string(),
+            QualityTarget::Stats(stats) => stats
+                .iter()
+                .map(|s| s.name())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    /// Whether quality scales modifiers granting `stat`
+    pub fn boosts_stat(&self, stat: StatType) -> bool {
+        matches!(self, QualityTarget::Stats(stats) if stats.contains(&stat))
+    }
+}
+
 /// Stat modifier types that affixes can grant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -291,12 +555,485 @@ pub enum StatType {
     AddedAccuracy,
     IncreasedAccuracy,
     IncreasedMovementSpeed,
+    /// Lowers the item's own requirements by a percentage (negative values raise them)
+    ReducedRequirements,
     IncreasedItemRarity,
     IncreasedItemQuantity,
 }
 
-/// Attribute requirements for equipping an item
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Range a single roll of a stat should stay within, as `(stat, min, max)`.
+///
+/// `Config::validation_warnings` flags affix tiers that reach outside it, which catches
+/// typos like a 500% attack speed tier. Stats left out have no sensible cap.
+pub static STAT_BOUNDS: &[(StatType, i32, i32)] = &[
+    (StatType::IncreasedAttackSpeed, -50, 50),
+    (StatType::IncreasedCriticalChance, -100, 200),
+    (StatType::IncreasedCriticalDamage, -100, 200),
+    (StatType::PoisonMaxStacks, 1, 20),
+    (StatType::BleedMaxStacks, 1, 20),
+    (StatType::BurnMaxStacks, 1, 20),
+    (StatType::FreezeMaxStacks, 1, 20),
+    (StatType::ChillMaxStacks, 1, 20),
+    (StatType::StaticMaxStacks, 1, 20),
+    (StatType::FearMaxStacks, 1, 20),
+    (StatType::SlowMaxStacks, 1, 20),
+    (StatType::ConvertPhysicalToPoison, 0, 100),
+    (StatType::ConvertFireToPoison, 0, 100),
+    (StatType::ConvertColdToPoison, 0, 100),
+    (StatType::ConvertLightningToPoison, 0, 100),
+    (StatType::ConvertChaosToPoison, 0, 100),
+    (StatType::ConvertPhysicalToBleed, 0, 100),
+    (StatType::ConvertFireToBleed, 0, 100),
+    (StatType::ConvertColdToBleed, 0, 100),
+    (StatType::ConvertLightningToBleed, 0, 100),
+    (StatType::ConvertChaosToBleed, 0, 100),
+    (StatType::ConvertPhysicalToBurn, 0, 100),
+    (StatType::ConvertFireToBurn, 0, 100),
+    (StatType::ConvertColdToBurn, 0, 100),
+    (StatType::ConvertLightningToBurn, 0, 100),
+    (StatType::ConvertChaosToBurn, 0, 100),
+    (StatType::ConvertPhysicalToFreeze, 0, 100),
+    (StatType::ConvertFireToFreeze, 0, 100),
+    (StatType::ConvertColdToFreeze, 0, 100),
+    (StatType::ConvertLightningToFreeze, 0, 100),
+    (StatType::ConvertChaosToFreeze, 0, 100),
+    (StatType::ConvertPhysicalToChill, 0, 100),
+    (StatType::ConvertFireToChill, 0, 100),
+    (StatType::ConvertColdToChill, 0, 100),
+    (StatType::ConvertLightningToChill, 0, 100),
+    (StatType::ConvertChaosToChill, 0, 100),
+    (StatType::ConvertPhysicalToStatic, 0, 100),
+    (StatType::ConvertFireToStatic, 0, 100),
+    (StatType::ConvertColdToStatic, 0, 100),
+    (StatType::ConvertLightningToStatic, 0, 100),
+    (StatType::ConvertChaosToStatic, 0, 100),
+    (StatType::ConvertPhysicalToFear, 0, 100),
+    (StatType::ConvertFireToFear, 0, 100),
+    (StatType::ConvertColdToFear, 0, 100),
+    (StatType::ConvertLightningToFear, 0, 100),
+    (StatType::ConvertChaosToFear, 0, 100),
+    (StatType::ConvertPhysicalToSlow, 0, 100),
+    (StatType::ConvertFireToSlow, 0, 100),
+    (StatType::ConvertColdToSlow, 0, 100),
+    (StatType::ConvertLightningToSlow, 0, 100),
+    (StatType::ConvertChaosToSlow, 0, 100),
+    (StatType::FireResistance, -100, 60),
+    (StatType::ColdResistance, -100, 60),
+    (StatType::LightningResistance, -100, 60),
+    (StatType::ChaosResistance, -100, 60),
+    (StatType::AllResistances, -100, 30),
+    (StatType::LifeLeech, 0, 20),
+    (StatType::ManaLeech, 0, 20),
+    (StatType::IncreasedMovementSpeed, -50, 50),
+    (StatType::ReducedRequirements, -100, 100),
+];
+
+impl StatType {
+    /// Every stat type, in declaration order
+    pub fn all() -> &'static [StatType] {
+        &[
+            StatType::AddedPhysicalDamage,
+            StatType::AddedFireDamage,
+            StatType::AddedColdDamage,
+            StatType::AddedLightningDamage,
+            StatType::AddedChaosDamage,
+            StatType::IncreasedPhysicalDamage,
+            StatType::IncreasedFireDamage,
+            StatType::IncreasedColdDamage,
+            StatType::IncreasedLightningDamage,
+            StatType::IncreasedElementalDamage,
+            StatType::IncreasedChaosDamage,
+            StatType::IncreasedAttackSpeed,
+            StatType::IncreasedCriticalChance,
+            StatType::IncreasedCriticalDamage,
+            StatType::PoisonDamageOverTime,
+            StatType::IncreasedPoisonDuration,
+            StatType::PoisonMagnitude,
+            StatType::PoisonMaxStacks,
+            StatType::ConvertPhysicalToPoison,
+            StatType::ConvertFireToPoison,
+            StatType::ConvertColdToPoison,
+            StatType::ConvertLightningToPoison,
+            StatType::ConvertChaosToPoison,
+            StatType::BleedDamageOverTime,
+            StatType::IncreasedBleedDuration,
+            StatType::BleedMagnitude,
+            StatType::BleedMaxStacks,
+            StatType::ConvertPhysicalToBleed,
+            StatType::ConvertFireToBleed,
+            StatType::ConvertColdToBleed,
+            StatType::ConvertLightningToBleed,
+            StatType::ConvertChaosToBleed,
+            StatType::BurnDamageOverTime,
+            StatType::IncreasedBurnDuration,
+            StatType::BurnMagnitude,
+            StatType::BurnMaxStacks,
+            StatType::ConvertPhysicalToBurn,
+            StatType::ConvertFireToBurn,
+            StatType::ConvertColdToBurn,
+            StatType::ConvertLightningToBurn,
+            StatType::ConvertChaosToBurn,
+            StatType::IncreasedFreezeDuration,
+            StatType::FreezeMagnitude,
+            StatType::FreezeMaxStacks,
+            StatType::ConvertPhysicalToFreeze,
+            StatType::ConvertFireToFreeze,
+            StatType::ConvertColdToFreeze,
+            StatType::ConvertLightningToFreeze,
+            StatType::ConvertChaosToFreeze,
+            StatType::IncreasedChillDuration,
+            StatType::ChillMagnitude,
+            StatType::ChillMaxStacks,
+            StatType::ConvertPhysicalToChill,
+            StatType::ConvertFireToChill,
+            StatType::ConvertColdToChill,
+            StatType::ConvertLightningToChill,
+            StatType::ConvertChaosToChill,
+            StatType::IncreasedStaticDuration,
+            StatType::StaticMagnitude,
+            StatType::StaticMaxStacks,
+            StatType::ConvertPhysicalToStatic,
+            StatType::ConvertFireToStatic,
+            StatType::ConvertColdToStatic,
+            StatType::ConvertLightningToStatic,
+            StatType::ConvertChaosToStatic,
+            StatType::IncreasedFearDuration,
+            StatType::FearMagnitude,
+            StatType::FearMaxStacks,
+            StatType::ConvertPhysicalToFear,
+            StatType::ConvertFireToFear,
+            StatType::ConvertColdToFear,
+            StatType::ConvertLightningToFear,
+            StatType::ConvertChaosToFear,
+            StatType::IncreasedSlowDuration,
+            StatType::SlowMagnitude,
+            StatType::SlowMaxStacks,
+            StatType::ConvertPhysicalToSlow,
+            StatType::ConvertFireToSlow,
+            StatType::ConvertColdToSlow,
+            StatType::ConvertLightningToSlow,
+            StatType::ConvertChaosToSlow,
+            StatType::AddedArmour,
+            StatType::AddedEvasion,
+            StatType::AddedEnergyShield,
+            StatType::IncreasedArmour,
+            StatType::IncreasedEvasion,
+            StatType::IncreasedEnergyShield,
+            StatType::AddedStrength,
+            StatType::AddedDexterity,
+            StatType::AddedConstitution,
+            StatType::AddedIntelligence,
+            StatType::AddedWisdom,
+            StatType::AddedCharisma,
+            StatType::AddedAllAttributes,
+            StatType::AddedLife,
+            StatType::AddedMana,
+            StatType::IncreasedLife,
+            StatType::IncreasedMana,
+            StatType::LifeRegeneration,
+            StatType::ManaRegeneration,
+            StatType::LifeOnHit,
+            StatType::LifeLeech,
+            StatType::ManaLeech,
+            StatType::FireResistance,
+            StatType::ColdResistance,
+            StatType::LightningResistance,
+            StatType::ChaosResistance,
+            StatType::AllResistances,
+            StatType::AddedAccuracy,
+            StatType::IncreasedAccuracy,
+            StatType::IncreasedMovementSpeed,
+            StatType::ReducedRequirements,
+            StatType::IncreasedItemRarity,
+            StatType::IncreasedItemQuantity,
+        ]
+    }
+
+    /// The broad category this stat belongs to
+    pub fn category(&self) -> StatCategory {
+        match self {
+            StatType::AddedPhysicalDamage
+            | StatType::AddedFireDamage
+            | StatType::AddedColdDamage
+            | StatType::AddedLightningDamage
+            | StatType::AddedChaosDamage
+            | StatType::IncreasedPhysicalDamage
+            | StatType::IncreasedFireDamage
+            | StatType::IncreasedColdDamage
+            | StatType::IncreasedLightningDamage
+            | StatType::IncreasedElementalDamage
+            | StatType::IncreasedChaosDamage
+            | StatType::IncreasedAttackSpeed
+            | StatType::IncreasedCriticalChance
+            | StatType::IncreasedCriticalDamage => StatCategory::Damage,
+            StatType::PoisonDamageOverTime
+            | StatType::IncreasedPoisonDuration
+            | StatType::PoisonMagnitude
+            | StatType::PoisonMaxStacks
+            | StatType::ConvertPhysicalToPoison
+            | StatType::ConvertFireToPoison
+            | StatType::ConvertColdToPoison
+            | StatType::ConvertLightningToPoison
+            | StatType::ConvertChaosToPoison
+            | StatType::BleedDamageOverTime
+            | StatType::IncreasedBleedDuration
+            | StatType::BleedMagnitude
+            | StatType::BleedMaxStacks
+            | StatType::ConvertPhysicalToBleed
+            | StatType::ConvertFireToBleed
+            | StatType::ConvertColdToBleed
+            | StatType::ConvertLightningToBleed
+            | StatType::ConvertChaosToBleed
+            | StatType::BurnDamageOverTime
+            | StatType::IncreasedBurnDuration
+            | StatType::BurnMagnitude
+            | StatType::BurnMaxStacks
+            | StatType::ConvertPhysicalToBurn
+            | StatType::ConvertFireToBurn
+            | StatType::ConvertColdToBurn
+            | StatType::ConvertLightningToBurn
+            | StatType::ConvertChaosToBurn
+            | StatType::IncreasedFreezeDuration
+            | StatType::FreezeMagnitude
+            | StatType::FreezeMaxStacks
+            | StatType::ConvertPhysicalToFreeze
+            | StatType::ConvertFireToFreeze
+            | StatType::ConvertColdToFreeze
+            | StatType::ConvertLightningToFreeze
+            | StatType::ConvertChaosToFreeze
+            | StatType::IncreasedChillDuration
+            | StatType::ChillMagnitude
+            | StatType::ChillMaxStacks
+            | StatType::ConvertPhysicalToChill
+            | StatType::ConvertFireToChill
+            | StatType::ConvertColdToChill
+            | StatType::ConvertLightningToChill
+            | StatType::ConvertChaosToChill
+            | StatType::IncreasedStaticDuration
+            | StatType::StaticMagnitude
+            | StatType::StaticMaxStacks
+            | StatType::ConvertPhysicalToStatic
+            | StatType::ConvertFireToStatic
+            | StatType::ConvertColdToStatic
+            | StatType::ConvertLightningToStatic
+            | StatType::ConvertChaosToStatic
+            | StatType::IncreasedFearDuration
+            | StatType::FearMagnitude
+            | StatType::FearMaxStacks
+            | StatType::ConvertPhysicalToFear
+            | StatType::ConvertFireToFear
+            | StatType::ConvertColdToFear
+            | StatType::ConvertLightningToFear
+            | StatType::ConvertChaosToFear
+            | StatType::IncreasedSlowDuration
+            | StatType::SlowMagnitude
+            | StatType::SlowMaxStacks
+            | StatType::ConvertPhysicalToSlow
+            | StatType::ConvertFireToSlow
+            | StatType::ConvertColdToSlow
+            | StatType::ConvertLightningToSlow
+            | StatType::ConvertChaosToSlow => StatCategory::Ailment,
+            StatType::AddedArmour
+            | StatType::AddedEvasion
+            | StatType::AddedEnergyShield
+            | StatType::IncreasedArmour
+            | StatType::IncreasedEvasion
+            | StatType::IncreasedEnergyShield => StatCategory::Defense,
+            StatType::AddedStrength
+            | StatType::AddedDexterity
+            | StatType::AddedConstitution
+            | StatType::AddedIntelligence
+            | StatType::AddedWisdom
+            | StatType::AddedCharisma
+            | StatType::AddedAllAttributes => StatCategory::Attribute,
+            StatType::AddedLife
+            | StatType::AddedMana
+            | StatType::IncreasedLife
+            | StatType::IncreasedMana
+            | StatType::LifeRegeneration
+            | StatType::ManaRegeneration
+            | StatType::LifeOnHit
+            | StatType::LifeLeech
+            | StatType::ManaLeech => StatCategory::Resource,
+            StatType::FireResistance
+            | StatType::ColdResistance
+            | StatType::LightningResistance
+            | StatType::ChaosResistance
+            | StatType::AllResistances => StatCategory::Resistance,
+            StatType::AddedAccuracy
+            | StatType::IncreasedAccuracy
+            | StatType::IncreasedMovementSpeed
+            | StatType::ReducedRequirements
+            | StatType::IncreasedItemRarity
+            | StatType::IncreasedItemQuantity => StatCategory::Utility,
+        }
+    }
+
+    /// Weapon damage types an "increased damage" stat scales (empty for other stats)
+    pub fn increased_damage_types(&self) -> &'static [DamageType] {
+        match self {
+            StatType::IncreasedPhysicalDamage => &[DamageType::Physical],
+            StatType::IncreasedFireDamage => &[DamageType::Fire],
+            StatType::IncreasedColdDamage => &[DamageType::Cold],
+            StatType::IncreasedLightningDamage => &[DamageType::Lightning],
+            StatType::IncreasedElementalDamage => {
+                &[DamageType::Fire, DamageType::Cold, DamageType::Lightning]
+            }
+            StatType::IncreasedChaosDamage => &[DamageType::Chaos],
+            _ => &[],
+        }
+    }
+
+    /// Sane `(min, max)` for one roll of this stat, from `STAT_BOUNDS`
+    pub fn sane_range(&self) -> Option<(i32, i32)> {
+        STAT_BOUNDS
+            .iter()
+            .find(|(stat, _, _)| stat == self)
+            .map(|&(_, min, max)| (min, max))
+    }
+
+    /// Variant name as written in the editor and error messages (e.g. "AddedPhysicalDamage")
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatType::AddedPhysicalDamage => "AddedPhysicalDamage",
+            StatType::AddedFireDamage => "AddedFireDamage",
+            StatType::AddedColdDamage => "AddedColdDamage",
+            StatType::AddedLightningDamage => "AddedLightningDamage",
+            StatType::AddedChaosDamage => "AddedChaosDamage",
+            StatType::IncreasedPhysicalDamage => "IncreasedPhysicalDamage",
+            StatType::IncreasedFireDamage => "IncreasedFireDamage",
+            StatType::IncreasedColdDamage => "IncreasedColdDamage",
+            StatType::IncreasedLightningDamage => "IncreasedLightningDamage",
+            StatType::IncreasedElementalDamage => "IncreasedElementalDamage",
+            StatType::IncreasedChaosDamage => "IncreasedChaosDamage",
+            StatType::IncreasedAttackSpeed => "IncreasedAttackSpeed",
+            StatType::IncreasedCriticalChance => "IncreasedCriticalChance",
+            StatType::IncreasedCriticalDamage => "IncreasedCriticalDamage",
+            StatType::PoisonDamageOverTime => "PoisonDamageOverTime",
+            StatType::IncreasedPoisonDuration => "IncreasedPoisonDuration",
+            StatType::PoisonMagnitude => "PoisonMagnitude",
+            StatType::PoisonMaxStacks => "PoisonMaxStacks",
+            StatType::ConvertPhysicalToPoison => "ConvertPhysicalToPoison",
+            StatType::ConvertFireToPoison => "ConvertFireToPoison",
+            StatType::ConvertColdToPoison => "ConvertColdToPoison",
+            StatType::ConvertLightningToPoison => "ConvertLightningToPoison",
+            StatType::ConvertChaosToPoison => "ConvertChaosToPoison",
+            StatType::BleedDamageOverTime => "BleedDamageOverTime",
+            StatType::IncreasedBleedDuration => "IncreasedBleedDuration",
+            StatType::BleedMagnitude => "BleedMagnitude",
+            StatType::BleedMaxStacks => "BleedMaxStacks",
+            StatType::ConvertPhysicalToBleed => "ConvertPhysicalToBleed",
+            StatType::ConvertFireToBleed => "ConvertFireToBleed",
+            StatType::ConvertColdToBleed => "ConvertColdToBleed",
+            StatType::ConvertLightningToBleed => "ConvertLightningToBleed",
+            StatType::ConvertChaosToBleed => "ConvertChaosToBleed",
+            StatType::BurnDamageOverTime => "BurnDamageOverTime",
+            StatType::IncreasedBurnDuration => "IncreasedBurnDuration",
+            StatType::BurnMagnitude => "BurnMagnitude",
+            StatType::BurnMaxStacks => "BurnMaxStacks",
+            StatType::ConvertPhysicalToBurn => "ConvertPhysicalToBurn",
+            StatType::ConvertFireToBurn => "ConvertFireToBurn",
+            StatType::ConvertColdToBurn => "ConvertColdToBurn",
+            StatType::ConvertLightningToBurn => "ConvertLightningToBurn",
+            StatType::ConvertChaosToBurn => "ConvertChaosToBurn",
+            StatType::IncreasedFreezeDuration => "IncreasedFreezeDuration",
+            StatType::FreezeMagnitude => "FreezeMagnitude",
+            StatType::FreezeMaxStacks => "FreezeMaxStacks",
+            StatType::ConvertPhysicalToFreeze => "ConvertPhysicalToFreeze",
+            StatType::ConvertFireToFreeze => "ConvertFireToFreeze",
+            StatType::ConvertColdToFreeze => "ConvertColdToFreeze",
+            StatType::ConvertLightningToFreeze => "ConvertLightningToFreeze",
+            StatType::ConvertChaosToFreeze => "ConvertChaosToFreeze",
+            StatType::IncreasedChillDuration => "IncreasedChillDuration",
+            StatType::ChillMagnitude => "ChillMagnitude",
+            StatType::ChillMaxStacks => "ChillMaxStacks",
+            StatType::ConvertPhysicalToChill => "ConvertPhysicalToChill",
+            StatType::ConvertFireToChill => "ConvertFireToChill",
+            StatType::ConvertColdToChill => "ConvertColdToChill",
+            StatType::ConvertLightningToChill => "ConvertLightningToChill",
+            StatType::ConvertChaosToChill => "ConvertChaosToChill",
+            StatType::IncreasedStaticDuration => "IncreasedStaticDuration",
+            StatType::StaticMagnitude => "StaticMagnitude",
+            StatType::StaticMaxStacks => "StaticMaxStacks",
+            StatType::ConvertPhysicalToStatic => "ConvertPhysicalToStatic",
+            StatType::ConvertFireToStatic => "ConvertFireToStatic",
+            StatType::ConvertColdToStatic => "ConvertColdToStatic",
+            StatType::ConvertLightningToStatic => "ConvertLightningToStatic",
+            StatType::ConvertChaosToStatic => "ConvertChaosToStatic",
+            StatType::IncreasedFearDuration => "IncreasedFearDuration",
+            StatType::FearMagnitude => "FearMagnitude",
+            StatType::FearMaxStacks => "FearMaxStacks",
+            StatType::ConvertPhysicalToFear => "ConvertPhysicalToFear",
+            StatType::ConvertFireToFear => "ConvertFireToFear",
+            StatType::ConvertColdToFear => "ConvertColdToFear",
+            StatType::ConvertLightningToFear => "ConvertLightningToFear",
+            StatType::ConvertChaosToFear => "ConvertChaosToFear",
+            StatType::IncreasedSlowDuration => "IncreasedSlowDuration",
+            StatType::SlowMagnitude => "SlowMagnitude",
+            StatType::SlowMaxStacks => "SlowMaxStacks",
+            StatType::ConvertPhysicalToSlow => "ConvertPhysicalToSlow",
+            StatType::ConvertFireToSlow => "ConvertFireToSlow",
+            StatType::ConvertColdToSlow => "ConvertColdToSlow",
+            StatType::ConvertLightningToSlow => "ConvertLightningToSlow",
+            StatType::ConvertChaosToSlow => "ConvertChaosToSlow",
+            StatType::AddedArmour => "AddedArmour",
+            StatType::AddedEvasion => "AddedEvasion",
+            StatType::AddedEnergyShield => "AddedEnergyShield",
+            StatType::IncreasedArmour => "IncreasedArmour",
+            StatType::IncreasedEvasion => "IncreasedEvasion",
+            StatType::IncreasedEnergyShield => "IncreasedEnergyShield",
+            StatType::AddedStrength => "AddedStrength",
+            StatType::AddedDexterity => "AddedDexterity",
+            StatType::AddedConstitution => "AddedConstitution",
+            StatType::AddedIntelligence => "AddedIntelligence",
+            StatType::AddedWisdom => "AddedWisdom",
+            StatType::AddedCharisma => "AddedCharisma",
+            StatType::AddedAllAttributes => "AddedAllAttributes",
+            StatType::AddedLife => "AddedLife",
+            StatType::AddedMana => "AddedMana",
+            StatType::IncreasedLife => "IncreasedLife",
+            StatType::IncreasedMana => "IncreasedMana",
+            StatType::LifeRegeneration => "LifeRegeneration",
+            StatType::ManaRegeneration => "ManaRegeneration",
+            StatType::LifeOnHit => "LifeOnHit",
+            StatType::LifeLeech => "LifeLeech",
+            StatType::ManaLeech => "ManaLeech",
+            StatType::FireResistance => "FireResistance",
+            StatType::ColdResistance => "ColdResistance",
+            StatType::LightningResistance => "LightningResistance",
+            StatType::ChaosResistance => "ChaosResistance",
+            StatType::AllResistances => "AllResistances",
+            StatType::AddedAccuracy => "AddedAccuracy",
+            StatType::IncreasedAccuracy => "IncreasedAccuracy",
+            StatType::IncreasedMovementSpeed => "IncreasedMovementSpeed",
+            StatType::ReducedRequirements => "ReducedRequirements",
+            StatType::IncreasedItemRarity => "IncreasedItemRarity",
+            StatType::IncreasedItemQuantity => "IncreasedItemQuantity",
+        }
+    }
+}
+
+impl std::fmt::Display for StatType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for StatType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StatType::all()
+            .iter()
+            .copied()
+            .find(|v| v.name() == s)
+            .ok_or_else(|| ParseEnumError::new("StatType", s))
+    }
+}
+
+/// Attribute requirements for equipping an item. Values are unsigned, so a config with a
+/// negative base requirement fails to load.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Requirements {
     #[serde(default)]
     pub level: u32,
@@ -314,5 +1051,109 @@ pub struct Requirements {
     pub charisma: u32,
 }
 
+/// Error returned when parsing an enum from its variant name fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    /// Name of the enum being parsed (e.g. "StatType")
+    pub kind: &'static str,
+    /// The text that didn't match any variant
+    pub value: String,
+}
+
+impl ParseEnumError {
+    fn new(kind: &'static str, value: &str) -> Self {
+        ParseEnumError {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown {} '{}'", self.kind, self.value)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
 /// A tag used for spawn weighting
 pub type Tag = String;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `all()` must list every variant in declaration order: discriminants run 0..len and
+    /// the last variant closes the range. `name()` is an exhaustive match, so a new variant
+    /// fails to compile until it is named, and then fails here until it is listed.
+    fn assert_lists_every_variant<T: Copy + std::fmt::Debug>(
+        all: &[T],
+        discriminant: fn(T) -> usize,
+        last: T,
+    ) {
+        assert_eq!(all.len(), discriminant(last) + 1);
+        for (i, &variant) in all.iter().enumerate() {
+            assert_eq!(discriminant(variant), i, "{:?} out of order", variant);
+        }
+    }
+
+    #[test]
+    fn test_all_lists_every_variant() {
+        assert_lists_every_variant(Rarity::all(), |v| v as usize, Rarity::Unique);
+        assert_lists_every_variant(ItemClass::all(), |v| v as usize, ItemClass::Belt);
+        assert_lists_every_variant(
+            StatType::all(),
+            |v| v as usize,
+            StatType::IncreasedItemQuantity,
+        );
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for stat in StatType::all() {
+            assert_eq!(stat.to_string().parse::<StatType>(), Ok(*stat));
+            assert_eq!(stat.name(), format!("{:?}", stat));
+        }
+        for class in ItemClass::all() {
+            assert_eq!(class.name().parse::<ItemClass>(), Ok(*class));
+        }
+        for rarity in Rarity::all() {
+            assert_eq!(rarity.name().parse::<Rarity>(), Ok(*rarity));
+        }
+
+        let err = "Legendary".parse::<Rarity>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown Rarity 'Legendary'");
+    }
+
+    #[test]
+    fn test_mod_order_compare() {
+        use std::cmp::Ordering;
+        let life = (AffixType::Prefix, StatType::AddedLife);
+        let fire = (AffixType::Prefix, StatType::AddedFireDamage);
+        let res = (AffixType::Suffix, StatType::FireResistance);
+        let speed = (AffixType::Suffix, StatType::IncreasedAttackSpeed);
+
+        assert_eq!(ModOrder::PrefixesFirst.compare(life, res), Ordering::Less);
+        assert_eq!(ModOrder::PrefixesFirst.compare(life, fire), Ordering::Equal);
+        assert_eq!(
+            ModOrder::SuffixesFirst.compare(life, res),
+            Ordering::Greater
+        );
+        assert_eq!(ModOrder::SuffixesFirst.compare(res, speed), Ordering::Equal);
+
+        // Damage before resources before resistances; prefixes first within a category
+        assert_eq!(ModOrder::Category.compare(speed, life), Ordering::Less);
+        assert_eq!(ModOrder::Category.compare(life, res), Ordering::Less);
+        assert_eq!(ModOrder::Category.compare(fire, speed), Ordering::Less);
+
+        let mut mods = vec![res, life, speed, fire];
+        mods.sort_by(|a, b| ModOrder::Category.compare(*a, *b));
+        assert_eq!(mods, vec![fire, speed, life, res]);
+
+        for order in ModOrder::all() {
+            assert_eq!(order.name().parse::<ModOrder>(), Ok(*order));
+        }
+        assert_eq!(ModOrder::Category.next(), ModOrder::PrefixesFirst);
+    }
+}
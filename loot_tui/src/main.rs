@@ -5,10 +5,14 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use loot_core::config::{Config, ConfigError, MappingMode};
+use loot_core::config::{
+    sorted_by_key, Config, ConfigError, CurrencyConfig, MappingMode, RecipeAffixRequirement,
+    UniqueConfig,
+};
+use loot_core::currency::AffixOdds;
 use loot_core::generator::Generator;
 use loot_core::storage::Operation;
-use loot_core::Item;
+use loot_core::{AffixType, Item, StatType};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -20,7 +24,25 @@ use ratatui::{
 use std::io;
 use std::path::Path;
 
+mod theme;
+
 fn main() -> Result<(), io::Error> {
+    // Non-interactive commands run without the terminal UI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("verify") {
+        let Some(path) = args.get(1) else {
+            eprintln!("Usage: loot_tui verify <collection.json> [config_dir]");
+            std::process::exit(2);
+        };
+        let config_dir = Config::dir_from_arg(args.get(2).map(String::as_str));
+        std::process::exit(run_verify(config_dir, Path::new(path)));
+    }
+    if args.first().map(String::as_str) == Some("validate") {
+        let config_dir = Config::dir_from_arg(args.get(1).map(String::as_str));
+        std::process::exit(run_validate(config_dir));
+    }
+    let config_path = Config::dir_from_arg(args.first().map(String::as_str));
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -28,8 +50,9 @@ fn main() -> Result<(), io::Error> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    theme::load_settings(Path::new(theme::SETTINGS_FILE));
+
     // Load config and create app
-    let config_path = Path::new("config");
     let mut app = match Config::load_from_dir(config_path) {
         Ok(config) => App::new(config),
         Err(e) => App::with_config_error(e),
@@ -54,6 +77,91 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Replay every item in a JSON collection against the current config and report drift.
+///
+/// Returns the process exit code: 0 if every item reproduces, 1 if any drifted, 2 if the
+/// config or collection couldn't be loaded.
+fn run_verify(config_dir: &Path, collection_path: &Path) -> i32 {
+    let config = match Config::load_from_dir(config_dir) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load config: {}", e);
+            return 2;
+        }
+    };
+    let collection = match loot_core::ItemCollection::load_json(collection_path) {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", collection_path.display(), e);
+            return 2;
+        }
+    };
+
+    let generator = Generator::new(config);
+    let drift = collection.verify_replay(&generator);
+    for mismatch in &drift {
+        let reason = if mismatch.replayed.is_some() {
+            "no longer reproduces"
+        } else {
+            "base type no longer exists"
+        };
+        println!(
+            "item {}: {} seed {} {}",
+            mismatch.index, mismatch.base_type_id, mismatch.seed, reason
+        );
+    }
+    println!(
+        "{} of {} items reproduce",
+        collection.items.len() - drift.len(),
+        collection.items.len()
+    );
+
+    if drift.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
+/// Load a config directory and report its validation errors and warnings, grouped.
+///
+/// Returns the process exit code: 0 if there's nothing to report, 1 if there are errors,
+/// 2 if the config couldn't be loaded, and 3 if there are only warnings.
+fn run_validate(config_dir: &Path) -> i32 {
+    let config = match Config::load_from_dir(config_dir) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load config: {}", e);
+            return 2;
+        }
+    };
+
+    let errors = config.validate().err().unwrap_or_default();
+    let warnings = config.validation_warnings();
+    for (label, issues) in [("Errors", &errors), ("Warnings", &warnings)] {
+        if !issues.is_empty() {
+            println!("{} ({}):", label, issues.len());
+            for issue in issues {
+                println!("  {}", issue);
+            }
+        }
+    }
+    println!(
+        "{}: {} error(s), {} warning(s)",
+        config_dir.display(),
+        errors.len(),
+        warnings.len()
+    );
+
+    if !errors.is_empty() {
+        1
+    } else if !warnings.is_empty() {
+        3
+    } else {
+        0
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum Focus {
     Inventory,
@@ -61,6 +169,10 @@ enum Focus {
     Uniques,
     Currencies,
     AddAffix,
+    SeedEntry,
+    RecipeFilter,
+    TargetEntry,
+    PendingItem,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -79,13 +191,137 @@ struct ChangedAffixes {
     suffixes: Vec<usize>,
 }
 
+/// Navigation state for the Recipes tab
+#[derive(Default)]
+struct RecipeViewState {
+    /// Case-insensitive text matched against unique and base type names/ids
+    filter: String,
+    /// Position of the selected recipe within the filtered list
+    selected: usize,
+    /// Indices into `unique_recipes` of the recipes shown expanded
+    expanded: std::collections::HashSet<usize>,
+}
+
+/// How the inventory panel groups items
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+enum InventoryGrouping {
+    #[default]
+    Flat,
+    Rarity,
+    Class,
+}
+
+impl InventoryGrouping {
+    fn next(self) -> Self {
+        match self {
+            InventoryGrouping::Flat => InventoryGrouping::Rarity,
+            InventoryGrouping::Rarity => InventoryGrouping::Class,
+            InventoryGrouping::Class => InventoryGrouping::Flat,
+        }
+    }
+}
+
+/// One row of the inventory panel: a group header or an index into `App::inventory`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InventoryRow {
+    Header {
+        group: String,
+        count: usize,
+        collapsed: bool,
+    },
+    Item(usize),
+}
+
+/// Grouping and display state for the inventory panel
+#[derive(Default)]
+struct InventoryView {
+    grouping: InventoryGrouping,
+    /// Whether the "(2p/3s)" affix counts are left off item rows
+    hide_affix_counts: bool,
+    /// Labels of the groups whose items are hidden
+    collapsed: std::collections::HashSet<String>,
+    /// Selected row while grouped or filtered, kept in step with `App::inventory_state`
+    row_state: ListState,
+    /// `created_index` of each item marked for batch currency application
+    marked: std::collections::HashSet<u64>,
+    /// Currency id whose requirements an item must meet to be listed (None lists all)
+    craftable_with: Option<String>,
+}
+
+/// One currency or affix application recorded in the session log
+struct LogEntry {
+    /// When the action was taken (Unix seconds)
+    time: u64,
+    /// What was applied (currency name or "Add <affix>")
+    action: String,
+    /// Item name before the action
+    item_name: String,
+    /// What changed on success, or the error message
+    result: Result<Vec<String>, String>,
+}
+
+/// Running totals for the current session, shown in the stats popup. Kept in memory only,
+/// so they reset when the app quits.
+#[derive(Default)]
+struct SessionStats {
+    /// Items generated (regular and unique)
+    items_generated: usize,
+    /// Successful applications per currency name
+    currencies_used: std::collections::BTreeMap<String, usize>,
+    /// Highest DPS seen and the name of the item that had it
+    best_dps: Option<(f32, String)>,
+    /// Highest total defense (armour + evasion + energy shield) seen and its item name
+    best_defense: Option<(i32, String)>,
+}
+
+impl SessionStats {
+    fn record_generated(&mut self, item: &Item) {
+        self.items_generated += 1;
+        self.record_item(item);
+    }
+
+    fn record_currency(&mut self, currency_name: &str, result: &Item) {
+        *self
+            .currencies_used
+            .entry(currency_name.to_string())
+            .or_insert(0) += 1;
+        self.record_item(result);
+    }
+
+    /// Keep the item as the best seen if it beats the current DPS or defense record
+    fn record_item(&mut self, item: &Item) {
+        if let Some(dps) = item.dps() {
+            if self.best_dps.as_ref().is_none_or(|(best, _)| dps > *best) {
+                self.best_dps = Some((dps, item.name.clone()));
+            }
+        }
+
+        let defense = total_defense(item);
+        if defense > 0
+            && self
+                .best_defense
+                .as_ref()
+                .is_none_or(|(best, _)| defense > *best)
+        {
+            self.best_defense = Some((defense, item.name.clone()));
+        }
+    }
+
+    fn currencies_applied(&self) -> usize {
+        self.currencies_used.values().sum()
+    }
+}
+
+/// Tier row shown in the Add Affix popup: (tier, min, max, max_value range)
+type TierEntry = (u32, i32, i32, Option<(i32, i32)>);
+
 /// State for the Add Affix popup
 #[derive(Default)]
 struct AddAffixState {
     /// Available affixes for the current item
-    affixes: Vec<(String, String, loot_core::AffixType)>, // (id, name, type)
+    affixes: Vec<(String, String, AffixType)>, // (id, name, type)
     /// Available tiers for the selected affix
-    tiers: Vec<(u32, i32, i32, Option<(i32, i32)>)>, // (tier, min, max, max_value range)
+    tiers: Vec<TierEntry>,
     /// Current selection in the affix list
     affix_state: ListState,
     /// Current selection in the tier list
@@ -105,6 +341,213 @@ struct CurrencyPopupState {
     currencies: Vec<(String, String, String)>,
     /// List state for currency selection
     list_state: ListState,
+    /// Whether j/k/Space pick modifiers to lock instead of currencies
+    locking: bool,
+    /// Position of the lock cursor among the item's explicit modifiers
+    lock_cursor: usize,
+    /// Affix ids of the modifiers the next currency must leave untouched
+    locked: Vec<String>,
+}
+
+/// Keybindings by context. Drives both the help bar and the '?' overlay, so update this
+/// alongside the key handlers.
+const KEYBINDINGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Global",
+        &[
+            ("n", "New item"),
+            ("U", "Unique"),
+            ("c", "Currency"),
+            ("A", "Add Affix"),
+            ("Tab", "Detail"),
+            ("T", "Toggle theme"),
+            ("?", "Help"),
+            ("q", "Quit"),
+            ("O", "Modifier order"),
+            ("P", "Roll percentages"),
+            ("X", "Rolls / stat totals"),
+        ],
+    ),
+    (
+        "Inventory",
+        &[
+            ("j/k", "Select"),
+            ("m", "Mirror (copy)"),
+            ("d", "Delete"),
+            ("R", "Reset to base"),
+            ("t", "Crafting target"),
+            ("D", "Dump item to file"),
+            ("v", "Mark for batch currency"),
+            ("V", "Clear marks"),
+            ("F", "Clear currency filter"),
+            ("g", "Group by"),
+            ("a", "Affix counts"),
+            ("Space", "Collapse group"),
+        ],
+    ),
+    ("Log", &[("L", "Toggle log"), ("PgUp/PgDn", "Scroll log")]),
+    ("Stats", &[("S", "Session stats"), ("Esc", "Close")]),
+    (
+        "Base Types",
+        &[
+            ("j/k", "Select"),
+            ("Enter", "Roll (preview first)"),
+            ("s", "Generate from seed"),
+            ("r", "Random drop (weighted)"),
+            ("f", "Cycle class filter"),
+            ("Esc", "Close"),
+        ],
+    ),
+    (
+        "Uniques",
+        &[("j/k", "Select"), ("Enter", "Generate"), ("Esc", "Close")],
+    ),
+    (
+        "Currency",
+        &[
+            ("h/l", "Category"),
+            ("j/k", "Select"),
+            ("Enter", "Apply"),
+            ("x", "Lock mods (Space toggles)"),
+            ("f", "Show only valid targets"),
+            ("c/Esc", "Close"),
+        ],
+    ),
+    (
+        "Add Affix",
+        &[
+            ("h/l", "Switch column"),
+            ("j/k", "Select affix/tier"),
+            ("Enter", "Add"),
+            ("Esc", "Close"),
+        ],
+    ),
+    (
+        "Recipes",
+        &[
+            ("j/k", "Select recipe"),
+            ("Enter", "Expand/collapse"),
+            ("/", "Filter"),
+        ],
+    ),
+    (
+        "Seed Entry",
+        &[
+            ("0-9 a-f x", "Type seed"),
+            ("Enter", "Generate"),
+            ("Esc", "Cancel"),
+        ],
+    ),
+    (
+        "Roll Preview",
+        &[
+            ("Enter", "Keep"),
+            ("r", "Reroll (new seed)"),
+            ("Esc", "Discard"),
+        ],
+    ),
+    (
+        "Target Entry",
+        &[
+            ("Stat[:tier] ...", "Type target"),
+            ("Enter", "Set (empty clears)"),
+            ("Esc", "Cancel"),
+        ],
+    ),
+];
+
+/// Contexts whose bindings are listed in the help bar
+const HELP_BAR_CONTEXTS: &[&str] = &["Global", "Inventory"];
+
+/// What the stat panel lists below the item's properties
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum StatView {
+    /// Each modifier with its tier and roll
+    #[default]
+    Rolls,
+    /// Each stat summed across all modifiers
+    Totals,
+}
+
+/// State for the Seed Entry popup
+#[derive(Default)]
+struct SeedEntryState {
+    /// Base type the seed will be applied to
+    base_type_id: String,
+    /// Raw text typed by the user (hex with 0x prefix, or decimal)
+    input: String,
+}
+
+/// Parse a crafting target typed by the user: stat names separated by spaces or commas,
+/// each optionally followed by `:N` for the worst acceptable tier (e.g.
+/// `AddedLife:2, FireResistance`). Stat names ignore case.
+fn parse_target_spec(input: &str) -> Result<Vec<RecipeAffixRequirement>, String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let (stat_name, tier) = match token.split_once(':') {
+                Some((stat_name, tier)) => (stat_name, Some(tier)),
+                None => (token, None),
+            };
+            let stat = StatType::all()
+                .iter()
+                .copied()
+                .find(|s| s.name().eq_ignore_ascii_case(stat_name))
+                .ok_or_else(|| format!("Unknown stat: {}", stat_name))?;
+            let max_tier = match tier {
+                Some(tier) => tier
+                    .trim_start_matches(['T', 't'])
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&t| t > 0)
+                    .ok_or_else(|| format!("Invalid tier in '{}'", token))?,
+                None => ANY_TIER,
+            };
+            Ok(RecipeAffixRequirement {
+                stat,
+                affix_type: None,
+                min_tier: 1,
+                max_tier,
+            })
+        })
+        .collect()
+}
+
+/// `max_tier` of a target requirement that accepts any tier
+const ANY_TIER: u32 = 99;
+
+/// A crafting target written back in the syntax `parse_target_spec` reads
+fn target_spec_text(target: &[RecipeAffixRequirement]) -> String {
+    target
+        .iter()
+        .map(|req| {
+            if req.max_tier >= ANY_TIER {
+                req.stat.name().to_string()
+            } else {
+                format!("{}:{}", req.stat.name(), req.max_tier)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse a seed typed by the user, accepting `0x`-prefixed hex or decimal
+fn parse_seed(input: &str) -> Result<u64, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Seed is empty".to_string());
+    }
+
+    let parsed = match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => trimmed.parse::<u64>(),
+    };
+
+    parsed.map_err(|_| format!("Invalid seed: {}", trimmed))
 }
 
 struct App {
@@ -128,12 +571,55 @@ struct App {
     add_affix_state: AddAffixState,
     /// State for the Currency popup
     currency_popup_state: CurrencyPopupState,
+    /// Whether the Seed Entry popup is open
+    show_seed_entry: bool,
+    show_help: bool,
+    /// State for the Seed Entry popup
+    seed_entry_state: SeedEntryState,
+    /// Text of the crafting target popup, shown while focus is `TargetEntry`
+    target_input: String,
+    /// Freshly rolled item waiting to be kept or rerolled, shown while focus is
+    /// `PendingItem`
+    pending_item: Option<Item>,
     /// Config error if loading failed
     config_error: Option<ConfigError>,
+    /// Every currency/affix application this session, oldest first
+    log: Vec<LogEntry>,
+    /// Whether the log panel is shown
+    show_log: bool,
+    /// How many lines the log panel is scrolled back from the newest entry
+    log_scroll: usize,
+    /// State for the Recipes tab
+    recipe_view: RecipeViewState,
+    /// Class the base type list and random drops are limited to (None for any class)
+    drop_class: Option<loot_core::ItemClass>,
+    /// Grouping of the inventory panel
+    inventory_view: InventoryView,
+    /// Counters for the session stats popup
+    stats: SessionStats,
+    /// Successful applications per currency id this session, checked against `stack_size`
+    currency_uses: std::collections::HashMap<String, u32>,
+    /// `created_index` of the most recently added item
+    last_created_index: u64,
+    /// Whether the session stats popup is shown
+    show_stats: bool,
+    /// Whether the "reset to base" prompt for the selected item is shown
+    show_reset_confirm: bool,
+    /// Whether modifiers show how close their roll is to the tier max
+    show_roll_percent: bool,
+    /// Whether the stat panel lists each modifier or the summed stats
+    stat_view: StatView,
 }
 
 impl App {
     fn new(config: Config) -> Self {
+        // The config still loads with problems, so point at the first one instead of refusing
+        let mut problems = config.validate().err().unwrap_or_default();
+        problems.extend(config.validation_warnings());
+        let message = problems
+            .first()
+            .map(|first| format!("Config has {} problem(s), first: {}", problems.len(), first));
+
         let generator = Generator::new(config);
         let base_type_ids: Vec<String> = generator.base_type_ids().into_iter().cloned().collect();
         let unique_ids: Vec<String> = generator.unique_ids().into_iter().cloned().collect();
@@ -170,6 +656,9 @@ impl App {
             selected_category: 0,
             currencies: Vec::new(),
             list_state: ListState::default(),
+            locking: false,
+            lock_cursor: 0,
+            locked: Vec::new(),
         };
 
         App {
@@ -184,13 +673,31 @@ impl App {
             show_uniques: false,
             show_add_affix: false,
             show_currencies: false,
-            message: None,
+            message,
             base_type_ids,
             unique_ids,
             changed_affixes: ChangedAffixes::default(),
             add_affix_state: AddAffixState::default(),
             currency_popup_state,
+            show_seed_entry: false,
+            show_help: false,
+            seed_entry_state: SeedEntryState::default(),
+            target_input: String::new(),
+            pending_item: None,
             config_error: None,
+            log: Vec::new(),
+            show_log: false,
+            log_scroll: 0,
+            recipe_view: RecipeViewState::default(),
+            drop_class: None,
+            inventory_view: InventoryView::default(),
+            stats: SessionStats::default(),
+            show_stats: false,
+            show_reset_confirm: false,
+            show_roll_percent: false,
+            stat_view: StatView::default(),
+            currency_uses: std::collections::HashMap::new(),
+            last_created_index: 0,
         }
     }
 
@@ -213,7 +720,25 @@ impl App {
             changed_affixes: ChangedAffixes::default(),
             add_affix_state: AddAffixState::default(),
             currency_popup_state: CurrencyPopupState::default(),
+            show_seed_entry: false,
+            show_help: false,
+            seed_entry_state: SeedEntryState::default(),
+            target_input: String::new(),
+            pending_item: None,
             config_error: Some(error),
+            log: Vec::new(),
+            show_log: false,
+            log_scroll: 0,
+            recipe_view: RecipeViewState::default(),
+            drop_class: None,
+            inventory_view: InventoryView::default(),
+            stats: SessionStats::default(),
+            show_stats: false,
+            show_reset_confirm: false,
+            show_roll_percent: false,
+            stat_view: StatView::default(),
+            currency_uses: std::collections::HashMap::new(),
+            last_created_index: 0,
         }
     }
 
@@ -229,277 +754,953 @@ impl App {
             .and_then(|i| self.inventory.get(i))
     }
 
-    fn generate_item(&mut self, base_type_id: &str) {
-        let seed: u64 = rand::random();
-        if let Some(item) = self.generator().generate(base_type_id, seed) {
-            self.message = Some(format!("Generated: {}", item.name));
-            self.inventory.push(item);
-            self.inventory_state.select(Some(self.inventory.len() - 1));
+    /// Called on terminal resize. Pulls each selection back inside its list and drops the
+    /// scroll offsets, so the next draw scrolls just far enough to keep the selection in view
+    /// instead of reusing an offset computed for the old size.
+    fn handle_resize(&mut self) {
+        let base_type_len = if self.generator.is_some() {
+            self.visible_base_type_ids().len()
+        } else {
+            self.base_type_ids.len()
+        };
+        let lists = [
+            (&mut self.inventory_state, self.inventory.len()),
+            (&mut self.base_type_state, base_type_len),
+            (&mut self.unique_state, self.unique_ids.len()),
+            (
+                &mut self.add_affix_state.affix_state,
+                self.add_affix_state.affixes.len(),
+            ),
+            (
+                &mut self.add_affix_state.tier_state,
+                self.add_affix_state.tiers.len(),
+            ),
+            (
+                &mut self.currency_popup_state.list_state,
+                self.currency_popup_state.currencies.len(),
+            ),
+        ];
+        for (state, len) in lists {
+            if state.selected().is_some() {
+                clamp_selection(state, len);
+            }
+            *state.offset_mut() = 0;
         }
+        // The grouped cursor is re-derived from `inventory_state` on every draw
+        *self.inventory_view.row_state.offset_mut() = 0;
     }
 
-    fn generate_unique_item(&mut self, unique_id: &str) {
-        let seed: u64 = rand::random();
-        if let Some(item) = self.generator().generate_unique(unique_id, seed) {
-            self.message = Some(format!("Generated unique: {}", item.name));
-            self.inventory.push(item);
-            self.inventory_state.select(Some(self.inventory.len() - 1));
+    /// Sort key and label of the group an item falls in under the current grouping
+    fn inventory_group(&self, item: &Item) -> (usize, String) {
+        match self.inventory_view.grouping {
+            InventoryGrouping::Flat => (0, String::new()),
+            InventoryGrouping::Rarity => (item.rarity as usize, format!("{:?}", item.rarity)),
+            InventoryGrouping::Class => {
+                let order = loot_core::ItemClass::all()
+                    .iter()
+                    .position(|c| *c == item.class)
+                    .unwrap_or(usize::MAX);
+                (order, item.class.to_string())
+            }
         }
     }
 
-    fn apply_currency_by_id(&mut self, currency_id: &str) {
-        // Clear previous highlights
-        self.changed_affixes = ChangedAffixes::default();
+    /// Whether the inventory panel lists `inventory_rows` rather than every item in order
+    fn inventory_uses_rows(&self) -> bool {
+        self.inventory_view.grouping != InventoryGrouping::Flat
+            || self.inventory_view.craftable_with.is_some()
+    }
 
-        let Some(currency) = self.generator().config().currencies.get(currency_id) else {
-            self.message = Some(format!("Unknown currency: {}", currency_id));
-            return;
-        };
+    /// Whether an item passes the inventory's currency filter: the filter currency passes
+    /// its requirement checks on it, or no filter is set
+    fn inventory_item_visible(&self, item: &Item) -> bool {
+        self.inventory_view
+            .craftable_with
+            .as_deref()
+            .is_none_or(|id| self.generator().check_currency(item, id).is_ok())
+    }
 
-        let currency_name = currency.name.clone();
+    /// Rows shown in the inventory panel, with headers when grouped
+    fn inventory_rows(&self) -> Vec<InventoryRow> {
+        if !self.inventory_uses_rows() {
+            return (0..self.inventory.len()).map(InventoryRow::Item).collect();
+        }
+        let visible =
+            (0..self.inventory.len()).filter(|&i| self.inventory_item_visible(&self.inventory[i]));
+        if self.inventory_view.grouping == InventoryGrouping::Flat {
+            return visible.map(InventoryRow::Item).collect();
+        }
 
+        let mut groups: std::collections::BTreeMap<(usize, String), Vec<usize>> =
+            std::collections::BTreeMap::new();
+        for (i, item) in visible.map(|i| (i, &self.inventory[i])) {
+            groups
+                .entry(self.inventory_group(item))
+                .or_default()
+                .push(i);
+        }
+
+        let mut rows = Vec::new();
+        for ((_, group), items) in groups {
+            let collapsed = self.inventory_view.collapsed.contains(&group);
+            rows.push(InventoryRow::Header {
+                group,
+                count: items.len(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(items.into_iter().map(InventoryRow::Item));
+            }
+        }
+        rows
+    }
+
+    /// Row of the inventory cursor: the selected item's row (its header if the group is
+    /// collapsed), or the header the cursor was left on
+    fn inventory_cursor(&self, rows: &[InventoryRow]) -> Option<usize> {
         let Some(idx) = self.inventory_state.selected() else {
-            self.message = Some("No item selected".to_string());
-            return;
+            return self
+                .inventory_view
+                .row_state
+                .selected()
+                .filter(|_| !rows.is_empty())
+                .map(|row| row.min(rows.len() - 1));
         };
+        if let Some(row) = rows.iter().position(|r| *r == InventoryRow::Item(idx)) {
+            return Some(row);
+        }
+        let group = self.inventory_group(self.inventory.get(idx)?).1;
+        rows.iter()
+            .position(|r| matches!(r, InventoryRow::Header { group: g, .. } if *g == group))
+    }
 
-        let Some(item) = self.inventory.get(idx) else {
-            self.message = Some("No item selected".to_string());
+    /// Point the cursor at a row, selecting its item (or no item for a header)
+    fn select_inventory_row(&mut self, rows: &[InventoryRow], row: Option<usize>) {
+        self.inventory_view.row_state.select(row);
+        let item = row.and_then(|r| match rows[r] {
+            InventoryRow::Item(i) => Some(i),
+            InventoryRow::Header { .. } => None,
+        });
+        self.inventory_state.select(item);
+    }
+
+    fn move_inventory_cursor(&mut self, delta: isize) {
+        if !self.inventory_uses_rows() {
+            move_selection(&mut self.inventory_state, self.inventory.len(), delta);
+            return;
+        }
+        let rows = self.inventory_rows();
+        let mut state = ListState::default();
+        state.select(self.inventory_cursor(&rows));
+        move_selection(&mut state, rows.len(), delta);
+        self.select_inventory_row(&rows, state.selected());
+    }
+
+    /// Collapse or expand the group under the cursor, leaving the cursor on its header
+    fn toggle_inventory_group(&mut self) {
+        if self.inventory_view.grouping == InventoryGrouping::Flat {
+            return;
+        }
+        let rows = self.inventory_rows();
+        let Some(row) = self.inventory_cursor(&rows) else {
             return;
         };
+        let group = match &rows[row] {
+            InventoryRow::Header { group, .. } => group.clone(),
+            InventoryRow::Item(i) => self.inventory_group(&self.inventory[*i]).1,
+        };
+        if !self.inventory_view.collapsed.remove(&group) {
+            self.inventory_view.collapsed.insert(group.clone());
+        }
 
-        // Save affix state before applying
-        let before_prefix_ids: Vec<String> = item
-            .prefixes
-            .iter()
-            .map(|m| format!("{}:{}", m.affix_id, m.value))
-            .collect();
-        let before_suffix_ids: Vec<String> = item
-            .suffixes
+        let rows = self.inventory_rows();
+        let header = rows
             .iter()
-            .map(|m| format!("{}:{}", m.affix_id, m.value))
-            .collect();
-
-        // Apply currency - returns a new item
-        let generator = self.generator.as_ref().unwrap();
-
-        match generator.apply_currency(item, currency_id) {
-            Ok(new_item) => {
-                self.message = Some(format!("Applied {} -> {}", currency_name, new_item.name));
-
-                // Find which affixes changed
-                for (i, prefix) in new_item.prefixes.iter().enumerate() {
-                    let key = format!("{}:{}", prefix.affix_id, prefix.value);
-                    if !before_prefix_ids.contains(&key) {
-                        self.changed_affixes.prefixes.push(i);
-                    }
-                }
-                for (i, suffix) in new_item.suffixes.iter().enumerate() {
-                    let key = format!("{}:{}", suffix.affix_id, suffix.value);
-                    if !before_suffix_ids.contains(&key) {
-                        self.changed_affixes.suffixes.push(i);
-                    }
-                }
-
-                // Replace the item in inventory
-                self.inventory[idx] = new_item;
-            }
-            Err(e) => {
-                self.message = Some(format!("Error: {}", e));
-            }
-        }
+            .position(|r| matches!(r, InventoryRow::Header { group: g, .. } if *g == group));
+        self.select_inventory_row(&rows, header);
     }
 
-    fn delete_selected(&mut self) {
-        if let Some(idx) = self.inventory_state.selected() {
-            if idx < self.inventory.len() {
-                self.inventory.remove(idx);
-                if self.inventory.is_empty() {
-                    self.inventory_state.select(None);
-                } else if idx >= self.inventory.len() {
-                    self.inventory_state.select(Some(self.inventory.len() - 1));
-                }
-                self.message = Some("Item deleted".to_string());
-            }
+    fn cycle_inventory_grouping(&mut self) {
+        let view = &mut self.inventory_view;
+        view.grouping = view.grouping.next();
+        view.collapsed.clear();
+        if view.grouping == InventoryGrouping::Flat {
+            clamp_selection(&mut self.inventory_state, self.inventory.len());
         }
+        self.message = Some(format!(
+            "Inventory grouping: {:?}",
+            self.inventory_view.grouping
+        ));
     }
 
-    fn open_add_affix(&mut self) {
-        let Some(idx) = self.inventory_state.selected() else {
-            self.message = Some("No item selected".to_string());
-            return;
-        };
-
-        let Some(item) = self.inventory.get(idx) else {
+    /// List only the items the selected currency can be applied to, or list everything
+    /// again when that currency is already the filter
+    fn toggle_craftable_filter(&mut self) {
+        let Some(id) = self.get_selected_currency_id().map(str::to_string) else {
             return;
         };
-
-        // Can't add affixes to Unique items
-        if item.rarity == loot_core::Rarity::Unique {
-            self.message = Some("Cannot add affixes to Unique items".to_string());
+        if self.inventory_view.craftable_with.as_deref() == Some(id.as_str()) {
+            self.clear_craftable_filter();
             return;
         }
-
-        // Get existing affix IDs to filter out
-        let existing_ids: Vec<String> = item
-            .prefixes
+        self.inventory_view.craftable_with = Some(id);
+        let rows = self.inventory_rows();
+        if self.inventory_cursor(&rows).is_none() {
+            self.select_inventory_row(&rows, (!rows.is_empty()).then_some(0));
+        }
+        let shown = rows
             .iter()
-            .chain(item.suffixes.iter())
-            .map(|m| m.affix_id.clone())
-            .collect();
+            .filter(|row| matches!(row, InventoryRow::Item(_)))
+            .count();
+        self.message = Some(format!(
+            "Showing {} of {} items {} can apply to",
+            shown,
+            self.inventory.len(),
+            self.craftable_filter_name().unwrap_or_default()
+        ));
+    }
 
-        // Get available affixes for this item class
-        // Allow up to 3 prefixes and 3 suffixes regardless of current rarity
-        let mut affixes: Vec<(String, String, loot_core::AffixType)> = Vec::new();
+    fn clear_craftable_filter(&mut self) {
+        if self.inventory_view.craftable_with.take().is_some() {
+            self.message = Some("Showing all items".to_string());
+        }
+    }
 
-        for affix in self.generator().config().affixes.values() {
-            // Skip if already on item
-            if existing_ids.contains(&affix.id) {
-                continue;
-            }
+    /// Name of the currency the inventory is filtered by
+    fn craftable_filter_name(&self) -> Option<String> {
+        let id = self.inventory_view.craftable_with.as_deref()?;
+        Some(
+            self.generator()
+                .get_currency(id)
+                .map_or_else(|| id.to_string(), |currency| currency.name.clone()),
+        )
+    }
 
-            // Check if allowed for this item class
-            if !affix.allowed_classes.is_empty() && !affix.allowed_classes.contains(&item.class) {
-                continue;
+    fn toggle_affix_counts(&mut self) {
+        let view = &mut self.inventory_view;
+        view.hide_affix_counts = !view.hide_affix_counts;
+        self.message = Some(
+            if view.hide_affix_counts {
+                "Affix counts hidden"
+            } else {
+                "Affix counts shown"
             }
+            .to_string(),
+        );
+    }
 
-            // Check if there's room for this affix type (max 3 each)
-            let can_add = match affix.affix_type {
-                loot_core::AffixType::Prefix => item.prefixes.len() < 3,
-                loot_core::AffixType::Suffix => item.suffixes.len() < 3,
-            };
+    fn generate_item_with_seed(&mut self, base_type_id: &str, seed: u64) -> bool {
+        let Some(item) = self.generator().generate(base_type_id, seed) else {
+            self.message = Some(format!("Unknown base type: {}", base_type_id));
+            return false;
+        };
+        self.message = Some(format!("Generated: {}", item.name));
+        self.stats.record_generated(&item);
+        self.add_to_inventory(item);
+        true
+    }
 
-            if can_add {
-                affixes.push((affix.id.clone(), affix.name.clone(), affix.affix_type));
-            }
+    /// Roll a new item on a random seed and show it for review instead of adding it
+    fn roll_pending_item(&mut self, base_type_id: &str) {
+        let seed: u64 = rand::random();
+        let Some(item) = self.generator().generate(base_type_id, seed) else {
+            self.message = Some(format!("Unknown base type: {}", base_type_id));
+            return;
+        };
+        // Currencies can't add anything to it, so point at the config gap up front
+        if !self.generator().has_affixes_for_class(item.class) {
+            self.message = Some(format!(
+                "No affixes available for this class ({})",
+                item.class.name()
+            ));
         }
+        self.pending_item = Some(item);
+        self.show_base_types = false;
+        self.focus = Focus::PendingItem;
+    }
 
-        if affixes.is_empty() {
-            self.message = Some("No affixes available".to_string());
-            return;
+    /// Replace the pending item with a roll of the same base on a new seed
+    fn reroll_pending_item(&mut self) {
+        if let Some(item) = self.pending_item.take() {
+            self.roll_pending_item(&item.base_type_id);
         }
+    }
 
-        // Sort by name
-        affixes.sort_by(|a, b| a.1.cmp(&b.1));
+    /// Add the pending item to the inventory
+    fn accept_pending_item(&mut self) {
+        if let Some(item) = self.pending_item.take() {
+            self.message = Some(format!("Generated: {}", item.name));
+            self.stats.record_generated(&item);
+            self.add_to_inventory(item);
+        }
+        self.focus = Focus::Inventory;
+    }
 
-        self.add_affix_state.affixes = affixes;
-        self.add_affix_state.affix_state.select(Some(0));
-        self.add_affix_state.column = 0;
-        self.update_affix_tiers();
+    fn discard_pending_item(&mut self) {
+        if let Some(item) = self.pending_item.take() {
+            self.message = Some(format!("Discarded: {}", item.name));
+        }
+        self.focus = Focus::Inventory;
+    }
 
-        self.show_add_affix = true;
-        self.focus = Focus::AddAffix;
+    /// Append an item to the inventory, numbered after every item added before it, and
+    /// select it
+    fn add_to_inventory(&mut self, mut item: Item) {
+        self.last_created_index += 1;
+        item.created_index = self.last_created_index;
+        self.inventory.push(item);
+        self.inventory_state.select(Some(self.inventory.len() - 1));
     }
 
-    fn update_affix_tiers(&mut self) {
-        let Some(idx) = self.add_affix_state.affix_state.selected() else {
-            return;
+    /// Generate an item from a base picked by drop weight, within `drop_class` if set
+    fn generate_random_drop(&mut self) -> bool {
+        let seed: u64 = rand::random();
+        let Some(base_id) = self
+            .generator()
+            .pick_base_type(self.drop_class, seed)
+            .map(|base| base.id.clone())
+        else {
+            self.message = Some("No base types can drop for this class".to_string());
+            return false;
         };
+        self.generate_item_with_seed(&base_id, seed)
+    }
 
-        let Some((affix_id, _, _)) = self.add_affix_state.affixes.get(idx) else {
-            return;
-        };
+    /// Base type ids listed in the popup: all of them, or those of `drop_class` if set
+    fn visible_base_type_ids(&self) -> Vec<&String> {
+        let generator = self.generator();
+        self.base_type_ids
+            .iter()
+            .filter(|id| {
+                self.drop_class.is_none_or(|class| {
+                    generator
+                        .get_base_type(id)
+                        .is_some_and(|base| base.class == class)
+                })
+            })
+            .collect()
+    }
 
-        let Some(affix) = self.generator().config().affixes.get(affix_id) else {
-            return;
-        };
+    fn selected_base_type_id(&self) -> Option<String> {
+        let idx = self.base_type_state.selected()?;
+        self.visible_base_type_ids()
+            .get(idx)
+            .map(|id| id.to_string())
+    }
 
-        let tiers: Vec<(u32, i32, i32, Option<(i32, i32)>)> = affix
-            .tiers
+    /// Step the class filter through the classes that have base types, then back to any.
+    /// The selected base stays selected if it's still listed.
+    fn cycle_drop_class(&mut self) {
+        let previous = self.selected_base_type_id();
+        let generator = self.generator.as_ref().unwrap();
+        let classes: Vec<loot_core::ItemClass> = loot_core::ItemClass::all()
             .iter()
-            .map(|t| (t.tier, t.min, t.max, t.max_value.map(|r| (r.min, r.max))))
+            .copied()
+            .filter(|class| {
+                generator
+                    .config()
+                    .base_types
+                    .values()
+                    .any(|bt| bt.class == *class)
+            })
             .collect();
 
-        self.add_affix_state.tiers = tiers;
-        self.add_affix_state.tier_state.select(Some(0));
+        self.drop_class = match self.drop_class {
+            None => classes.first().copied(),
+            Some(current) => classes
+                .iter()
+                .position(|class| *class == current)
+                .and_then(|i| classes.get(i + 1))
+                .copied(),
+        };
+
+        let visible = self.visible_base_type_ids();
+        let kept = previous.and_then(|id| visible.iter().position(|v| **v == id));
+        let len = visible.len();
+        self.base_type_state.select(kept);
+        clamp_selection(&mut self.base_type_state, len);
     }
 
-    fn add_selected_affix(&mut self) {
-        let affix_idx = self.add_affix_state.affix_state.selected();
-        let tier_idx = self.add_affix_state.tier_state.selected();
+    fn open_seed_entry(&mut self, base_type_id: String) {
+        self.seed_entry_state = SeedEntryState {
+            base_type_id,
+            input: String::new(),
+        };
+        self.show_base_types = false;
+        self.show_seed_entry = true;
+        self.focus = Focus::SeedEntry;
+    }
 
-        let Some(affix_idx) = affix_idx else { return };
-        let Some(tier_idx) = tier_idx else { return };
+    /// Indices into `unique_recipes` of the recipes matching the current filter
+    fn filtered_recipes(&self) -> Vec<usize> {
+        let generator = self.generator();
+        let filter = self.recipe_view.filter.to_lowercase();
+        generator
+            .config()
+            .unique_recipes
+            .iter()
+            .enumerate()
+            .filter(|(_, recipe)| {
+                filter.is_empty() || recipe_matches_filter(generator, recipe, &filter)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
 
-        let Some((affix_id, _, _)) = self.add_affix_state.affixes.get(affix_idx) else {
+    fn move_recipe_selection(&mut self, delta: isize) {
+        let count = self.filtered_recipes().len();
+        let selected = self.recipe_view.selected as isize + delta;
+        self.recipe_view.selected = selected.clamp(0, count.saturating_sub(1) as isize) as usize;
+    }
+
+    fn toggle_selected_recipe(&mut self) {
+        let Some(&index) = self.filtered_recipes().get(self.recipe_view.selected) else {
             return;
         };
+        if !self.recipe_view.expanded.remove(&index) {
+            self.recipe_view.expanded.insert(index);
+        }
+    }
 
-        let Some(affix) = self.generator().config().affixes.get(affix_id).cloned() else {
+    /// Edit the selected item's crafting target, starting from the current one
+    fn open_target_entry(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.message = Some("No item selected".to_string());
             return;
         };
+        self.target_input = target_spec_text(&item.target);
+        self.focus = Focus::TargetEntry;
+    }
 
-        let Some(tier) = affix.tiers.get(tier_idx).cloned() else {
+    fn submit_target_entry(&mut self) {
+        let target = match parse_target_spec(&self.target_input) {
+            Ok(target) => target,
+            Err(e) => {
+                self.message = Some(e);
+                return;
+            }
+        };
+        let Some(item) = self
+            .inventory_state
+            .selected()
+            .and_then(|idx| self.inventory.get_mut(idx))
+        else {
             return;
         };
+        self.message = Some(if target.is_empty() {
+            "Crafting target cleared".to_string()
+        } else {
+            format!("Crafting target set ({} affixes)", target.len())
+        });
+        item.target = target;
+        self.focus = Focus::Inventory;
+    }
 
-        let Some(inv_idx) = self.inventory_state.selected() else {
+    fn submit_seed_entry(&mut self) {
+        let seed = match parse_seed(&self.seed_entry_state.input) {
+            Ok(seed) => seed,
+            Err(e) => {
+                self.message = Some(e);
+                return;
+            }
+        };
+
+        let base_type_id = self.seed_entry_state.base_type_id.clone();
+        if self.generate_item_with_seed(&base_type_id, seed) {
+            self.show_seed_entry = false;
+            self.focus = Focus::Inventory;
+        }
+    }
+
+    fn generate_unique_item(&mut self, unique_id: &str) {
+        let seed: u64 = rand::random();
+        if let Some(item) = self.generator().generate_unique(unique_id, seed) {
+            self.message = Some(format!("Generated unique: {}", item.name));
+            self.stats.record_generated(&item);
+            self.add_to_inventory(item);
+        }
+    }
+
+    /// Uses of a currency left this session, or None if its stack is unlimited
+    fn currency_uses_left(&self, currency_id: &str) -> Option<u32> {
+        let stack_size = self
+            .generator()
+            .config()
+            .currencies
+            .get(currency_id)?
+            .stack_size?;
+        let used = self.currency_uses.get(currency_id).copied().unwrap_or(0);
+        Some(stack_size.saturating_sub(used))
+    }
+
+    fn apply_currency_by_id(&mut self, currency_id: &str) {
+        // Clear previous highlights
+        self.changed_affixes = ChangedAffixes::default();
+
+        let Some(currency) = self.generator().config().currencies.get(currency_id) else {
+            self.message = Some(format!("Unknown currency: {}", currency_id));
+            return;
+        };
+
+        let currency_name = currency.name.clone();
+
+        if self.currency_uses_left(currency_id) == Some(0) {
+            self.message = Some(format!("{} is used up for this session", currency_name));
+            return;
+        }
+
+        if self.marked_count() > 0 {
+            self.apply_currency_to_marked(currency_id, &currency_name);
+            return;
+        }
+
+        let Some(idx) = self.inventory_state.selected() else {
+            self.message = Some("No item selected".to_string());
             return;
         };
 
-        // Roll a value within the tier's range
-        let (value, value_max) = {
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            let v = rng.gen_range(tier.min..=tier.max);
-            let v_max = tier
-                .max_value
-                .map(|range| rng.gen_range(range.min..=range.max));
-            (v, v_max)
+        let Some(item) = self.inventory.get(idx) else {
+            self.message = Some("No item selected".to_string());
+            return;
         };
 
-        let modifier = loot_core::item::Modifier {
-            affix_id: affix.id.clone(),
-            name: affix.name.clone(),
-            stat: affix.stat,
-            scope: affix.scope,
-            tier: tier.tier,
-            value,
-            value_max,
-            tier_min: tier.min,
-            tier_max: tier.max,
-            tier_max_value: tier.max_value.map(|r| (r.min, r.max)),
+        // Save affix state before applying
+        let before_prefixes: Vec<u64> = item.prefixes.iter().map(|m| m.fingerprint()).collect();
+        let before_suffixes: Vec<u64> = item.suffixes.iter().map(|m| m.fingerprint()).collect();
+
+        match self.apply_currency_at(idx, currency_id, &currency_name) {
+            Ok(()) => {
+                let new_item = &self.inventory[idx];
+                self.message = Some(format!("Applied {} -> {}", currency_name, new_item.name));
+
+                // Find which affixes changed
+                for (i, prefix) in new_item.prefixes.iter().enumerate() {
+                    if !before_prefixes.contains(&prefix.fingerprint()) {
+                        self.changed_affixes.prefixes.push(i);
+                    }
+                }
+                for (i, suffix) in new_item.suffixes.iter().enumerate() {
+                    if !before_suffixes.contains(&suffix.fingerprint()) {
+                        self.changed_affixes.suffixes.push(i);
+                    }
+                }
+            }
+            Err(e) => {
+                self.message = Some(format!("Error: {}", e));
+            }
+        }
+    }
+
+    /// Apply a currency to every marked item, in inventory order, and report how many
+    /// took it, failed, or were skipped once the currency's stack ran out. Each item
+    /// replays from its own seed, so the results are independent.
+    fn apply_currency_to_marked(&mut self, currency_id: &str, currency_name: &str) {
+        let targets: Vec<usize> = (0..self.inventory.len())
+            .filter(|&i| {
+                self.inventory_view
+                    .marked
+                    .contains(&self.inventory[i].created_index)
+            })
+            .collect();
+
+        let mut applied = 0;
+        let mut failed = 0;
+        for &idx in &targets {
+            if self.currency_uses_left(currency_id) == Some(0) {
+                break;
+            }
+            match self.apply_currency_at(idx, currency_id, currency_name) {
+                Ok(()) => applied += 1,
+                Err(_) => failed += 1,
+            }
+        }
+        let skipped = targets.len() - applied - failed;
+        let mut message = format!(
+            "Applied {} to {} of {} marked items ({} failed",
+            currency_name,
+            applied,
+            targets.len(),
+            failed
+        );
+        if skipped > 0 {
+            message.push_str(&format!(", {} skipped: stack used up", skipped));
+        }
+        message.push(')');
+        self.message = Some(message);
+    }
+
+    /// Apply a currency to the item at `idx`, replacing it on success and logging the
+    /// attempt either way
+    fn apply_currency_at(
+        &mut self,
+        idx: usize,
+        currency_id: &str,
+        currency_name: &str,
+    ) -> Result<(), loot_core::CurrencyError> {
+        let item = &self.inventory[idx];
+
+        // Only locks on modifiers still on the item matter
+        let locked: Vec<String> = self
+            .currency_popup_state
+            .locked
+            .iter()
+            .filter(|id| {
+                item.prefixes
+                    .iter()
+                    .chain(item.suffixes.iter())
+                    .any(|m| m.affix_id == **id)
+            })
+            .cloned()
+            .collect();
+        let action = if locked.is_empty() {
+            currency_name.to_string()
+        } else {
+            format!("{} (keeping {})", currency_name, locked.join(", "))
         };
 
-        let affix_type = affix.affix_type;
-        let affix_name = affix.name.clone();
-        let tier_num = tier.tier;
+        // Apply currency - returns a new item
+        let generator = self.generator.as_ref().unwrap();
+        let result = generator.apply_currency_locked(item, currency_id, &locked);
+        self.log.push(LogEntry {
+            time: unix_now(),
+            action,
+            item_name: item.name.clone(),
+            result: match &result {
+                Ok(new_item) => Ok(describe_changes(item, new_item)),
+                Err(e) => Err(e.to_string()),
+            },
+        });
+        self.log_scroll = 0;
+
+        let mut new_item = result?;
+        new_item.stamp_last_operation(unix_now());
+        self.stats.record_currency(currency_name, &new_item);
+        *self
+            .currency_uses
+            .entry(currency_id.to_string())
+            .or_insert(0) += 1;
+        self.inventory[idx] = new_item;
+        Ok(())
+    }
+
+    /// Write the selected item's dump (see `item_dump`) to a timestamped file in `dumps/`
+    fn dump_selected(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.message = Some("No item selected".to_string());
+            return;
+        };
+        let dump = item_dump(item);
+        let path = std::path::Path::new("dumps").join(format!(
+            "{}_{}_{:016x}.txt",
+            unix_now(),
+            item.base_type_id,
+            item.seed
+        ));
+        let written = std::fs::create_dir_all("dumps").and_then(|()| std::fs::write(&path, dump));
+        self.message = Some(match written {
+            Ok(()) => format!("Dumped item to {}", path.display()),
+            Err(e) => format!("Failed to write {}: {}", path.display(), e),
+        });
+    }
 
-        let Some(item) = self.inventory.get_mut(inv_idx) else {
+    /// Mark or unmark the selected item for batch currency application
+    fn toggle_marked(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.message = Some("No item selected".to_string());
             return;
         };
+        let created_index = item.created_index;
+        let marked = &mut self.inventory_view.marked;
+        if !marked.remove(&created_index) {
+            marked.insert(created_index);
+        }
+        self.message = Some(format!(
+            "{} item(s) marked; currencies apply to all of them",
+            self.marked_count()
+        ));
+    }
 
-        // Track the change
-        self.changed_affixes = ChangedAffixes::default();
+    fn clear_marked(&mut self) {
+        self.inventory_view.marked.clear();
+        self.message = Some("Marks cleared".to_string());
+    }
+
+    /// Number of inventory items currently marked
+    fn marked_count(&self) -> usize {
+        self.inventory
+            .iter()
+            .filter(|item| self.inventory_view.marked.contains(&item.created_index))
+            .count()
+    }
 
-        match affix_type {
-            loot_core::AffixType::Prefix => {
-                self.changed_affixes.prefixes.push(item.prefixes.len());
-                item.prefixes.push(modifier);
+    fn delete_selected(&mut self) {
+        if let Some(idx) = self.inventory_state.selected() {
+            if idx < self.inventory.len() {
+                let item = self.inventory.remove(idx);
+                self.inventory_view.marked.remove(&item.created_index);
+                clamp_selection(&mut self.inventory_state, self.inventory.len());
+                self.message = Some("Item deleted".to_string());
             }
-            loot_core::AffixType::Suffix => {
-                self.changed_affixes.suffixes.push(item.suffixes.len());
-                item.suffixes.push(modifier);
+        }
+    }
+
+    /// Ask to discard every operation on the selected item (confirmed with `reset_selected`)
+    fn request_reset(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.message = Some("No item selected".to_string());
+            return;
+        };
+        if item.mirrored {
+            self.message = Some("Mirrored items can't be modified".to_string());
+        } else if item.crafting_operations().is_empty() {
+            self.message = Some("Item has no crafting to undo".to_string());
+        } else {
+            self.show_reset_confirm = true;
+        }
+    }
+
+    /// Replace the selected item with a fresh generation from its base type and seed
+    fn reset_selected(&mut self) {
+        self.show_reset_confirm = false;
+        let Some(idx) = self.inventory_state.selected() else {
+            return;
+        };
+        let Some(item) = self.inventory.get(idx) else {
+            return;
+        };
+
+        // Keep how the item dropped (e.g. as a unique) and discard the rest
+        let crafted = item.crafting_operations().len();
+        let origin = &item.operations[..item.operations.len() - crafted];
+        let Some(mut fresh) = self
+            .generator()
+            .reconstruct(&item.base_type_id, item.seed, origin)
+        else {
+            self.message = Some(format!("Unknown base type: {}", item.base_type_id));
+            return;
+        };
+        self.log.push(LogEntry {
+            time: unix_now(),
+            action: "Reset".to_string(),
+            item_name: item.name.clone(),
+            result: Ok(vec![format!("Discarded {} operation(s)", crafted)]),
+        });
+        self.log_scroll = 0;
+        fresh.target = item.target.clone();
+        fresh.created_index = item.created_index;
+        self.message = Some(format!("Reset to base: {}", fresh.name));
+        self.inventory[idx] = fresh;
+    }
+
+    /// Push a mirrored copy of the selected item to the inventory and select it
+    fn mirror_selected(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.message = Some("No item selected".to_string());
+            return;
+        };
+
+        let result = self.generator().mirror(item);
+        self.log.push(LogEntry {
+            time: unix_now(),
+            action: "Mirror".to_string(),
+            item_name: item.name.clone(),
+            result: match &result {
+                Ok(_) => Ok(vec!["Copied to inventory".to_string()]),
+                Err(e) => Err(e.to_string()),
+            },
+        });
+        self.log_scroll = 0;
+
+        match result {
+            Ok(mut copy) => {
+                copy.stamp_last_operation(unix_now());
+                self.message = Some(format!("Mirrored: {}", copy.name));
+                self.add_to_inventory(copy);
+            }
+            Err(e) => {
+                self.message = Some(format!("Error: {}", e));
             }
         }
+    }
 
-        // Upgrade rarity based on total affix count
-        let total_affixes = item.prefixes.len() + item.suffixes.len();
-        if total_affixes >= 3 && item.rarity != loot_core::Rarity::Rare {
-            item.rarity = loot_core::Rarity::Rare;
-            // Generate a rare name if upgrading to rare
-            let seed: u64 = rand::random();
-            let mut rng = Generator::make_rng(seed);
-            item.name = self
-                .generator
-                .as_ref()
-                .unwrap()
-                .generate_rare_name(&mut rng);
-        } else if total_affixes >= 1 && item.rarity == loot_core::Rarity::Normal {
-            item.rarity = loot_core::Rarity::Magic;
+    fn toggle_theme(&mut self) {
+        let theme = theme::toggle();
+        self.message = Some(
+            match theme::save_settings(Path::new(theme::SETTINGS_FILE)) {
+                Ok(()) => format!("Theme: {}", theme.name),
+                Err(e) => format!("Theme: {} (not saved: {})", theme.name, e),
+            },
+        );
+    }
+
+    fn cycle_mod_order(&mut self) {
+        let order = theme::cycle_mod_order().name().replace('_', " ");
+        self.message = Some(
+            match theme::save_settings(Path::new(theme::SETTINGS_FILE)) {
+                Ok(()) => format!("Modifier order: {}", order),
+                Err(e) => format!("Modifier order: {} (not saved: {})", order, e),
+            },
+        );
+    }
+
+    fn toggle_roll_percent(&mut self) {
+        self.show_roll_percent = !self.show_roll_percent;
+        self.message = Some(format!(
+            "Roll percentages: {}",
+            if self.show_roll_percent { "on" } else { "off" }
+        ));
+    }
+
+    fn toggle_stat_view(&mut self) {
+        self.stat_view = match self.stat_view {
+            StatView::Rolls => StatView::Totals,
+            StatView::Totals => StatView::Rolls,
+        };
+        self.message = Some(match self.stat_view {
+            StatView::Rolls => "Stat panel: per-modifier rolls".to_string(),
+            StatView::Totals => "Stat panel: stat totals".to_string(),
+        });
+    }
+
+    fn open_add_affix(&mut self) {
+        let Some(idx) = self.inventory_state.selected() else {
+            self.message = Some("No item selected".to_string());
+            return;
+        };
+
+        let Some(item) = self.inventory.get(idx) else {
+            return;
+        };
+
+        // Can't add affixes to Unique items
+        if item.rarity == loot_core::Rarity::Unique {
+            self.message = Some("Cannot add affixes to Unique items".to_string());
+            return;
+        }
+
+        let mut affixes: Vec<(String, String, AffixType)> = self
+            .generator()
+            .eligible_affixes(item)
+            .into_iter()
+            .map(|affix| (affix.id.clone(), affix.name.clone(), affix.affix_type))
+            .collect();
+
+        if affixes.is_empty() {
+            self.message = Some("No affixes available".to_string());
+            return;
+        }
+
+        // Sort by name
+        affixes.sort_by(|a, b| a.1.cmp(&b.1));
+
+        self.add_affix_state.affixes = affixes;
+        self.add_affix_state.affix_state.select(Some(0));
+        self.add_affix_state.column = 0;
+        self.update_affix_tiers();
+
+        self.show_add_affix = true;
+        self.focus = Focus::AddAffix;
+    }
+
+    fn update_affix_tiers(&mut self) {
+        let Some(idx) = self.add_affix_state.affix_state.selected() else {
+            return;
+        };
+
+        let Some((affix_id, _, _)) = self.add_affix_state.affixes.get(idx) else {
+            return;
+        };
+
+        let Some(affix) = self.generator().config().affixes.get(affix_id) else {
+            return;
+        };
+
+        let tiers: Vec<TierEntry> = affix
+            .tiers
+            .iter()
+            .map(|t| (t.tier, t.min, t.max, t.max_value.map(|r| (r.min, r.max))))
+            .collect();
+
+        self.add_affix_state.tiers = tiers;
+        self.add_affix_state.tier_state.select(Some(0));
+        clamp_selection(
+            &mut self.add_affix_state.tier_state,
+            self.add_affix_state.tiers.len(),
+        );
+    }
+
+    fn add_selected_affix(&mut self) {
+        let affix_idx = self.add_affix_state.affix_state.selected();
+        let tier_idx = self.add_affix_state.tier_state.selected();
+
+        let Some(affix_idx) = affix_idx else { return };
+        let Some(tier_idx) = tier_idx else { return };
+
+        let Some((affix_id, _, affix_type)) = self.add_affix_state.affixes.get(affix_idx).cloned()
+        else {
+            return;
+        };
+
+        let Some(&(tier_num, _, _, _)) = self.add_affix_state.tiers.get(tier_idx) else {
+            return;
+        };
+
+        let Some(inv_idx) = self.inventory_state.selected() else {
+            return;
+        };
+
+        let Some(item) = self.inventory.get(inv_idx) else {
+            return;
+        };
+
+        // Roll through the generator so the addition is recorded and reproducible
+        let result = self.generator().add_affix(item, &affix_id, tier_num);
+        self.log.push(LogEntry {
+            time: unix_now(),
+            action: format!("Add {} T{}", affix_id, tier_num),
+            item_name: item.name.clone(),
+            result: match &result {
+                Ok(new_item) => Ok(describe_changes(item, new_item)),
+                Err(e) => Err(e.to_string()),
+            },
+        });
+        self.log_scroll = 0;
+
+        match result {
+            Ok(mut new_item) => {
+                new_item.stamp_last_operation(unix_now());
+                // Track the change (the new affix is always appended)
+                self.changed_affixes = ChangedAffixes::default();
+                let modifier = match affix_type {
+                    AffixType::Prefix => {
+                        self.changed_affixes
+                            .prefixes
+                            .push(new_item.prefixes.len() - 1);
+                        new_item.prefixes.last()
+                    }
+                    AffixType::Suffix => {
+                        self.changed_affixes
+                            .suffixes
+                            .push(new_item.suffixes.len() - 1);
+                        new_item.suffixes.last()
+                    }
+                };
+                if let Some(modifier) = modifier {
+                    self.message = Some(format!(
+                        "Added {} T{} ({})",
+                        modifier.name, modifier.tier, modifier.value
+                    ));
+                }
+                self.stats.record_item(&new_item);
+                if let Some(slot) = self.inventory.get_mut(inv_idx) {
+                    *slot = new_item;
+                }
+            }
+            Err(e) => {
+                self.message = Some(format!("Error: {}", e));
+            }
         }
 
-        self.message = Some(format!("Added {} T{} ({})", affix_name, tier_num, value));
         self.show_add_affix = false;
         self.focus = Focus::Inventory;
     }
@@ -509,10 +1710,63 @@ impl App {
             self.message = Some("No item selected".to_string());
             return;
         }
-
-        self.update_currency_list();
-        self.show_currencies = true;
-        self.focus = Focus::Currencies;
+
+        self.update_currency_list();
+        self.currency_popup_state.locking = false;
+        self.currency_popup_state.locked.clear();
+        self.show_currencies = true;
+        self.focus = Focus::Currencies;
+    }
+
+    /// Enter or leave lock mode in the currency popup
+    fn toggle_lock_mode(&mut self) {
+        let state = &mut self.currency_popup_state;
+        if state.locking {
+            state.locking = false;
+            return;
+        }
+        if self
+            .selected_item()
+            .is_none_or(|item| item.affix_count() == 0)
+        {
+            self.message = Some("No modifiers to lock".to_string());
+            return;
+        }
+        let state = &mut self.currency_popup_state;
+        state.locking = true;
+        state.lock_cursor = 0;
+    }
+
+    /// Affix id of the explicit modifier under the lock cursor
+    fn lock_cursor_affix(&self) -> Option<String> {
+        let item = self.selected_item()?;
+        item.prefixes
+            .iter()
+            .chain(item.suffixes.iter())
+            .nth(self.currency_popup_state.lock_cursor)
+            .map(|m| m.affix_id.clone())
+    }
+
+    fn move_lock_cursor(&mut self, delta: isize) {
+        let len = self.selected_item().map_or(0, Item::affix_count);
+        let state = &mut self.currency_popup_state;
+        state.lock_cursor = state
+            .lock_cursor
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Lock or unlock the modifier under the lock cursor
+    fn toggle_lock_at_cursor(&mut self) {
+        let Some(affix_id) = self.lock_cursor_affix() else {
+            return;
+        };
+        let locked = &mut self.currency_popup_state.locked;
+        if let Some(pos) = locked.iter().position(|id| *id == affix_id) {
+            locked.remove(pos);
+        } else {
+            locked.push(affix_id);
+        }
     }
 
     fn update_currency_list(&mut self) {
@@ -523,30 +1777,25 @@ impl App {
             .cloned()
             .unwrap_or_default();
 
-        let mut currencies: Vec<(String, String, String)> = self
-            .generator()
-            .config()
-            .currencies
-            .values()
-            .filter(|c| {
-                let c_cat = if c.category.is_empty() {
-                    "Other"
-                } else {
-                    &c.category
-                };
-                c_cat == category
-            })
-            .map(|c| (c.id.clone(), c.name.clone(), c.description.clone()))
-            .collect();
-
-        currencies.sort_by(|a, b| a.1.cmp(&b.1));
+        let currencies: Vec<(String, String, String)> =
+            sorted_by_key(&self.generator().config().currencies, |c| c.name.clone())
+                .filter(|c| {
+                    let c_cat = if c.category.is_empty() {
+                        "Other"
+                    } else {
+                        &c.category
+                    };
+                    c_cat == category
+                })
+                .map(|c| (c.id.clone(), c.name.clone(), c.description.clone()))
+                .collect();
 
         self.currency_popup_state.currencies = currencies;
-        if !self.currency_popup_state.currencies.is_empty() {
-            self.currency_popup_state.list_state.select(Some(0));
-        } else {
-            self.currency_popup_state.list_state.select(None);
-        }
+        self.currency_popup_state.list_state.select(Some(0));
+        clamp_selection(
+            &mut self.currency_popup_state.list_state,
+            self.currency_popup_state.currencies.len(),
+        );
     }
 
     fn next_currency_category(&mut self) {
@@ -597,7 +1846,13 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
     loop {
         terminal.draw(|f| ui(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
+        let event = event::read()?;
+        // Redraw from clamped list state so selections stay visible at the new size
+        if let Event::Resize(..) = event {
+            app.handle_resize();
+            continue;
+        }
+        if let Event::Key(key) = event {
             // Only handle key press events, not release or repeat
             if key.kind != KeyEventKind::Press {
                 continue;
@@ -606,6 +1861,40 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
             // Clear message on any keypress
             app.message = None;
 
+            // Seed entry captures all text input, so handle it before global keys
+            if app.focus == Focus::SeedEntry {
+                handle_seed_entry_keys(app, key.code);
+                continue;
+            }
+            if app.focus == Focus::RecipeFilter {
+                handle_recipe_filter_keys(app, key.code);
+                continue;
+            }
+            if app.focus == Focus::TargetEntry {
+                handle_target_entry_keys(app, key.code);
+                continue;
+            }
+            if app.focus == Focus::PendingItem {
+                handle_pending_item_keys(app, key.code);
+                continue;
+            }
+
+            // The help overlay swallows keys until it is closed
+            if app.show_help {
+                if matches!(
+                    key.code,
+                    KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q')
+                ) {
+                    app.show_help = false;
+                }
+                continue;
+            }
+
+            if app.show_reset_confirm {
+                handle_reset_confirm_keys(app, key.code);
+                continue;
+            }
+
             // Global keys
             match key.code {
                 KeyCode::Char('q') => return Ok(()),
@@ -638,6 +1927,43 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
                     app.open_add_affix();
                     continue;
                 }
+                KeyCode::Char('?') => {
+                    app.show_help = true;
+                    continue;
+                }
+                KeyCode::Char('T') => {
+                    app.toggle_theme();
+                    continue;
+                }
+                KeyCode::Char('O') => {
+                    app.cycle_mod_order();
+                    continue;
+                }
+                KeyCode::Char('P') => {
+                    app.toggle_roll_percent();
+                    continue;
+                }
+                KeyCode::Char('X') => {
+                    app.toggle_stat_view();
+                    continue;
+                }
+                KeyCode::Char('S') => {
+                    app.show_stats = !app.show_stats;
+                    continue;
+                }
+                KeyCode::Char('L') => {
+                    app.show_log = !app.show_log;
+                    app.log_scroll = 0;
+                    continue;
+                }
+                KeyCode::PageUp if app.show_log => {
+                    app.log_scroll = app.log_scroll.saturating_add(5);
+                    continue;
+                }
+                KeyCode::PageDown if app.show_log => {
+                    app.log_scroll = app.log_scroll.saturating_sub(5);
+                    continue;
+                }
                 KeyCode::Char('c') => {
                     if app.show_currencies {
                         app.show_currencies = false;
@@ -648,6 +1974,9 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
                     continue;
                 }
                 KeyCode::Esc => {
+                    if app.show_stats {
+                        app.show_stats = false;
+                    }
                     if app.show_base_types {
                         app.show_base_types = false;
                         app.focus = Focus::Inventory;
@@ -676,61 +2005,172 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
                 Focus::Uniques => handle_unique_keys(app, key.code),
                 Focus::Currencies => handle_currency_keys(app, key.code),
                 Focus::AddAffix => handle_add_affix_keys(app, key.code),
+                Focus::SeedEntry => handle_seed_entry_keys(app, key.code),
+                Focus::RecipeFilter => handle_recipe_filter_keys(app, key.code),
+                Focus::TargetEntry => handle_target_entry_keys(app, key.code),
+                Focus::PendingItem => handle_pending_item_keys(app, key.code),
             }
         }
     }
 }
 
 fn handle_inventory_keys(app: &mut App, code: KeyCode) {
+    // The Recipes tab doesn't depend on the selected item, so it takes over navigation
+    if app.detail_tab == DetailTab::Recipes {
+        handle_recipe_keys(app, code);
+        return;
+    }
+
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(selected) = app.inventory_state.selected() {
-                if selected > 0 {
-                    app.inventory_state.select(Some(selected - 1));
-                }
-            }
+            app.move_inventory_cursor(-1);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(selected) = app.inventory_state.selected() {
-                if selected < app.inventory.len().saturating_sub(1) {
-                    app.inventory_state.select(Some(selected + 1));
-                }
-            } else if !app.inventory.is_empty() {
-                app.inventory_state.select(Some(0));
-            }
+            app.move_inventory_cursor(1);
+        }
+        KeyCode::Char('g') => {
+            app.cycle_inventory_grouping();
+        }
+        KeyCode::Char('a') => {
+            app.toggle_affix_counts();
+        }
+        KeyCode::Char(' ') | KeyCode::Enter => {
+            app.toggle_inventory_group();
+        }
+        KeyCode::Char('m') => {
+            app.mirror_selected();
         }
         KeyCode::Char('d') | KeyCode::Delete => {
             app.delete_selected();
         }
+        KeyCode::Char('R') => {
+            app.request_reset();
+        }
+        KeyCode::Char('t') => {
+            app.open_target_entry();
+        }
+        KeyCode::Char('v') => {
+            app.toggle_marked();
+        }
+        KeyCode::Char('V') => {
+            app.clear_marked();
+        }
+        KeyCode::Char('D') => {
+            app.dump_selected();
+        }
+        KeyCode::Char('F') => {
+            app.clear_craftable_filter();
+        }
+        _ => {}
+    }
+}
+
+/// The reset prompt swallows keys until it is answered
+fn handle_reset_confirm_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('y') => app.reset_selected(),
+        KeyCode::Char('n') | KeyCode::Esc => app.show_reset_confirm = false,
+        _ => {}
+    }
+}
+
+fn handle_recipe_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => app.move_recipe_selection(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.move_recipe_selection(1),
+        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_selected_recipe(),
+        KeyCode::Char('/') => app.focus = Focus::RecipeFilter,
+        _ => {}
+    }
+}
+
+fn handle_recipe_filter_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.focus = Focus::Inventory,
+        KeyCode::Esc => {
+            app.recipe_view.filter.clear();
+            app.focus = Focus::Inventory;
+        }
+        KeyCode::Backspace => {
+            app.recipe_view.filter.pop();
+        }
+        KeyCode::Char(c) => app.recipe_view.filter.push(c),
         _ => {}
     }
+    app.recipe_view.selected = 0;
 }
 
 fn handle_base_type_keys(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(selected) = app.base_type_state.selected() {
-                if selected > 0 {
-                    app.base_type_state.select(Some(selected - 1));
-                }
-            }
+            let len = app.visible_base_type_ids().len();
+            move_selection(&mut app.base_type_state, len, -1);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(selected) = app.base_type_state.selected() {
-                if selected < app.base_type_ids.len().saturating_sub(1) {
-                    app.base_type_state.select(Some(selected + 1));
-                }
-            }
+            let len = app.visible_base_type_ids().len();
+            move_selection(&mut app.base_type_state, len, 1);
         }
         KeyCode::Enter => {
-            if let Some(idx) = app.base_type_state.selected() {
-                if let Some(id) = app.base_type_ids.get(idx).cloned() {
-                    app.generate_item(&id);
-                    app.show_base_types = false;
-                    app.focus = Focus::Inventory;
-                }
+            if let Some(id) = app.selected_base_type_id() {
+                app.roll_pending_item(&id);
+            }
+        }
+        KeyCode::Char('s') => {
+            if let Some(id) = app.selected_base_type_id() {
+                app.open_seed_entry(id);
+            }
+        }
+        KeyCode::Char('r') => {
+            let generated = app.generate_random_drop();
+            if generated {
+                app.show_base_types = false;
+                app.focus = Focus::Inventory;
             }
         }
+        KeyCode::Char('f') => {
+            app.cycle_drop_class();
+        }
+        _ => {}
+    }
+}
+
+fn handle_seed_entry_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => {
+            app.show_seed_entry = false;
+            app.focus = Focus::Inventory;
+        }
+        KeyCode::Enter => {
+            app.submit_seed_entry();
+        }
+        KeyCode::Backspace => {
+            app.seed_entry_state.input.pop();
+        }
+        KeyCode::Char(c) if c.is_ascii_hexdigit() || c == 'x' || c == 'X' => {
+            app.seed_entry_state.input.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// The roll preview swallows keys until the item is kept or discarded
+fn handle_pending_item_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.accept_pending_item(),
+        KeyCode::Char('r') => app.reroll_pending_item(),
+        KeyCode::Esc => app.discard_pending_item(),
+        _ => {}
+    }
+}
+
+fn handle_target_entry_keys(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.focus = Focus::Inventory,
+        KeyCode::Enter => app.submit_target_entry(),
+        KeyCode::Backspace => {
+            app.target_input.pop();
+        }
+        KeyCode::Char(c) => app.target_input.push(c),
         _ => {}
     }
 }
@@ -738,18 +2178,10 @@ fn handle_base_type_keys(app: &mut App, code: KeyCode) {
 fn handle_unique_keys(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(selected) = app.unique_state.selected() {
-                if selected > 0 {
-                    app.unique_state.select(Some(selected - 1));
-                }
-            }
+            move_selection(&mut app.unique_state, app.unique_ids.len(), -1);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(selected) = app.unique_state.selected() {
-                if selected < app.unique_ids.len().saturating_sub(1) {
-                    app.unique_state.select(Some(selected + 1));
-                }
-            }
+            move_selection(&mut app.unique_state, app.unique_ids.len(), 1);
         }
         KeyCode::Enter => {
             if let Some(idx) = app.unique_state.selected() {
@@ -765,26 +2197,25 @@ fn handle_unique_keys(app: &mut App, code: KeyCode) {
 }
 
 fn handle_currency_keys(app: &mut App, code: KeyCode) {
+    if app.currency_popup_state.locking {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => app.move_lock_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_lock_cursor(1),
+            KeyCode::Char(' ') => app.toggle_lock_at_cursor(),
+            KeyCode::Enter | KeyCode::Char('x') => app.toggle_lock_mode(),
+            _ => {}
+        }
+        return;
+    }
+
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(selected) = app.currency_popup_state.list_state.selected() {
-                if selected > 0 {
-                    app.currency_popup_state
-                        .list_state
-                        .select(Some(selected - 1));
-                }
-            }
+            let len = app.currency_popup_state.currencies.len();
+            move_selection(&mut app.currency_popup_state.list_state, len, -1);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(selected) = app.currency_popup_state.list_state.selected() {
-                if selected < app.currency_popup_state.currencies.len().saturating_sub(1) {
-                    app.currency_popup_state
-                        .list_state
-                        .select(Some(selected + 1));
-                }
-            } else if !app.currency_popup_state.currencies.is_empty() {
-                app.currency_popup_state.list_state.select(Some(0));
-            }
+            let len = app.currency_popup_state.currencies.len();
+            move_selection(&mut app.currency_popup_state.list_state, len, 1);
         }
         KeyCode::Left | KeyCode::Char('h') => {
             app.prev_currency_category();
@@ -801,57 +2232,39 @@ fn handle_currency_keys(app: &mut App, code: KeyCode) {
         KeyCode::Enter => {
             app.apply_selected_currency();
         }
+        KeyCode::Char('x') => {
+            app.toggle_lock_mode();
+        }
+        KeyCode::Char('f') => {
+            app.toggle_craftable_filter();
+        }
         _ => {}
     }
 }
 
+/// Move within the focused add-affix column, refreshing tiers when the affix changes
+fn move_add_affix_selection(app: &mut App, delta: isize) {
+    let state = &mut app.add_affix_state;
+    if state.column == 0 {
+        let before = state.affix_state.selected();
+        move_selection(&mut state.affix_state, state.affixes.len(), delta);
+        if state.affix_state.selected() != before {
+            app.update_affix_tiers();
+        }
+    } else {
+        move_selection(&mut state.tier_state, state.tiers.len(), delta);
+    }
+}
+
 fn handle_add_affix_keys(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.add_affix_state.column == 0 {
-                // Navigate affix list
-                if let Some(selected) = app.add_affix_state.affix_state.selected() {
-                    if selected > 0 {
-                        app.add_affix_state.affix_state.select(Some(selected - 1));
-                        app.update_affix_tiers();
-                    }
-                }
-            } else {
-                // Navigate tier list
-                if let Some(selected) = app.add_affix_state.tier_state.selected() {
-                    if selected > 0 {
-                        app.add_affix_state.tier_state.select(Some(selected - 1));
-                    }
-                }
-            }
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.add_affix_state.column == 0 {
-                // Navigate affix list
-                if let Some(selected) = app.add_affix_state.affix_state.selected() {
-                    if selected < app.add_affix_state.affixes.len().saturating_sub(1) {
-                        app.add_affix_state.affix_state.select(Some(selected + 1));
-                        app.update_affix_tiers();
-                    }
-                }
-            } else {
-                // Navigate tier list
-                if let Some(selected) = app.add_affix_state.tier_state.selected() {
-                    if selected < app.add_affix_state.tiers.len().saturating_sub(1) {
-                        app.add_affix_state.tier_state.select(Some(selected + 1));
-                    }
-                }
-            }
-        }
-        KeyCode::Left | KeyCode::Char('h') => {
-            if app.add_affix_state.column > 0 {
-                app.add_affix_state.column = 0;
-            }
+        KeyCode::Up | KeyCode::Char('k') => move_add_affix_selection(app, -1),
+        KeyCode::Down | KeyCode::Char('j') => move_add_affix_selection(app, 1),
+        KeyCode::Left | KeyCode::Char('h') if app.add_affix_state.column > 0 => {
+            app.add_affix_state.column = 0;
         }
-        KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
-            if app.add_affix_state.column == 0 {
-                app.add_affix_state.column = 1;
-            }
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab if app.add_affix_state.column == 0 => {
+            app.add_affix_state.column = 1;
         }
         KeyCode::Enter => {
             app.add_selected_affix();
@@ -860,6 +2273,36 @@ fn handle_add_affix_keys(app: &mut App, code: KeyCode) {
     }
 }
 
+/// Smallest terminal the layout renders in; below this only a resize hint is shown
+const MIN_WIDTH: u16 = 80;
+const MIN_HEIGHT: u16 = 20;
+
+/// Whether `area` is too small for the panels to get usable sizes
+fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+fn render_too_small(f: &mut Frame) {
+    let area = f.area();
+    let theme = theme::current();
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{}x{}, need {}x{}",
+                area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+            ),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+}
+
 fn ui(f: &mut Frame, app: &mut App) {
     // If there's a config error, show it instead of the normal UI
     if let Some(ref error) = app.config_error {
@@ -867,9 +2310,18 @@ fn ui(f: &mut Frame, app: &mut App) {
         return;
     }
 
+    if too_small(f.area()) {
+        render_too_small(f);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
         .split(f.area());
 
     let main_chunks = Layout::default()
@@ -880,11 +2332,21 @@ fn ui(f: &mut Frame, app: &mut App) {
     // Inventory panel (full left side)
     render_inventory(f, app, main_chunks[0]);
 
-    // Detail panel (right side)
-    render_detail(f, app, main_chunks[1]);
+    // Detail panel (right side), with the session log below it when toggled on
+    if app.show_log {
+        let detail_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(main_chunks[1]);
+        render_detail(f, app, detail_chunks[0]);
+        render_log(f, app, detail_chunks[1]);
+    } else {
+        render_detail(f, app, main_chunks[1]);
+    }
 
-    // Help bar
-    render_help(f, app, chunks[1]);
+    // Status line and help bar
+    f.render_widget(Paragraph::new(status_line(app)), chunks[1]);
+    render_help(f, app, chunks[2]);
 
     // Popups
     if app.show_base_types {
@@ -902,6 +2364,30 @@ fn ui(f: &mut Frame, app: &mut App) {
     if app.show_currencies {
         render_currency_popup(f, app);
     }
+
+    if app.show_seed_entry {
+        render_seed_entry_popup(f, app);
+    }
+
+    if app.focus == Focus::TargetEntry {
+        render_target_entry_popup(f, app);
+    }
+
+    if let Some(ref item) = app.pending_item {
+        render_pending_item_popup(f, app, item);
+    }
+
+    if app.show_stats {
+        render_stats_popup(f, app);
+    }
+
+    if app.show_reset_confirm {
+        render_reset_confirm(f, app);
+    }
+
+    if app.show_help {
+        render_help_overlay(f);
+    }
 }
 
 fn render_config_error(f: &mut Frame, error: &ConfigError) {
@@ -914,7 +2400,9 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
     let mut lines = vec![
         Line::from(vec![Span::styled(
             "Configuration Error",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme::current().error)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
     ];
@@ -923,9 +2411,10 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
     let error_type = match error {
         ConfigError::Io { .. } => "File I/O Error",
         ConfigError::Parse { .. } => "TOML Parse Error",
+        ConfigError::DuplicateId { .. } => "Duplicate ID",
     };
     lines.push(Line::from(vec![
-        Span::styled("Type: ", Style::default().fg(Color::Yellow)),
+        Span::styled("Type: ", Style::default().fg(theme::current().highlight)),
         Span::raw(error_type),
     ]));
     lines.push(Line::from(""));
@@ -934,13 +2423,16 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
     lines.push(Line::from(vec![Span::styled(
         "Location:",
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme::current().highlight)
             .add_modifier(Modifier::BOLD),
     )]));
     for line in error.location_description().lines() {
         lines.push(Line::from(vec![
             Span::raw("  "),
-            Span::styled(line.to_string(), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                line.to_string(),
+                Style::default().fg(theme::current().accent),
+            ),
         ]));
     }
     lines.push(Line::from(""));
@@ -949,7 +2441,7 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
     lines.push(Line::from(vec![Span::styled(
         "Error:",
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme::current().highlight)
             .add_modifier(Modifier::BOLD),
     )]));
     for line in error.error_message().lines() {
@@ -963,16 +2455,16 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
     // Instructions
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("Press ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Press ", Style::default().fg(theme::current().muted)),
         Span::styled(
             "q",
             Style::default()
-                .fg(Color::White)
+                .fg(theme::current().text)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             " to quit and fix the configuration file.",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         ),
     ]));
 
@@ -980,9 +2472,13 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red))
+                .border_style(Style::default().fg(theme::current().error))
                 .title(" Config Error ")
-                .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                .title_style(
+                    Style::default()
+                        .fg(theme::current().error)
+                        .add_modifier(Modifier::BOLD),
+                ),
         )
         .wrap(Wrap { trim: false });
 
@@ -991,29 +2487,64 @@ fn render_config_error(f: &mut Frame, error: &ConfigError) {
 }
 
 fn render_inventory(f: &mut Frame, app: &mut App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .inventory
+    let grouped = app.inventory_view.grouping != InventoryGrouping::Flat;
+    let rows = app.inventory_rows();
+    let items: Vec<ListItem> = rows
         .iter()
-        .map(|item| {
-            let rarity_color = match item.rarity {
-                loot_core::Rarity::Normal => Color::White,
-                loot_core::Rarity::Magic => Color::Blue,
-                loot_core::Rarity::Rare => Color::Yellow,
-                loot_core::Rarity::Unique => Color::Rgb(175, 95, 0),
-            };
-            ListItem::new(Line::from(vec![
-                Span::styled(&item.name, Style::default().fg(rarity_color)),
-                Span::raw(" "),
-                Span::styled(
-                    format!("({:?})", item.rarity),
-                    Style::default().fg(Color::DarkGray),
+        .map(|row| match row {
+            InventoryRow::Header {
+                group,
+                count,
+                collapsed,
+            } => ListItem::new(Line::from(Span::styled(
+                format!(
+                    "{} {} ({})",
+                    if *collapsed { "▸" } else { "▾" },
+                    group,
+                    count
                 ),
-            ]))
+                Style::default()
+                    .fg(theme::current().label)
+                    .add_modifier(Modifier::BOLD),
+            ))),
+            InventoryRow::Item(i) => {
+                let item = &app.inventory[*i];
+                let rarity_color = rarity_color(item.rarity);
+                let mut spans = vec![
+                    Span::raw(if grouped { "  " } else { "" }),
+                    if app.inventory_view.marked.contains(&item.created_index) {
+                        Span::styled("* ", Style::default().fg(theme::current().highlight))
+                    } else {
+                        Span::raw("")
+                    },
+                    Span::styled(item.name.clone(), Style::default().fg(rarity_color)),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("({:?})", item.rarity),
+                        Style::default().fg(theme::current().muted),
+                    ),
+                ];
+                if let Some(label) = affix_count_label(item) {
+                    if !app.inventory_view.hide_affix_counts {
+                        // Brighter the closer the item is to having no open slots
+                        let color = if !item.can_add_prefix() && !item.can_add_suffix() {
+                            theme::current().highlight
+                        } else if item.affix_count() == 0 {
+                            theme::current().muted
+                        } else {
+                            theme::current().text
+                        };
+                        spans.push(Span::raw(" "));
+                        spans.push(Span::styled(label, Style::default().fg(color)));
+                    }
+                }
+                ListItem::new(Line::from(spans))
+            }
         })
         .collect();
 
     let border_style = if app.focus == Focus::Inventory {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme::current().accent)
     } else {
         Style::default()
     };
@@ -1023,16 +2554,55 @@ fn render_inventory(f: &mut Frame, app: &mut App, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(border_style)
-                .title(" Inventory "),
+                .title(inventory_title(app)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme::current().muted)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
 
-    f.render_stateful_widget(list, area, &mut app.inventory_state);
+    if app.inventory_uses_rows() {
+        let cursor = app.inventory_cursor(&rows);
+        app.inventory_view.row_state.select(cursor);
+        f.render_stateful_widget(list, area, &mut app.inventory_view.row_state);
+    } else {
+        f.render_stateful_widget(list, area, &mut app.inventory_state);
+    }
+}
+
+/// Inventory panel title, noting the grouping, currency filter and marked items
+fn inventory_title(app: &App) -> String {
+    let mut notes = Vec::new();
+    if app.inventory_view.grouping != InventoryGrouping::Flat {
+        notes.push(format!("by {:?}", app.inventory_view.grouping));
+    }
+    if let Some(name) = app.craftable_filter_name() {
+        notes.push(format!("{} targets", name));
+    }
+    match app.marked_count() {
+        0 => {}
+        marked => notes.push(format!("{} marked", marked)),
+    }
+    if notes.is_empty() {
+        " Inventory ".to_string()
+    } else {
+        format!(" Inventory ({}) ", notes.join(", "))
+    }
+}
+
+/// Compact prefix/suffix count for an inventory row, e.g. "(3p/2s)". None for rarities
+/// without affix slots.
+fn affix_count_label(item: &Item) -> Option<String> {
+    if item.rarity.max_prefixes() + item.rarity.max_suffixes() == 0 {
+        return None;
+    }
+    Some(format!(
+        "({}p/{}s)",
+        item.prefixes.len(),
+        item.suffixes.len()
+    ))
 }
 
 fn render_currency_popup(f: &mut Frame, app: &mut App) {
@@ -1066,27 +2636,46 @@ fn render_currency_popup(f: &mut Frame, app: &mut App) {
         .style(Style::default())
         .highlight_style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme::current().accent)
                 .add_modifier(Modifier::BOLD),
         )
         .divider("|");
 
+    let marked = app.marked_count();
+    let title = if app.currency_popup_state.locking {
+        " j/k: Select mod | Space: Lock | x/Enter: Done ".to_string()
+    } else if marked > 0 {
+        format!(" ←/→ Tab | Enter: Apply to {} marked | Esc: Close ", marked)
+    } else {
+        " ←/→ Tab | Enter: Apply | x: Lock | Esc: Close ".to_string()
+    };
     let tab_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" ←/→ Tab | Enter: Apply | Esc: Close ");
+        .border_style(Style::default().fg(theme::current().accent))
+        .title(title);
     f.render_widget(tabs.block(tab_block), left_chunks[0]);
 
-    // Currency list
+    // Currency list, with remaining uses for limited currencies
     let items: Vec<ListItem> = app
         .currency_popup_state
         .currencies
         .iter()
-        .map(|(_, name, _)| {
-            ListItem::new(Span::styled(
-                name,
+        .map(|(id, name, _)| match app.currency_uses_left(id) {
+            None => ListItem::new(Span::styled(
+                name.clone(),
                 Style::default().add_modifier(Modifier::BOLD),
-            ))
+            )),
+            Some(0) => ListItem::new(Line::from(vec![
+                Span::styled(name.clone(), Style::default().fg(theme::current().muted)),
+                Span::styled(" (used up)", Style::default().fg(theme::current().muted)),
+            ])),
+            Some(left) => ListItem::new(Line::from(vec![
+                Span::styled(name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" ({} left)", left),
+                    Style::default().fg(theme::current().muted),
+                ),
+            ])),
         })
         .collect();
 
@@ -1094,12 +2683,12 @@ fn render_currency_popup(f: &mut Frame, app: &mut App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme::current().accent))
                 .title(" Currencies "),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme::current().muted)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -1116,7 +2705,7 @@ fn render_currency_popup(f: &mut Frame, app: &mut App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme::current().accent))
                 .title(" Preview "),
         )
         .wrap(Wrap { trim: false });
@@ -1142,22 +2731,17 @@ fn build_currency_preview(app: &App) -> Text<'static> {
     )));
 
     if let Some(item) = item {
-        let rarity_color = match item.rarity {
-            loot_core::Rarity::Normal => Color::White,
-            loot_core::Rarity::Magic => Color::Blue,
-            loot_core::Rarity::Rare => Color::Yellow,
-            loot_core::Rarity::Unique => Color::Rgb(175, 95, 0),
-        };
+        let rarity_color = rarity_color(item.rarity);
         lines.push(Line::from(vec![
             Span::styled(item.name.clone(), Style::default().fg(rarity_color)),
             Span::styled(
                 format!(" ({:?})", item.rarity),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             ),
         ]));
         lines.push(Line::from(Span::styled(
             item.base_name.clone(),
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme::current().label),
         )));
 
         // Defenses
@@ -1165,19 +2749,19 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             if let Some(armour) = item.defenses.armour {
                 lines.push(Line::from(Span::styled(
                     format!("  Armour: {}", armour),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
             if let Some(evasion) = item.defenses.evasion {
                 lines.push(Line::from(Span::styled(
                     format!("  Evasion: {}", evasion),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
             if let Some(es) = item.defenses.energy_shield {
                 lines.push(Line::from(Span::styled(
                     format!("  Energy Shield: {}", es),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 )));
             }
         }
@@ -1186,11 +2770,11 @@ fn build_currency_preview(app: &App) -> Text<'static> {
         if let Some(ref dmg) = item.damage {
             for entry in &dmg.damages {
                 let color = match entry.damage_type {
-                    loot_core::types::DamageType::Physical => Color::White,
-                    loot_core::types::DamageType::Fire => Color::Red,
-                    loot_core::types::DamageType::Cold => Color::Cyan,
-                    loot_core::types::DamageType::Lightning => Color::Yellow,
-                    loot_core::types::DamageType::Chaos => Color::Magenta,
+                    loot_core::types::DamageType::Physical => theme::current().physical,
+                    loot_core::types::DamageType::Fire => theme::current().fire,
+                    loot_core::types::DamageType::Cold => theme::current().cold,
+                    loot_core::types::DamageType::Lightning => theme::current().lightning,
+                    loot_core::types::DamageType::Chaos => theme::current().chaos,
                 };
                 lines.push(Line::from(Span::styled(
                     format!("  {:?}: {}-{}", entry.damage_type, entry.min, entry.max),
@@ -1199,11 +2783,17 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             }
         }
 
-        // Implicit
-        if let Some(ref imp) = item.implicit {
+        // Implicits
+        for imp in &item.implicits {
             lines.push(Line::from(Span::styled(
                 format!("  {}", imp.display()),
-                Style::default().fg(Color::Magenta),
+                Style::default().fg(theme::current().special),
+            )));
+        }
+        if let Some(ref enchant) = item.enchant {
+            lines.push(Line::from(Span::styled(
+                format!("  Enchant: {}", enchant.display()),
+                Style::default().fg(theme::current().special),
             )));
         }
 
@@ -1219,49 +2809,51 @@ fn build_currency_preview(app: &App) -> Text<'static> {
                     item.suffixes.len(),
                     item.rarity.max_suffixes()
                 ),
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme::current().label),
             )));
 
-            for (i, prefix) in item.prefixes.iter().enumerate() {
-                let marker = if changed.prefixes.contains(&i) {
-                    Span::styled(">> ", Style::default().fg(Color::LightGreen))
-                } else {
-                    Span::raw("   ")
-                };
-                lines.push(Line::from(vec![
-                    marker,
-                    Span::styled(prefix.display(), Style::default().fg(Color::Cyan)),
-                    Span::styled(
-                        format!(" [T{}]", prefix.tier),
-                        Style::default().fg(Color::DarkGray),
+            for (affix_type, i, modifier) in item.ordered_explicits(theme::mod_order()) {
+                let (changed_indices, lock_index, color) = match affix_type {
+                    AffixType::Prefix => (&changed.prefixes, i, theme::current().prefix),
+                    AffixType::Suffix => (
+                        &changed.suffixes,
+                        item.prefixes.len() + i,
+                        theme::current().suffix,
                     ),
-                ]));
-            }
-            for (i, suffix) in item.suffixes.iter().enumerate() {
-                let marker = if changed.suffixes.contains(&i) {
-                    Span::styled(">> ", Style::default().fg(Color::LightGreen))
+                };
+                let marker = if changed_indices.contains(&i) {
+                    Span::styled(">> ", Style::default().fg(theme::current().preview))
                 } else {
                     Span::raw("   ")
                 };
                 lines.push(Line::from(vec![
                     marker,
-                    Span::styled(suffix.display(), Style::default().fg(Color::Green)),
+                    lock_marker(app, lock_index, &modifier.affix_id),
+                    Span::styled(modifier.display(), Style::default().fg(color)),
                     Span::styled(
-                        format!(" [T{}]", suffix.tier),
-                        Style::default().fg(Color::DarkGray),
+                        format!(" [T{}]", modifier.tier),
+                        Style::default().fg(theme::current().muted),
                     ),
                 ]));
             }
         } else {
             lines.push(Line::from(Span::styled(
                 "  (no affixes)".to_string(),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme::current().muted),
             )));
         }
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Totals: {:+} Life, {:+}% Res",
+                item.stat_total(StatType::AddedLife),
+                total_resistance(item)
+            ),
+            Style::default().fg(theme::current().label),
+        )));
     } else {
         lines.push(Line::from(Span::styled(
             "No item selected".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     }
 
@@ -1272,12 +2864,12 @@ fn build_currency_preview(app: &App) -> Text<'static> {
         lines.push(Line::from(Span::styled(
             currency.name.clone(),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme::current().accent)
                 .add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from(Span::styled(
             currency.description.clone(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
         lines.push(Line::from(""));
 
@@ -1294,7 +2886,11 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             let meets_req = item
                 .map(|i| reqs.rarities.contains(&i.rarity))
                 .unwrap_or(false);
-            let color = if meets_req { Color::Green } else { Color::Red };
+            let color = if meets_req {
+                theme::current().success
+            } else {
+                theme::current().error
+            };
             lines.push(Line::from(vec![
                 Span::raw("  Rarity: ".to_string()),
                 Span::styled(rarity_str.join(", "), Style::default().fg(color)),
@@ -1304,7 +2900,11 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             let meets_req = item
                 .map(|i| !i.prefixes.is_empty() || !i.suffixes.is_empty())
                 .unwrap_or(false);
-            let color = if meets_req { Color::Green } else { Color::Red };
+            let color = if meets_req {
+                theme::current().success
+            } else {
+                theme::current().error
+            };
             lines.push(Line::from(Span::styled(
                 "  Must have affixes".to_string(),
                 Style::default().fg(color),
@@ -1314,32 +2914,49 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             let meets_req = item
                 .map(|i| i.can_add_prefix() || i.can_add_suffix())
                 .unwrap_or(false);
-            // Check against target rarity if set_rarity is specified
-            let meets_req = if !meets_req && currency.effects.set_rarity.is_some() {
-                let target = currency.effects.set_rarity.unwrap();
-                item.map(|i| {
-                    let prefix_count = if currency.effects.clear_affixes {
-                        0
-                    } else {
-                        i.prefixes.len()
-                    };
-                    let suffix_count = if currency.effects.clear_affixes {
-                        0
-                    } else {
-                        i.suffixes.len()
-                    };
-                    prefix_count < target.max_prefixes() || suffix_count < target.max_suffixes()
-                })
-                .unwrap_or(false)
+            // Check against target rarity if set_rarity is specified
+            let meets_req = match currency.effects.set_rarity {
+                Some(target) if !meets_req => item
+                    .map(|i| {
+                        let prefix_count = if currency.effects.clear_affixes {
+                            0
+                        } else {
+                            i.prefixes.len()
+                        };
+                        let suffix_count = if currency.effects.clear_affixes {
+                            0
+                        } else {
+                            i.suffixes.len()
+                        };
+                        prefix_count < target.max_prefixes() || suffix_count < target.max_suffixes()
+                    })
+                    .unwrap_or(false),
+                _ => meets_req,
+            };
+            let color = if meets_req {
+                theme::current().success
             } else {
-                meets_req
+                theme::current().error
             };
-            let color = if meets_req { Color::Green } else { Color::Red };
             lines.push(Line::from(Span::styled(
                 "  Must have affix slot".to_string(),
                 Style::default().fg(color),
             )));
         }
+        if !reqs.required_tags.is_empty() {
+            let meets_req = item
+                .map(|i| reqs.required_tags.iter().all(|t| i.tags.contains(t)))
+                .unwrap_or(false);
+            let color = if meets_req {
+                theme::current().success
+            } else {
+                theme::current().error
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  Tags: ".to_string()),
+                Span::styled(reqs.required_tags.join(", "), Style::default().fg(color)),
+            ]));
+        }
 
         lines.push(Line::from(""));
 
@@ -1353,25 +2970,36 @@ fn build_currency_preview(app: &App) -> Text<'static> {
         if let Some(rarity) = effects.set_rarity {
             lines.push(Line::from(vec![
                 Span::raw("  Set rarity: ".to_string()),
-                Span::styled(format!("{:?}", rarity), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{:?}", rarity),
+                    Style::default().fg(theme::current().highlight),
+                ),
             ]));
         }
         if effects.clear_affixes {
             lines.push(Line::from(Span::styled(
                 "  Clear all affixes".to_string(),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme::current().error),
             )));
         }
         if let Some(count) = effects.remove_affixes {
             lines.push(Line::from(Span::styled(
                 format!("  Remove {} random affix(es)", count),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme::current().error),
             )));
+            if let Some(item) = item {
+                lines.extend(removal_odds_lines(app.generator(), item));
+            }
         }
         if let Some(count) = effects.reroll_affixes {
+            let group = match effects.reroll_scope() {
+                Some(AffixType::Prefix) => "prefix(es)",
+                Some(AffixType::Suffix) => "suffix(es)",
+                None => "affix(es)",
+            };
             lines.push(Line::from(Span::styled(
-                format!("  Reroll {} random affix(es)", count),
-                Style::default().fg(Color::Yellow),
+                format!("  Reroll {} random {}", count, group),
+                Style::default().fg(theme::current().highlight),
             )));
         }
         if let Some(ref count) = effects.add_affixes {
@@ -1382,9 +3010,43 @@ fn build_currency_preview(app: &App) -> Text<'static> {
             };
             lines.push(Line::from(Span::styled(
                 format!("  Add {} random affix(es)", range),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme::current().success),
             )));
         }
+        if !effects.weighted_tags.is_empty() {
+            // A zero multiplier blocks the tag outright; the rest scale its weight
+            let (blocked, scaled): (Vec<_>, Vec<_>) = effects
+                .weighted_tags
+                .iter()
+                .partition(|(_, multiplier)| *multiplier == 0.0);
+            let (favored, disfavored): (Vec<_>, Vec<_>) = scaled
+                .into_iter()
+                .partition(|(_, multiplier)| *multiplier >= 1.0);
+            let groups = [
+                ("Blocks", blocked, theme::current().error),
+                ("Favors", favored, theme::current().highlight),
+                ("Disfavors", disfavored, theme::current().muted),
+            ];
+            for (label, tags, color) in groups {
+                if tags.is_empty() {
+                    continue;
+                }
+                let tags: Vec<String> = tags
+                    .iter()
+                    .map(|(tag, multiplier)| {
+                        if *multiplier == 0.0 {
+                            tag.to_string()
+                        } else {
+                            format!("{} x{}", tag, multiplier)
+                        }
+                    })
+                    .collect();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {}: ", label)),
+                    Span::styled(tags.join(", "), Style::default().fg(color)),
+                ]));
+            }
+        }
         if !effects.add_specific_affix.is_empty() {
             if effects.add_specific_affix.len() == 1 {
                 let affix_id = &effects.add_specific_affix[0].id;
@@ -1397,12 +3059,12 @@ fn build_currency_preview(app: &App) -> Text<'static> {
                     .unwrap_or_else(|| affix_id.clone());
                 lines.push(Line::from(Span::styled(
                     format!("  Add: {}", affix_name),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme::current().success),
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
                     format!("  Add one of {} affixes:", effects.add_specific_affix.len()),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme::current().success),
                 )));
                 for specific in &effects.add_specific_affix {
                     let affix_name = app
@@ -1418,21 +3080,47 @@ fn build_currency_preview(app: &App) -> Text<'static> {
                         .unwrap_or_default();
                     lines.push(Line::from(Span::styled(
                         format!("    - {}{}", affix_name, tier_str),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme::current().muted),
                     )));
                 }
             }
         }
+        if let Some(ref conversion) = effects.convert_damage {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Convert {}% {:?} damage to {:?}",
+                    conversion.percent, conversion.from, conversion.to
+                ),
+                Style::default().fg(theme::current().highlight),
+            )));
+        }
+        if let Some(ref pool) = effects.enchant_pool {
+            let pool_name = app
+                .generator()
+                .config()
+                .affix_pools
+                .get(pool)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| pool.clone());
+            lines.push(Line::from(Span::styled(
+                format!("  Set enchant from: {}", pool_name),
+                Style::default().fg(theme::current().special),
+            )));
+        }
         if effects.try_unique {
             lines.push(Line::from(Span::styled(
                 "  Transform to unique (if recipe matches)".to_string(),
-                Style::default().fg(Color::Rgb(175, 95, 0)),
+                Style::default().fg(theme::current().unique),
             )));
         }
+
+        if let Some(item) = item {
+            lines.extend(outcome_odds_lines(app.generator(), item, currency));
+        }
     } else {
         lines.push(Line::from(Span::styled(
             "Select a currency".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     }
 
@@ -1449,7 +3137,7 @@ fn render_detail(f: &mut Frame, app: &App, area: Rect) {
         .style(Style::default())
         .highlight_style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme::current().accent)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -1463,10 +3151,17 @@ fn render_detail(f: &mut Frame, app: &App, area: Rect) {
         .title(" Item Detail ");
     f.render_widget(tabs.block(tab_block), chunks[0]);
 
+    let mut scroll = 0;
     let content = match app.detail_tab {
         DetailTab::Stats => {
             if let Some(item) = app.selected_item() {
-                render_item_stats(item, &app.changed_affixes, app.generator())
+                render_item_stats(
+                    item,
+                    &app.changed_affixes,
+                    app.generator(),
+                    app.show_roll_percent,
+                    app.stat_view,
+                )
             } else {
                 Text::from("No item selected\n\nPress 'n' to create a new item")
             }
@@ -1478,30 +3173,141 @@ fn render_detail(f: &mut Frame, app: &App, area: Rect) {
                 Text::from("No item selected\n\nPress 'n' to create a new item")
             }
         }
-        DetailTab::Recipes => render_recipes(app.generator()),
+        DetailTab::Recipes => {
+            let (text, selected_line) = render_recipes(app);
+            // Keep the selected recipe's header on screen
+            let visible = chunks[1].height.saturating_sub(2) as usize;
+            scroll = selected_line.saturating_sub(visible.saturating_sub(1) / 2);
+            text
+        }
     };
 
     let paragraph = Paragraph::new(content)
         .block(Block::default().borders(Borders::ALL))
-        .wrap(Wrap { trim: false });
+        .wrap(Wrap { trim: false })
+        .scroll((scroll as u16, 0));
 
     f.render_widget(paragraph, chunks[1]);
 }
 
+/// Render the session log, newest entries at the bottom
+fn render_log(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines: Vec<Line> = Vec::new();
+    for entry in &app.log {
+        let (status, color) = match entry.result {
+            Ok(_) => ("ok ", theme::current().success),
+            Err(_) => ("err", theme::current().error),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format_time_of_day(entry.time),
+                Style::default().fg(theme::current().muted),
+            ),
+            Span::raw(" "),
+            Span::styled(status, Style::default().fg(color)),
+            Span::raw(" "),
+            Span::styled(
+                entry.action.clone(),
+                Style::default().fg(theme::current().accent),
+            ),
+            Span::raw(format!(" on {}", entry.item_name)),
+        ]));
+        match &entry.result {
+            Ok(changes) if changes.is_empty() => {
+                lines.push(Line::from(Span::styled(
+                    "    (no change)",
+                    Style::default().fg(theme::current().muted),
+                )));
+            }
+            Ok(changes) => {
+                for change in changes {
+                    lines.push(Line::from(format!("    {}", change)));
+                }
+            }
+            Err(e) => {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", e),
+                    Style::default().fg(theme::current().error),
+                )));
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No currency applied yet",
+            Style::default().fg(theme::current().muted),
+        )));
+    }
+
+    // Pin to the newest line unless scrolled back
+    let visible = area.height.saturating_sub(2) as usize;
+    let max_offset = lines.len().saturating_sub(visible);
+    let offset = max_offset.saturating_sub(app.log_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Log ({}) ", app.log.len())),
+        )
+        .scroll((offset as u16, 0));
+
+    f.render_widget(paragraph, area);
+}
+
+/// Summarize what an operation changed on an item, one line per change
+fn describe_changes(before: &Item, after: &Item) -> Vec<String> {
+    let mut changes = Vec::new();
+    if before.rarity != after.rarity {
+        changes.push(format!("Rarity: {:?} -> {:?}", before.rarity, after.rarity));
+    }
+    if before.name != after.name {
+        changes.push(format!("Name: {} -> {}", before.name, after.name));
+    }
+
+    let before_mods: Vec<&loot_core::item::Modifier> = before
+        .prefixes
+        .iter()
+        .chain(before.suffixes.iter())
+        .collect();
+    let after_mods: Vec<&loot_core::item::Modifier> =
+        after.prefixes.iter().chain(after.suffixes.iter()).collect();
+    for modifier in &before_mods {
+        if !after_mods
+            .iter()
+            .any(|m| m.fingerprint() == modifier.fingerprint())
+        {
+            changes.push(format!("- {} T{}", modifier.name, modifier.tier));
+        }
+    }
+    for modifier in &after_mods {
+        if !before_mods
+            .iter()
+            .any(|m| m.fingerprint() == modifier.fingerprint())
+        {
+            changes.push(format!("+ {} T{}", modifier.name, modifier.tier));
+        }
+    }
+    if before.enchant != after.enchant {
+        if let Some(ref enchant) = after.enchant {
+            changes.push(format!("Enchant: {} T{}", enchant.name, enchant.tier));
+        }
+    }
+    changes
+}
+
 fn render_item_stats(
     item: &Item,
     changed: &ChangedAffixes,
     generator: &Generator,
+    roll_percent: bool,
+    view: StatView,
 ) -> Text<'static> {
     let mut lines: Vec<Line> = Vec::new();
 
     // Header
-    let rarity_color = match item.rarity {
-        loot_core::Rarity::Normal => Color::White,
-        loot_core::Rarity::Magic => Color::Blue,
-        loot_core::Rarity::Rare => Color::Yellow,
-        loot_core::Rarity::Unique => Color::Rgb(175, 95, 0),
-    };
+    let rarity_color = rarity_color(item.rarity);
 
     lines.push(Line::from(vec![Span::styled(
         item.name.clone(),
@@ -1510,13 +3316,34 @@ fn render_item_stats(
             .add_modifier(Modifier::BOLD),
     )]));
     lines.push(Line::from(vec![
-        Span::styled(item.base_name.clone(), Style::default().fg(Color::Gray)),
+        Span::styled(
+            item.base_name.clone(),
+            Style::default().fg(theme::current().label),
+        ),
         Span::raw(" "),
         Span::styled(
             format!("({:?})", item.rarity),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         ),
     ]));
+    if item.quality > 0 {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Quality: +{}%", item.quality),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::styled(
+                format!(" ({})", item.quality_target.describe()),
+                Style::default().fg(theme::current().muted),
+            ),
+        ]));
+    }
+    if item.mirrored {
+        lines.push(Line::from(Span::styled(
+            "Mirrored (cannot be modified)",
+            Style::default().fg(theme::current().accent),
+        )));
+    }
 
     // Item tags
     if !item.tags.is_empty() {
@@ -1525,51 +3352,65 @@ fn render_item_stats(
             .iter()
             .enumerate()
             .flat_map(|(i, tag)| {
-                let mut spans = vec![Span::styled(tag.clone(), Style::default().fg(Color::Cyan))];
+                let mut spans = vec![Span::styled(
+                    tag.clone(),
+                    Style::default().fg(theme::current().accent),
+                )];
                 if i < item.tags.len() - 1 {
-                    spans.push(Span::styled(", ", Style::default().fg(Color::DarkGray)));
+                    spans.push(Span::styled(
+                        ", ",
+                        Style::default().fg(theme::current().muted),
+                    ));
                 }
                 spans
             })
             .collect();
-        let mut line_spans = vec![Span::styled("Tags: ", Style::default().fg(Color::DarkGray))];
+        let mut line_spans = vec![Span::styled(
+            "Tags: ",
+            Style::default().fg(theme::current().muted),
+        )];
         line_spans.extend(tag_spans);
         lines.push(Line::from(line_spans));
     }
 
     lines.push(Line::from(""));
 
-    // Defenses
-    if item.defenses.has_any() {
+    // Defenses (local modifiers applied)
+    let defenses = item.computed_defenses();
+    if defenses.has_any() {
         lines.push(Line::from(Span::styled(
             "Defenses".to_string(),
             Style::default().add_modifier(Modifier::UNDERLINED),
         )));
-        if let Some(armour) = item.defenses.armour {
-            lines.push(Line::from(format!("  Armour: {}", armour)));
-        }
-        if let Some(evasion) = item.defenses.evasion {
-            lines.push(Line::from(format!("  Evasion: {}", evasion)));
-        }
-        if let Some(es) = item.defenses.energy_shield {
-            lines.push(Line::from(format!("  Energy Shield: {}", es)));
+        for (label, value, base) in [
+            ("Armour", defenses.armour, item.defenses.armour),
+            ("Evasion", defenses.evasion, item.defenses.evasion),
+            (
+                "Energy Shield",
+                defenses.energy_shield,
+                item.defenses.energy_shield,
+            ),
+        ] {
+            if let Some(value) = value {
+                lines.push(local_value_line(label, value, base));
+            }
         }
         lines.push(Line::from(""));
     }
 
-    // Damage
-    if let Some(ref dmg) = item.damage {
+    // Damage (local modifiers applied)
+    if let Some(ref dmg) = item.computed_damage() {
         lines.push(Line::from(Span::styled(
             "Damage".to_string(),
             Style::default().add_modifier(Modifier::UNDERLINED),
         )));
         for entry in &dmg.damages {
             let color = match entry.damage_type {
-                loot_core::types::DamageType::Physical => Color::White,
-                loot_core::types::DamageType::Fire => Color::Red,
-                loot_core::types::DamageType::Cold => Color::Cyan,
-                loot_core::types::DamageType::Lightning => Color::Yellow,
-                loot_core::types::DamageType::Chaos => Color::Magenta,
+                loot_core::types::DamageType::Physical => theme::current().physical,
+                loot_core::types::DamageType::Fire => theme::current().fire,
+                loot_core::types::DamageType::Cold => theme::current().cold,
+                loot_core::types::DamageType::Lightning => theme::current().lightning,
+                loot_core::types::DamageType::Chaos => theme::current().chaos,
             };
             lines.push(Line::from(Span::styled(
                 format!("  {:?}: {}-{}", entry.damage_type, entry.min, entry.max),
@@ -1594,25 +3435,100 @@ fn render_item_stats(
                 dmg.spell_efficiency
             )));
         }
+        if let Some(dps) = item.dps() {
+            lines.push(Line::from(vec![
+                Span::raw("  DPS: "),
+                Span::styled(
+                    format!("{:.1}", dps),
+                    Style::default()
+                        .fg(theme::current().text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    " (local mods only)".to_string(),
+                    Style::default().fg(theme::current().muted),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    match view {
+        StatView::Rolls => lines.extend(modifier_lines(item, changed, generator, roll_percent)),
+        StatView::Totals => lines.extend(total_lines(item)),
+    }
+
+    // Requirements
+    let requirements = item.computed_requirements();
+    let mut reqs = Vec::new();
+    if requirements.level > 0 {
+        reqs.push(format!("Level {}", requirements.level));
+    }
+    if requirements.strength > 0 {
+        reqs.push(format!("{} Str", requirements.strength));
+    }
+    if requirements.dexterity > 0 {
+        reqs.push(format!("{} Dex", requirements.dexterity));
+    }
+    if requirements.intelligence > 0 {
+        reqs.push(format!("{} Int", requirements.intelligence));
+    }
+    if !reqs.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Requires: {}", reqs.join(", ")),
+            Style::default().fg(theme::current().muted),
+        )));
+    }
+
+    if !item.target.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(target_lines(item, generator));
+    }
+
+    if let Some(flavor) = item_unique(item, generator).and_then(|u| u.flavor.as_deref()) {
         lines.push(Line::from(""));
+        for line in wrap_words(flavor, FLAVOR_WIDTH) {
+            lines.push(Line::from(Span::styled(
+                line,
+                Style::default()
+                    .fg(theme::current().muted)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        }
     }
 
-    // Implicit
-    if let Some(ref imp) = item.implicit {
+    Text::from(lines)
+}
+
+/// Implicit, explicit and enchant modifiers one per line, with tiers and roll ranges
+fn modifier_lines(
+    item: &Item,
+    changed: &ChangedAffixes,
+    generator: &Generator,
+    roll_percent: bool,
+) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Implicits
+    if !item.implicits.is_empty() {
         lines.push(Line::from(Span::styled(
             "Implicit".to_string(),
             Style::default().add_modifier(Modifier::UNDERLINED),
         )));
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("  {} ", imp.display()),
-                Style::default().fg(Color::Magenta),
-            ),
-            Span::styled(
-                format!("({}-{})", imp.tier_min, imp.tier_max),
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]));
+        for imp in &item.implicits {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", imp.display()),
+                    Style::default().fg(theme::current().special),
+                ),
+                Span::styled(
+                    format!("({}-{})", imp.tier_min, imp.tier_max),
+                    Style::default().fg(theme::current().muted),
+                ),
+                quality_note(imp, item.quality_scaled_implicit(imp)),
+                roll_percent_note(imp, roll_percent),
+            ]));
+        }
         lines.push(Line::from(""));
     }
 
@@ -1622,131 +3538,490 @@ fn render_item_stats(
             "Modifiers".to_string(),
             Style::default().add_modifier(Modifier::UNDERLINED),
         )));
-        for (i, prefix) in item.prefixes.iter().enumerate() {
-            let marker = if changed.prefixes.contains(&i) {
-                Span::styled(">> ", Style::default().fg(Color::LightRed))
+        for (affix_type, i, modifier) in item.ordered_explicits(theme::mod_order()) {
+            let (changed_indices, color, letter) = match affix_type {
+                AffixType::Prefix => (&changed.prefixes, theme::current().prefix, "P"),
+                AffixType::Suffix => (&changed.suffixes, theme::current().suffix, "S"),
+            };
+            let marker = if changed_indices.contains(&i) {
+                Span::styled(">> ", Style::default().fg(theme::current().changed))
             } else {
                 Span::raw("   ")
             };
-            let tier_range = if let Some((max_min, max_max)) = prefix.tier_max_value {
+            let tier_range = if let Some((max_min, max_max)) = modifier.tier_max_value {
                 format!(
                     "({}-{} to {}-{}) ",
-                    prefix.tier_min, prefix.tier_max, max_min, max_max
+                    modifier.tier_min, modifier.tier_max, max_min, max_max
                 )
             } else {
-                format!("({}-{}) ", prefix.tier_min, prefix.tier_max)
+                format!("({}-{}) ", modifier.tier_min, modifier.tier_max)
             };
             lines.push(Line::from(vec![
                 marker,
                 Span::styled(
-                    format!("{} ", prefix.display()),
-                    Style::default().fg(Color::Cyan),
+                    format!("{} ", modifier.display()),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    format!("[T{}] ", modifier.tier),
+                    Style::default().fg(theme::current().highlight),
                 ),
+                Span::styled(tier_range, Style::default().fg(theme::current().muted)),
                 Span::styled(
-                    format!("[T{}] ", prefix.tier),
-                    Style::default().fg(Color::Yellow),
+                    letter.to_string(),
+                    Style::default().fg(theme::current().muted),
                 ),
-                Span::styled(tier_range, Style::default().fg(Color::DarkGray)),
-                Span::styled("P".to_string(), Style::default().fg(Color::DarkGray)),
+                quality_note(modifier, item.quality_scaled(modifier)),
+                roll_percent_note(modifier, roll_percent),
             ]));
-            // Show affix scope and tags
-            if let Some(affix_config) = generator.config().affixes.get(&prefix.affix_id) {
-                let scope_color = match affix_config.scope {
-                    loot_core::types::AffixScope::Local => Color::Blue,
-                    loot_core::types::AffixScope::Global => Color::Magenta,
-                };
-                let mut info_parts = vec![
-                    Span::raw("      "),
-                    Span::styled(
-                        format!("{:?}", affix_config.scope),
-                        Style::default().fg(scope_color),
-                    ),
-                ];
-                if !affix_config.tags.is_empty() {
-                    let tags_str = affix_config.tags.join(", ");
-                    info_parts.push(Span::styled(
-                        format!(" | tags: {}", tags_str),
-                        Style::default().fg(Color::DarkGray),
-                    ));
+            // Show affix scope and tags, as rolled onto the modifier
+            let mut info_parts = vec![
+                Span::raw("      "),
+                Span::styled(
+                    scope_tooltip(modifier.scope).to_string(),
+                    Style::default().fg(scope_color(modifier.scope)),
+                ),
+            ];
+            if !modifier.tags.is_empty() {
+                info_parts.push(Span::styled(
+                    format!(" | tags: {}", modifier.tags.join(", ")),
+                    Style::default().fg(theme::current().muted),
+                ));
+            }
+            lines.push(Line::from(info_parts));
+            if let Some(affix_config) = generator.config().affixes.get(&modifier.affix_id) {
+                if let Some(ref flavor) = affix_config.flavor {
+                    lines.push(Line::from(Span::styled(
+                        format!("      \"{}\"", flavor),
+                        Style::default()
+                            .fg(theme::current().muted)
+                            .add_modifier(Modifier::ITALIC),
+                    )));
                 }
-                lines.push(Line::from(info_parts));
             }
         }
-        for (i, suffix) in item.suffixes.iter().enumerate() {
-            let marker = if changed.suffixes.contains(&i) {
-                Span::styled(">> ", Style::default().fg(Color::LightRed))
-            } else {
-                Span::raw("   ")
-            };
-            let tier_range = if let Some((max_min, max_max)) = suffix.tier_max_value {
+        lines.push(Line::from(""));
+    }
+
+    // Enchant
+    if let Some(ref enchant) = item.enchant {
+        lines.push(Line::from(Span::styled(
+            "Enchant".to_string(),
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        )));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", enchant.display()),
+                Style::default().fg(theme::current().special),
+            ),
+            Span::styled(
+                format!("[T{}] ", enchant.tier),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::styled(
+                format!("({}-{})", enchant.tier_min, enchant.tier_max),
+                Style::default().fg(theme::current().muted),
+            ),
+            quality_note(enchant, item.quality_scaled(enchant)),
+            roll_percent_note(enchant, roll_percent),
+        ]));
+        lines.push(Line::from(""));
+    }
+
+    lines
+}
+
+/// Each stat the item grants summed across its modifiers, in place of the per-modifier
+/// lines
+fn total_lines(item: &Item) -> Vec<Line<'static>> {
+    let totals = item.total_stats();
+    if totals.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![Line::from(Span::styled(
+        "Totals".to_string(),
+        Style::default().add_modifier(Modifier::UNDERLINED),
+    ))];
+    for (stat, total, total_max) in totals {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {}",
+                loot_core::item::Modifier::stat_display(stat, total, total_max)
+            ),
+            Style::default().fg(theme::current().text),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Column width unique flavor text is wrapped to in the stat panel
+const FLAVOR_WIDTH: usize = 50;
+
+/// The unique config an item was made from: the drop recorded as its first operation,
+/// or for items chanced into a unique, the unique with its name on its base
+fn item_unique<'a>(item: &Item, generator: &'a Generator) -> Option<&'a UniqueConfig> {
+    if item.rarity != loot_core::Rarity::Unique {
+        return None;
+    }
+    if let Some(Operation::Unique(unique_id)) = item.operations.first() {
+        return generator.get_unique(unique_id);
+    }
+    generator
+        .config()
+        .uniques_sorted()
+        .find(|unique| unique.name == item.name && unique.base_type == item.base_type_id)
+}
+
+/// Split text into lines of at most `width` characters, breaking between words (a
+/// single longer word gets a line of its own)
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// The crafting target's met/unmet requirements and the currency most likely to help next
+fn target_lines(item: &Item, generator: &Generator) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        "Crafting Target".to_string(),
+        Style::default().add_modifier(Modifier::UNDERLINED),
+    ))];
+    let progress = item.target_progress();
+    for (req, met) in &progress {
+        let tier = if req.max_tier >= ANY_TIER {
+            "any tier".to_string()
+        } else {
+            format!("T{} or better", req.max_tier)
+        };
+        let (mark, color) = if *met {
+            ("  [x] ", theme::current().success)
+        } else {
+            ("  [ ] ", theme::current().error)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(mark, Style::default().fg(color)),
+            Span::raw(format!("{} ", req.stat.name())),
+            Span::styled(tier, Style::default().fg(theme::current().muted)),
+        ]));
+    }
+
+    let hint = if progress.iter().all(|(_, met)| *met) {
+        "Target met".to_string()
+    } else {
+        match generator.suggest_target_currency(item) {
+            Some((currency_id, chance)) => {
+                let name = generator
+                    .config()
+                    .currencies
+                    .get(&currency_id)
+                    .map_or(currency_id.clone(), |c| c.name.clone());
                 format!(
-                    "({}-{} to {}-{}) ",
-                    suffix.tier_min, suffix.tier_max, max_min, max_max
+                    "Try {}: {:.1}% to add a missing affix",
+                    name,
+                    chance * 100.0
                 )
+            }
+            None => "No currency can add a missing affix right now".to_string(),
+        }
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme::current().highlight),
+    )));
+    lines
+}
+
+/// Each explicit modifier's chance of being the one removed by a random removal
+fn removal_odds_lines(generator: &Generator, item: &Item) -> Vec<Line<'static>> {
+    let weights = generator.annul_weights(item);
+    let total: u32 = weights.iter().sum();
+    let uniform = weights.iter().all(|&w| w == weights[0]);
+
+    item.prefixes
+        .iter()
+        .chain(item.suffixes.iter())
+        .zip(weights)
+        .map(|(modifier, weight)| {
+            let odds = if uniform {
+                format!("1/{}", item.affix_count())
             } else {
-                format!("({}-{}) ", suffix.tier_min, suffix.tier_max)
+                format!("{:.1}%", weight as f64 * 100.0 / total as f64)
             };
-            lines.push(Line::from(vec![
-                marker,
+            Line::from(vec![
                 Span::styled(
-                    format!("{} ", suffix.display()),
-                    Style::default().fg(Color::Green),
+                    format!("    {:>6} ", odds),
+                    Style::default().fg(theme::current().highlight),
                 ),
                 Span::styled(
-                    format!("[T{}] ", suffix.tier),
-                    Style::default().fg(Color::Yellow),
+                    modifier.display(),
+                    Style::default().fg(theme::current().muted),
                 ),
-                Span::styled(tier_range, Style::default().fg(Color::DarkGray)),
-                Span::styled("S".to_string(), Style::default().fg(Color::DarkGray)),
-            ]));
-            // Show affix scope and tags
-            if let Some(affix_config) = generator.config().affixes.get(&suffix.affix_id) {
-                let scope_color = match affix_config.scope {
-                    loot_core::types::AffixScope::Local => Color::Blue,
-                    loot_core::types::AffixScope::Global => Color::Magenta,
-                };
-                let mut info_parts = vec![
-                    Span::raw("      "),
-                    Span::styled(
-                        format!("{:?}", affix_config.scope),
-                        Style::default().fg(scope_color),
-                    ),
-                ];
-                if !affix_config.tags.is_empty() {
-                    let tags_str = affix_config.tags.join(", ");
-                    info_parts.push(Span::styled(
-                        format!(" | tags: {}", tags_str),
-                        Style::default().fg(Color::DarkGray),
-                    ));
-                }
-                lines.push(Line::from(info_parts));
-            }
-        }
-        lines.push(Line::from(""));
+            ])
+        })
+        .collect()
+}
+
+/// Lock state of the explicit modifier at `index` (prefixes first) in the currency preview
+fn lock_marker(app: &App, index: usize, affix_id: &str) -> Span<'static> {
+    let state = &app.currency_popup_state;
+    let text = if state.locked.iter().any(|id| id == affix_id) {
+        "[L] "
+    } else if state.locking {
+        "[ ] "
+    } else {
+        ""
+    };
+    let mut style = Style::default().fg(theme::current().accent);
+    if state.locking && state.lock_cursor == index {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    Span::styled(text, style)
+}
+
+/// "What can happen" list: each affix a currency could add, most likely first
+fn outcome_odds_lines(
+    generator: &Generator,
+    item: &Item,
+    currency: &CurrencyConfig,
+) -> Vec<Line<'static>> {
+    let Ok(odds) = generator.currency_outcome_odds(item, &currency.id) else {
+        return Vec::new();
+    };
+    let affix_name = |id: &str| {
+        generator
+            .config()
+            .affixes
+            .get(id)
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let mut adds: Vec<&AffixOdds> = odds.added.iter().chain(odds.specific.iter()).collect();
+    adds.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+    let replaced = if currency.effects.reroll_affixes.is_some() {
+        odds.removed.as_slice()
+    } else {
+        &[]
+    };
+    if adds.is_empty() && replaced.is_empty() {
+        return Vec::new();
     }
 
-    // Requirements
-    let mut reqs = Vec::new();
-    if item.requirements.level > 0 {
-        reqs.push(format!("Level {}", item.requirements.level));
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "What Can Happen".to_string(),
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        )),
+    ];
+    for (affix_id, chance) in replaced {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>5.1}% ", chance * 100.0),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::styled(
+                format!("replace {}", affix_name(affix_id)),
+                Style::default().fg(theme::current().error),
+            ),
+        ]));
+    }
+    for add in adds {
+        let color = match generator.config().affixes.get(&add.affix_id) {
+            Some(affix) if affix.affix_type == AffixType::Suffix => theme::current().suffix,
+            _ => theme::current().prefix,
+        };
+        let tiers: Vec<String> = add
+            .tiers
+            .iter()
+            .map(|(tier, chance)| format!("T{} {:.1}%", tier, chance * 100.0))
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>5.1}% ", add.chance * 100.0),
+                Style::default().fg(theme::current().highlight),
+            ),
+            Span::styled(affix_name(&add.affix_id), Style::default().fg(color)),
+            Span::styled(
+                format!(" ({})", tiers.join(", ")),
+                Style::default().fg(theme::current().muted),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// Armour, evasion and energy shield after local modifiers, summed
+fn total_defense(item: &Item) -> i32 {
+    let defenses = item.computed_defenses();
+    [defenses.armour, defenses.evasion, defenses.energy_shield]
+        .into_iter()
+        .flatten()
+        .sum()
+}
+
+/// Current wall-clock time in Unix seconds, for operation timestamps
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format a Unix timestamp as a UTC time of day (HH:MM:SS)
+fn format_time_of_day(unix_secs: u64) -> String {
+    let secs = unix_secs % 86_400;
+    format!(
+        "{:02}:{:02}:{:02} UTC",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Resistance from every modifier on the item, with all resistances counted once for
+/// each element it covers
+fn total_resistance(item: &Item) -> i32 {
+    [
+        StatType::FireResistance,
+        StatType::ColdResistance,
+        StatType::LightningResistance,
+        StatType::ChaosResistance,
+    ]
+    .into_iter()
+    .map(|stat| item.stat_total(stat))
+    .sum::<i32>()
+        + 3 * item.stat_total(StatType::AllResistances)
+}
+
+/// Where `value` sits between the tier's `min` and `max`, as a whole percentage. A tier
+/// with a single value always counts as a perfect roll.
+fn roll_percent(value: i32, min: i32, max: i32) -> u32 {
+    if max <= min {
+        return 100;
     }
-    if item.requirements.strength > 0 {
-        reqs.push(format!("{} Str", item.requirements.strength));
+    let fraction = (value - min) as f64 / (max - min) as f64;
+    (fraction * 100.0).round().clamp(0.0, 100.0) as u32
+}
+
+/// The modifier's roll as a percentage of its tier range, when that display is on
+fn roll_percent_note(modifier: &loot_core::item::Modifier, enabled: bool) -> Span<'static> {
+    if !enabled {
+        return Span::raw("");
     }
-    if item.requirements.dexterity > 0 {
-        reqs.push(format!("{} Dex", item.requirements.dexterity));
+    Span::styled(
+        format!(
+            " ({}%)",
+            roll_percent(modifier.value, modifier.tier_min, modifier.tier_max)
+        ),
+        Style::default().fg(theme::current().muted),
+    )
+}
+
+/// The modifier's value after quality (`scaled`), when quality changed it
+fn quality_note(
+    modifier: &loot_core::item::Modifier,
+    (value, value_max): (i32, Option<i32>),
+) -> Span<'static> {
+    if value == modifier.value && value_max == modifier.value_max {
+        return Span::raw("");
     }
-    if item.requirements.intelligence > 0 {
-        reqs.push(format!("{} Int", item.requirements.intelligence));
+    let scaled = match value_max {
+        Some(max) => format!("{}-{}", value, max),
+        None => value.to_string(),
+    };
+    Span::styled(
+        format!(" (quality: {})", scaled),
+        Style::default().fg(theme::current().highlight),
+    )
+}
+
+/// Display color for a rarity in the active theme
+fn rarity_color(rarity: loot_core::Rarity) -> Color {
+    match rarity {
+        loot_core::Rarity::Normal => theme::current().normal,
+        loot_core::Rarity::Magic => theme::current().magic,
+        loot_core::Rarity::Rare => theme::current().rare,
+        loot_core::Rarity::Unique => theme::current().unique,
     }
-    if !reqs.is_empty() {
-        lines.push(Line::from(Span::styled(
-            format!("Requires: {}", reqs.join(", ")),
-            Style::default().fg(Color::DarkGray),
-        )));
+}
+
+/// Display color for a modifier scope in the active theme
+fn scope_color(scope: loot_core::types::AffixScope) -> Color {
+    match scope {
+        loot_core::types::AffixScope::Local => theme::current().local,
+        loot_core::types::AffixScope::Global => theme::current().global,
     }
+}
 
-    Text::from(lines)
+/// Explain what a modifier's scope means for the item's displayed stats
+fn scope_tooltip(scope: loot_core::types::AffixScope) -> &'static str {
+    match scope {
+        loot_core::types::AffixScope::Local => "Local (affects this item)",
+        loot_core::types::AffixScope::Global => "Global (affects character)",
+    }
+}
+
+/// A stat line that notes the base value when local modifiers changed it
+fn local_value_line(label: &str, value: i32, base: Option<i32>) -> Line<'static> {
+    let mut spans = vec![Span::raw(format!("  {}: {}", label, value))];
+    if base != Some(value) {
+        spans.push(Span::styled(
+            format!(" (base {})", base.unwrap_or(0)),
+            Style::default().fg(theme::current().muted),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// One recorded operation as shown in the seed tab and item dumps
+fn operation_text(op: &Operation) -> String {
+    match op {
+        Operation::Currency(action) => action.clone(),
+        Operation::AddAffix { affix_id, tier } => format!("add {} (T{})", affix_id, tier),
+        Operation::Mirror => "mirror".to_string(),
+        Operation::Unique(unique_id) => format!("drop {}", unique_id),
+        Operation::Rarity(rarity) => format!("drop {}", rarity.name().to_lowercase()),
+        Operation::LockedCurrency {
+            currency_id,
+            locked,
+        } => format!("{} (keeping {})", currency_id, locked.join(", ")),
+    }
+}
+
+/// Everything needed to reproduce or inspect an item, for bug reports: the seed, the
+/// operations to replay from it, and the full JSON state
+fn item_dump(item: &Item) -> String {
+    let mut dump = format!(
+        "{}\nBase Type: {}\nSeed: 0x{:016X}\n\nOperations\n",
+        item.name, item.base_type_id, item.seed
+    );
+    if item.operations.is_empty() {
+        dump.push_str("  (none)\n");
+    }
+    for (i, op) in item.operations.iter().enumerate() {
+        dump.push_str(&format!("  {}. {}\n", i + 1, operation_text(op)));
+    }
+    dump.push_str("\nJSON\n");
+    match item.to_json() {
+        Ok(json) => dump.push_str(&json),
+        Err(e) => dump.push_str(&format!("(failed to serialize: {})", e)),
+    }
+    dump.push('\n');
+    dump
 }
 
 fn render_item_seed(item: &Item) -> Text<'static> {
@@ -1759,15 +4034,21 @@ fn render_item_seed(item: &Item) -> Text<'static> {
     lines.push(Line::from(""));
 
     lines.push(Line::from(vec![
-        Span::styled("Base Type: ".to_string(), Style::default().fg(Color::Gray)),
+        Span::styled(
+            "Base Type: ".to_string(),
+            Style::default().fg(theme::current().label),
+        ),
         Span::raw(item.base_type_id.clone()),
     ]));
 
     lines.push(Line::from(vec![
-        Span::styled("Seed: ".to_string(), Style::default().fg(Color::Gray)),
+        Span::styled(
+            "Seed: ".to_string(),
+            Style::default().fg(theme::current().label),
+        ),
         Span::styled(
             format!("0x{:016X}", item.seed),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme::current().highlight),
         ),
     ]));
 
@@ -1780,20 +4061,25 @@ fn render_item_seed(item: &Item) -> Text<'static> {
     if item.operations.is_empty() {
         lines.push(Line::from(Span::styled(
             "  (none)".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
         )));
     } else {
         for (i, op) in item.operations.iter().enumerate() {
-            let op_str = match op {
-                Operation::Currency(action) => action.clone(),
-            };
-            lines.push(Line::from(vec![
+            let op_str = operation_text(op);
+            let mut spans = vec![
                 Span::styled(
                     format!("  {}. ", i + 1),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
-                Span::styled(op_str, Style::default().fg(Color::Cyan)),
-            ]));
+                Span::styled(op_str, Style::default().fg(theme::current().accent)),
+            ];
+            if let Some(time) = item.operation_time(i) {
+                spans.push(Span::styled(
+                    format!("  {}", format_time_of_day(time)),
+                    Style::default().fg(theme::current().muted),
+                ));
+            }
+            lines.push(Line::from(spans));
         }
     }
 
@@ -1808,17 +4094,48 @@ fn render_item_seed(item: &Item) -> Text<'static> {
     use loot_core::BinaryEncode;
     let encoded = item.encode_to_vec();
     lines.push(Line::from(vec![
-        Span::styled("Encoded: ".to_string(), Style::default().fg(Color::Gray)),
+        Span::styled(
+            "Encoded: ".to_string(),
+            Style::default().fg(theme::current().label),
+        ),
         Span::styled(
             format!("{} bytes", encoded.len()),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme::current().success),
         ),
     ]));
 
     Text::from(lines)
 }
 
-fn render_recipes(generator: &Generator) -> Text<'static> {
+/// Whether a recipe's unique or base type name/id contains `filter` (already lowercased)
+fn recipe_matches_filter(
+    generator: &Generator,
+    recipe: &loot_core::config::UniqueRecipeConfig,
+    filter: &str,
+) -> bool {
+    let unique_name = generator
+        .get_unique(&recipe.unique_id)
+        .map(|u| u.name.as_str())
+        .unwrap_or_default();
+    let base_name = generator
+        .get_base_type(&recipe.base_type)
+        .map(|b| b.name.as_str())
+        .unwrap_or_default();
+    [
+        unique_name,
+        recipe.unique_id.as_str(),
+        base_name,
+        recipe.base_type.as_str(),
+    ]
+    .iter()
+    .any(|text| text.to_lowercase().contains(filter))
+}
+
+/// Render the Recipes tab; also returns the line of the selected recipe so it can be
+/// scrolled into view
+fn render_recipes(app: &App) -> (Text<'static>, usize) {
+    let generator = app.generator();
+    let view = &app.recipe_view;
     let mut lines: Vec<Line> = Vec::new();
 
     lines.push(Line::from(Span::styled(
@@ -1827,19 +4144,44 @@ fn render_recipes(generator: &Generator) -> Text<'static> {
     )));
     lines.push(Line::from(Span::styled(
         "Use a transform_unique currency on matching items".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme::current().muted),
     )));
+
+    let editing = app.focus == Focus::RecipeFilter;
+    if editing || !view.filter.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(
+                "Filter: ".to_string(),
+                Style::default().fg(theme::current().label),
+            ),
+            Span::styled(
+                format!("{}{}", view.filter, if editing { "_" } else { "" }),
+                Style::default().fg(theme::current().highlight),
+            ),
+        ]));
+    }
     lines.push(Line::from(""));
 
     let recipes = &generator.config().unique_recipes;
+    let filtered = app.filtered_recipes();
+    let mut selected_line = 0;
 
     if recipes.is_empty() {
         lines.push(Line::from(Span::styled(
             "No recipes configured".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme::current().muted),
+        )));
+    } else if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No recipes match '{}'", view.filter),
+            Style::default().fg(theme::current().muted),
         )));
     } else {
-        for recipe in recipes {
+        for (position, &index) in filtered.iter().enumerate() {
+            let recipe = &recipes[index];
+            let selected = position == view.selected;
+            let expanded = view.expanded.contains(&index);
+
             // Get unique name
             let unique_name = generator
                 .get_unique(&recipe.unique_id)
@@ -1852,124 +4194,141 @@ fn render_recipes(generator: &Generator) -> Text<'static> {
                 .map(|b| b.name.clone())
                 .unwrap_or_else(|| recipe.base_type.clone());
 
+            if selected {
+                selected_line = lines.len();
+            }
+
             // Recipe header
             lines.push(Line::from(vec![
+                Span::styled(
+                    if selected { ">> " } else { "   " }.to_string(),
+                    Style::default().fg(theme::current().changed),
+                ),
+                Span::raw(if expanded { "▾ " } else { "▸ " }),
                 Span::styled(
                     unique_name,
                     Style::default()
-                        .fg(Color::Rgb(175, 95, 0))
+                        .fg(theme::current().unique)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     format!(" (weight: {})", recipe.weight),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]));
 
             // Base type requirement
             lines.push(Line::from(vec![
-                Span::styled("  Base: ".to_string(), Style::default().fg(Color::Gray)),
-                Span::styled(base_name, Style::default().fg(Color::White)),
+                Span::styled(
+                    "  Base: ".to_string(),
+                    Style::default().fg(theme::current().label),
+                ),
+                Span::styled(base_name, Style::default().fg(theme::current().text)),
             ]));
 
-            // Required affixes
-            lines.push(Line::from(Span::styled(
-                "  Required Affixes:".to_string(),
-                Style::default().fg(Color::Gray),
-            )));
-
-            for req in &recipe.required_affixes {
-                let affix_type_str = match req.affix_type {
-                    Some(loot_core::AffixType::Prefix) => " (Prefix)",
-                    Some(loot_core::AffixType::Suffix) => " (Suffix)",
-                    None => "",
-                };
-
-                let tier_str = if req.min_tier == 1 && req.max_tier == 99 {
-                    "any tier".to_string()
-                } else if req.min_tier == req.max_tier {
-                    format!("T{}", req.min_tier)
-                } else {
-                    format!("T{}-T{}", req.min_tier, req.max_tier)
-                };
-
-                // Check if this required affix has a mapping
-                let has_mapping = recipe.mappings.iter().any(|m| m.from_stat == req.stat);
-                let mapping_indicator = if has_mapping {
-                    Span::styled(" → mapped", Style::default().fg(Color::Green))
-                } else {
-                    Span::styled(" (gate only)", Style::default().fg(Color::DarkGray))
-                };
-
-                lines.push(Line::from(vec![
-                    Span::raw("    • "),
-                    Span::styled(format!("{:?}", req.stat), Style::default().fg(Color::Cyan)),
-                    Span::styled(
-                        affix_type_str.to_string(),
-                        Style::default().fg(Color::Yellow),
-                    ),
-                    Span::styled(
-                        format!(" [{}]", tier_str),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                    mapping_indicator,
-                ]));
-            }
-
-            // Show unique mods and their mappings
-            if let Some(unique) = generator.get_unique(&recipe.unique_id) {
+            if expanded {
+                // Required affixes
                 lines.push(Line::from(Span::styled(
-                    "  Unique Mods:".to_string(),
-                    Style::default().fg(Color::Gray),
+                    "  Required Affixes:".to_string(),
+                    Style::default().fg(theme::current().label),
                 )));
 
-                for (mod_idx, mod_cfg) in unique.mods.iter().enumerate() {
-                    // Check if this mod has a mapping
-                    let mapping = recipe.mappings.iter().find(|m| m.to_mod_index == mod_idx);
+                for req in &recipe.required_affixes {
+                    let affix_type_str = match req.affix_type {
+                        Some(AffixType::Prefix) => " (Prefix)",
+                        Some(AffixType::Suffix) => " (Suffix)",
+                        None => "",
+                    };
+
+                    let tier_str = if req.min_tier == 1 && req.max_tier == 99 {
+                        "any tier".to_string()
+                    } else if req.min_tier == req.max_tier {
+                        format!("T{}", req.min_tier)
+                    } else {
+                        format!("T{}-T{}", req.min_tier, req.max_tier)
+                    };
+
+                    // Check if this required affix has a mapping
+                    let has_mapping = recipe.mappings.iter().any(|m| m.from_stat == req.stat);
+                    let mapping_indicator = if has_mapping {
+                        Span::styled(" → mapped", Style::default().fg(theme::current().success))
+                    } else {
+                        Span::styled(" (gate only)", Style::default().fg(theme::current().muted))
+                    };
 
-                    let mut spans = vec![
-                        Span::raw("    "),
+                    lines.push(Line::from(vec![
+                        Span::raw("    • "),
+                        Span::styled(
+                            format!("{:?}", req.stat),
+                            Style::default().fg(theme::current().accent),
+                        ),
                         Span::styled(
-                            format!("#{} {:?}", mod_idx + 1, mod_cfg.stat),
-                            Style::default().fg(Color::Magenta),
+                            affix_type_str.to_string(),
+                            Style::default().fg(theme::current().highlight),
                         ),
                         Span::styled(
-                            format!(" ({}-{})", mod_cfg.min, mod_cfg.max),
-                            Style::default().fg(Color::DarkGray),
+                            format!(" [{}]", tier_str),
+                            Style::default().fg(theme::current().muted),
                         ),
-                    ];
+                        mapping_indicator,
+                    ]));
+                }
 
-                    if let Some(mapping) = mapping {
-                        let mode_str = match mapping.mode {
-                            MappingMode::Percentage => "pct",
-                            MappingMode::Direct => "direct",
-                            MappingMode::Random => "random",
-                        };
+                // Show unique mods and their mappings
+                if let Some(unique) = generator.get_unique(&recipe.unique_id) {
+                    lines.push(Line::from(Span::styled(
+                        "  Unique Mods:".to_string(),
+                        Style::default().fg(theme::current().label),
+                    )));
 
-                        let influence_str = if mapping.mode == MappingMode::Random {
-                            String::new()
-                        } else if mapping.influence >= 1.0 {
-                            String::new()
+                    for (mod_idx, mod_cfg) in unique.mods.iter().enumerate() {
+                        // Check if this mod has a mapping
+                        let mapping = recipe.mappings.iter().find(|m| m.to_mod_index == mod_idx);
+
+                        let mut spans = vec![
+                            Span::raw("    "),
+                            Span::styled(
+                                format!("#{} {:?}", mod_idx + 1, mod_cfg.stat),
+                                Style::default().fg(theme::current().special),
+                            ),
+                            Span::styled(
+                                format!(" ({}-{})", mod_cfg.min, mod_cfg.max),
+                                Style::default().fg(theme::current().muted),
+                            ),
+                        ];
+
+                        if let Some(mapping) = mapping {
+                            let mode_str = match mapping.mode {
+                                MappingMode::Percentage => "pct",
+                                MappingMode::Direct => "direct",
+                                MappingMode::Random => "random",
+                            };
+
+                            let influence_str = if mapping.mode == MappingMode::Random
+                                || mapping.influence >= 1.0
+                            {
+                                String::new()
+                            } else {
+                                format!(" {:.0}%", mapping.influence * 100.0)
+                            };
+
+                            spans.push(Span::styled(
+                                format!(" ← {:?}", mapping.from_stat),
+                                Style::default().fg(theme::current().success),
+                            ));
+                            spans.push(Span::styled(
+                                format!(" [{}{}]", mode_str, influence_str),
+                                Style::default().fg(theme::current().highlight),
+                            ));
                         } else {
-                            format!(" {:.0}%", mapping.influence * 100.0)
-                        };
+                            spans.push(Span::styled(
+                                " (random)".to_string(),
+                                Style::default().fg(theme::current().muted),
+                            ));
+                        }
 
-                        spans.push(Span::styled(
-                            format!(" ← {:?}", mapping.from_stat),
-                            Style::default().fg(Color::Green),
-                        ));
-                        spans.push(Span::styled(
-                            format!(" [{}{}]", mode_str, influence_str),
-                            Style::default().fg(Color::Yellow),
-                        ));
-                    } else {
-                        spans.push(Span::styled(
-                            " (random)".to_string(),
-                            Style::default().fg(Color::DarkGray),
-                        ));
+                        lines.push(Line::from(spans));
                     }
-
-                    lines.push(Line::from(spans));
                 }
             }
 
@@ -1977,70 +4336,319 @@ fn render_recipes(generator: &Generator) -> Text<'static> {
         }
     }
 
-    Text::from(lines)
+    (Text::from(lines), selected_line)
+}
+
+/// One-line summary of the selected item, shown regardless of the detail tab
+fn status_line(app: &App) -> Line<'static> {
+    let Some(item) = app.selected_item() else {
+        return Line::from(Span::styled(
+            " No item selected",
+            Style::default().fg(theme::current().muted),
+        ));
+    };
+
+    let muted = Style::default().fg(theme::current().muted);
+    let mut spans = vec![
+        Span::styled(
+            format!(" {}", item.name),
+            Style::default()
+                .fg(rarity_color(item.rarity))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" ({})", item.base_name), muted),
+    ];
+
+    let mut summary = Vec::new();
+    if let Some(dps) = item.dps() {
+        summary.push(format!("DPS {:.1}", dps));
+    }
+    let defenses = item.computed_defenses();
+    for (label, value) in [
+        ("AR", defenses.armour),
+        ("EV", defenses.evasion),
+        ("ES", defenses.energy_shield),
+    ] {
+        if let Some(value) = value {
+            summary.push(format!("{} {}", label, value));
+        }
+    }
+    summary.push(format!("{}P/{}S", item.prefixes.len(), item.suffixes.len()));
+    if item.mirrored {
+        summary.push("Mirrored".to_string());
+    }
+
+    spans.push(Span::styled(" | ", muted));
+    spans.push(Span::styled(
+        summary.join(" | "),
+        Style::default().fg(theme::current().text),
+    ));
+    Line::from(spans)
 }
 
 fn render_help(f: &mut Frame, app: &App, area: Rect) {
     let help_text = if let Some(ref msg) = app.message {
-        Span::styled(msg.clone(), Style::default().fg(Color::Yellow))
+        Span::styled(msg.clone(), Style::default().fg(theme::current().highlight))
     } else {
-        Span::raw(
-            "n: New | U: Unique | c: Currency | A: Add Affix | Tab: Detail | d: Delete | q: Quit",
-        )
+        let bindings: Vec<String> = KEYBINDINGS
+            .iter()
+            .filter(|(context, _)| HELP_BAR_CONTEXTS.contains(context))
+            .flat_map(|(_, keys)| keys.iter())
+            .map(|(key, action)| format!("{}: {}", key, action))
+            .collect();
+        Span::raw(bindings.join(" | "))
+    };
+
+    let help = Paragraph::new(Line::from(help_text))
+        .block(Block::default().borders(Borders::ALL).title(" Help "));
+
+    f.render_widget(help, area);
+}
+
+fn render_base_type_popup(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(50, 60, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    // Collect base type data first to avoid borrow conflicts
+    let generator = app.generator();
+    let item_data: Vec<(String, String, u32)> = app
+        .visible_base_type_ids()
+        .into_iter()
+        .filter_map(|id| {
+            generator.get_base_type(id).map(|base| {
+                (
+                    base.name.clone(),
+                    format!("{:?}", base.class),
+                    base.drop_weight
+                        .unwrap_or(loot_core::config::BaseTypeConfig::DEFAULT_DROP_WEIGHT),
+                )
+            })
+        })
+        .collect();
+
+    let items: Vec<ListItem> = item_data
+        .iter()
+        .map(|(name, class, weight)| {
+            ListItem::new(Line::from(vec![
+                Span::raw(name.as_str()),
+                Span::styled(
+                    format!(" ({}, weight {})", class, weight),
+                    Style::default().fg(theme::current().muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let drop_class = match app.drop_class {
+        Some(class) => class.to_string(),
+        None => "Any".to_string(),
     };
 
-    let help = Paragraph::new(Line::from(help_text))
-        .block(Block::default().borders(Borders::ALL).title(" Help "));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::current().accent))
+                .title(format!(
+                    " Select Base Type [{}] (Enter to confirm, s: from seed, Esc to cancel) ",
+                    drop_class
+                ))
+                .title_bottom(" r: random drop | f: class filter "),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(theme::current().muted)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(list, area, &mut app.base_type_state);
+}
+
+fn render_target_entry_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Target: ", Style::default().fg(theme::current().label)),
+            Span::styled(
+                format!("{}|", app.target_input),
+                Style::default()
+                    .fg(theme::current().highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Stats with an optional worst tier, e.g. AddedLife:2, FireResistance".to_string(),
+            Style::default().fg(theme::current().muted),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().accent))
+            .title(" Crafting Target (Enter to set, Esc to cancel) "),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_seed_entry_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let base_name = app
+        .generator()
+        .get_base_type(&app.seed_entry_state.base_type_id)
+        .map(|b| b.name.clone())
+        .unwrap_or_else(|| app.seed_entry_state.base_type_id.clone());
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Base Type: ", Style::default().fg(theme::current().label)),
+            Span::raw(base_name),
+        ]),
+        Line::from(vec![
+            Span::styled("Seed: ", Style::default().fg(theme::current().label)),
+            Span::styled(
+                format!("{}|", app.seed_entry_state.input),
+                Style::default()
+                    .fg(theme::current().highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Hex (0x...) or decimal".to_string(),
+            Style::default().fg(theme::current().muted),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().accent))
+            .title(" Reproduce From Seed (Enter to generate, Esc to cancel) "),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_pending_item_popup(f: &mut Frame, app: &App, item: &Item) {
+    let area = centered_rect(60, 70, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let content = render_item_stats(
+        item,
+        &ChangedAffixes::default(),
+        app.generator(),
+        app.show_roll_percent,
+        app.stat_view,
+    );
+    let paragraph = Paragraph::new(content)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::current().accent))
+                .title(" Roll Preview (Enter to keep, r to reroll, Esc to discard) "),
+        )
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_stats_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let label = Style::default().fg(theme::current().label);
+    let muted = Style::default().fg(theme::current().muted);
+    let stats = &app.stats;
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Items generated: ", label),
+            Span::raw(stats.items_generated.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Currencies applied: ", label),
+            Span::raw(stats.currencies_applied().to_string()),
+        ]),
+    ];
+    for (name, count) in &stats.currencies_used {
+        lines.push(Line::from(Span::styled(
+            format!("  {} x{}", name, count),
+            muted,
+        )));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Best DPS: ", label),
+        Span::raw(match &stats.best_dps {
+            Some((dps, name)) => format!("{:.1} ({})", dps, name),
+            None => "-".to_string(),
+        }),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Best defense: ", label),
+        Span::raw(match &stats.best_defense {
+            Some((defense, name)) => format!("{} ({})", defense, name),
+            None => "-".to_string(),
+        }),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().accent))
+            .title(" Session Stats (S or Esc to close) "),
+    );
 
-    f.render_widget(help, area);
+    f.render_widget(paragraph, area);
 }
 
-fn render_base_type_popup(f: &mut Frame, app: &mut App) {
-    let area = centered_rect(50, 60, f.area());
+fn render_reset_confirm(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
 
     // Clear the area
     f.render_widget(ratatui::widgets::Clear, area);
 
-    // Collect base type data first to avoid borrow conflicts
-    let generator = app.generator();
-    let item_data: Vec<(String, String)> = app
-        .base_type_ids
-        .iter()
-        .filter_map(|id| {
-            generator
-                .get_base_type(id)
-                .map(|base| (base.name.clone(), format!("{:?}", base.class)))
-        })
-        .collect();
-
-    let items: Vec<ListItem> = item_data
-        .iter()
-        .map(|(name, class)| {
-            ListItem::new(Line::from(vec![
-                Span::raw(name.as_str()),
-                Span::styled(
-                    format!(" ({})", class),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]))
-        })
-        .collect();
+    let (name, operations) = app
+        .selected_item()
+        .map(|item| (item.name.as_str(), item.operations.len()))
+        .unwrap_or(("", 0));
+    let lines = vec![
+        Line::from(format!(
+            "Discard all {} operation(s) on {}?",
+            operations, name
+        )),
+        Line::from("The item is regenerated from its base type and seed."),
+        Line::from(""),
+        Line::from("Press y to confirm, n to cancel"),
+    ];
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(" Select Base Type (Enter to confirm, Esc to cancel) "),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("> ");
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::current().error))
+            .title(" Reset To Base "),
+    );
 
-    f.render_stateful_widget(list, area, &mut app.base_type_state);
+    f.render_widget(paragraph, area);
 }
 
 fn render_unique_popup(f: &mut Frame, app: &mut App) {
@@ -2051,31 +4659,30 @@ fn render_unique_popup(f: &mut Frame, app: &mut App) {
 
     // Collect unique data first to avoid borrow conflicts
     let generator = app.generator();
-    let item_data: Vec<(String, String)> = app
+    let item_data: Vec<(String, String, u32)> = app
         .unique_ids
         .iter()
         .filter_map(|id| {
             generator.get_unique(id).map(|unique| {
-                let base_name = generator
-                    .get_base_type(&unique.base_type)
+                let base = generator.get_base_type(&unique.base_type);
+                let base_name = base
                     .map(|b| b.name.clone())
                     .unwrap_or_else(|| "???".to_string());
-                (unique.name.clone(), base_name)
+                let base_level = base.map(|b| b.requirements.level).unwrap_or(0);
+                let level = base_level.max(unique.required_level);
+                (unique.name.clone(), base_name, level)
             })
         })
         .collect();
 
     let items: Vec<ListItem> = item_data
         .iter()
-        .map(|(name, base_name)| {
+        .map(|(name, base_name, level)| {
             ListItem::new(Line::from(vec![
+                Span::styled(name.as_str(), Style::default().fg(theme::current().unique)),
                 Span::styled(
-                    name.as_str(),
-                    Style::default().fg(Color::Rgb(175, 95, 0)), // Orange for uniques
-                ),
-                Span::styled(
-                    format!(" ({})", base_name),
-                    Style::default().fg(Color::DarkGray),
+                    format!(" ({}, lvl {})", base_name, level),
+                    Style::default().fg(theme::current().muted),
                 ),
             ]))
         })
@@ -2085,12 +4692,12 @@ fn render_unique_popup(f: &mut Frame, app: &mut App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Rgb(175, 95, 0)))
+                .border_style(Style::default().fg(theme::current().unique))
                 .title(" Select Unique (Enter to confirm, Esc to cancel) "),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme::current().muted)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -2117,12 +4724,12 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
         .iter()
         .map(|(_, name, affix_type)| {
             let type_char = match affix_type {
-                loot_core::AffixType::Prefix => "P",
-                loot_core::AffixType::Suffix => "S",
+                AffixType::Prefix => "P",
+                AffixType::Suffix => "S",
             };
             let type_color = match affix_type {
-                loot_core::AffixType::Prefix => Color::Cyan,
-                loot_core::AffixType::Suffix => Color::Green,
+                AffixType::Prefix => theme::current().prefix,
+                AffixType::Suffix => theme::current().suffix,
             };
             ListItem::new(Line::from(vec![
                 Span::styled(format!("[{}] ", type_char), Style::default().fg(type_color)),
@@ -2132,9 +4739,9 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
         .collect();
 
     let affix_border_color = if app.add_affix_state.column == 0 {
-        Color::Cyan
+        theme::current().accent
     } else {
-        Color::DarkGray
+        theme::current().muted
     };
 
     let affix_list = List::new(affix_items)
@@ -2146,7 +4753,7 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme::current().muted)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -2165,16 +4772,19 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
                 format!(" ({}-{})", min, max)
             };
             ListItem::new(Line::from(vec![
-                Span::styled(format!("T{}", tier), Style::default().fg(Color::Yellow)),
-                Span::styled(range_str, Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("T{}", tier),
+                    Style::default().fg(theme::current().highlight),
+                ),
+                Span::styled(range_str, Style::default().fg(theme::current().muted)),
             ]))
         })
         .collect();
 
     let tier_border_color = if app.add_affix_state.column == 1 {
-        Color::Cyan
+        theme::current().accent
     } else {
-        Color::DarkGray
+        theme::current().muted
     };
 
     let tier_list = List::new(tier_items)
@@ -2186,7 +4796,7 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme::current().muted)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -2194,6 +4804,130 @@ fn render_add_affix_popup(f: &mut Frame, app: &mut App) {
     f.render_stateful_widget(tier_list, chunks[1], &mut app.add_affix_state.tier_state);
 }
 
+fn render_help_overlay(f: &mut Frame) {
+    let area = centered_rect(70, 80, f.area());
+
+    // Clear the area
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let heading = Style::default()
+        .fg(theme::current().accent)
+        .add_modifier(Modifier::BOLD);
+    let key_style = Style::default().fg(theme::current().highlight);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (context, keys) in KEYBINDINGS {
+        lines.push(Line::from(Span::styled(context.to_string(), heading)));
+        for (key, action) in keys.iter() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<12}", key), key_style),
+                Span::raw(action.to_string()),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Legend goes in a second column so everything fits without scrolling
+    let mut legend = vec![Line::from(Span::styled("Rarity".to_string(), heading))];
+    for rarity in [
+        loot_core::Rarity::Normal,
+        loot_core::Rarity::Magic,
+        loot_core::Rarity::Rare,
+        loot_core::Rarity::Unique,
+    ] {
+        legend.push(Line::from(Span::styled(
+            format!("  {:?}", rarity),
+            Style::default().fg(rarity_color(rarity)),
+        )));
+    }
+    legend.push(Line::from(""));
+
+    legend.push(Line::from(Span::styled("Modifiers".to_string(), heading)));
+    for scope in [
+        loot_core::types::AffixScope::Local,
+        loot_core::types::AffixScope::Global,
+    ] {
+        legend.push(Line::from(Span::styled(
+            format!("  {}", scope_tooltip(scope)),
+            Style::default().fg(scope_color(scope)),
+        )));
+    }
+    legend.push(Line::from(vec![
+        Span::styled(
+            "  Prefix".to_string(),
+            Style::default().fg(theme::current().prefix),
+        ),
+        Span::raw(" / "),
+        Span::styled(
+            "Suffix".to_string(),
+            Style::default().fg(theme::current().suffix),
+        ),
+        Span::raw(" (P/S after the tier range)"),
+    ]));
+    legend.push(Line::from(vec![
+        Span::styled(
+            "  [T1]".to_string(),
+            Style::default().fg(theme::current().highlight),
+        ),
+        Span::raw(" Tier, 1 is the best; "),
+        Span::styled(
+            "(10-19)".to_string(),
+            Style::default().fg(theme::current().muted),
+        ),
+        Span::raw(" is the tier's roll range"),
+    ]));
+    legend.push(Line::from(vec![
+        Span::styled(
+            "  >>".to_string(),
+            Style::default().fg(theme::current().changed),
+        ),
+        Span::raw(" Changed by the last currency or added affix"),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::current().accent))
+        .title(" Help (Esc or ? to close) ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(lines), columns[0]);
+    f.render_widget(
+        Paragraph::new(legend).wrap(Wrap { trim: false }),
+        columns[1],
+    );
+}
+
+/// Keep a list selection within `len` items: nothing when empty, the last item when
+/// past the end, and the first item when nothing was selected.
+///
+/// Call after any change to the list backing a `ListState`.
+fn clamp_selection(state: &mut ListState, len: usize) {
+    if len == 0 {
+        state.select(None);
+    } else if let Some(idx) = state.selected() {
+        if idx >= len {
+            state.select(Some(len - 1));
+        }
+    } else {
+        state.select(Some(0));
+    }
+}
+
+/// Step a list selection by `delta`, stopping at either end
+fn move_selection(state: &mut ListState, len: usize, delta: isize) {
+    clamp_selection(state, len);
+    if let Some(idx) = state.selected() {
+        let target = (idx as isize + delta).clamp(0, len as isize - 1);
+        state.select(Some(target as usize));
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -2213,3 +4947,1022 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_app() -> App {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+        App::new(config)
+    }
+
+    /// Generate straight into the inventory on a random seed, skipping the roll preview
+    fn generate_item(app: &mut App, base_type_id: &str) {
+        let seed: u64 = rand::random();
+        app.generate_item_with_seed(base_type_id, seed);
+    }
+
+    /// An app with a fixed inventory: a normal sword, a rare sword crafted from specific
+    /// affixes and a unique. Everything comes from fixed seeds and explicit affix ids
+    /// (random affix rolls are avoided), so renders are identical from run to run.
+    fn snapshot_app() -> App {
+        let mut app = make_app();
+        let generator = app.generator();
+        let normal = generator.generate("iron_sword", 1).unwrap();
+        let mut crafted = generator.generate("iron_sword", 2).unwrap();
+        for (affix_id, tier) in [
+            ("added_fire_damage", 1),
+            ("increased_attack_speed", 2),
+            ("added_accuracy", 3),
+        ] {
+            crafted = generator.add_affix(&crafted, affix_id, tier).unwrap();
+        }
+        let unique = generator.generate_unique("titans_grip", 3).unwrap();
+
+        app.inventory = vec![normal, crafted, unique];
+        app.inventory_state.select(Some(1));
+        app
+    }
+
+    /// Render the full screen as text, one line per row with trailing spaces trimmed
+    fn render_to_string(app: &mut App, width: u16, height: u16) -> String {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                let row: String = (0..width)
+                    .map(|x| buffer.cell((x, y)).unwrap().symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    /// Compare a 120x40 render against `snapshots/<name>.txt`. Run with
+    /// `UPDATE_SNAPSHOTS=1` to write the file after an intended layout change.
+    fn assert_snapshot(name: &str, app: &mut App) {
+        let actual = render_to_string(app, 120, 40);
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("snapshots")
+            .join(format!("{}.txt", name));
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "missing snapshot {} ({}); run with UPDATE_SNAPSHOTS=1",
+                path.display(),
+                e
+            )
+        });
+        assert!(
+            actual == expected,
+            "render differs from snapshot {}; run with UPDATE_SNAPSHOTS=1 if the change is \
+             intended\n--- expected\n{}\n--- actual\n{}",
+            path.display(),
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_parse_seed_hex_and_decimal() {
+        assert_eq!(parse_seed("0x1F"), Ok(31));
+        assert_eq!(parse_seed("0XFF"), Ok(255));
+        assert_eq!(parse_seed(" 12345 "), Ok(12345));
+        assert!(parse_seed("").is_err());
+        assert!(parse_seed("0x").is_err());
+        assert!(parse_seed("12ab").is_err());
+    }
+
+    #[test]
+    fn test_seed_entry_reproduces_generated_item() {
+        let mut app = make_app();
+        generate_item(&mut app, "iron_sword");
+        let original = app.inventory[0].clone();
+
+        app.open_seed_entry("iron_sword".to_string());
+        for c in format!("0x{:016X}", original.seed).chars() {
+            handle_seed_entry_keys(&mut app, KeyCode::Char(c));
+        }
+        handle_seed_entry_keys(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.inventory.len(), 2);
+        assert!(app.focus == Focus::Inventory);
+        let mut reproduced = app.inventory[1].clone();
+        assert_eq!(reproduced.seed, original.seed);
+        assert_eq!(reproduced.created_index, original.created_index + 1);
+        reproduced.created_index = original.created_index;
+        assert_eq!(reproduced.to_json().unwrap(), original.to_json().unwrap());
+    }
+
+    #[test]
+    fn test_seed_entry_rejects_invalid_input() {
+        let mut app = make_app();
+        app.open_seed_entry("iron_sword".to_string());
+        handle_seed_entry_keys(&mut app, KeyCode::Char('0'));
+        handle_seed_entry_keys(&mut app, KeyCode::Char('x'));
+        handle_seed_entry_keys(&mut app, KeyCode::Enter);
+
+        assert!(app.inventory.is_empty());
+        assert!(app.show_seed_entry);
+        assert!(app.message.is_some());
+    }
+
+    #[test]
+    fn test_locked_mod_survives_chaos() {
+        let mut app = make_app();
+        app.generate_item_with_seed("iron_sword", 99);
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("alchemy");
+        let kept = app.inventory[0].prefixes[0].clone();
+
+        app.open_currency_popup();
+        handle_currency_keys(&mut app, KeyCode::Char('x'));
+        assert!(app.currency_popup_state.locking);
+        handle_currency_keys(&mut app, KeyCode::Char(' '));
+        handle_currency_keys(&mut app, KeyCode::Enter);
+        assert!(!app.currency_popup_state.locking);
+        assert_eq!(app.currency_popup_state.locked, vec![kept.affix_id.clone()]);
+
+        app.apply_currency_by_id("chaos");
+        assert!(app.inventory[0].prefixes.contains(&kept));
+        assert!(app.log.last().unwrap().action.contains("keeping"));
+    }
+
+    #[test]
+    fn test_status_line_shows_selected_item() {
+        let mut app = make_app();
+        app.generate_item_with_seed("iron_sword", 5);
+        let name = app.inventory[0].name.clone();
+        let dps = app.inventory[0].dps().unwrap();
+
+        // The status line is the row just above the help bar
+        let backend = ratatui::backend::TestBackend::new(120, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..120)
+            .map(|x| buffer.cell((x, 36)).unwrap().symbol())
+            .collect();
+
+        assert!(row.contains(&name), "{}", row);
+        assert!(row.contains(&format!("DPS {:.1}", dps)), "{}", row);
+        let name_cell = buffer.cell((1, 36)).unwrap();
+        assert_eq!(name_cell.fg, rarity_color(app.inventory[0].rarity));
+    }
+
+    #[test]
+    fn test_grouped_inventory_maps_rows_to_items() {
+        let mut app = make_app();
+        app.generate_item_with_seed("iron_sword", 1);
+        app.generate_item_with_seed("iron_sword", 2);
+        app.apply_currency_by_id("alchemy");
+        app.generate_item_with_seed("iron_sword", 3);
+        app.inventory_state.select(Some(0));
+
+        handle_inventory_keys(&mut app, KeyCode::Char('g'));
+        let rows = app.inventory_rows();
+        assert_eq!(
+            rows,
+            vec![
+                InventoryRow::Header {
+                    group: "Normal".to_string(),
+                    count: 2,
+                    collapsed: false,
+                },
+                InventoryRow::Item(0),
+                InventoryRow::Item(2),
+                InventoryRow::Header {
+                    group: "Rare".to_string(),
+                    count: 1,
+                    collapsed: false,
+                },
+                InventoryRow::Item(1),
+            ]
+        );
+
+        // Moving down skips across the header onto the rare item
+        handle_inventory_keys(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.inventory_state.selected(), Some(2));
+        handle_inventory_keys(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.inventory_state.selected(), None);
+        handle_inventory_keys(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.inventory_state.selected(), Some(1));
+
+        // Collapsing hides the group's items and parks the cursor on its header
+        handle_inventory_keys(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.inventory_rows().len(), 4);
+        assert_eq!(app.inventory_state.selected(), None);
+        assert_eq!(app.inventory_cursor(&app.inventory_rows()), Some(3));
+
+        // Back to flat mode selects a real item again
+        handle_inventory_keys(&mut app, KeyCode::Char('g'));
+        handle_inventory_keys(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.inventory_view.grouping, InventoryGrouping::Flat);
+        assert!(app.selected_item().is_some());
+    }
+
+    #[test]
+    fn test_currency_log_records_results() {
+        let mut app = make_app();
+        generate_item(&mut app, "iron_sword");
+        app.inventory_state.select(Some(0));
+
+        app.apply_currency_by_id("transmute");
+        app.apply_currency_by_id("transmute");
+
+        assert_eq!(app.log.len(), 2);
+        let changes = app.log[0].result.as_ref().unwrap();
+        assert!(changes
+            .iter()
+            .any(|c| c.starts_with("Rarity: Normal -> Magic")));
+        assert!(changes.iter().any(|c| c.starts_with("+ ")));
+        assert!(app.log[1].result.is_err());
+
+        app.show_log = true;
+        let backend = ratatui::backend::TestBackend::new(120, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(text.contains("Log (2)"));
+    }
+
+    #[test]
+    fn test_session_stats_track_rolls_and_best_items() {
+        let mut app = make_app();
+        assert!(app.generate_item_with_seed("iron_sword", 1));
+        assert!(app.generate_item_with_seed("plate_vest", 2));
+        assert_eq!(app.stats.items_generated, 2);
+
+        // Only successful applications count
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("transmute");
+        app.apply_currency_by_id("transmute");
+        assert_eq!(app.stats.currencies_applied(), 1);
+        assert_eq!(app.stats.currencies_used["Orb of Transmutation"], 1);
+
+        // The sword's DPS record is kept from whichever roll was higher
+        let (dps, name) = app.stats.best_dps.clone().unwrap();
+        assert!(name.contains("Iron Sword"), "{}", name);
+        assert!(dps >= app.inventory[0].dps().unwrap());
+        let vest = &app.inventory[1];
+        assert_eq!(
+            app.stats.best_defense,
+            Some((total_defense(vest), vest.name.clone()))
+        );
+
+        // A weaker item doesn't replace the record
+        app.stats.best_dps = Some((dps + 100.0, "Record".to_string()));
+        app.stats.record_item(&app.inventory[0]);
+        assert_eq!(app.stats.best_dps.as_ref().unwrap().1, "Record");
+
+        app.show_stats = true;
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("Items generated: 2"), "{}", screen);
+        assert!(screen.contains("Orb of Transmutation x1"), "{}", screen);
+    }
+
+    #[test]
+    fn test_currency_stack_size_limits_uses() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.currencies.get_mut("chaos").unwrap().stack_size = Some(2);
+        let mut app = App::new(config);
+        assert_eq!(app.currency_uses_left("chaos"), Some(2));
+        assert_eq!(app.currency_uses_left("transmute"), None);
+
+        generate_item(&mut app, "iron_sword");
+        app.inventory_state.select(Some(0));
+
+        // A failed application (chaos needs a rare item) doesn't use up a charge
+        app.apply_currency_by_id("chaos");
+        assert_eq!(app.currency_uses_left("chaos"), Some(2));
+
+        app.apply_currency_by_id("alchemy");
+        app.apply_currency_by_id("chaos");
+        app.apply_currency_by_id("chaos");
+        assert_eq!(app.currency_uses_left("chaos"), Some(0));
+
+        let before = app.inventory[0].clone();
+        app.apply_currency_by_id("chaos");
+        assert_eq!(app.inventory[0], before);
+        assert!(app.message.as_deref().unwrap().contains("used up"));
+        assert_eq!(app.currency_uses_left("chaos"), Some(0));
+
+        app.open_currency_popup();
+        let state = &mut app.currency_popup_state;
+        state.selected_category = state
+            .categories
+            .iter()
+            .position(|c| c == "Crafting")
+            .unwrap();
+        app.update_currency_list();
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("Chaos Orb (used up)"), "{}", screen);
+    }
+
+    #[test]
+    fn test_reset_restores_base_generation() {
+        let mut app = make_app();
+        assert!(app.generate_item_with_seed("iron_sword", 7));
+        let base = app.inventory[0].clone();
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("alchemy");
+        app.apply_currency_by_id("exalt");
+        assert_ne!(app.inventory[0], base);
+
+        // Cancelling keeps the crafting
+        handle_inventory_keys(&mut app, KeyCode::Char('R'));
+        assert!(app.show_reset_confirm);
+        handle_reset_confirm_keys(&mut app, KeyCode::Char('n'));
+        assert!(!app.show_reset_confirm);
+        assert_ne!(app.inventory[0], base);
+
+        handle_inventory_keys(&mut app, KeyCode::Char('R'));
+        handle_reset_confirm_keys(&mut app, KeyCode::Char('y'));
+        assert!(!app.show_reset_confirm);
+        assert_eq!(app.inventory[0], base);
+        assert_eq!(app.inventory[0].seed, 7);
+
+        // Nothing left to undo
+        handle_inventory_keys(&mut app, KeyCode::Char('R'));
+        assert!(!app.show_reset_confirm);
+    }
+
+    #[test]
+    fn test_roll_percent_of_tier_range() {
+        assert_eq!(roll_percent(10, 10, 20), 0);
+        assert_eq!(roll_percent(20, 10, 20), 100);
+        assert_eq!(roll_percent(17, 10, 25), 47);
+        assert_eq!(roll_percent(-5, -10, 0), 50);
+        // A fixed value is always a perfect roll
+        assert_eq!(roll_percent(7, 7, 7), 100);
+    }
+
+    #[test]
+    fn test_roll_percent_toggle_shows_notes() {
+        let mut app = snapshot_app();
+        app.inventory_state.select(Some(1));
+        let modifier = app.inventory[1].prefixes[0].clone();
+        let note = format!(
+            "({}%)",
+            roll_percent(modifier.value, modifier.tier_min, modifier.tier_max)
+        );
+
+        assert!(!render_to_string(&mut app, 160, 50).contains(&note));
+        app.toggle_roll_percent();
+        assert!(render_to_string(&mut app, 160, 50).contains(&note));
+        assert_eq!(app.message.as_deref(), Some("Roll percentages: on"));
+
+        // Quality comes first, then the roll, as on implicits and enchants
+        let item = &mut app.inventory[1];
+        item.quality = 20;
+        item.quality_target = loot_core::types::QualityTarget::Stats(vec![modifier.stat]);
+        let screen = render_to_string(&mut app, 160, 50);
+        let line = screen.lines().find(|line| line.contains(&note)).unwrap();
+        assert!(
+            line.find("(quality: ").unwrap() < line.find(&note).unwrap(),
+            "{}",
+            line
+        );
+    }
+
+    #[test]
+    fn test_craftable_filter_matches_apply_currency() {
+        let mut app = snapshot_app();
+        let magic = app
+            .generator()
+            .apply_currency(&app.inventory[0], "transmute")
+            .unwrap();
+        app.inventory.push(magic);
+
+        // A unique recipe can still be missing once the requirements pass, so chance-style
+        // currencies are left out
+        let ids: Vec<String> = app
+            .generator()
+            .config()
+            .currencies_sorted()
+            .filter(|currency| !currency.effects.try_unique)
+            .map(|currency| currency.id.clone())
+            .collect();
+        for id in ids {
+            app.inventory_view.craftable_with = Some(id.clone());
+            for item in &app.inventory {
+                assert_eq!(
+                    app.inventory_item_visible(item),
+                    app.generator().apply_currency(item, &id).is_ok(),
+                    "{} on {}",
+                    id,
+                    item.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_craftable_filter_toggles_from_currency_popup() {
+        let mut app = snapshot_app();
+        app.inventory_state.select(Some(0));
+        app.open_currency_popup();
+        let state = &mut app.currency_popup_state;
+        state.selected_category = state.categories.iter().position(|c| c == "Rarity").unwrap();
+        app.update_currency_list();
+        let position = app
+            .currency_popup_state
+            .currencies
+            .iter()
+            .position(|(id, _, _)| id == "transmute")
+            .unwrap();
+        app.currency_popup_state.list_state.select(Some(position));
+
+        handle_currency_keys(&mut app, KeyCode::Char('f'));
+        // Only the normal sword takes a transmute
+        assert_eq!(app.inventory_rows(), vec![InventoryRow::Item(0)]);
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("Inventory (Orb of Transmutation"));
+
+        handle_currency_keys(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.inventory_rows().len(), app.inventory.len());
+
+        handle_currency_keys(&mut app, KeyCode::Char('f'));
+        handle_inventory_keys(&mut app, KeyCode::Char('F'));
+        assert!(app.inventory_view.craftable_with.is_none());
+        assert_eq!(app.message.as_deref(), Some("Showing all items"));
+    }
+
+    #[test]
+    fn test_affix_count_label() {
+        let mut app = snapshot_app();
+        assert_eq!(affix_count_label(&app.inventory[0]), None);
+        assert_eq!(
+            affix_count_label(&app.inventory[1]).as_deref(),
+            Some("(1p/2s)")
+        );
+        assert_eq!(affix_count_label(&app.inventory[2]), None);
+
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("(Rare) (1p/2s)"), "{}", screen);
+        handle_inventory_keys(&mut app, KeyCode::Char('a'));
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(!screen.contains("(1p/2s)"), "{}", screen);
+    }
+
+    #[test]
+    fn test_crafting_target_entry() {
+        let target = parse_target_spec("addedlife:2, IncreasedAttackSpeed").unwrap();
+        assert_eq!(target.len(), 2);
+        assert_eq!(target[0].stat, StatType::AddedLife);
+        assert_eq!(target[0].max_tier, 2);
+        assert_eq!(target[1].max_tier, ANY_TIER);
+        assert_eq!(
+            target_spec_text(&target),
+            "AddedLife:2, IncreasedAttackSpeed"
+        );
+        assert!(parse_target_spec("NotAStat").is_err());
+        assert!(parse_target_spec("AddedLife:0").is_err());
+
+        let mut app = snapshot_app();
+        handle_inventory_keys(&mut app, KeyCode::Char('t'));
+        assert!(app.focus == Focus::TargetEntry);
+        for c in "AddedFireDamage:1 AddedLife".chars() {
+            handle_target_entry_keys(&mut app, KeyCode::Char(c));
+        }
+        handle_target_entry_keys(&mut app, KeyCode::Enter);
+        assert!(app.focus == Focus::Inventory);
+        assert_eq!(app.inventory[1].target.len(), 2);
+
+        let screen = render_to_string(&mut app, 120, 60);
+        assert!(screen.contains("Crafting Target"), "{}", screen);
+        assert!(
+            screen.contains("[x] AddedFireDamage T1 or better"),
+            "{}",
+            screen
+        );
+        assert!(screen.contains("[ ] AddedLife any tier"), "{}", screen);
+    }
+
+    #[test]
+    fn test_mirror_pushes_locked_copy() {
+        let mut app = make_app();
+        generate_item(&mut app, "iron_sword");
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("alchemy");
+
+        handle_inventory_keys(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.inventory.len(), 2);
+        assert_eq!(app.inventory_state.selected(), Some(1));
+        assert!(app.inventory[1].mirrored);
+        assert_eq!(app.inventory[1].prefixes, app.inventory[0].prefixes);
+        assert_eq!(app.inventory[1].suffixes, app.inventory[0].suffixes);
+
+        let before = app.inventory[1].clone();
+        app.apply_currency_by_id("chaos");
+        assert_eq!(app.inventory[1], before);
+        assert!(app.log.last().unwrap().result.is_err());
+    }
+
+    #[test]
+    fn test_currency_applies_to_every_marked_item() {
+        let mut app = make_app();
+        for _ in 0..3 {
+            generate_item(&mut app, "iron_sword");
+        }
+        for idx in 0..3 {
+            app.inventory_state.select(Some(idx));
+            handle_inventory_keys(&mut app, KeyCode::Char('v'));
+        }
+        assert_eq!(app.marked_count(), 3);
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("Inventory (3 marked)"), "{}", screen);
+
+        app.apply_currency_by_id("transmute");
+        assert_eq!(app.log.len(), 3);
+        assert!(app.log.iter().all(|entry| entry.result.is_ok()));
+        for item in &app.inventory {
+            assert_eq!(item.operations.len(), 1);
+            assert_eq!(item.rarity, loot_core::Rarity::Magic);
+        }
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Applied Orb of Transmutation to 3 of 3 marked items (0 failed)")
+        );
+
+        // A second transmute fails on every magic item, and unmarked items are left alone
+        app.inventory_state.select(Some(2));
+        handle_inventory_keys(&mut app, KeyCode::Char('v'));
+        app.apply_currency_by_id("transmute");
+        assert_eq!(app.log.len(), 5);
+        assert!(app.log[3..].iter().all(|entry| entry.result.is_err()));
+        assert!(app
+            .message
+            .as_deref()
+            .unwrap()
+            .ends_with("0 of 2 marked items (2 failed)"));
+
+        handle_inventory_keys(&mut app, KeyCode::Char('V'));
+        assert_eq!(app.marked_count(), 0);
+    }
+
+    #[test]
+    fn test_marked_batch_reports_skips_and_forgets_deleted_items() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.currencies.get_mut("transmute").unwrap().stack_size = Some(2);
+        let mut app = App::new(config);
+        for _ in 0..3 {
+            generate_item(&mut app, "iron_sword");
+        }
+        for idx in 0..3 {
+            app.inventory_state.select(Some(idx));
+            app.toggle_marked();
+        }
+
+        // The stack runs out before the third item, which is skipped rather than failed
+        app.apply_currency_by_id("transmute");
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Applied Orb of Transmutation to 2 of 3 marked items (0 failed, 1 skipped: stack used up)")
+        );
+
+        // Deleting marked items drops their marks, so currencies go back to the selection
+        for _ in 0..3 {
+            app.inventory_state.select(Some(0));
+            app.delete_selected();
+        }
+        assert!(app.inventory_view.marked.is_empty());
+        generate_item(&mut app, "iron_sword");
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("alchemy");
+        assert_eq!(app.inventory[0].rarity, loot_core::Rarity::Rare);
+    }
+
+    #[test]
+    fn test_created_index_survives_reordering() {
+        let mut app = make_app();
+        for seed in 0..3 {
+            app.generate_item_with_seed("iron_sword", seed);
+        }
+        let indices: Vec<u64> = app.inventory.iter().map(|i| i.created_index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+
+        let middle = app.inventory[1].clone();
+        app.inventory.reverse();
+        let found = app
+            .inventory
+            .iter()
+            .find(|i| i.created_index == middle.created_index)
+            .unwrap();
+        assert_eq!(found, &middle);
+
+        // Numbering keeps counting after a delete and is kept through JSON
+        app.inventory_state.select(Some(0));
+        handle_inventory_keys(&mut app, KeyCode::Char('d'));
+        handle_inventory_keys(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.inventory.last().unwrap().created_index, 4);
+        let json = app.inventory[0].to_json().unwrap();
+        assert_eq!(
+            Item::from_json(&json).unwrap().created_index,
+            app.inventory[0].created_index
+        );
+    }
+
+    #[test]
+    fn test_roll_preview_accept_and_reroll() {
+        let mut app = make_app();
+        app.show_base_types = true;
+        app.focus = Focus::BaseTypes;
+        app.base_type_state.select(Some(0));
+        let id = app.selected_base_type_id().unwrap();
+        handle_base_type_keys(&mut app, KeyCode::Enter);
+        assert!(app.focus == Focus::PendingItem);
+        assert!(!app.show_base_types);
+        assert!(app.inventory.is_empty());
+        let first = app.pending_item.clone().unwrap();
+        assert_eq!(first.base_type_id, id);
+        let screen = render_to_string(&mut app, 120, 40);
+        assert!(screen.contains("Roll Preview"), "{}", screen);
+        assert!(screen.contains(&first.name), "{}", screen);
+
+        // Rerolling keeps the base but picks a new seed, still without adding anything
+        handle_pending_item_keys(&mut app, KeyCode::Char('r'));
+        let second = app.pending_item.clone().unwrap();
+        assert_eq!(second.base_type_id, id);
+        assert_ne!(second.seed, first.seed);
+        assert!(app.inventory.is_empty());
+
+        handle_pending_item_keys(&mut app, KeyCode::Enter);
+        assert!(app.focus == Focus::Inventory);
+        assert!(app.pending_item.is_none());
+        assert_eq!(app.inventory.len(), 1);
+        assert_eq!(app.inventory[0].seed, second.seed);
+        assert_eq!(app.stats.items_generated, 1);
+
+        // Discarding leaves the inventory as it was
+        handle_base_type_keys(&mut app, KeyCode::Enter);
+        handle_pending_item_keys(&mut app, KeyCode::Esc);
+        assert!(app.focus == Focus::Inventory);
+        assert!(app.pending_item.is_none());
+        assert_eq!(app.inventory.len(), 1);
+        assert_eq!(app.stats.items_generated, 1);
+    }
+
+    #[test]
+    fn test_affix_flavor_shown_under_modifier() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.affixes.get_mut("added_accuracy").unwrap().flavor =
+            Some("Every swing finds its mark.".to_string());
+        let mut app = App::new(config);
+        let generator = app.generator();
+        let mut item = generator.generate("iron_sword", 2).unwrap();
+        for (affix_id, tier) in [("increased_attack_speed", 2), ("added_accuracy", 3)] {
+            item = generator.add_affix(&item, affix_id, tier).unwrap();
+        }
+        app.inventory.push(item);
+        app.inventory_state.select(Some(0));
+
+        let screen = render_to_string(&mut app, 140, 50);
+        assert_eq!(
+            screen.matches("\"Every swing finds its mark.\"").count(),
+            1,
+            "{}",
+            screen
+        );
+        let lines: Vec<&str> = screen.lines().collect();
+        let flavor_row = lines
+            .iter()
+            .position(|l| l.contains("Every swing finds its mark."))
+            .unwrap();
+        let accuracy_row = lines.iter().position(|l| l.contains("Accuracy")).unwrap();
+        assert!(accuracy_row < flavor_row, "{}", screen);
+    }
+
+    #[test]
+    fn test_unique_flavor_shown_only_when_set() {
+        let flavor = "The strength of giants flows through these ancient plates.";
+        let mut app = make_app();
+        let unique = app.generator().generate_unique("titans_grip", 3).unwrap();
+        let lines = render_item_stats(
+            &unique,
+            &ChangedAffixes::default(),
+            app.generator(),
+            false,
+            StatView::Rolls,
+        )
+        .lines
+        .len();
+        app.inventory.push(unique);
+        app.inventory_state.select(Some(0));
+        let screen = render_to_string(&mut app, 140, 50);
+        // Wrapped onto two lines at the flavor width
+        assert_eq!(
+            wrap_words(flavor, FLAVOR_WIDTH),
+            vec![
+                "The strength of giants flows through these ancient",
+                "plates."
+            ]
+        );
+        let lines_on_screen: Vec<&str> = screen.lines().collect();
+        let row = lines_on_screen
+            .iter()
+            .position(|l| l.contains("flows through these ancient"))
+            .expect("flavor is rendered");
+        assert!(lines_on_screen[row + 1].contains("plates."));
+
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        config.uniques.get_mut("titans_grip").unwrap().flavor = None;
+        let generator = Generator::new(config);
+        let plain = generator.generate_unique("titans_grip", 3).unwrap();
+        let text = render_item_stats(
+            &plain,
+            &ChangedAffixes::default(),
+            &generator,
+            false,
+            StatView::Rolls,
+        );
+        // Only the blank separator and the two flavor lines are gone
+        assert_eq!(text.lines.len(), lines - 3);
+        assert!(!text.to_string().contains("giants"));
+    }
+
+    #[test]
+    fn test_currency_preview_totals_include_implicit() {
+        let mut app = make_app();
+        let generator = app.generator();
+        let mut item = generator.generate("iron_shield", 4).unwrap();
+        for (affix_id, tier) in [("added_life", 1), ("cold_resistance", 1)] {
+            item = generator.add_affix(&item, affix_id, tier).unwrap();
+        }
+        let all_res = item.implicits[0].value;
+        let life = item.prefixes[0].value;
+        let cold = item.suffixes[0].value;
+        app.inventory.push(item);
+        app.inventory_state.select(Some(0));
+
+        let text: String = build_currency_preview(&app)
+            .lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let expected = format!("Totals: +{} Life, +{}% Res", life, 3 * all_res + cold);
+        assert!(text.contains(&expected), "{}", text);
+    }
+
+    #[test]
+    fn test_item_dump_has_seed_operations_and_json() {
+        let mut app = make_app();
+        generate_item(&mut app, "iron_sword");
+        app.inventory_state.select(Some(0));
+        app.apply_currency_by_id("transmute");
+        let item = app.selected_item().unwrap();
+
+        let dump = item_dump(item);
+        assert!(
+            dump.contains(&format!("Seed: 0x{:016X}", item.seed)),
+            "{}",
+            dump
+        );
+        assert!(dump.contains("  1. transmute\n"), "{}", dump);
+        let json = &dump[dump.find("\nJSON\n").unwrap() + "\nJSON\n".len()..];
+        assert_eq!(&Item::from_json(json.trim_end()).unwrap(), item);
+    }
+
+    #[test]
+    fn test_delete_at_end_keeps_valid_selection() {
+        let mut app = make_app();
+        for _ in 0..3 {
+            generate_item(&mut app, "iron_sword");
+        }
+        assert_eq!(app.inventory_state.selected(), Some(2));
+
+        handle_inventory_keys(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.inventory.len(), 2);
+        assert_eq!(app.inventory_state.selected(), Some(1));
+
+        // A stale selection past the end is pulled back before moving
+        app.inventory_state.select(Some(7));
+        handle_inventory_keys(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.inventory_state.selected(), Some(1));
+        handle_inventory_keys(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.inventory_state.selected(), Some(0));
+
+        handle_inventory_keys(&mut app, KeyCode::Char('d'));
+        handle_inventory_keys(&mut app, KeyCode::Char('d'));
+        assert!(app.inventory.is_empty());
+        assert_eq!(app.inventory_state.selected(), None);
+
+        // Nothing left to act on, and nothing panics
+        handle_inventory_keys(&mut app, KeyCode::Char('d'));
+        handle_inventory_keys(&mut app, KeyCode::Char('j'));
+        app.apply_currency_by_id("transmute");
+        assert_eq!(app.inventory_state.selected(), None);
+    }
+
+    #[test]
+    fn test_recipe_filter_and_expand() {
+        let mut app = make_app();
+        app.detail_tab = DetailTab::Recipes;
+        assert_eq!(app.filtered_recipes().len(), 1);
+
+        handle_inventory_keys(&mut app, KeyCode::Enter);
+        assert!(app.recipe_view.expanded.contains(&0));
+        handle_inventory_keys(&mut app, KeyCode::Enter);
+        assert!(app.recipe_view.expanded.is_empty());
+
+        handle_inventory_keys(&mut app, KeyCode::Char('/'));
+        assert!(app.focus == Focus::RecipeFilter);
+        for c in "GAUNT".chars() {
+            handle_recipe_filter_keys(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.filtered_recipes().len(), 1);
+        handle_recipe_filter_keys(&mut app, KeyCode::Char('x'));
+        assert!(app.filtered_recipes().is_empty());
+
+        handle_recipe_filter_keys(&mut app, KeyCode::Esc);
+        assert!(app.focus == Focus::Inventory);
+        assert!(app.recipe_view.filter.is_empty());
+        assert_eq!(app.filtered_recipes().len(), 1);
+    }
+
+    #[test]
+    fn test_random_drop_respects_class_filter() {
+        let mut app = make_app();
+        app.cycle_drop_class();
+        let class = app.drop_class.unwrap();
+
+        for _ in 0..8 {
+            assert!(app.generate_random_drop());
+        }
+        assert!(app.inventory.iter().all(|item| item.class == class));
+
+        // Cycling past the last class returns to any class
+        let mut steps = 0;
+        while app.drop_class.is_some() {
+            app.cycle_drop_class();
+            steps += 1;
+            assert!(steps <= loot_core::ItemClass::all().len());
+        }
+    }
+
+    #[test]
+    fn test_base_type_list_filters_by_class() {
+        let mut app = make_app();
+        let all = app.visible_base_type_ids().len();
+        assert_eq!(all, app.base_type_ids.len());
+
+        // Select a bow, then filter until only bows are listed
+        let bow = app
+            .base_type_ids
+            .iter()
+            .position(|id| {
+                app.generator().get_base_type(id).unwrap().class == loot_core::ItemClass::Bow
+            })
+            .unwrap();
+        app.base_type_state.select(Some(bow));
+        let bow_id = app.selected_base_type_id().unwrap();
+        while app.drop_class != Some(loot_core::ItemClass::Bow) {
+            app.cycle_drop_class();
+        }
+
+        let visible = app.visible_base_type_ids();
+        assert!(!visible.is_empty() && visible.len() < all);
+        let generator = app.generator();
+        assert!(visible
+            .iter()
+            .all(|id| generator.get_base_type(id).unwrap().class == loot_core::ItemClass::Bow));
+        assert_eq!(app.selected_base_type_id(), Some(bow_id));
+
+        // Moving on to another class keeps the selection inside the shorter list
+        app.cycle_drop_class();
+        let len = app.visible_base_type_ids().len();
+        assert!(app.base_type_state.selected().is_some_and(|i| i < len));
+
+        app.show_base_types = true;
+        let screen = render_to_string(&mut app, 120, 40);
+        let class = app
+            .drop_class
+            .map(|c| c.to_string())
+            .unwrap_or("Any".into());
+        assert!(screen.contains(&format!("Select Base Type [{}]", class)));
+    }
+
+    #[test]
+    fn test_resize_keeps_selection_visible() {
+        let mut app = make_app();
+        for seed in 0..40 {
+            app.generate_item_with_seed("iron_sword", seed);
+        }
+        app.inventory_state.select(Some(39));
+
+        render_to_string(&mut app, 100, 20);
+        let small_offset = app.inventory_state.offset();
+        assert!(small_offset > 0);
+
+        app.handle_resize();
+        render_to_string(&mut app, 100, 80);
+        assert_eq!(app.inventory_state.selected(), Some(39));
+        assert_eq!(app.inventory_state.offset(), 0);
+
+        app.handle_resize();
+        render_to_string(&mut app, 100, 20);
+        assert_eq!(app.inventory_state.selected(), Some(39));
+        assert_eq!(app.inventory_state.offset(), small_offset);
+
+        // A selection left past the end of a shrunk list is pulled back in
+        app.inventory.truncate(5);
+        app.handle_resize();
+        assert_eq!(app.inventory_state.selected(), Some(4));
+    }
+
+    #[test]
+    fn test_help_bar_contexts_exist() {
+        for context in HELP_BAR_CONTEXTS {
+            assert!(
+                KEYBINDINGS.iter().any(|(name, _)| name == context),
+                "help bar context {} has no bindings",
+                context
+            );
+        }
+    }
+
+    #[test]
+    fn test_help_overlay_lists_bindings() {
+        let mut app = make_app();
+        app.show_help = true;
+
+        let backend = ratatui::backend::TestBackend::new(120, 60);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("Add Affix"));
+        assert!(text.contains("Rarity"));
+        assert!(text.contains("Changed by the last currency"));
+    }
+
+    #[test]
+    fn test_snapshot_inventory_and_stats() {
+        let mut app = snapshot_app();
+        assert_snapshot("inventory_and_stats", &mut app);
+    }
+
+    #[test]
+    fn test_snapshot_grouped_inventory() {
+        let mut app = snapshot_app();
+        handle_inventory_keys(&mut app, KeyCode::Char('g'));
+        assert_snapshot("grouped_inventory", &mut app);
+    }
+
+    #[test]
+    fn test_small_terminal_shows_resize_hint() {
+        assert!(!too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT)));
+        assert!(too_small(Rect::new(0, 0, MIN_WIDTH - 1, MIN_HEIGHT)));
+        assert!(too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT - 1)));
+        assert!(too_small(Rect::new(0, 0, 200, 0)));
+
+        let mut app = snapshot_app();
+        let screen = render_to_string(&mut app, 40, 10);
+        assert!(screen.starts_with("Terminal too small\n40x10, need 80x20"));
+        assert!(!render_to_string(&mut app, MIN_WIDTH, MIN_HEIGHT).contains("too small"));
+    }
+
+    #[test]
+    fn test_stat_view_toggles_between_rolls_and_totals() {
+        let mut app = snapshot_app();
+        assert_eq!(app.stat_view, StatView::Rolls);
+        let rolls = render_to_string(&mut app, 160, 50);
+        assert!(rolls.contains("Modifiers"));
+        assert!(!rolls.contains("Totals"));
+
+        app.toggle_stat_view();
+        assert_eq!(app.stat_view, StatView::Totals);
+        let totals = render_to_string(&mut app, 160, 50);
+        assert!(totals.contains("Totals"));
+        assert!(!totals.contains("Modifiers"));
+        assert!(!totals.contains("[T"));
+        let item = app.selected_item().unwrap();
+        for (stat, total, total_max) in item.total_stats() {
+            let line = loot_core::item::Modifier::stat_display(stat, total, total_max);
+            assert!(totals.contains(&line), "{}", line);
+        }
+
+        app.toggle_stat_view();
+        app.message = None;
+        assert_eq!(render_to_string(&mut app, 160, 50), rolls);
+    }
+}
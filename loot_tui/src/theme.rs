@@ -0,0 +1,212 @@
+//! Color themes for the TUI, with a colorblind-safe alternative to the default palette.
+//!
+//! Render functions look colors up by role through `current()`. The choice is stored in
+//! a small settings file shared with the config editor, along with the modifier display order.
+
+use loot_core::types::ModOrder;
+use ratatui::style::Color;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Settings file, relative to the working directory (next to `config/`)
+pub const SETTINGS_FILE: &str = "loot_settings.toml";
+
+/// Named colors used by the render functions
+pub struct Theme {
+    pub name: &'static str,
+    /// Regular text
+    pub text: Color,
+    /// Field labels
+    pub label: Color,
+    /// Hints, ranges and secondary information
+    pub muted: Color,
+    /// Borders, headings and focused elements
+    pub accent: Color,
+    /// Values worth noticing (tiers, messages)
+    pub highlight: Color,
+    /// Requirement met, positive effects
+    pub success: Color,
+    /// Requirement failed, destructive effects, errors
+    pub error: Color,
+    pub prefix: Color,
+    pub suffix: Color,
+    /// Local scope (affects the item)
+    pub local: Color,
+    /// Global scope (affects the character)
+    pub global: Color,
+    /// Implicits and unique mods
+    pub special: Color,
+    /// Marker for affixes changed by the last operation
+    pub changed: Color,
+    /// Marker for affixes that would change in a preview
+    pub preview: Color,
+    pub normal: Color,
+    pub magic: Color,
+    pub rare: Color,
+    pub unique: Color,
+    pub physical: Color,
+    pub fire: Color,
+    pub cold: Color,
+    pub lightning: Color,
+    pub chaos: Color,
+}
+
+pub const DEFAULT: Theme = Theme {
+    name: "default",
+    text: Color::White,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    accent: Color::Cyan,
+    highlight: Color::Yellow,
+    success: Color::Green,
+    error: Color::Red,
+    prefix: Color::Cyan,
+    suffix: Color::Green,
+    local: Color::Blue,
+    global: Color::Magenta,
+    special: Color::Magenta,
+    changed: Color::LightRed,
+    preview: Color::LightGreen,
+    normal: Color::White,
+    magic: Color::Blue,
+    rare: Color::Yellow,
+    unique: Color::Rgb(175, 95, 0),
+    physical: Color::White,
+    fire: Color::Red,
+    cold: Color::Cyan,
+    lightning: Color::Yellow,
+    chaos: Color::Magenta,
+};
+
+// Okabe-Ito palette: distinguishable with the common forms of color blindness
+const SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+const BLUE: Color = Color::Rgb(0, 114, 178);
+const ORANGE: Color = Color::Rgb(230, 159, 0);
+const VERMILLION: Color = Color::Rgb(213, 94, 0);
+const YELLOW: Color = Color::Rgb(240, 228, 66);
+const BLUISH_GREEN: Color = Color::Rgb(0, 158, 115);
+const REDDISH_PURPLE: Color = Color::Rgb(204, 121, 167);
+
+pub const COLORBLIND: Theme = Theme {
+    name: "colorblind",
+    text: Color::White,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    accent: SKY_BLUE,
+    highlight: YELLOW,
+    success: BLUE,
+    error: ORANGE,
+    prefix: SKY_BLUE,
+    suffix: ORANGE,
+    local: BLUE,
+    global: REDDISH_PURPLE,
+    special: REDDISH_PURPLE,
+    changed: VERMILLION,
+    preview: BLUISH_GREEN,
+    normal: Color::White,
+    magic: SKY_BLUE,
+    rare: YELLOW,
+    unique: VERMILLION,
+    physical: Color::White,
+    fire: VERMILLION,
+    cold: SKY_BLUE,
+    lightning: YELLOW,
+    chaos: REDDISH_PURPLE,
+};
+
+static COLORBLIND_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Index of the active order in `ModOrder::all()`
+static MOD_ORDER: AtomicUsize = AtomicUsize::new(0);
+
+/// The active theme
+pub fn current() -> &'static Theme {
+    if COLORBLIND_ENABLED.load(Ordering::Relaxed) {
+        &COLORBLIND
+    } else {
+        &DEFAULT
+    }
+}
+
+pub fn set_colorblind(enabled: bool) {
+    COLORBLIND_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Switch between the default and colorblind themes, returning the new one
+pub fn toggle() -> &'static Theme {
+    set_colorblind(!COLORBLIND_ENABLED.load(Ordering::Relaxed));
+    current()
+}
+
+/// How explicit modifiers are ordered when items are displayed
+pub fn mod_order() -> ModOrder {
+    ModOrder::all()[MOD_ORDER.load(Ordering::Relaxed)]
+}
+
+pub fn set_mod_order(order: ModOrder) {
+    MOD_ORDER.store(order as usize, Ordering::Relaxed);
+}
+
+/// Switch to the next modifier order, returning it
+pub fn cycle_mod_order() -> ModOrder {
+    set_mod_order(mod_order().next());
+    mod_order()
+}
+
+/// Apply the theme and modifier order saved in the settings file (missing or unreadable
+/// files and unknown values keep the defaults)
+pub fn load_settings(path: &Path) {
+    if let Ok(content) = fs::read_to_string(path) {
+        set_colorblind(parse_setting(&content, "theme") == Some(COLORBLIND.name));
+        if let Some(order) = parse_setting(&content, "mod_order").and_then(|v| v.parse().ok()) {
+            set_mod_order(order);
+        }
+    }
+}
+
+/// Save the active theme and modifier order to the settings file
+pub fn save_settings(path: &Path) -> io::Result<()> {
+    fs::write(
+        path,
+        format!(
+            "theme = \"{}\"\nmod_order = \"{}\"\n",
+            current().name,
+            mod_order().name()
+        ),
+    )
+}
+
+/// Read a `key = "..."` line from the settings file
+fn parse_setting<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    content.lines().find_map(|line| {
+        let (k, value) = line.split_once('=')?;
+        (k.trim() == key).then(|| value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_theme_setting() {
+        assert_eq!(
+            parse_setting("theme = \"colorblind\"\n", "theme"),
+            Some("colorblind")
+        );
+        assert_eq!(
+            parse_setting("# comment\ntheme=default", "theme"),
+            Some("default")
+        );
+        assert_eq!(parse_setting("other = 1", "theme"), None);
+        assert_eq!(
+            parse_setting(
+                "theme = \"default\"\nmod_order = \"category\"\n",
+                "mod_order"
+            ),
+            Some("category")
+        );
+    }
+}
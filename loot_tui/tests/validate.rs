@@ -0,0 +1,34 @@
+use std::process::Command;
+
+fn validate(config_dir: &str) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_loot_tui"))
+        .args(["validate", config_dir])
+        .output()
+        .expect("failed to run loot_tui");
+    (
+        output.status.code().expect("exited with a code"),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_validate_shipped_config() {
+    let (code, stdout) = validate("../config");
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(stdout.contains("0 error(s), 0 warning(s)"), "{}", stdout);
+}
+
+#[test]
+fn test_validate_broken_config() {
+    let (code, stdout) = validate("tests/fixtures/broken_config");
+    assert_eq!(code, 1, "{}", stdout);
+
+    // Errors are listed before warnings, each under its own heading
+    let errors = stdout.find("Errors (3):").expect("errors heading");
+    let warnings = stdout.find("Warnings (1):").expect("warnings heading");
+    assert!(errors < warnings, "{}", stdout);
+    assert!(stdout[errors..warnings].contains("affix 'heavy': all tiers have zero weight"));
+    assert!(stdout[errors..warnings].contains("base_type 'iron_sword'"));
+    assert!(stdout[warnings..].contains("unique 'plated_blade'"));
+    assert!(stdout.contains("3 error(s), 1 warning(s)"), "{}", stdout);
+}